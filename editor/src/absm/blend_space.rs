@@ -0,0 +1,417 @@
+use crate::absm::{
+    command::blend::SetBlendSpacePointPositionCommand, message::MessageSender, AbsmDataModel,
+    SelectedEntity,
+};
+use fyrox::{
+    animation::machine::{
+        node::{blend_space::triangulate_blend_space, PoseNodeDefinition},
+        Parameter,
+    },
+    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Control, UiNode, UserInterface,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+const POINT_RADIUS: f32 = 5.0;
+const PADDING: f32 = 12.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlendSpaceFieldMessage {
+    /// Replaces displayed content of the field.
+    Sync {
+        points: Vec<Vector2<f32>>,
+        is_2d: bool,
+        sample: Option<Vector2<f32>>,
+    },
+    /// Emitted when user has finished dragging of a point.
+    MovePoint {
+        index: usize,
+        position: Vector2<f32>,
+    },
+}
+
+impl BlendSpaceFieldMessage {
+    define_constructor!(BlendSpaceFieldMessage:Sync => fn sync(points: Vec<Vector2<f32>>, is_2d: bool, sample: Option<Vector2<f32>>), layout: false);
+    define_constructor!(BlendSpaceFieldMessage:MovePoint => fn move_point(index: usize, position: Vector2<f32>), layout: false);
+}
+
+/// A widget that shows samples of a blend space and allows to move them by dragging.
+#[derive(Debug, Clone)]
+pub struct BlendSpaceField {
+    widget: Widget,
+    points: Vec<Vector2<f32>>,
+    triangles: Vec<[usize; 3]>,
+    is_2d: bool,
+    sample: Option<Vector2<f32>>,
+    min: Vector2<f32>,
+    max: Vector2<f32>,
+    dragged_point: Option<usize>,
+}
+
+define_widget_deref!(BlendSpaceField);
+
+impl BlendSpaceField {
+    fn update_bounds(&mut self) {
+        let mut min = Vector2::new(-1.0f32, -1.0);
+        let mut max = Vector2::new(1.0f32, 1.0);
+        for point in self.points.iter().chain(self.sample.iter()) {
+            min = min.inf(point);
+            max = max.sup(point);
+        }
+        self.min = Vector2::new(min.x.floor(), min.y.floor());
+        self.max = Vector2::new(max.x.ceil(), max.y.ceil());
+    }
+
+    fn field_rect(&self) -> Rect<f32> {
+        self.bounding_rect().deflate(PADDING, PADDING)
+    }
+
+    fn space_to_local(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let rect = self.field_rect();
+        let size = self.max - self.min;
+        let kx = (point.x - self.min.x) / size.x;
+        let ky = if self.is_2d {
+            (point.y - self.min.y) / size.y
+        } else {
+            0.5
+        };
+        Vector2::new(
+            rect.position.x + kx * rect.size.x,
+            // Y axis goes up.
+            rect.position.y + (1.0 - ky) * rect.size.y,
+        )
+    }
+
+    fn local_to_space(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let rect = self.field_rect();
+        let size = self.max - self.min;
+        let kx = ((point.x - rect.position.x) / rect.size.x).clamp(0.0, 1.0);
+        let ky = (1.0 - (point.y - rect.position.y) / rect.size.y).clamp(0.0, 1.0);
+        Vector2::new(
+            self.min.x + kx * size.x,
+            if self.is_2d {
+                self.min.y + ky * size.y
+            } else {
+                0.0
+            },
+        )
+    }
+
+    fn pick_point(&self, local_position: Vector2<f32>) -> Option<usize> {
+        self.points.iter().position(|p| {
+            self.space_to_local(*p).metric_distance(&local_position) <= POINT_RADIUS * 2.0
+        })
+    }
+}
+
+impl Control for BlendSpaceField {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        ctx.push_rect_filled(&self.bounding_rect(), None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        // Grid with unit step.
+        let rect = self.field_rect();
+        ctx.push_rect(&rect, 1.0);
+        let mut x = self.min.x.ceil();
+        while x < self.max.x {
+            let local = self.space_to_local(Vector2::new(x, 0.0));
+            ctx.push_line(
+                Vector2::new(local.x, rect.position.y),
+                Vector2::new(local.x, rect.position.y + rect.size.y),
+                1.0,
+            );
+            x += 1.0;
+        }
+        if self.is_2d {
+            let mut y = self.min.y.ceil();
+            while y < self.max.y {
+                let local = self.space_to_local(Vector2::new(0.0, y));
+                ctx.push_line(
+                    Vector2::new(rect.position.x, local.y),
+                    Vector2::new(rect.position.x + rect.size.x, local.y),
+                    1.0,
+                );
+                y += 1.0;
+            }
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(60, 60, 60)),
+            CommandTexture::None,
+            None,
+        );
+
+        // Triangulation (2D) or a line through sorted samples (1D).
+        if self.is_2d {
+            for triangle in self.triangles.iter() {
+                for i in 0..3 {
+                    ctx.push_line(
+                        self.space_to_local(self.points[triangle[i]]),
+                        self.space_to_local(self.points[triangle[(i + 1) % 3]]),
+                        1.0,
+                    );
+                }
+            }
+        } else if self.points.len() > 1 {
+            let (min, max) = self
+                .points
+                .iter()
+                .fold((f32::MAX, -f32::MAX), |(min, max), p| {
+                    (min.min(p.x), max.max(p.x))
+                });
+            ctx.push_line(
+                self.space_to_local(Vector2::new(min, 0.0)),
+                self.space_to_local(Vector2::new(max, 0.0)),
+                1.0,
+            );
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(120, 120, 120)),
+            CommandTexture::None,
+            None,
+        );
+
+        for (i, point) in self.points.iter().enumerate() {
+            let color = if self.dragged_point == Some(i) {
+                Color::opaque(255, 200, 0)
+            } else {
+                Color::opaque(200, 200, 200)
+            };
+            ctx.push_circle(self.space_to_local(*point), POINT_RADIUS, 16, color);
+        }
+
+        if let Some(sample) = self.sample {
+            ctx.push_circle(
+                self.space_to_local(sample),
+                POINT_RADIUS * 0.6,
+                16,
+                Color::opaque(0, 200, 0),
+            );
+        }
+
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::WHITE),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { button, pos } => {
+                    if *button == MouseButton::Left {
+                        self.dragged_point = self.pick_point(self.screen_to_local(*pos));
+                        if self.dragged_point.is_some() {
+                            ui.capture_mouse(self.handle());
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(index) = self.dragged_point {
+                        let position = self.local_to_space(self.screen_to_local(*pos));
+                        self.points[index] = position;
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. } => {
+                    if *button == MouseButton::Left {
+                        if let Some(index) = self.dragged_point.take() {
+                            ui.release_mouse_capture();
+
+                            ui.send_message(BlendSpaceFieldMessage::move_point(
+                                self.handle(),
+                                MessageDirection::FromWidget,
+                                index,
+                                self.points[index],
+                            ));
+
+                            message.set_handled(true);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        } else if let Some(BlendSpaceFieldMessage::Sync {
+            points,
+            is_2d,
+            sample,
+        }) = message.data()
+        {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+                && self.dragged_point.is_none()
+            {
+                self.triangles = if *is_2d {
+                    triangulate_blend_space(points)
+                } else {
+                    Default::default()
+                };
+                self.points = points.clone();
+                self.is_2d = *is_2d;
+                self.sample = *sample;
+                self.update_bounds();
+            }
+        }
+    }
+}
+
+pub struct BlendSpaceFieldBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl BlendSpaceFieldBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let mut field = BlendSpaceField {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(40, 40, 40)))
+                .build(),
+            points: Default::default(),
+            triangles: Default::default(),
+            is_2d: false,
+            sample: None,
+            min: Default::default(),
+            max: Default::default(),
+            dragged_point: None,
+        };
+        field.update_bounds();
+
+        ctx.add_node(UiNode::new(field))
+    }
+}
+
+/// A panel that shows samples of selected blend space node.
+pub struct BlendSpaceEditor {
+    pub window: Handle<UiNode>,
+    field: Handle<UiNode>,
+    node: Handle<PoseNodeDefinition>,
+}
+
+impl BlendSpaceEditor {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let field = BlendSpaceFieldBuilder::new(WidgetBuilder::new()).build(ctx);
+        let window = WindowBuilder::new(WidgetBuilder::new())
+            .can_close(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Blend Space"))
+            .with_content(field)
+            .build(ctx);
+
+        Self {
+            window,
+            field,
+            node: Default::default(),
+        }
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.node = Default::default();
+
+        ui.send_message(BlendSpaceFieldMessage::sync(
+            self.field,
+            MessageDirection::ToWidget,
+            Default::default(),
+            false,
+            None,
+        ));
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface, data_model: &AbsmDataModel) {
+        let definition = &data_model.resource.data_ref().absm_definition;
+
+        self.node = Default::default();
+
+        if let Some(SelectedEntity::PoseNode(handle)) = data_model.selection.first() {
+            if let Some(PoseNodeDefinition::BlendSpace(blend_space)) =
+                definition.nodes.try_borrow(*handle)
+            {
+                self.node = *handle;
+
+                let fetch_parameter = |name: &str| {
+                    definition
+                        .parameters
+                        .container
+                        .iter()
+                        .find(|p| p.name == name)
+                        .and_then(|p| {
+                            if let Parameter::Weight(weight) = p.value {
+                                Some(weight)
+                            } else {
+                                None
+                            }
+                        })
+                };
+
+                let sample = fetch_parameter(&blend_space.x_parameter).map(|x| {
+                    Vector2::new(x, fetch_parameter(&blend_space.y_parameter).unwrap_or(0.0))
+                });
+
+                ui.send_message(BlendSpaceFieldMessage::sync(
+                    self.field,
+                    MessageDirection::ToWidget,
+                    blend_space.points.iter().map(|p| p.position).collect(),
+                    blend_space.is_2d(),
+                    sample,
+                ));
+
+                return;
+            }
+        }
+
+        ui.send_message(BlendSpaceFieldMessage::sync(
+            self.field,
+            MessageDirection::ToWidget,
+            Default::default(),
+            false,
+            None,
+        ));
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, sender: &MessageSender) {
+        if let Some(BlendSpaceFieldMessage::MovePoint { index, position }) = message.data() {
+            if message.destination() == self.field
+                && message.direction() == MessageDirection::FromWidget
+                && self.node.is_some()
+            {
+                sender.do_command(SetBlendSpacePointPositionCommand {
+                    handle: self.node,
+                    index: *index,
+                    value: *position,
+                });
+            }
+        }
+    }
+}
@@ -7,11 +7,12 @@ use fyrox::{
     animation::machine::{
         node::{
             blend::{BlendPoseDefinition, IndexedBlendInputDefinition},
+            blend_space::BlendSpacePointDefinition,
             PoseNodeDefinition,
         },
         PoseWeight,
     },
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
 };
 
 define_push_element_to_collection_command!(AddInputCommand<Handle<PoseNodeDefinition>, IndexedBlendInputDefinition>(self, context) {
@@ -111,3 +112,52 @@ define_absm_swap_command!(SetPoseWeightParameterCommand<Handle<PoseNodeDefinitio
         unreachable!()
     }
 });
+
+define_push_element_to_collection_command!(AddBlendSpacePointCommand<Handle<PoseNodeDefinition>, BlendSpacePointDefinition>(self, context) {
+    match &mut context.resource.absm_definition.nodes[self.handle] {
+        PoseNodeDefinition::BlendSpace(definition) => &mut definition.points,
+        _ => unreachable!(),
+    }
+});
+
+define_remove_collection_element_command!(RemoveBlendSpacePointCommand<Handle<PoseNodeDefinition>, BlendSpacePointDefinition>(self, context) {
+    match &mut context.resource.absm_definition.nodes[self.handle] {
+        PoseNodeDefinition::BlendSpace(definition) => &mut definition.points,
+        _ => unreachable!(),
+    }
+});
+
+define_set_collection_element_command!(
+    SetBlendSpacePointPoseSourceCommand<Handle<PoseNodeDefinition>, Handle<PoseNodeDefinition>>(self, context) {
+        match context.resource.absm_definition.nodes[self.handle] {
+            PoseNodeDefinition::BlendSpace(ref mut definition) => {
+                &mut definition.points[self.index].pose_source
+            }
+            _ => unreachable!(),
+        }
+    }
+);
+
+define_absm_swap_command!(SetBlendSpacePointPositionCommand<Handle<PoseNodeDefinition>, Vector2<f32>>[index: usize](self, context) {
+    if let PoseNodeDefinition::BlendSpace(ref mut definition) = context.resource.absm_definition.nodes[self.handle] {
+        &mut definition.points[self.index].position
+    } else {
+        unreachable!()
+    }
+});
+
+define_absm_swap_command!(SetBlendSpaceXParameterCommand<Handle<PoseNodeDefinition>, String>[](self, context) {
+    if let PoseNodeDefinition::BlendSpace(ref mut definition) = context.resource.absm_definition.nodes[self.handle] {
+        &mut definition.x_parameter
+    } else {
+        unreachable!()
+    }
+});
+
+define_absm_swap_command!(SetBlendSpaceYParameterCommand<Handle<PoseNodeDefinition>, String>[](self, context) {
+    if let PoseNodeDefinition::BlendSpace(ref mut definition) = context.resource.absm_definition.nodes[self.handle] {
+        &mut definition.y_parameter
+    } else {
+        unreachable!()
+    }
+});
@@ -0,0 +1,64 @@
+use crate::{
+    absm::command::{AbsmCommandTrait, AbsmEditorContext},
+    define_absm_swap_command, define_push_element_to_collection_command,
+    define_remove_collection_element_command, define_set_collection_element_command,
+};
+use fyrox::{
+    animation::machine::{
+        layer::{LayerBlendMode, LayerDefinition, LayerMaskEntryDefinition},
+        node::PoseNodeDefinition,
+        state::StateDefinition,
+        PoseWeight,
+    },
+    core::pool::Handle,
+};
+
+define_absm_swap_command!(SetLayerNameCommand<Handle<LayerDefinition>, String>[](self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].name
+});
+
+define_absm_swap_command!(SetLayerWeightCommand<Handle<LayerDefinition>, PoseWeight>[](self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].weight
+});
+
+define_absm_swap_command!(SetLayerWeightConstantCommand<Handle<LayerDefinition>, f32>[](self, context) {
+    if let PoseWeight::Constant(ref mut constant) = context.resource.absm_definition.layers[self.handle].weight {
+        constant
+    } else {
+        unreachable!()
+    }
+});
+
+define_absm_swap_command!(SetLayerWeightParameterCommand<Handle<LayerDefinition>, String>[](self, context) {
+    if let PoseWeight::Parameter(ref mut parameter) = context.resource.absm_definition.layers[self.handle].weight {
+        parameter
+    } else {
+        unreachable!()
+    }
+});
+
+define_absm_swap_command!(SetLayerBlendModeCommand<Handle<LayerDefinition>, LayerBlendMode>[](self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].blend_mode
+});
+
+define_absm_swap_command!(SetLayerEntryStateCommand<Handle<LayerDefinition>, Handle<StateDefinition>>[](self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].entry_state
+});
+
+define_absm_swap_command!(SetLayerAdditiveReferenceCommand<Handle<LayerDefinition>, Handle<PoseNodeDefinition>>[](self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].additive_reference
+});
+
+define_push_element_to_collection_command!(AddLayerMaskEntryCommand<Handle<LayerDefinition>, LayerMaskEntryDefinition>(self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].mask
+});
+
+define_remove_collection_element_command!(RemoveLayerMaskEntryCommand<Handle<LayerDefinition>, LayerMaskEntryDefinition>(self, context) {
+    &mut context.resource.absm_definition.layers[self.handle].mask
+});
+
+define_set_collection_element_command!(
+    SetLayerMaskEntryNodeNameCommand<Handle<LayerDefinition>, String>(self, context) {
+        &mut context.resource.absm_definition.layers[self.handle].mask[self.index].node_name
+    }
+);
@@ -2,8 +2,9 @@ use crate::{absm::SelectedEntity, define_command_stack};
 use fyrox::animation::machine::node::play::TimeSlice;
 use fyrox::{
     animation::machine::{
-        node::PoseNodeDefinition, parameter::ParameterDefinition, state::StateDefinition,
-        transition::TransitionDefinition, MachineDefinition, MachineInstantiationError, Parameter,
+        layer::LayerDefinition, node::PoseNodeDefinition, parameter::ParameterDefinition,
+        state::StateDefinition, transition::TransitionDefinition, MachineDefinition,
+        MachineInstantiationError, Parameter,
     },
     asset::ResourceDataRef,
     core::{
@@ -24,6 +25,7 @@ pub struct AbsmEditorContext<'a> {
 }
 
 pub mod blend;
+pub mod layer;
 
 define_command_stack!(AbsmCommandTrait, AbsmCommandStack, AbsmEditorContext);
 
@@ -178,6 +180,20 @@ macro_rules! define_spawn_command {
 }
 
 define_spawn_command!(AddTransitionCommand, TransitionDefinition, transitions);
+define_spawn_command!(AddLayerCommand, LayerDefinition, layers);
+
+/// Returns entry state of a given layer, or entry state of the machine if the layer is the base
+/// layer.
+fn entry_state_mut(
+    definition: &mut MachineDefinition,
+    layer: Handle<LayerDefinition>,
+) -> &mut Handle<StateDefinition> {
+    if let Some(layer) = definition.layers.try_borrow_mut(layer) {
+        &mut layer.entry_state
+    } else {
+        &mut definition.entry_state
+    }
+}
 
 #[derive(Debug)]
 pub enum AddStateCommand {
@@ -209,13 +225,16 @@ impl AbsmCommandTrait for AddStateCommand {
     fn execute(&mut self, context: &mut AbsmEditorContext) {
         match std::mem::replace(self, AddStateCommand::Unknown) {
             AddStateCommand::NonExecuted { state } => {
+                let layer = state.layer;
                 let handle = context.resource.absm_definition.states.spawn(state);
 
-                let prev_entry_state = context.resource.absm_definition.entry_state;
+                let entry_state = entry_state_mut(&mut context.resource.absm_definition, layer);
+
+                let prev_entry_state = *entry_state;
 
                 // Set entry state if it wasn't set yet.
-                if context.resource.absm_definition.entry_state.is_none() {
-                    context.resource.absm_definition.entry_state = handle;
+                if entry_state.is_none() {
+                    *entry_state = handle;
                 }
 
                 *self = AddStateCommand::Executed {
@@ -224,17 +243,20 @@ impl AbsmCommandTrait for AddStateCommand {
                 };
             }
             AddStateCommand::Reverted { ticket, state } => {
+                let layer = state.layer;
                 let handle = context
                     .resource
                     .absm_definition
                     .states
                     .put_back(ticket, state);
 
-                let prev_entry_state = context.resource.absm_definition.entry_state;
+                let entry_state = entry_state_mut(&mut context.resource.absm_definition, layer);
+
+                let prev_entry_state = *entry_state;
 
                 // Set entry state if it wasn't set yet.
-                if context.resource.absm_definition.entry_state.is_none() {
-                    context.resource.absm_definition.entry_state = handle;
+                if entry_state.is_none() {
+                    *entry_state = handle;
                 }
 
                 *self = AddStateCommand::Executed {
@@ -252,7 +274,8 @@ impl AbsmCommandTrait for AddStateCommand {
                 handle,
                 prev_entry_state,
             } => {
-                context.resource.absm_definition.entry_state = prev_entry_state;
+                let layer = context.resource.absm_definition.states[handle].layer;
+                *entry_state_mut(&mut context.resource.absm_definition, layer) = prev_entry_state;
 
                 let (ticket, state) = context.resource.absm_definition.states.take_reserve(handle);
                 *self = AddStateCommand::Reverted { ticket, state }
@@ -519,6 +542,7 @@ macro_rules! define_free_command {
 define_free_command!(DeleteStateCommand, StateDefinition, states);
 define_free_command!(DeletePoseNodeCommand, PoseNodeDefinition, nodes);
 define_free_command!(DeleteTransitionCommand, TransitionDefinition, transitions);
+define_free_command!(DeleteLayerCommand, LayerDefinition, layers);
 
 #[macro_export]
 macro_rules! define_push_element_to_collection_command {
@@ -711,6 +735,14 @@ define_absm_swap_command!(SetPlayAnimationResourceCommand<Handle<PoseNodeDefinit
     }
 });
 
+define_absm_swap_command!(SetPlayAnimationSkeletonMappingCommand<Handle<PoseNodeDefinition>, String>[](self, context) {
+    if let PoseNodeDefinition::PlayAnimation(ref mut play_animation) = context.resource.absm_definition.nodes[self.handle] {
+        &mut play_animation.skeleton_mapping
+    } else {
+        unreachable!()
+    }
+});
+
 define_absm_swap_command!(SetPlayAnimationSpeedCommand<Handle<PoseNodeDefinition>, f32>[](self, context) {
     if let PoseNodeDefinition::PlayAnimation(ref mut play_animation) = context.resource.absm_definition.nodes[self.handle] {
         &mut play_animation.speed
@@ -767,6 +799,14 @@ define_absm_swap_command!(SetParameterIndexValueCommand<usize, u32>[](self, cont
     }
 });
 
+define_absm_swap_command!(SetParameterTriggerValueCommand<usize, bool>[](self, context) {
+    if let Parameter::Trigger(ref mut trigger) = context.resource.absm_definition.parameters.container[self.handle].value {
+        trigger
+    } else {
+        unreachable!()
+    }
+});
+
 define_absm_swap_command!(SetTransitionNameCommand<Handle<TransitionDefinition>, String>[](self, context) {
     &mut context.resource.absm_definition.transitions[self.handle].name
 });
@@ -782,3 +822,7 @@ define_absm_swap_command!(SetTransitionRuleCommand<Handle<TransitionDefinition>,
 define_absm_swap_command!(SetTransitionInvertRuleCommand<Handle<TransitionDefinition>, bool>[](self, context) {
     &mut context.resource.absm_definition.transitions[self.handle].invert_rule
 });
+
+define_absm_swap_command!(SetTransitionConditionCommand<Handle<TransitionDefinition>, String>[](self, context) {
+    &mut context.resource.absm_definition.transitions[self.handle].condition
+});
@@ -2,14 +2,23 @@ use crate::{
     absm::{
         command::{
             blend::{
-                AddInputCommand, AddPoseSourceCommand, RemoveInputCommand, RemovePoseSourceCommand,
+                AddBlendSpacePointCommand, AddInputCommand, AddPoseSourceCommand,
+                RemoveBlendSpacePointCommand, RemoveInputCommand, RemovePoseSourceCommand,
                 SetBlendAnimationsByIndexInputBlendTimeCommand,
                 SetBlendAnimationsByIndexParameterCommand, SetBlendAnimationsPoseWeightCommand,
-                SetPoseWeightConstantCommand, SetPoseWeightParameterCommand,
+                SetBlendSpacePointPositionCommand, SetBlendSpaceXParameterCommand,
+                SetBlendSpaceYParameterCommand, SetPoseWeightConstantCommand,
+                SetPoseWeightParameterCommand,
+            },
+            layer::{
+                AddLayerMaskEntryCommand, RemoveLayerMaskEntryCommand, SetLayerBlendModeCommand,
+                SetLayerMaskEntryNodeNameCommand, SetLayerNameCommand, SetLayerWeightCommand,
+                SetLayerWeightConstantCommand, SetLayerWeightParameterCommand,
             },
             AbsmCommand, CommandGroup, MovePoseNodeCommand, MoveStateNodeCommand,
-            SetPlayAnimationResourceCommand, SetPlayAnimationSpeedCommand,
-            SetPlayAnimationTimeSliceCommand, SetStateNameCommand, SetTransitionInvertRuleCommand,
+            SetPlayAnimationResourceCommand, SetPlayAnimationSkeletonMappingCommand,
+            SetPlayAnimationSpeedCommand, SetPlayAnimationTimeSliceCommand, SetStateNameCommand,
+            SetTransitionConditionCommand, SetTransitionInvertRuleCommand,
             SetTransitionNameCommand, SetTransitionRuleCommand, SetTransitionTimeCommand,
         },
         message::MessageSender,
@@ -20,11 +29,13 @@ use crate::{
 };
 use fyrox::{
     animation::machine::{
+        layer::{LayerBlendMode, LayerDefinition, LayerMaskEntryDefinition},
         node::{
             blend::{
                 BlendAnimationsByIndexDefinition, BlendAnimationsDefinition, BlendPoseDefinition,
                 IndexedBlendInputDefinition,
             },
+            blend_space::{BlendSpaceDefinition, BlendSpacePointDefinition},
             play::PlayAnimationDefinition,
             play::TimeSlice,
             BasePoseNodeDefinition, PoseNodeDefinition,
@@ -83,7 +94,20 @@ impl Inspector {
         property_editors.insert(InspectablePropertyEditorDefinition::<BlendPoseDefinition>::new());
         property_editors
             .insert(VecCollectionPropertyEditorDefinition::<BlendPoseDefinition>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<
+            BlendSpacePointDefinition,
+        >::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<
+            BlendSpacePointDefinition,
+        >::new());
         property_editors.insert(EnumPropertyEditorDefinition::<PoseWeight>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<LayerBlendMode>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<
+            LayerMaskEntryDefinition,
+        >::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<
+            LayerMaskEntryDefinition,
+        >::new());
         property_editors.insert(InspectablePropertyEditorDefinition::<TimeSlice>::new());
         property_editors.insert(EnumPropertyEditorDefinition::<TimeSlice>::new_optional());
 
@@ -99,12 +123,17 @@ impl Inspector {
         &self,
         definition: &'a MachineDefinition,
     ) -> Option<&'a dyn Inspect> {
-        self.selection.first().map(|first| match first {
+        self.selection.first().and_then(|first| match first {
             SelectedEntity::Transition(transition) => {
-                &definition.transitions[*transition] as &dyn Inspect
+                Some(&definition.transitions[*transition] as &dyn Inspect)
             }
-            SelectedEntity::State(state) => &definition.states[*state] as &dyn Inspect,
-            SelectedEntity::PoseNode(pose) => &definition.nodes[*pose] as &dyn Inspect,
+            SelectedEntity::State(state) => Some(&definition.states[*state] as &dyn Inspect),
+            SelectedEntity::PoseNode(pose) => Some(&definition.nodes[*pose] as &dyn Inspect),
+            // Layer could be removed by undo while selected.
+            SelectedEntity::Layer(layer) => definition
+                .layers
+                .try_borrow(*layer)
+                .map(|layer| layer as &dyn Inspect),
         })
     }
 
@@ -197,10 +226,13 @@ impl Inspector {
                                 handle_blend_animations_node_property_changed(
                                     args, *pose_node, node,
                                 )
+                            } else if args.owner_type_id == TypeId::of::<BlendSpaceDefinition>() {
+                                handle_blend_space_node_property_changed(args, *pose_node, node)
                             } else {
                                 None
                             }
                         }
+                        SelectedEntity::Layer(layer) => handle_layer_property_changed(args, *layer),
                     })
                     .collect::<Vec<_>>();
 
@@ -240,12 +272,90 @@ fn handle_transition_property_changed(
                     value: value.cast_clone()?,
                 }))
             }
+            TransitionDefinition::CONDITION => {
+                Some(AbsmCommand::new(SetTransitionConditionCommand {
+                    handle,
+                    value: value.cast_clone()?,
+                }))
+            }
             _ => None,
         },
         _ => None,
     }
 }
 
+fn handle_layer_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<LayerDefinition>,
+) -> Option<AbsmCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => match args.name.as_ref() {
+            LayerDefinition::NAME => Some(AbsmCommand::new(SetLayerNameCommand {
+                handle,
+                value: value.cast_clone()?,
+            })),
+            LayerDefinition::WEIGHT => Some(AbsmCommand::new(SetLayerWeightCommand {
+                handle,
+                value: value.cast_clone()?,
+            })),
+            LayerDefinition::BLEND_MODE => Some(AbsmCommand::new(SetLayerBlendModeCommand {
+                handle,
+                value: value.cast_clone()?,
+            })),
+            _ => None,
+        },
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            LayerDefinition::WEIGHT => match inner.value {
+                FieldKind::Object(ref value) => match inner.name.as_ref() {
+                    PoseWeight::CONSTANT_F_0 => {
+                        Some(AbsmCommand::new(SetLayerWeightConstantCommand {
+                            handle,
+                            value: value.cast_clone()?,
+                        }))
+                    }
+                    PoseWeight::PARAMETER_F_0 => {
+                        Some(AbsmCommand::new(SetLayerWeightParameterCommand {
+                            handle,
+                            value: value.cast_clone()?,
+                        }))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        },
+        FieldKind::Collection(ref collection_changed) => match args.name.as_ref() {
+            LayerDefinition::MASK => match **collection_changed {
+                CollectionChanged::Add => Some(AbsmCommand::new(AddLayerMaskEntryCommand {
+                    handle,
+                    value: Some(Default::default()),
+                })),
+                CollectionChanged::Remove(i) => Some(AbsmCommand::new(
+                    RemoveLayerMaskEntryCommand::new(handle, i),
+                )),
+                CollectionChanged::ItemChanged {
+                    index,
+                    ref property,
+                } => match property.value {
+                    FieldKind::Object(ref value) => match property.name.as_ref() {
+                        LayerMaskEntryDefinition::NODE_NAME => {
+                            Some(AbsmCommand::new(SetLayerMaskEntryNodeNameCommand {
+                                handle,
+                                index,
+                                value: value.cast_clone()?,
+                            }))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                },
+            },
+            _ => None,
+        },
+    }
+}
+
 fn handle_state_property_changed(
     args: &PropertyChanged,
     handle: Handle<StateDefinition>,
@@ -281,6 +391,12 @@ fn handle_play_animation_node_property_changed(
                     value: value.cast_clone()?,
                 }))
             }
+            PlayAnimationDefinition::SKELETON_MAPPING => {
+                Some(AbsmCommand::new(SetPlayAnimationSkeletonMappingCommand {
+                    handle,
+                    value: value.cast_clone()?,
+                }))
+            }
             PlayAnimationDefinition::SPEED => {
                 Some(AbsmCommand::new(SetPlayAnimationSpeedCommand {
                     handle,
@@ -425,6 +541,64 @@ fn handle_blend_animations_node_property_changed(
     }
 }
 
+fn handle_blend_space_node_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<PoseNodeDefinition>,
+    node: &PoseNodeDefinition,
+) -> Option<AbsmCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => match args.name.as_ref() {
+            BlendSpaceDefinition::X_PARAMETER => {
+                Some(AbsmCommand::new(SetBlendSpaceXParameterCommand {
+                    handle,
+                    value: value.cast_clone()?,
+                }))
+            }
+            BlendSpaceDefinition::Y_PARAMETER => {
+                Some(AbsmCommand::new(SetBlendSpaceYParameterCommand {
+                    handle,
+                    value: value.cast_clone()?,
+                }))
+            }
+            _ => None,
+        },
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            BlendSpaceDefinition::BASE => {
+                handle_base_pose_node_property_changed(inner, handle, node)
+            }
+            _ => None,
+        },
+        FieldKind::Collection(ref collection_changed) => match args.name.as_ref() {
+            BlendSpaceDefinition::POINTS => match **collection_changed {
+                CollectionChanged::Add => Some(AbsmCommand::new(AddBlendSpacePointCommand {
+                    handle,
+                    value: Some(Default::default()),
+                })),
+                CollectionChanged::Remove(i) => Some(AbsmCommand::new(
+                    RemoveBlendSpacePointCommand::new(handle, i),
+                )),
+                CollectionChanged::ItemChanged {
+                    index,
+                    ref property,
+                } => match property.value {
+                    FieldKind::Object(ref value) => match property.name.as_ref() {
+                        BlendSpacePointDefinition::POSITION => {
+                            Some(AbsmCommand::new(SetBlendSpacePointPositionCommand {
+                                handle,
+                                index,
+                                value: value.cast_clone()?,
+                            }))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                },
+            },
+            _ => None,
+        },
+    }
+}
+
 fn handle_base_pose_node_property_changed(
     args: &PropertyChanged,
     handle: Handle<PoseNodeDefinition>,
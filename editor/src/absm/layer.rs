@@ -0,0 +1,216 @@
+use crate::{
+    absm::{
+        command::{
+            AbsmCommand, AddLayerCommand, ChangeSelectionCommand, CommandGroup, DeleteLayerCommand,
+            DeleteStateCommand, DeleteTransitionCommand,
+        },
+        message::MessageSender,
+        AbsmDataModel, SelectedEntity,
+    },
+    gui::make_dropdown_list_option,
+    send_sync_message,
+};
+use fyrox::{
+    animation::machine::layer::LayerDefinition,
+    core::pool::Handle,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface,
+    },
+};
+
+/// A list of animation layers of the machine. First item is always the base layer.
+pub struct LayerPanel {
+    pub window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    add: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    // Layers of respective list items, Handle::NONE is the base layer.
+    layers: Vec<(Handle<LayerDefinition>, String)>,
+}
+
+impl LayerPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let add;
+        let remove;
+        let window = WindowBuilder::new(WidgetBuilder::new())
+            .with_title(WindowTitle::text("Layers"))
+            .can_close(false)
+            .can_minimize(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child({
+                                        add = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(60.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Add")
+                                        .build(ctx);
+                                        add
+                                    })
+                                    .with_child({
+                                        remove = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(60.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Remove")
+                                        .build(ctx);
+                                        remove
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .on_row(1),
+                            )
+                            .with_content({
+                                list = ListViewBuilder::new(WidgetBuilder::new()).build(ctx);
+                                list
+                            })
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::strict(24.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            list,
+            add,
+            remove,
+            layers: Default::default(),
+        }
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.layers.clear();
+
+        send_sync_message(
+            ui,
+            ListViewMessage::items(self.list, MessageDirection::ToWidget, vec![]),
+        );
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface, data_model: &AbsmDataModel) {
+        let definition = &data_model.resource.data_ref().absm_definition;
+
+        let layers = std::iter::once((Handle::NONE, "Base Layer".to_owned()))
+            .chain(
+                definition
+                    .layers
+                    .pair_iter()
+                    .map(|(handle, layer)| (handle, layer.name.clone())),
+            )
+            .collect::<Vec<_>>();
+
+        if layers != self.layers {
+            let items = layers
+                .iter()
+                .map(|(_, name)| make_dropdown_list_option(&mut ui.build_ctx(), name))
+                .collect::<Vec<_>>();
+
+            send_sync_message(
+                ui,
+                ListViewMessage::items(self.list, MessageDirection::ToWidget, items),
+            );
+
+            self.layers = layers;
+        }
+
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(
+                self.list,
+                MessageDirection::ToWidget,
+                self.layers
+                    .iter()
+                    .position(|(handle, _)| *handle == data_model.current_layer),
+            ),
+        );
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        data_model: &AbsmDataModel,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.add {
+                sender.do_command(AddLayerCommand::new(LayerDefinition::default()));
+            } else if message.destination() == self.remove && data_model.current_layer.is_some() {
+                let layer = data_model.current_layer;
+                let definition = &data_model.resource.data_ref().absm_definition;
+
+                let mut group = vec![AbsmCommand::new(ChangeSelectionCommand {
+                    selection: vec![],
+                })];
+
+                // Remove every state of the layer together with its transitions.
+                group.extend(
+                    definition
+                        .transitions
+                        .pair_iter()
+                        .filter(|(_, transition)| {
+                            definition
+                                .states
+                                .try_borrow(transition.source)
+                                .map_or(false, |state| state.layer == layer)
+                        })
+                        .map(|(handle, _)| AbsmCommand::new(DeleteTransitionCommand::new(handle))),
+                );
+
+                group.extend(
+                    definition
+                        .states
+                        .pair_iter()
+                        .filter(|(_, state)| state.layer == layer)
+                        .map(|(handle, _)| AbsmCommand::new(DeleteStateCommand::new(handle))),
+                );
+
+                group.push(AbsmCommand::new(DeleteLayerCommand::new(layer)));
+
+                sender.set_current_layer(Handle::NONE);
+                sender.do_command(CommandGroup::from(group));
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some((layer, _)) = self.layers.get(*index) {
+                    if *layer != data_model.current_layer {
+                        sender.set_current_layer(*layer);
+
+                        if layer.is_some() {
+                            sender.do_command(ChangeSelectionCommand {
+                                selection: vec![SelectedEntity::Layer(*layer)],
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
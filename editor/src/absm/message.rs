@@ -1,4 +1,5 @@
 use crate::absm::command::{AbsmCommand, AbsmCommandTrait};
+use fyrox::{animation::machine::layer::LayerDefinition, core::pool::Handle};
 use std::{path::PathBuf, sync::mpsc::Sender};
 
 pub enum AbsmMessage {
@@ -11,6 +12,8 @@ pub enum AbsmMessage {
     SaveCurrentAbsm,
     Sync,
     SetPreviewModel(PathBuf),
+    /// Switches the state graph to the states of a given layer, `Handle::NONE` is the base layer.
+    SetCurrentLayer(Handle<LayerDefinition>),
 }
 
 pub struct MessageSender {
@@ -65,4 +68,8 @@ impl MessageSender {
     pub fn set_preview_model(&self, path: PathBuf) {
         self.send(AbsmMessage::SetPreviewModel(path))
     }
+
+    pub fn set_current_layer(&self, layer: Handle<LayerDefinition>) {
+        self.send(AbsmMessage::SetCurrentLayer(layer))
+    }
 }
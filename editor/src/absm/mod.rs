@@ -1,10 +1,12 @@
 use crate::{
     absm::{
+        blend_space::BlendSpaceEditor,
         command::{
-            blend::{AddInputCommand, AddPoseSourceCommand},
+            blend::{AddBlendSpacePointCommand, AddInputCommand, AddPoseSourceCommand},
             AbsmCommand, AbsmCommandStack, AbsmEditorContext,
         },
         inspector::Inspector,
+        layer::LayerPanel,
         menu::Menu,
         message::{AbsmMessage, MessageSender},
         node::{AbsmNode, AbsmNodeMessage},
@@ -18,8 +20,10 @@ use crate::{
 };
 use fyrox::{
     animation::machine::{
+        layer::LayerDefinition,
         node::{
             blend::{BlendPoseDefinition, IndexedBlendInputDefinition},
+            blend_space::BlendSpacePointDefinition,
             PoseNodeDefinition,
         },
         state::StateDefinition,
@@ -51,10 +55,12 @@ use std::{
     sync::mpsc::{channel, Receiver, Sender},
 };
 
+mod blend_space;
 mod canvas;
 mod command;
 mod connection;
 mod inspector;
+mod layer;
 mod menu;
 mod message;
 mod node;
@@ -78,6 +84,7 @@ pub enum SelectedEntity {
     Transition(Handle<TransitionDefinition>),
     State(Handle<StateDefinition>),
     PoseNode(Handle<PoseNodeDefinition>),
+    Layer(Handle<LayerDefinition>),
 }
 
 pub struct AbsmDataModel {
@@ -85,6 +92,8 @@ pub struct AbsmDataModel {
     preview_model_path: PathBuf,
     selection: Vec<SelectedEntity>,
     resource: AbsmResource,
+    // A layer which states are shown in the state graph, Handle::NONE is the base layer.
+    current_layer: Handle<LayerDefinition>,
 }
 
 impl AbsmDataModel {
@@ -97,6 +106,7 @@ impl AbsmDataModel {
                 path: Default::default(),
                 absm_definition: Default::default(),
             }))),
+            current_layer: Default::default(),
         }
     }
 
@@ -140,6 +150,7 @@ pub struct AbsmEditor {
     message_sender: MessageSender,
     message_receiver: Receiver<AbsmMessage>,
     inspector: Inspector,
+    blend_space_editor: BlendSpaceEditor,
     state_graph_viewer: StateGraphViewer,
     save_dialog: Handle<UiNode>,
     load_dialog: Handle<UiNode>,
@@ -147,6 +158,7 @@ pub struct AbsmEditor {
     state_viewer: StateViewer,
     menu: Menu,
     parameter_panel: ParameterPanel,
+    layer_panel: LayerPanel,
 }
 
 impl AbsmEditor {
@@ -161,9 +173,11 @@ impl AbsmEditor {
         let menu = Menu::new(ctx);
 
         let inspector = Inspector::new(ctx, sender.clone());
+        let blend_space_editor = BlendSpaceEditor::new(ctx);
         let state_graph_viewer = StateGraphViewer::new(ctx);
         let state_viewer = StateViewer::new(ctx);
         let parameter_panel = ParameterPanel::new(ctx, sender);
+        let layer_panel = LayerPanel::new(ctx);
 
         let docking_manager = DockingManagerBuilder::new(
             WidgetBuilder::new().on_row(1).with_child(
@@ -185,9 +199,25 @@ impl AbsmEditor {
                                                         ))
                                                         .build(ctx),
                                                     TileBuilder::new(WidgetBuilder::new())
-                                                        .with_content(TileContent::Window(
-                                                            parameter_panel.window,
-                                                        ))
+                                                        .with_content(TileContent::VerticalTiles {
+                                                            splitter: 0.6,
+                                                            tiles: [
+                                                                TileBuilder::new(
+                                                                    WidgetBuilder::new(),
+                                                                )
+                                                                .with_content(TileContent::Window(
+                                                                    parameter_panel.window,
+                                                                ))
+                                                                .build(ctx),
+                                                                TileBuilder::new(
+                                                                    WidgetBuilder::new(),
+                                                                )
+                                                                .with_content(TileContent::Window(
+                                                                    layer_panel.window,
+                                                                ))
+                                                                .build(ctx),
+                                                            ],
+                                                        })
                                                         .build(ctx),
                                                 ],
                                             })
@@ -213,7 +243,19 @@ impl AbsmEditor {
                                 })
                                 .build(ctx),
                             TileBuilder::new(WidgetBuilder::new())
-                                .with_content(TileContent::Window(inspector.window))
+                                .with_content(TileContent::VerticalTiles {
+                                    splitter: 0.6,
+                                    tiles: [
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::Window(inspector.window))
+                                            .build(ctx),
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::Window(
+                                                blend_space_editor.window,
+                                            ))
+                                            .build(ctx),
+                                    ],
+                                })
                                 .build(ctx),
                         ],
                     })
@@ -256,11 +298,13 @@ impl AbsmEditor {
             menu,
             state_graph_viewer,
             inspector,
+            blend_space_editor,
             save_dialog,
             load_dialog,
             previewer,
             state_viewer,
             parameter_panel,
+            layer_panel,
         }
     }
 
@@ -268,9 +312,11 @@ impl AbsmEditor {
         if let Some(data_model) = self.data_model.as_ref() {
             let ui = &mut engine.user_interface;
             self.parameter_panel.sync_to_model(ui, data_model);
+            self.layer_panel.sync_to_model(ui, data_model);
             self.state_graph_viewer.sync_to_model(data_model, ui);
             self.state_viewer.sync_to_model(ui, data_model);
             self.inspector.sync_to_model(ui, data_model);
+            self.blend_space_editor.sync_to_model(ui, data_model);
             self.previewer.set_absm(engine, &data_model.resource);
         }
     }
@@ -332,6 +378,8 @@ impl AbsmEditor {
             self.previewer.clear(engine);
             self.parameter_panel.reset(&mut engine.user_interface, None);
             self.inspector.clear(&engine.user_interface);
+            self.blend_space_editor.clear(&engine.user_interface);
+            self.layer_panel.clear(&engine.user_interface);
         }
     }
 
@@ -432,6 +480,12 @@ impl AbsmEditor {
                     need_sync = true;
                 }
                 AbsmMessage::SetPreviewModel(path) => self.set_preview_model(engine, &path),
+                AbsmMessage::SetCurrentLayer(layer) => {
+                    if let Some(data_model) = self.data_model.as_mut() {
+                        data_model.current_layer = layer;
+                        need_sync = true;
+                    }
+                }
             }
         }
 
@@ -455,8 +509,20 @@ impl AbsmEditor {
                 match event {
                     Event::ActiveStateChanged(state) => {
                         if let Some(state_ref) = machine.states().try_borrow(state) {
-                            self.state_graph_viewer
-                                .activate_state(&engine.user_interface, state_ref.definition);
+                            // Only the states of the current layer are shown.
+                            let layer = machine
+                                .layers()
+                                .try_borrow(state_ref.layer())
+                                .map_or(Handle::NONE, |layer| layer.definition);
+
+                            if self
+                                .data_model
+                                .as_ref()
+                                .map_or(false, |data_model| data_model.current_layer == layer)
+                            {
+                                self.state_graph_viewer
+                                    .activate_state(&engine.user_interface, state_ref.definition);
+                            }
                         }
                     }
                     Event::ActiveTransitionChanged(transition) => {
@@ -491,8 +557,12 @@ impl AbsmEditor {
             );
             self.inspector
                 .handle_ui_message(message, data_model, &self.message_sender);
+            self.blend_space_editor
+                .handle_ui_message(message, &self.message_sender);
             self.parameter_panel
                 .handle_ui_message(message, &self.message_sender);
+            self.layer_panel
+                .handle_ui_message(message, &self.message_sender, data_model);
         }
 
         if let Some(FileSelectorMessage::Commit(path)) = message.data() {
@@ -543,6 +613,13 @@ impl AbsmEditor {
                                         IndexedBlendInputDefinition::default(),
                                     ));
                                 }
+                                PoseNodeDefinition::BlendSpace(_) => {
+                                    self.message_sender
+                                        .do_command(AddBlendSpacePointCommand::new(
+                                            node.model_handle,
+                                            BlendSpacePointDefinition::default(),
+                                        ));
+                                }
                             }
                         }
                     }
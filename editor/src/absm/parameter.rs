@@ -3,7 +3,8 @@ use crate::{
         command::{
             AbsmCommand, AddParameterCommand, RemoveParameterCommand,
             SetParameterIndexValueCommand, SetParameterNameCommand, SetParameterRuleValueCommand,
-            SetParameterValueCommand, SetParameterWeightValueCommand,
+            SetParameterTriggerValueCommand, SetParameterValueCommand,
+            SetParameterWeightValueCommand,
         },
         message::MessageSender,
         AbsmDataModel,
@@ -176,6 +177,12 @@ fn handle_parameter_property_change(
                                 value: value.cast_clone::<u32>()?,
                             }))
                         }
+                        Parameter::TRIGGER_F_0 => {
+                            Some(AbsmCommand::new(SetParameterTriggerValueCommand {
+                                handle: index,
+                                value: value.cast_clone::<bool>()?,
+                            }))
+                        }
                         _ => None,
                     }
                 } else {
@@ -2,8 +2,8 @@ use crate::{
     absm::{
         canvas::{AbsmCanvasMessage, Mode},
         command::{
-            AbsmCommand, AddStateCommand, ChangeSelectionCommand, CommandGroup, DeleteStateCommand,
-            DeleteTransitionCommand, SetMachineEntryStateCommand,
+            layer::SetLayerEntryStateCommand, AbsmCommand, AddStateCommand, ChangeSelectionCommand,
+            CommandGroup, DeleteStateCommand, DeleteTransitionCommand, SetMachineEntryStateCommand,
         },
         message::MessageSender,
         node::AbsmNode,
@@ -58,6 +58,7 @@ impl CanvasContextMenu {
         sender: &MessageSender,
         message: &UiMessage,
         ui: &mut UserInterface,
+        data_model: &AbsmDataModel,
     ) {
         if let Some(MenuItemMessage::Click) = message.data() {
             if message.destination() == self.create_state {
@@ -67,6 +68,7 @@ impl CanvasContextMenu {
                     position: ui.node(self.canvas).screen_to_local(screen_position),
                     name: "New State".to_string(),
                     root: Default::default(),
+                    layer: data_model.current_layer,
                 }));
             }
         }
@@ -188,13 +190,21 @@ impl NodeContextMenu {
 
                 sender.do_command(CommandGroup::from(group));
             } else if message.destination() == self.set_as_entry_state {
-                sender.do_command(SetMachineEntryStateCommand {
-                    entry: ui
-                        .node(self.placement_target)
-                        .query_component::<AbsmNode<StateDefinition>>()
-                        .unwrap()
-                        .model_handle,
-                });
+                let entry = ui
+                    .node(self.placement_target)
+                    .query_component::<AbsmNode<StateDefinition>>()
+                    .unwrap()
+                    .model_handle;
+
+                let layer = data_model.resource.data_ref().absm_definition.states[entry].layer;
+                if layer.is_some() {
+                    sender.do_command(SetLayerEntryStateCommand {
+                        handle: layer,
+                        value: entry,
+                    });
+                } else {
+                    sender.do_command(SetMachineEntryStateCommand { entry });
+                }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == self.menu {
@@ -25,7 +25,6 @@ use fyrox::{
         BuildContext, Thickness, UiNode, UserInterface,
     },
 };
-use std::cmp::Ordering;
 
 mod context;
 
@@ -155,6 +154,7 @@ impl StateGraphViewer {
                                 dest,
                                 rule: "".to_string(),
                                 invert_rule: false,
+                                condition: "".to_string(),
                             }));
                         }
                     }
@@ -207,7 +207,7 @@ impl StateGraphViewer {
         self.node_context_menu
             .handle_ui_message(message, ui, data_model, sender);
         self.canvas_context_menu
-            .handle_ui_message(sender, message, ui);
+            .handle_ui_message(sender, message, ui, data_model);
         self.transition_context_menu
             .handle_ui_message(message, ui, sender);
     }
@@ -234,83 +234,77 @@ impl StateGraphViewer {
             .filter(|c| ui.node(*c).has_component::<Transition>())
             .collect::<Vec<_>>();
 
-        match states
-            .len()
-            .cmp(&(definition.states.alive_count() as usize))
+        // Only the states of the current layer are shown.
+        let layer_states = definition
+            .states
+            .pair_iter()
+            .filter(|(_, state)| state.layer == data_model.current_layer)
+            .collect::<Vec<_>>();
+
+        let entry_state = definition
+            .layers
+            .try_borrow(data_model.current_layer)
+            .map_or(definition.entry_state, |layer| layer.entry_state);
+
+        // Remove views of the states that were removed or belong to other layers.
+        for (state_view_handle, state_model_handle) in
+            states.clone().iter().cloned().map(|state_view| {
+                (
+                    state_view,
+                    ui.node(state_view)
+                        .query_component::<AbsmNode<StateDefinition>>()
+                        .unwrap()
+                        .model_handle,
+                )
+            })
         {
-            Ordering::Less => {
-                // A state was added.
-                for (state_handle, state) in definition.states.pair_iter() {
-                    if states.iter().all(|state_view| {
-                        ui.node(*state_view)
-                            .query_component::<AbsmNode<StateDefinition>>()
-                            .unwrap()
-                            .model_handle
-                            != state_handle
-                    }) {
-                        let state_view_handle = AbsmNodeBuilder::new(
-                            WidgetBuilder::new()
-                                .with_context_menu(self.node_context_menu.menu)
-                                .with_desired_position(state.position),
-                        )
-                        .with_normal_color(if state_handle == definition.entry_state {
-                            NORMAL_ROOT_COLOR
-                        } else {
-                            NORMAL_BACKGROUND
-                        })
-                        .with_selected_color(if state_handle == definition.entry_state {
-                            SELECTED_ROOT_COLOR
-                        } else {
-                            SELECTED_BACKGROUND
-                        })
-                        .with_model_handle(state_handle)
-                        .with_name(state.name.clone())
-                        .build(&mut ui.build_ctx());
-
-                        states.push(state_view_handle);
-
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::link(
-                                state_view_handle,
-                                MessageDirection::ToWidget,
-                                self.canvas,
-                            ),
-                        );
-                    }
+            if layer_states.iter().all(|(h, _)| *h != state_model_handle) {
+                send_sync_message(
+                    ui,
+                    WidgetMessage::remove(state_view_handle, MessageDirection::ToWidget),
+                );
+
+                if let Some(position) = states.iter().position(|s| *s == state_view_handle) {
+                    states.remove(position);
                 }
             }
-            Ordering::Greater => {
-                // A state was removed.
-                for (state_view_handle, state_model_handle) in
-                    states.clone().iter().cloned().map(|state_view| {
-                        (
-                            state_view,
-                            ui.node(state_view)
-                                .query_component::<AbsmNode<StateDefinition>>()
-                                .unwrap()
-                                .model_handle,
-                        )
-                    })
-                {
-                    if definition
-                        .states
-                        .pair_iter()
-                        .all(|(h, _)| h != state_model_handle)
-                    {
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::remove(state_view_handle, MessageDirection::ToWidget),
-                        );
-
-                        if let Some(position) = states.iter().position(|s| *s == state_view_handle)
-                        {
-                            states.remove(position);
-                        }
-                    }
-                }
+        }
+
+        // Add views for the states that were added or became visible.
+        for &(state_handle, state) in layer_states.iter() {
+            if states.iter().all(|state_view| {
+                ui.node(*state_view)
+                    .query_component::<AbsmNode<StateDefinition>>()
+                    .unwrap()
+                    .model_handle
+                    != state_handle
+            }) {
+                let state_view_handle = AbsmNodeBuilder::new(
+                    WidgetBuilder::new()
+                        .with_context_menu(self.node_context_menu.menu)
+                        .with_desired_position(state.position),
+                )
+                .with_normal_color(if state_handle == entry_state {
+                    NORMAL_ROOT_COLOR
+                } else {
+                    NORMAL_BACKGROUND
+                })
+                .with_selected_color(if state_handle == entry_state {
+                    SELECTED_ROOT_COLOR
+                } else {
+                    SELECTED_BACKGROUND
+                })
+                .with_model_handle(state_handle)
+                .with_name(state.name.clone())
+                .build(&mut ui.build_ctx());
+
+                states.push(state_view_handle);
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::link(state_view_handle, MessageDirection::ToWidget, self.canvas),
+                );
             }
-            _ => (),
         }
 
         // Sync state nodes.
@@ -347,7 +341,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::normal_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == definition.entry_state {
+                    if state_model_handle == entry_state {
                         NORMAL_ROOT_COLOR
                     } else {
                         NORMAL_BACKGROUND
@@ -359,7 +353,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::selected_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == definition.entry_state {
+                    if state_model_handle == entry_state {
                         SELECTED_ROOT_COLOR
                     } else {
                         SELECTED_BACKGROUND
@@ -372,101 +366,93 @@ impl StateGraphViewer {
         ui.update(ui.screen_size(), 0.0);
 
         // Sync transitions.
-        match transitions
-            .len()
-            .cmp(&(definition.transitions.alive_count() as usize))
+        let layer_transitions = definition
+            .transitions
+            .pair_iter()
+            .filter(|(_, transition)| {
+                definition
+                    .states
+                    .try_borrow(transition.source)
+                    .map_or(false, |source| source.layer == data_model.current_layer)
+            })
+            .collect::<Vec<_>>();
+
+        // Remove views of the transitions that were removed or belong to other layers.
+        for (transition_view_handle, transition_model_handle) in
+            transitions.clone().iter().cloned().map(|transition_view| {
+                (
+                    transition_view,
+                    ui.node(transition_view)
+                        .query_component::<Transition>()
+                        .unwrap()
+                        .model_handle,
+                )
+            })
         {
-            Ordering::Less => {
-                // A transition was added.
-                for (transition_handle, transition) in definition.transitions.pair_iter() {
-                    if transitions.iter().all(|transition_view| {
-                        ui.node(*transition_view)
-                            .query_component::<Transition>()
-                            .unwrap()
-                            .model_handle
-                            != transition_handle
-                    }) {
-                        fn find_state_view(
-                            state_handle: Handle<StateDefinition>,
-                            states: &[Handle<UiNode>],
-                            ui: &UserInterface,
-                        ) -> Handle<UiNode> {
-                            states
-                                .iter()
-                                .find(|s| {
-                                    ui.node(**s)
-                                        .query_component::<AbsmNode<StateDefinition>>()
-                                        .unwrap()
-                                        .model_handle
-                                        == state_handle
-                                })
-                                .cloned()
-                                .unwrap_or_default()
-                        }
+            if layer_transitions
+                .iter()
+                .all(|(h, _)| *h != transition_model_handle)
+            {
+                send_sync_message(
+                    ui,
+                    WidgetMessage::remove(transition_view_handle, MessageDirection::ToWidget),
+                );
 
-                        let transition_view = TransitionBuilder::new(
-                            WidgetBuilder::new()
-                                .with_context_menu(self.transition_context_menu.menu),
-                        )
-                        .with_source(find_state_view(transition.source, &states, ui))
-                        .with_dest(find_state_view(transition.dest, &states, ui))
-                        .build(transition_handle, &mut ui.build_ctx());
-
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::link(
-                                transition_view,
-                                MessageDirection::ToWidget,
-                                self.canvas,
-                            ),
-                        );
-
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::lowermost(transition_view, MessageDirection::ToWidget),
-                        );
-
-                        transitions.push(transition_view);
-                    }
+                if let Some(position) = transitions
+                    .iter()
+                    .position(|s| *s == transition_view_handle)
+                {
+                    transitions.remove(position);
                 }
             }
+        }
 
-            Ordering::Greater => {
-                // A transition was removed.
-                for (transition_view_handle, transition_model_handle) in
-                    transitions.clone().iter().cloned().map(|transition_view| {
-                        (
-                            transition_view,
-                            ui.node(transition_view)
-                                .query_component::<Transition>()
+        // Add views for the transitions that were added or became visible.
+        for &(transition_handle, transition) in layer_transitions.iter() {
+            if transitions.iter().all(|transition_view| {
+                ui.node(*transition_view)
+                    .query_component::<Transition>()
+                    .unwrap()
+                    .model_handle
+                    != transition_handle
+            }) {
+                fn find_state_view(
+                    state_handle: Handle<StateDefinition>,
+                    states: &[Handle<UiNode>],
+                    ui: &UserInterface,
+                ) -> Handle<UiNode> {
+                    states
+                        .iter()
+                        .find(|s| {
+                            ui.node(**s)
+                                .query_component::<AbsmNode<StateDefinition>>()
                                 .unwrap()
-                                .model_handle,
-                        )
-                    })
-                {
-                    if definition
-                        .transitions
-                        .pair_iter()
-                        .all(|(h, _)| h != transition_model_handle)
-                    {
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::remove(
-                                transition_view_handle,
-                                MessageDirection::ToWidget,
-                            ),
-                        );
-
-                        if let Some(position) = transitions
-                            .iter()
-                            .position(|s| *s == transition_view_handle)
-                        {
-                            transitions.remove(position);
-                        }
-                    }
+                                .model_handle
+                                == state_handle
+                        })
+                        .cloned()
+                        .unwrap_or_default()
                 }
+
+                let transition_view = TransitionBuilder::new(
+                    WidgetBuilder::new().with_context_menu(self.transition_context_menu.menu),
+                )
+                .with_source(find_state_view(transition.source, &states, ui))
+                .with_dest(find_state_view(transition.dest, &states, ui))
+                .build(transition_handle, &mut ui.build_ctx());
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::link(transition_view, MessageDirection::ToWidget, self.canvas),
+                );
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::lowermost(transition_view, MessageDirection::ToWidget),
+                );
+
+                transitions.push(transition_view);
             }
-            Ordering::Equal => {}
         }
 
         // Sync selection.
@@ -488,7 +474,7 @@ impl StateGraphViewer {
                         .model_handle
                         == *state
                 }),
-                SelectedEntity::PoseNode(_) => {
+                SelectedEntity::PoseNode(_) | SelectedEntity::Layer(_) => {
                     // No such nodes possible to have on this canvas.
                     None
                 }
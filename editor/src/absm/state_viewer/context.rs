@@ -2,8 +2,10 @@ use crate::{
     absm::{
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
+                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAnimationsPoseSourceCommand, SetBlendSpacePointPoseSourceCommand,
             },
+            layer::SetLayerAdditiveReferenceCommand,
             AbsmCommand, AddPoseNodeCommand, ChangeSelectionCommand, CommandGroup,
             DeletePoseNodeCommand, SetStateRootPoseCommand,
         },
@@ -18,6 +20,7 @@ use fyrox::{
     animation::machine::{
         node::{
             blend::{BlendAnimationsByIndexDefinition, BlendAnimationsDefinition},
+            blend_space::BlendSpaceDefinition,
             play::PlayAnimationDefinition,
             BasePoseNodeDefinition, PoseNodeDefinition,
         },
@@ -33,12 +36,14 @@ use fyrox::{
         widget::WidgetBuilder,
         BuildContext, UiNode, UserInterface,
     },
+    utils::log::Log,
 };
 
 pub struct CanvasContextMenu {
     create_play_animation: Handle<UiNode>,
     create_blend_animations: Handle<UiNode>,
     create_blend_by_index: Handle<UiNode>,
+    create_blend_space: Handle<UiNode>,
     pub menu: Handle<UiNode>,
     pub canvas: Handle<UiNode>,
     pub node_context_menu: Handle<UiNode>,
@@ -49,6 +54,7 @@ impl CanvasContextMenu {
         let create_play_animation;
         let create_blend_animations;
         let create_blend_by_index;
+        let create_blend_space;
         let menu = PopupBuilder::new(
             WidgetBuilder::new()
                 .with_enabled(false) // Disabled by default.
@@ -68,6 +74,10 @@ impl CanvasContextMenu {
                     .with_child({
                         create_blend_by_index = create_menu_item("Blend By Index", vec![], ctx);
                         create_blend_by_index
+                    })
+                    .with_child({
+                        create_blend_space = create_menu_item("Blend Space", vec![], ctx);
+                        create_blend_space
                     }),
             )
             .build(ctx),
@@ -78,6 +88,7 @@ impl CanvasContextMenu {
             create_play_animation,
             create_blend_animations,
             create_blend_by_index,
+            create_blend_space,
             menu,
             canvas: Default::default(),
             node_context_menu: Default::default(),
@@ -105,6 +116,7 @@ impl CanvasContextMenu {
                     animation: Default::default(),
                     speed: 1.0,
                     time_slice: None,
+                    skeleton_mapping: Default::default(),
                 }))
             } else if message.destination() == self.create_blend_animations {
                 Some(PoseNodeDefinition::BlendAnimations(
@@ -127,6 +139,16 @@ impl CanvasContextMenu {
                         inputs: Default::default(),
                     },
                 ))
+            } else if message.destination() == self.create_blend_space {
+                Some(PoseNodeDefinition::BlendSpace(BlendSpaceDefinition {
+                    base: BasePoseNodeDefinition {
+                        position,
+                        parent_state: current_state,
+                    },
+                    x_parameter: "".to_string(),
+                    y_parameter: "".to_string(),
+                    points: Default::default(),
+                }))
             } else {
                 None
             };
@@ -141,6 +163,7 @@ impl CanvasContextMenu {
 pub struct NodeContextMenu {
     remove: Handle<UiNode>,
     set_as_root: Handle<UiNode>,
+    set_as_additive_reference: Handle<UiNode>,
     pub menu: Handle<UiNode>,
     pub canvas: Handle<UiNode>,
     placement_target: Handle<UiNode>,
@@ -150,6 +173,7 @@ impl NodeContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let remove;
         let set_as_root;
+        let set_as_additive_reference;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
                 StackPanelBuilder::new(
@@ -158,6 +182,11 @@ impl NodeContextMenu {
                             set_as_root = create_menu_item("Set As Root", vec![], ctx);
                             set_as_root
                         })
+                        .with_child({
+                            set_as_additive_reference =
+                                create_menu_item("Set As Additive Reference", vec![], ctx);
+                            set_as_additive_reference
+                        })
                         .with_child({
                             remove = create_menu_item("Remove", vec![], ctx);
                             remove
@@ -170,6 +199,7 @@ impl NodeContextMenu {
         Self {
             remove,
             set_as_root,
+            set_as_additive_reference,
             menu,
             canvas: Default::default(),
             placement_target: Default::default(),
@@ -210,6 +240,30 @@ impl NodeContextMenu {
                     handle: definition.nodes[root].parent_state,
                     value: root,
                 })
+            } else if message.destination() == self.set_as_additive_reference {
+                let node = ui
+                    .node(self.placement_target)
+                    .query_component::<AbsmNode<PoseNodeDefinition>>()
+                    .unwrap()
+                    .model_handle;
+
+                let layer = definition
+                    .states
+                    .try_borrow(definition.nodes[node].parent_state)
+                    .map(|state| state.layer)
+                    .unwrap_or_default();
+
+                if layer.is_some() {
+                    sender.do_command(SetLayerAdditiveReferenceCommand {
+                        handle: layer,
+                        value: node,
+                    })
+                } else {
+                    Log::warn(
+                        "Additive reference can be set only for a state of an animation layer!"
+                            .to_owned(),
+                    );
+                }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == self.menu {
@@ -290,6 +344,13 @@ impl ConnectionContextMenu {
                             value: Default::default(),
                         })
                     }
+                    PoseNodeDefinition::BlendSpace(_) => {
+                        sender.do_command(SetBlendSpacePointPoseSourceCommand {
+                            handle: model_handle,
+                            index,
+                            value: Default::default(),
+                        })
+                    }
                 }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
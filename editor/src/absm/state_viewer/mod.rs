@@ -1,4 +1,6 @@
-use crate::absm::command::blend::SetBlendAnimationsPoseSourceCommand;
+use crate::absm::command::blend::{
+    SetBlendAnimationsPoseSourceCommand, SetBlendSpacePointPoseSourceCommand,
+};
 use crate::{
     absm::{
         canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
@@ -260,6 +262,13 @@ impl StateViewer {
                                     value: source_node,
                                 });
                             }
+                            PoseNodeDefinition::BlendSpace(_) => {
+                                sender.do_command(SetBlendSpacePointPoseSourceCommand {
+                                    handle: dest_node,
+                                    index: dest_socket_ref.index,
+                                    value: source_node,
+                                });
+                            }
                         }
                     }
                     _ => (),
@@ -358,6 +367,9 @@ impl StateViewer {
                                 "Blend Animations By Index",
                                 true,
                             ),
+                            PoseNodeDefinition::BlendSpace(blend_space) => {
+                                (blend_space.points.len(), "Blend Space", true)
+                            }
                         };
 
                         let node_view = AbsmNodeBuilder::new(
@@ -558,7 +570,9 @@ impl StateViewer {
             .selection
             .iter()
             .filter_map(|entry| match entry {
-                SelectedEntity::Transition(_) | SelectedEntity::State(_) => {
+                SelectedEntity::Transition(_)
+                | SelectedEntity::State(_)
+                | SelectedEntity::Layer(_) => {
                     // No such nodes possible to have on this canvas.
                     None
                 }
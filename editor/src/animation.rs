@@ -0,0 +1,1001 @@
+//! Key frame animation editor. It allows to scrub and preview an animation of a scene, record key
+//! frames by moving nodes, edit curves of each animated channel and add signal markers. Edited
+//! animation could be saved as a model resource together with the animated nodes, so it can be
+//! used in animation state machines.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    scene::commands::animation::{
+        AddAnimationCommand, AddAnimationSignalCommand, ConvertTracksToCurvesCommand,
+        RemoveAnimationSignalCommand, SetCurveTracksCommand,
+    },
+    send_sync_message,
+    utils::{create_file_selector, open_file_selector, window_content},
+    EditorScene, GridBuilder, Message, MessageDirection, Mode, SceneCommand, Selection,
+    UserInterface,
+};
+use fyrox::{
+    animation::{
+        curve_track::{CurveTrack, TrackBinding},
+        Animation, AnimationPose, AnimationSignal,
+    },
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        curve::Curve,
+        pool::Handle,
+        visitor::Visitor,
+    },
+    engine::Engine,
+    fxhash::FxHashMap,
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        curve::{CurveEditorBuilder, CurveEditorMessage},
+        decorator::DecoratorBuilder,
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::UiMessage,
+        scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBox, TextBoxBuilder, TextCommitMode},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node, Scene},
+    utils::log::Log,
+};
+use std::{path::Path, sync::mpsc::Sender};
+
+#[derive(Copy, Clone, PartialEq)]
+struct NodeTransform {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    scale: Vector3<f32>,
+}
+
+impl NodeTransform {
+    fn of(node: &Node) -> Self {
+        let transform = node.local_transform();
+        Self {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+        }
+    }
+
+    fn apply(&self, node: &mut Node) {
+        node.local_transform_mut()
+            .set_position(self.position)
+            .set_rotation(self.rotation)
+            .set_scale(self.scale);
+    }
+
+    fn euler_angles(&self) -> Vector3<f32> {
+        let (x, y, z) = self.rotation.euler_angles();
+        Vector3::new(x, y, z)
+    }
+}
+
+/// A single curve of a curve track, shown as a row in the channels list.
+#[derive(Copy, Clone, PartialEq)]
+struct Channel {
+    track: usize,
+    curve: usize,
+}
+
+pub struct AnimationEditor {
+    pub window: Handle<UiNode>,
+    animations: Handle<UiNode>,
+    add_animation: Handle<UiNode>,
+    save_animation: Handle<UiNode>,
+    convert_tracks: Handle<UiNode>,
+    play_pause: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    record: Handle<UiNode>,
+    add_key: Handle<UiNode>,
+    time_text: Handle<UiNode>,
+    timeline: Handle<UiNode>,
+    channels: Handle<UiNode>,
+    curve_editor: Handle<UiNode>,
+    signal_name: Handle<UiNode>,
+    add_signal: Handle<UiNode>,
+    remove_signal: Handle<UiNode>,
+    signals: Handle<UiNode>,
+    save_dialog: Handle<UiNode>,
+    animation_list: Vec<Handle<Animation>>,
+    animation: Handle<Animation>,
+    channel_list: Vec<Channel>,
+    selected_channel: Option<usize>,
+    selected_signal: Option<usize>,
+    time: f32,
+    playing: bool,
+    recording: bool,
+    // Transforms of the nodes before preview, they're restored when preview is stopped.
+    rest_pose: FxHashMap<Handle<Node>, NodeTransform>,
+    // Last known transforms of selected nodes, used to detect movement while recording.
+    recorded: FxHashMap<Handle<Node>, NodeTransform>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_item(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_child(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .with_text(text)
+                .build(ctx),
+        ),
+    ))
+    .build(ctx)
+}
+
+fn binding_name(binding: &TrackBinding) -> String {
+    match binding {
+        TrackBinding::Position => "Position".to_owned(),
+        TrackBinding::Rotation => "Rotation".to_owned(),
+        TrackBinding::Scale => "Scale".to_owned(),
+        TrackBinding::BlendShape(name) => format!("Blend Shape {}", name),
+        TrackBinding::Property(path) => path.clone(),
+    }
+}
+
+fn channel_name(track: &CurveTrack, curve: usize, graph: &Graph) -> String {
+    let node = graph
+        .try_get(track.node())
+        .map(|n| n.name().to_owned())
+        .unwrap_or_else(|| "<Invalid>".to_owned());
+    let component = if track.curves().len() > 1 {
+        [".X", ".Y", ".Z", ".W"]
+            .get(curve)
+            .copied()
+            .unwrap_or_default()
+    } else {
+        ""
+    };
+    format!("{}: {}{}", node, binding_name(track.binding()), component)
+}
+
+/// Sets a key of a transform track of given node at given time, the track is created if needed.
+fn set_transform_key(
+    tracks: &mut Vec<CurveTrack>,
+    node: Handle<Node>,
+    binding: TrackBinding,
+    value: Vector3<f32>,
+    time: f32,
+) {
+    let index = match tracks
+        .iter()
+        .position(|t| t.node() == node && *t.binding() == binding)
+    {
+        Some(index) => index,
+        None => {
+            tracks.push(CurveTrack::new(node, binding, vec![Curve::default(); 3]));
+            tracks.len() - 1
+        }
+    };
+    tracks[index].set_key(time, value.as_slice());
+}
+
+fn animated_nodes(animation: &Animation) -> Vec<Handle<Node>> {
+    animation
+        .get_tracks()
+        .iter()
+        .map(|t| t.get_node())
+        .chain(animation.curve_tracks().iter().map(|t| t.node()))
+        .collect()
+}
+
+impl AnimationEditor {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let animations;
+        let add_animation;
+        let save_animation;
+        let convert_tracks;
+        let play_pause;
+        let stop;
+        let record;
+        let add_key;
+        let time_text;
+        let timeline;
+        let channels;
+        let curve_editor;
+        let signal_name;
+        let add_signal;
+        let remove_signal;
+        let signals;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(800.0).with_height(500.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child({
+                                        animations = DropdownListBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(150.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        animations
+                                    })
+                                    .with_child({
+                                        add_animation = make_button(ctx, "New");
+                                        add_animation
+                                    })
+                                    .with_child({
+                                        save_animation = make_button(ctx, "Save As...");
+                                        save_animation
+                                    })
+                                    .with_child({
+                                        convert_tracks = make_button(ctx, "To Curves");
+                                        convert_tracks
+                                    })
+                                    .with_child({
+                                        play_pause = make_button(ctx, "Play/Pause");
+                                        play_pause
+                                    })
+                                    .with_child({
+                                        stop = make_button(ctx, "Stop");
+                                        stop
+                                    })
+                                    .with_child({
+                                        record = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_content(
+                                            TextBuilder::new(WidgetBuilder::new())
+                                                .with_vertical_text_alignment(
+                                                    VerticalAlignment::Center,
+                                                )
+                                                .with_text("Record")
+                                                .build(ctx),
+                                        )
+                                        .checked(Some(false))
+                                        .build(ctx);
+                                        record
+                                    })
+                                    .with_child({
+                                        add_key = make_button(ctx, "Add Key");
+                                        add_key
+                                    })
+                                    .with_child({
+                                        time_text = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("0.000 s")
+                                        .build(ctx);
+                                        time_text
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            timeline = ScrollBarBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .with_min(0.0)
+                            .with_max(0.0)
+                            .with_value(0.0)
+                            .with_step(0.01)
+                            .build(ctx);
+                            timeline
+                        })
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_child({
+                                        channels = ListViewBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        channels
+                                    })
+                                    .with_child({
+                                        curve_editor = CurveEditorBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        curve_editor
+                                    }),
+                            )
+                            .add_column(Column::strict(220.0))
+                            .add_column(Column::stretch())
+                            .add_row(Row::stretch())
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .with_child({
+                                        signal_name = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(150.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text_commit_mode(TextCommitMode::Immediate)
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Signal")
+                                        .build(ctx);
+                                        signal_name
+                                    })
+                                    .with_child({
+                                        add_signal = make_button(ctx, "Add Signal");
+                                        add_signal
+                                    })
+                                    .with_child({
+                                        remove_signal = make_button(ctx, "Remove Signal");
+                                        remove_signal
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            signals = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(4)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            signals
+                        }),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::strict(25.0))
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_row(Row::strict(80.0))
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Keyframe Editor"))
+            .build(ctx);
+
+        let save_dialog = create_file_selector(
+            ctx,
+            "rgs",
+            FileBrowserMode::Save {
+                default_file_name: "animation.rgs".into(),
+            },
+        );
+
+        Self {
+            window,
+            animations,
+            add_animation,
+            save_animation,
+            convert_tracks,
+            play_pause,
+            stop,
+            record,
+            add_key,
+            time_text,
+            timeline,
+            channels,
+            curve_editor,
+            signal_name,
+            add_signal,
+            remove_signal,
+            signals,
+            save_dialog,
+            animation_list: Default::default(),
+            animation: Default::default(),
+            channel_list: Default::default(),
+            selected_channel: None,
+            selected_signal: None,
+            time: 0.0,
+            playing: false,
+            recording: false,
+            rest_pose: Default::default(),
+            recorded: Default::default(),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &Sender<Message>,
+        engine: &mut Engine,
+    ) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.add_animation {
+                sender
+                    .send(Message::DoSceneCommand(SceneCommand::new(
+                        AddAnimationCommand::new(Animation::default()),
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.save_animation {
+                if scene.animations.try_get(self.animation).is_some() {
+                    open_file_selector(self.save_dialog, &engine.user_interface);
+                }
+            } else if scene.animations.try_get(self.animation).is_none() {
+                // Rest of the buttons require an animation.
+            } else if message.destination() == self.convert_tracks {
+                sender
+                    .send(Message::DoSceneCommand(SceneCommand::new(
+                        ConvertTracksToCurvesCommand::new(self.animation),
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.play_pause {
+                self.playing = !self.playing;
+            } else if message.destination() == self.stop {
+                self.playing = false;
+                self.restore_rest_pose(&mut scene.graph);
+                self.set_time(0.0, &engine.user_interface);
+            } else if message.destination() == self.add_key {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let mut tracks = scene.animations[self.animation].curve_tracks().to_vec();
+                    for &node in selection.nodes() {
+                        let transform = NodeTransform::of(&scene.graph[node]);
+                        self.rest_pose.entry(node).or_insert(transform);
+                        set_transform_key(
+                            &mut tracks,
+                            node,
+                            TrackBinding::Position,
+                            transform.position,
+                            self.time,
+                        );
+                        set_transform_key(
+                            &mut tracks,
+                            node,
+                            TrackBinding::Rotation,
+                            transform.euler_angles(),
+                            self.time,
+                        );
+                        set_transform_key(
+                            &mut tracks,
+                            node,
+                            TrackBinding::Scale,
+                            transform.scale,
+                            self.time,
+                        );
+                    }
+                    sender
+                        .send(Message::DoSceneCommand(SceneCommand::new(
+                            SetCurveTracksCommand::new(self.animation, tracks),
+                        )))
+                        .unwrap();
+                }
+            } else if message.destination() == self.add_signal {
+                let animation = &scene.animations[self.animation];
+                let id = animation
+                    .signals()
+                    .iter()
+                    .map(|s| s.id())
+                    .max()
+                    .map_or(0, |id| id + 1);
+                let name = engine
+                    .user_interface
+                    .node(self.signal_name)
+                    .cast::<TextBox>()
+                    .expect("Must be TextBox!")
+                    .text();
+                sender
+                    .send(Message::DoSceneCommand(SceneCommand::new(
+                        AddAnimationSignalCommand::new(
+                            self.animation,
+                            AnimationSignal::new(id, self.time).with_name(name),
+                        ),
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.remove_signal {
+                if let Some(index) = self.selected_signal {
+                    sender
+                        .send(Message::DoSceneCommand(SceneCommand::new(
+                            RemoveAnimationSignalCommand::new(self.animation, index),
+                        )))
+                        .unwrap();
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.record
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.recording = *value;
+                self.recorded.clear();
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    for &node in selection.nodes() {
+                        self.recorded
+                            .insert(node, NodeTransform::of(&scene.graph[node]));
+                    }
+                }
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.timeline
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.playing = false;
+                self.set_time(*value, &engine.user_interface);
+                self.apply_preview(scene);
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.animations
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(&animation) = self.animation_list.get(*index) {
+                    self.set_animation(animation, &mut scene.graph, &engine.user_interface);
+                }
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.channels {
+                    self.selected_channel = *selection;
+                    self.sync_curve_editor(scene, &engine.user_interface);
+                } else if message.destination() == self.signals {
+                    self.selected_signal = *selection;
+                }
+            }
+        } else if let Some(CurveEditorMessage::Sync(curve)) = message.data() {
+            if message.destination() == self.curve_editor
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let (Some(animation), Some(channel)) = (
+                    scene.animations.try_get(self.animation),
+                    self.selected_channel
+                        .and_then(|i| self.channel_list.get(i))
+                        .copied(),
+                ) {
+                    let mut tracks = animation.curve_tracks().to_vec();
+                    if let Some(track) = tracks.get_mut(channel.track) {
+                        if let Some(target) = track.curves_mut().get_mut(channel.curve) {
+                            *target = curve.clone();
+                            sender
+                                .send(Message::DoSceneCommand(SceneCommand::new(
+                                    SetCurveTracksCommand::new(self.animation, tracks),
+                                )))
+                                .unwrap();
+                        }
+                    }
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.save_dialog {
+                self.save_as(scene, path);
+            }
+        }
+    }
+
+    fn set_animation(
+        &mut self,
+        animation: Handle<Animation>,
+        graph: &mut Graph,
+        ui: &UserInterface,
+    ) {
+        if self.animation != animation {
+            self.restore_rest_pose(graph);
+            self.animation = animation;
+            self.playing = false;
+            self.selected_channel = None;
+            self.selected_signal = None;
+            self.channel_list.clear();
+            self.set_time(0.0, ui);
+        }
+    }
+
+    fn set_time(&mut self, time: f32, ui: &UserInterface) {
+        self.time = time;
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.timeline, MessageDirection::ToWidget, time),
+        );
+        ui.send_message(TextMessage::text(
+            self.time_text,
+            MessageDirection::ToWidget,
+            format!("{:.3} s", time),
+        ));
+    }
+
+    fn restore_rest_pose(&mut self, graph: &mut Graph) {
+        for (node, transform) in self.rest_pose.drain() {
+            if let Some(node) = graph.try_get_mut(node) {
+                transform.apply(node);
+            }
+        }
+        self.recorded.clear();
+    }
+
+    /// Applies the pose of the animation at current time to the scene. Only transforms of the
+    /// nodes are restored when the preview ends.
+    fn apply_preview(&mut self, scene: &mut Scene) {
+        let animation = match scene.animations.try_get(self.animation) {
+            Some(animation) => animation,
+            None => return,
+        };
+
+        for node in animated_nodes(animation) {
+            if let Some(node_ref) = scene.graph.try_get(node) {
+                self.rest_pose
+                    .entry(node)
+                    .or_insert_with(|| NodeTransform::of(node_ref));
+            }
+        }
+
+        let range = animation.playback_range();
+        let length = range.end - range.start;
+        let normalized = if length > 0.0 {
+            (self.time - range.start) / length
+        } else {
+            0.0
+        };
+        let mut pose = AnimationPose::default();
+        animation.evaluate_pose_at_normalized(normalized, &mut pose);
+        pose.apply(&mut scene.graph);
+
+        // Preview is not a movement that should be recorded.
+        for (node, transform) in self.recorded.iter_mut() {
+            if let Some(node) = scene.graph.try_get(*node) {
+                *transform = NodeTransform::of(node);
+            }
+        }
+    }
+
+    fn sync_curve_editor(&self, scene: &Scene, ui: &UserInterface) {
+        let curve = scene
+            .animations
+            .try_get(self.animation)
+            .zip(self.selected_channel.and_then(|i| self.channel_list.get(i)))
+            .and_then(|(animation, channel)| {
+                animation
+                    .curve_tracks()
+                    .get(channel.track)
+                    .and_then(|t| t.curves().get(channel.curve))
+            })
+            .cloned()
+            .unwrap_or_default();
+        send_sync_message(
+            ui,
+            CurveEditorMessage::sync(self.curve_editor, MessageDirection::ToWidget, curve),
+        );
+    }
+
+    /// Copies animated hierarchy into a new scene together with the animation and saves it, so
+    /// it could be instantiated as a model resource.
+    fn save_as(&self, scene: &Scene, path: &Path) {
+        let animation = match scene.animations.try_get(self.animation) {
+            Some(animation) => animation,
+            None => return,
+        };
+
+        let graph = &scene.graph;
+        let mut root = match animation.first_animated_node() {
+            Some(node) if graph.is_valid_handle(node) => node,
+            _ => {
+                Log::err("Unable to save animation without animated nodes!".to_owned());
+                return;
+            }
+        };
+        while graph[root].parent().is_some() && graph[root].parent() != graph.get_root() {
+            root = graph[root].parent();
+        }
+
+        let mut new_scene = Scene::new();
+        let (copy, old_new_map) = graph.copy_node(root, &mut new_scene.graph, &mut |_, _| true);
+        let new_root = new_scene.graph.get_root();
+        new_scene.graph.link_nodes(copy, new_root);
+
+        let mut new_animation = Animation::default();
+        new_animation
+            .set_loop(animation.is_loop())
+            .set_speed(animation.get_speed());
+        for signal in animation.signals() {
+            new_animation.add_signal(signal.clone());
+        }
+        if let Some(mut settings) = animation.root_motion_settings().cloned() {
+            if old_new_map.try_map(&mut settings.node) {
+                new_animation.set_root_motion_settings(Some(settings));
+            }
+        }
+        let curve_tracks = animation
+            .curve_tracks()
+            .iter()
+            .cloned()
+            .chain(
+                animation
+                    .get_tracks()
+                    .iter()
+                    .flat_map(|t| t.to_curve_tracks()),
+            )
+            .chain(
+                animation
+                    .blend_shape_tracks()
+                    .iter()
+                    .map(|t| t.to_curve_track()),
+            );
+        for mut track in curve_tracks {
+            let mut node = track.node();
+            if old_new_map.try_map(&mut node) {
+                track.set_node(node);
+                new_animation.add_curve_track(track);
+            }
+        }
+        new_animation.fit_length_to_content();
+        new_scene.animations.add(new_animation);
+
+        let mut visitor = Visitor::new();
+        Log::verify(new_scene.save("Scene", &mut visitor));
+        Log::verify(visitor.save_binary(path));
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        sender: &Sender<Message>,
+    ) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+        let ui = &mut engine.user_interface;
+
+        let animation_list = scene
+            .animations
+            .pair_iter()
+            .map(|(h, _)| h)
+            .collect::<Vec<_>>();
+        if animation_list != self.animation_list {
+            let items = animation_list
+                .iter()
+                .enumerate()
+                .map(|(i, &h)| {
+                    let name = match scene.animations[h].get_resource() {
+                        Some(resource) => format!("{} - {}", i, resource.state().path().display()),
+                        None => format!("Animation {}", i),
+                    };
+                    make_dropdown_list_option(&mut ui.build_ctx(), &name)
+                })
+                .collect();
+            ui.send_message(DropdownListMessage::items(
+                self.animations,
+                MessageDirection::ToWidget,
+                items,
+            ));
+            self.animation_list = animation_list;
+        }
+        if !self.animation_list.contains(&self.animation) {
+            let animation = self.animation_list.first().cloned().unwrap_or_default();
+            self.set_animation(animation, &mut scene.graph, ui);
+        }
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.animations,
+                MessageDirection::ToWidget,
+                self.animation_list
+                    .iter()
+                    .position(|&h| h == self.animation),
+            ),
+        );
+
+        let animation = match scene.animations.try_get(self.animation) {
+            Some(animation) => animation,
+            None => return,
+        };
+
+        send_sync_message(
+            ui,
+            ScrollBarMessage::max_value(
+                self.timeline,
+                MessageDirection::ToWidget,
+                animation.length(),
+            ),
+        );
+
+        let mut channel_list = Vec::new();
+        let mut channel_items = Vec::new();
+        for (track_index, track) in animation.curve_tracks().iter().enumerate() {
+            for curve in 0..track.curves().len() {
+                channel_list.push(Channel {
+                    track: track_index,
+                    curve,
+                });
+                channel_items.push(make_item(
+                    &mut ui.build_ctx(),
+                    &channel_name(track, curve, &scene.graph),
+                ));
+            }
+        }
+        // Key frame tracks must be converted to curves first to be editable.
+        for track in animation.get_tracks() {
+            let name = scene
+                .graph
+                .try_get(track.get_node())
+                .map(|n| n.name().to_owned())
+                .unwrap_or_default();
+            channel_items.push(make_item(
+                &mut ui.build_ctx(),
+                &format!("{}: Key Frames (Read Only)", name),
+            ));
+        }
+        if channel_list != self.channel_list {
+            self.selected_channel = None;
+        }
+        self.channel_list = channel_list;
+        ui.send_message(ListViewMessage::items(
+            self.channels,
+            MessageDirection::ToWidget,
+            channel_items,
+        ));
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(
+                self.channels,
+                MessageDirection::ToWidget,
+                self.selected_channel,
+            ),
+        );
+
+        let signal_items = animation
+            .signals()
+            .iter()
+            .map(|s| {
+                make_item(
+                    &mut ui.build_ctx(),
+                    &format!("{} @ {:.3} s", s.name(), s.time()),
+                )
+            })
+            .collect::<Vec<_>>();
+        if self
+            .selected_signal
+            .map_or(false, |i| i >= signal_items.len())
+        {
+            self.selected_signal = None;
+        }
+        ui.send_message(ListViewMessage::items(
+            self.signals,
+            MessageDirection::ToWidget,
+            signal_items,
+        ));
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(
+                self.signals,
+                MessageDirection::ToWidget,
+                self.selected_signal,
+            ),
+        );
+
+        self.sync_curve_editor(scene, ui);
+
+        if self.recording && !self.playing {
+            if let Selection::Graph(selection) = &editor_scene.selection {
+                let mut tracks = animation.curve_tracks().to_vec();
+                let mut changed = false;
+                for &node in selection.nodes() {
+                    let current = match scene.graph.try_get(node) {
+                        Some(node) => NodeTransform::of(node),
+                        None => continue,
+                    };
+                    if let Some(previous) = self.recorded.insert(node, current) {
+                        if previous == current {
+                            continue;
+                        }
+                        // Node must return to its state before recording when preview stops.
+                        self.rest_pose.entry(node).or_insert(previous);
+                        if previous.position != current.position {
+                            set_transform_key(
+                                &mut tracks,
+                                node,
+                                TrackBinding::Position,
+                                current.position,
+                                self.time,
+                            );
+                        }
+                        if previous.rotation != current.rotation {
+                            set_transform_key(
+                                &mut tracks,
+                                node,
+                                TrackBinding::Rotation,
+                                current.euler_angles(),
+                                self.time,
+                            );
+                        }
+                        if previous.scale != current.scale {
+                            set_transform_key(
+                                &mut tracks,
+                                node,
+                                TrackBinding::Scale,
+                                current.scale,
+                                self.time,
+                            );
+                        }
+                        changed = true;
+                    }
+                }
+                if changed {
+                    sender
+                        .send(Message::DoSceneCommand(SceneCommand::new(
+                            SetCurveTracksCommand::new(self.animation, tracks),
+                        )))
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine, dt: f32) {
+        if !self.playing {
+            return;
+        }
+
+        let scene = &mut engine.scenes[editor_scene.scene];
+        let range = match scene.animations.try_get(self.animation) {
+            Some(animation) => animation.playback_range(),
+            None => return,
+        };
+
+        let mut time = self.time + dt;
+        if time > range.end {
+            time = range.start;
+        }
+        self.set_time(time, &engine.user_interface);
+        self.apply_preview(scene);
+    }
+
+    pub fn clear(&mut self, engine: &mut Engine) {
+        self.playing = false;
+        self.animation = Default::default();
+        self.animation_list.clear();
+        self.channel_list.clear();
+        self.selected_channel = None;
+        self.selected_signal = None;
+        // Scene is already gone at this moment, so there is nothing to restore.
+        self.rest_pose.clear();
+        self.recorded.clear();
+
+        let ui = &engine.user_interface;
+        ui.send_message(DropdownListMessage::items(
+            self.animations,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        ui.send_message(ListViewMessage::items(
+            self.channels,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        ui.send_message(ListViewMessage::items(
+            self.signals,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        self.set_time(0.0, ui);
+    }
+
+    pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
+        ui.send_message(WidgetMessage::enabled(
+            window_content(self.window, ui),
+            MessageDirection::ToWidget,
+            mode.is_edit(),
+        ));
+    }
+}
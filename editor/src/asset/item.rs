@@ -151,7 +151,7 @@ impl AssetItemBuilder {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
                     }
-                    "ogg" | "wav" => {
+                    "ogg" | "wav" | "flac" | "mp3" | "opus" => {
                         kind = AssetKind::Sound;
                         load_image(include_bytes!("../../resources/embed/sound.png"))
                     }
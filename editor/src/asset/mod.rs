@@ -261,6 +261,9 @@ impl AssetBrowser {
                                     | "bmp"
                                     | "ogg"
                                     | "wav"
+                                    | "flac"
+                                    | "mp3"
+                                    | "opus"
                                     | "shader"
                                     | "absm"
                             )
@@ -1,7 +1,12 @@
 use crate::utils::window_content;
 use crate::{
-    scene::commands::effect::AddEffectCommand, ChangeSelectionCommand, EditorScene, GridBuilder,
-    Message, MessageDirection, Mode, SceneCommand, Selection, UserInterface,
+    gui::make_dropdown_list_option,
+    scene::commands::{
+        audio_bus::{AddAudioBusCommand, RemoveAudioBusCommand},
+        effect::AddEffectCommand,
+    },
+    ChangeSelectionCommand, CommandGroup, EditorScene, GridBuilder, Message, MessageDirection,
+    Mode, SceneCommand, Selection, UserInterface,
 };
 use fyrox::gui::widget::WidgetMessage;
 use fyrox::{
@@ -11,6 +16,7 @@ use fyrox::{
         border::BorderBuilder,
         button::{ButtonBuilder, ButtonMessage},
         decorator::DecoratorBuilder,
+        dropdown_list::{DropdownList, DropdownListBuilder},
         grid::{Column, Row},
         list_view::{ListView, ListViewBuilder, ListViewMessage},
         message::UiMessage,
@@ -20,7 +26,14 @@ use fyrox::{
         window::{WindowBuilder, WindowTitle},
         Orientation, Thickness, UiNode,
     },
-    scene::sound::effect::{BaseEffectBuilder, Effect, ReverbEffectBuilder},
+    scene::sound::{
+        effect::{
+            BaseEffectBuilder, ChorusEffectBuilder, CompressorEffectBuilder, DelayEffectBuilder,
+            DistortionEffectBuilder, Effect, EqualizerEffectBuilder, LimiterEffectBuilder,
+            ReverbEffectBuilder,
+        },
+        AudioBus,
+    },
 };
 use std::{cmp::Ordering, rc::Rc, sync::mpsc::Sender};
 
@@ -39,11 +52,31 @@ impl EffectSelection {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioBusSelection {
+    pub buses: Vec<Handle<AudioBus>>,
+}
+
+impl AudioBusSelection {
+    pub fn is_empty(&self) -> bool {
+        self.buses.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buses.len()
+    }
+}
+
 pub struct AudioPanel {
     pub window: Handle<UiNode>,
     edit_context: Handle<UiNode>,
+    effect_kind: Handle<UiNode>,
     add_effect: Handle<UiNode>,
     effects: Handle<UiNode>,
+    add_bus: Handle<UiNode>,
+    remove_bus: Handle<UiNode>,
+    buses: Handle<UiNode>,
+    bus_items: Vec<(Handle<AudioBus>, String)>,
 }
 
 fn item_effect(item: Handle<UiNode>, ui: &UserInterface) -> Handle<Effect> {
@@ -52,13 +85,49 @@ fn item_effect(item: Handle<UiNode>, ui: &UserInterface) -> Handle<Effect> {
         .expect("Must be Handle<Effect>")
 }
 
+fn item_bus(item: Handle<UiNode>, ui: &UserInterface) -> Handle<AudioBus> {
+    *ui.node(item)
+        .user_data_ref::<Handle<AudioBus>>()
+        .expect("Must be Handle<AudioBus>")
+}
+
+const EFFECT_KINDS: [&str; 8] = [
+    "Reverb",
+    "Equalizer",
+    "Compressor",
+    "Limiter",
+    "Delay",
+    "Chorus",
+    "Flanger",
+    "Distortion",
+];
+
+fn make_effect(kind: usize) -> Effect {
+    let base = BaseEffectBuilder::new().with_name(EFFECT_KINDS[kind].to_owned());
+    match kind {
+        0 => ReverbEffectBuilder::new(base).build_effect(),
+        1 => EqualizerEffectBuilder::new(base).build_effect(),
+        2 => CompressorEffectBuilder::new(base).build_effect(),
+        3 => LimiterEffectBuilder::new(base).build_effect(),
+        4 => DelayEffectBuilder::new(base).build_effect(),
+        5 => ChorusEffectBuilder::new(base).build_effect(),
+        6 => ChorusEffectBuilder::flanger(base).build_effect(),
+        7 => DistortionEffectBuilder::new(base).build_effect(),
+        _ => unreachable!(),
+    }
+}
+
 impl AudioPanel {
     pub fn new(engine: &mut Engine) -> Self {
         let ctx = &mut engine.user_interface.build_ctx();
 
         let edit_context;
+        let effect_kind;
         let add_effect;
         let effects;
+        let add_bus;
+        let remove_bus;
+        let buses;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_content(
                 GridBuilder::new(
@@ -72,13 +141,28 @@ impl AudioPanel {
                             StackPanelBuilder::new(
                                 WidgetBuilder::new()
                                     .on_row(1)
+                                    .with_child({
+                                        effect_kind = DropdownListBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_items(
+                                            EFFECT_KINDS
+                                                .iter()
+                                                .map(|kind| make_dropdown_list_option(ctx, kind))
+                                                .collect(),
+                                        )
+                                        .with_selected(0)
+                                        .build(ctx);
+                                        effect_kind
+                                    })
                                     .with_child({
                                         add_effect = ButtonBuilder::new(
                                             WidgetBuilder::new()
                                                 .with_margin(Thickness::uniform(1.0)),
                                         )
-                                        // TODO: Add selector when there's more effects.
-                                        .with_text("Add Reverb")
+                                        .with_text("Add Effect")
                                         .build(ctx);
                                         add_effect
                                     })
@@ -94,11 +178,43 @@ impl AudioPanel {
                             )
                             .with_orientation(Orientation::Horizontal)
                             .build(ctx),
+                        )
+                        .with_child({
+                            buses = ListViewBuilder::new(WidgetBuilder::new().on_row(2)).build(ctx);
+                            buses
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .with_child({
+                                        add_bus = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Add Bus")
+                                        .build(ctx);
+                                        add_bus
+                                    })
+                                    .with_child({
+                                        remove_bus = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Remove Bus")
+                                        .build(ctx);
+                                        remove_bus
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
                         ),
                 )
                 .add_column(Column::stretch())
                 .add_row(Row::stretch())
                 .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
                 .build(ctx),
             )
             .with_title(WindowTitle::text("Audio Context"))
@@ -107,8 +223,13 @@ impl AudioPanel {
         Self {
             window,
             effects,
+            effect_kind,
             add_effect,
             edit_context,
+            buses,
+            add_bus,
+            remove_bus,
+            bus_items: Default::default(),
         }
     }
 
@@ -130,19 +251,66 @@ impl AudioPanel {
                     )))
                     .unwrap();
             } else if message.destination() == self.add_effect {
+                let kind = engine
+                    .user_interface
+                    .node(self.effect_kind)
+                    .cast::<DropdownList>()
+                    .expect("Must be DropdownList")
+                    .selection()
+                    .unwrap_or_default();
+
                 sender
                     .send(Message::DoSceneCommand(SceneCommand::new(
-                        AddEffectCommand::new(
-                            ReverbEffectBuilder::new(
-                                BaseEffectBuilder::new().with_name("Reverb".to_owned()),
-                            )
-                            .build_effect(),
-                        ),
+                        AddEffectCommand::new(make_effect(kind)),
                     )))
                     .unwrap()
+            } else if message.destination() == self.add_bus {
+                sender
+                    .send(Message::do_scene_command(AddAudioBusCommand::new(
+                        AudioBus::new("Bus"),
+                    )))
+                    .unwrap()
+            } else if message.destination() == self.remove_bus {
+                if let Selection::AudioBus(ref selection) = editor_scene.selection {
+                    let commands = selection
+                        .buses
+                        .iter()
+                        .map(|&bus| SceneCommand::new(RemoveAudioBusCommand::new(bus)))
+                        .chain(std::iter::once(SceneCommand::new(
+                            ChangeSelectionCommand::new(
+                                Selection::None,
+                                editor_scene.selection.clone(),
+                            ),
+                        )))
+                        .collect::<Vec<_>>();
+                    sender
+                        .send(Message::do_scene_command(CommandGroup::from(commands)))
+                        .unwrap()
+                }
             }
-        } else if let Some(ListViewMessage::SelectionChanged(Some(effect_index))) = message.data() {
-            if message.destination() == self.effects
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.buses
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let ui = &engine.user_interface;
+
+                let bus = item_bus(
+                    ui.node(self.buses)
+                        .cast::<ListView>()
+                        .expect("Must be ListView")
+                        .items()[*index],
+                    ui,
+                );
+
+                sender
+                    .send(Message::DoSceneCommand(SceneCommand::new(
+                        ChangeSelectionCommand::new(
+                            Selection::AudioBus(AudioBusSelection { buses: vec![bus] }),
+                            editor_scene.selection.clone(),
+                        ),
+                    )))
+                    .unwrap()
+            } else if message.destination() == self.effects
                 && message.direction() == MessageDirection::FromWidget
             {
                 let ui = &engine.user_interface;
@@ -151,7 +319,7 @@ impl AudioPanel {
                     ui.node(self.effects)
                         .cast::<ListView>()
                         .expect("Must be ListView")
-                        .items()[*effect_index],
+                        .items()[*index],
                     ui,
                 );
 
@@ -217,6 +385,37 @@ impl AudioPanel {
             }
             _ => (),
         }
+
+        // Buses could be renamed or re-parented, so the list is rebuilt on any change.
+        let bus_items = context
+            .bus_graph()
+            .buses()
+            .map(|(handle, bus)| (handle, bus.name().to_owned()))
+            .collect::<Vec<_>>();
+        if bus_items != self.bus_items {
+            let items = bus_items
+                .iter()
+                .map(|(handle, name)| {
+                    let ctx = &mut ui.build_ctx();
+                    DecoratorBuilder::new(BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .with_user_data(Rc::new(*handle))
+                            .with_child(
+                                TextBuilder::new(WidgetBuilder::new())
+                                    .with_text(name)
+                                    .build(ctx),
+                            ),
+                    ))
+                    .build(ctx)
+                })
+                .collect();
+            ui.send_message(ListViewMessage::items(
+                self.buses,
+                MessageDirection::ToWidget,
+                items,
+            ));
+            self.bus_items = bus_items;
+        }
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
@@ -19,6 +19,9 @@ use fyrox::{
         absm::{AbsmResource, AbsmResourceState},
         curve::{CurveResource, CurveResourceError, CurveResourceState},
         model::{MaterialSearchOptions, Model, ModelData, ModelLoadError},
+        physics_material::{
+            PhysicsMaterialResource, PhysicsMaterialResourceError, PhysicsMaterialResourceState,
+        },
         texture::{
             CompressionOptions, TextureMagnificationFilter, TextureMinificationFilter,
             TextureWrapMode,
@@ -46,8 +49,12 @@ use fyrox::{
         rigidbody::RigidBodyType,
         sound::{
             self,
-            effect::{BaseEffect, EffectInput},
-            Biquad, DistanceModel, Status,
+            effect::{
+                BaseEffect, DistortionShape, EffectInput, EqualizerBand, EqualizerBandKind,
+                TempoSync,
+            },
+            occlusion::OcclusionSettings,
+            AudioBus, Biquad, DistanceModel, Ducking, Status,
         },
         sound::{SoundBufferResource, SoundBufferResourceLoadError, SoundBufferState},
         terrain::Layer,
@@ -100,6 +107,7 @@ pub fn make_property_editors_container(
     container.insert(VecCollectionPropertyEditorDefinition::<LodControlledObject>::new());
     container.insert(VecCollectionPropertyEditorDefinition::<GeometrySource>::new());
     container.insert(VecCollectionPropertyEditorDefinition::<EffectInput>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<EqualizerBand>::new());
     container.insert(make_status_enum_editor_definition());
     container.insert(EnumPropertyEditorDefinition::<f32>::new_optional());
     container.insert(EnumPropertyEditorDefinition::<LodGroup>::new_optional());
@@ -131,6 +139,13 @@ pub fn make_property_editors_container(
     >::new(Rc::new(|resource_manager, path| {
         block_on(resource_manager.request_curve(path))
     })));
+    container.insert(ResourceFieldPropertyEditorDefinition::<
+        PhysicsMaterialResource,
+        PhysicsMaterialResourceState,
+        PhysicsMaterialResourceError,
+    >::new(Rc::new(|resource_manager, path| {
+        block_on(resource_manager.request_physics_material(path))
+    })));
     container.insert(ResourceFieldPropertyEditorDefinition::<
         Shader,
         ShaderState,
@@ -150,11 +165,18 @@ pub fn make_property_editors_container(
     container.insert(InspectablePropertyEditorDefinition::<OrthographicProjection>::new());
     container.insert(InspectablePropertyEditorDefinition::<Transform>::new());
     container.insert(InspectablePropertyEditorDefinition::<CsmOptions>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Ducking>::new());
+    container.insert(InspectablePropertyEditorDefinition::<OcclusionSettings>::new());
     container.insert(ArrayPropertyEditorDefinition::<f32, 3>::new());
     container.insert(ArrayPropertyEditorDefinition::<f32, 2>::new());
     container.insert(EnumPropertyEditorDefinition::<ColorGradingLut>::new_optional());
     container.insert(EnumPropertyEditorDefinition::<Biquad>::new_optional());
     container.insert(EnumPropertyEditorDefinition::<Box<SkyBox>>::new_optional());
+    container.insert(EnumPropertyEditorDefinition::<Ducking>::new_optional());
+    container.insert(EnumPropertyEditorDefinition::<TempoSync>::new_optional());
+    container.insert(HandlePropertyEditorDefinition::<AudioBus>::new(
+        sender.clone(),
+    ));
     container.insert(HandlePropertyEditorDefinition::<Node>::new(sender));
     container.insert(EnumPropertyEditorDefinition::<dim2::collider::ColliderShape>::new());
     container.insert(EnumPropertyEditorDefinition::<CoefficientCombineRule>::new());
@@ -172,6 +194,8 @@ pub fn make_property_editors_container(
     container.insert(EnumPropertyEditorDefinition::<FrustumSplitOptions>::new());
     container.insert(EnumPropertyEditorDefinition::<MaterialSearchOptions>::new());
     container.insert(EnumPropertyEditorDefinition::<DistanceModel>::new());
+    container.insert(EnumPropertyEditorDefinition::<EqualizerBandKind>::new());
+    container.insert(EnumPropertyEditorDefinition::<DistortionShape>::new());
     container.insert(EnumPropertyEditorDefinition::<sound::Renderer>::new());
     container.insert(ScriptPropertyEditorDefinition {});
 
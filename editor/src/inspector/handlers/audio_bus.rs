@@ -0,0 +1,48 @@
+use crate::{handle_properties, scene::commands::audio_bus::*, SceneCommand};
+use fyrox::{
+    core::pool::Handle,
+    gui::inspector::{FieldKind, PropertyChanged},
+    scene::sound::{AudioBus, Ducking},
+};
+
+pub fn handle_audio_bus_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<AudioBus>,
+    bus: &AudioBus,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                AudioBus::NAME => SetAudioBusNameCommand,
+                AudioBus::PARENT => SetAudioBusParentCommand,
+                AudioBus::GAIN => SetAudioBusGainCommand,
+                AudioBus::MUTED => SetAudioBusMutedCommand,
+                AudioBus::SOLO => SetAudioBusSoloCommand,
+                AudioBus::DUCKING => SetAudioBusDuckingCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            AudioBus::DUCKING => {
+                if let FieldKind::Object(ref value) = inner.value {
+                    let mut ducking = bus.ducking().cloned()?;
+                    match inner.name.as_ref() {
+                        Ducking::SIDECHAIN => ducking.sidechain = value.cast_clone()?,
+                        Ducking::THRESHOLD => ducking.threshold = value.cast_clone()?,
+                        Ducking::DUCKED_GAIN => ducking.ducked_gain = value.cast_clone()?,
+                        Ducking::ATTACK => ducking.attack = value.cast_clone()?,
+                        Ducking::RELEASE => ducking.release = value.cast_clone()?,
+                        _ => return None,
+                    }
+                    Some(SceneCommand::new(SetAudioBusDuckingCommand::new(
+                        handle,
+                        Some(ducking),
+                    )))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
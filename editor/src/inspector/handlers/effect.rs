@@ -3,11 +3,32 @@ use fyrox::{
     core::pool::Handle,
     gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
     scene::sound::{
-        effect::{BaseEffect, Effect, EffectInput, ReverbEffect},
+        effect::{
+            BaseEffect, ChorusEffect, CompressorEffect, DelayEffect, DistortionEffect, Effect,
+            EffectInput, EqualizerBand, EqualizerEffect, LimiterEffect, ReverbEffect, TempoSync,
+        },
         Biquad,
     },
 };
 
+pub fn handle_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+    effect: &Effect,
+) -> Option<SceneCommand> {
+    match effect {
+        Effect::Reverb(_) => handle_reverb_effect_property_changed(args, handle),
+        Effect::Equalizer(equalizer) => {
+            handle_equalizer_effect_property_changed(args, handle, equalizer)
+        }
+        Effect::Compressor(_) => handle_compressor_effect_property_changed(args, handle),
+        Effect::Limiter(_) => handle_limiter_effect_property_changed(args, handle),
+        Effect::Delay(delay) => handle_delay_effect_property_changed(args, handle, delay),
+        Effect::Chorus(_) => handle_chorus_effect_property_changed(args, handle),
+        Effect::Distortion(_) => handle_distortion_effect_property_changed(args, handle),
+    }
+}
+
 pub fn handle_base_effect_property_changed(
     args: &PropertyChanged,
     handle: Handle<Effect>,
@@ -41,7 +62,7 @@ pub fn handle_base_effect_property_changed(
     }
 }
 
-pub fn handle_reverb_effect_property_changed(
+fn handle_reverb_effect_property_changed(
     args: &PropertyChanged,
     handle: Handle<Effect>,
 ) -> Option<SceneCommand> {
@@ -62,6 +83,178 @@ pub fn handle_reverb_effect_property_changed(
     }
 }
 
+fn handle_equalizer_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+    equalizer: &EqualizerEffect,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Collection(ref collection_changed) => match args.name.as_ref() {
+            EqualizerEffect::BANDS => {
+                let mut bands = equalizer.bands_owned();
+                match **collection_changed {
+                    CollectionChanged::Add => bands.push(Default::default()),
+                    CollectionChanged::Remove(i) => {
+                        bands.remove(i);
+                    }
+                    CollectionChanged::ItemChanged {
+                        index,
+                        ref property,
+                    } => {
+                        if let FieldKind::Object(ref value) = property.value {
+                            let band = bands.get_mut(index)?;
+                            match property.name.as_ref() {
+                                EqualizerBand::KIND => band.kind = value.cast_clone()?,
+                                EqualizerBand::FREQUENCY => band.frequency = value.cast_clone()?,
+                                EqualizerBand::GAIN => band.gain = value.cast_clone()?,
+                                EqualizerBand::QUALITY => band.quality = value.cast_clone()?,
+                                _ => return None,
+                            }
+                        } else {
+                            return None;
+                        }
+                    }
+                }
+                Some(SceneCommand::new(SetEqualizerBandsCommand::new(
+                    handle, bands,
+                )))
+            }
+            _ => None,
+        },
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            EqualizerEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handle_compressor_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                CompressorEffect::THRESHOLD => SetCompressorThresholdCommand,
+                CompressorEffect::RATIO => SetCompressorRatioCommand,
+                CompressorEffect::ATTACK => SetCompressorAttackCommand,
+                CompressorEffect::RELEASE => SetCompressorReleaseCommand,
+                CompressorEffect::MAKEUP_GAIN => SetCompressorMakeupGainCommand,
+                CompressorEffect::LOOKAHEAD => SetCompressorLookaheadCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            CompressorEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handle_limiter_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                LimiterEffect::THRESHOLD => SetLimiterThresholdCommand,
+                LimiterEffect::RELEASE => SetLimiterReleaseCommand,
+                LimiterEffect::LOOKAHEAD => SetLimiterLookaheadCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            LimiterEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handle_delay_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+    delay: &DelayEffect,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                DelayEffect::TIME => SetDelayTimeCommand,
+                DelayEffect::FEEDBACK => SetDelayFeedbackCommand,
+                DelayEffect::DRY => SetDelayDryCommand,
+                DelayEffect::WET => SetDelayWetCommand,
+                DelayEffect::TEMPO_SYNC => SetDelayTempoSyncCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            DelayEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            DelayEffect::TEMPO_SYNC => {
+                if let FieldKind::Object(ref value) = inner.value {
+                    let mut tempo_sync = delay.tempo_sync()?;
+                    match inner.name.as_ref() {
+                        TempoSync::BPM => tempo_sync.bpm = value.cast_clone()?,
+                        TempoSync::BEATS => tempo_sync.beats = value.cast_clone()?,
+                        _ => return None,
+                    }
+                    Some(SceneCommand::new(SetDelayTempoSyncCommand::new(
+                        handle,
+                        Some(tempo_sync),
+                    )))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handle_chorus_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                ChorusEffect::RATE => SetChorusRateCommand,
+                ChorusEffect::DEPTH => SetChorusDepthCommand,
+                ChorusEffect::DELAY => SetChorusDelayCommand,
+                ChorusEffect::FEEDBACK => SetChorusFeedbackCommand,
+                ChorusEffect::DRY => SetChorusDryCommand,
+                ChorusEffect::WET => SetChorusWetCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            ChorusEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn handle_distortion_effect_property_changed(
+    args: &PropertyChanged,
+    handle: Handle<Effect>,
+) -> Option<SceneCommand> {
+    match args.value {
+        FieldKind::Object(ref value) => {
+            handle_properties!(args.name.as_ref(), handle, value,
+                DistortionEffect::SHAPE => SetDistortionShapeCommand,
+                DistortionEffect::DRIVE => SetDistortionDriveCommand,
+                DistortionEffect::DRY => SetDistortionDryCommand,
+                DistortionEffect::WET => SetDistortionWetCommand
+            )
+        }
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            DistortionEffect::BASE => handle_base_effect_property_changed(inner, handle),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn handle_effect_input_property_changed(
     args: &PropertyChanged,
     handle: Handle<Effect>,
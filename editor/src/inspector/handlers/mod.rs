@@ -1,3 +1,4 @@
+pub mod audio_bus;
 pub mod effect;
 pub mod node;
 pub mod sound_context;
@@ -27,7 +27,8 @@ pub fn handle_collider_property_changed(
                 Collider::DENSITY => SetColliderDensityCommand,
                 Collider::SHAPE => SetColliderShapeCommand,
                 Collider::FRICTION_COMBINE_RULE => SetColliderFrictionCombineRule,
-                Collider::RESTITUTION_COMBINE_RULE => SetColliderRestitutionCombineRule
+                Collider::RESTITUTION_COMBINE_RULE => SetColliderRestitutionCombineRule,
+                Collider::MATERIAL => SetColliderMaterialCommand
             )
         }
         FieldKind::Inspectable(ref inner_property) => match args.name.as_ref() {
@@ -92,6 +93,8 @@ pub fn handle_collider_property_changed(
                     handle_heightfield(handle, inner_property)
                 } else if inner_property.owner_type_id == TypeId::of::<ConvexPolyhedronShape>() {
                     handle_convex_polyhedron(handle, inner_property)
+                } else if inner_property.owner_type_id == TypeId::of::<ConvexDecompositionShape>() {
+                    handle_convex_decomposition(handle, inner_property)
                 } else {
                     None
                 }
@@ -220,3 +223,28 @@ fn handle_convex_polyhedron(handle: Handle<Node>, args: &PropertyChanged) -> Opt
     }
     None
 }
+
+fn handle_convex_decomposition(
+    handle: Handle<Node>,
+    args: &PropertyChanged,
+) -> Option<SceneCommand> {
+    if args.name == ConvexDecompositionShape::GEOMETRY_SOURCE {
+        if let FieldKind::Inspectable(ref inner) = args.value {
+            if inner.name == GeometrySource::F_0 {
+                if let FieldKind::Object(ref val) = inner.value {
+                    return Some(SceneCommand::new(SetConvexDecompositionSourceCommand::new(
+                        handle,
+                        val.cast_clone()?,
+                    )));
+                }
+            }
+        }
+        None
+    } else {
+        handle_property_changed!(args, handle,
+            ConvexDecompositionShape::CONCAVITY => SetConvexDecompositionConcavityCommand,
+            ConvexDecompositionShape::MAX_CONVEX_HULLS => SetConvexDecompositionMaxConvexHullsCommand,
+            ConvexDecompositionShape::RESOLUTION => SetConvexDecompositionResolutionCommand
+        )
+    }
+}
@@ -28,7 +28,8 @@ pub fn handle_collider2d_property_changed(
                 Collider::DENSITY => SetColliderDensityCommand,
                 Collider::SHAPE => SetColliderShapeCommand,
                 Collider::FRICTION_COMBINE_RULE => SetColliderFrictionCombineRule,
-                Collider::RESTITUTION_COMBINE_RULE => SetColliderRestitutionCombineRule
+                Collider::RESTITUTION_COMBINE_RULE => SetColliderRestitutionCombineRule,
+                Collider::MATERIAL => SetColliderMaterialCommand
             )
         }
         FieldKind::Inspectable(ref inner_property) => match args.name.as_ref() {
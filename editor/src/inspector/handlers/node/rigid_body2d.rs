@@ -25,7 +25,10 @@ pub fn handle_rigid_body2d_property_changed(
                 RigidBody::CAN_SLEEP => SetBodyCanSleepCommand,
                 RigidBody::CCD_ENABLED => SetBodyCcdEnabledCommand,
                 RigidBody::LIN_DAMPING => SetBodyLinDampingCommand,
-                RigidBody::ANG_DAMPING => SetBodyAngDampingCommand
+                RigidBody::ANG_DAMPING => SetBodyAngDampingCommand,
+                RigidBody::DOMINANCE => SetBodyDominanceCommand,
+                RigidBody::GRAVITY_SCALE => SetBodyGravityScaleCommand,
+                RigidBody::ADDITIONAL_SOLVER_ITERATIONS => SetBodyAdditionalSolverIterationsCommand
             )
         }
         FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
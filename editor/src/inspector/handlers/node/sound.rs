@@ -44,6 +44,15 @@ pub fn handle_sound_property_changed(
             Sound::SPATIAL_BLEND => {
                 make_command!(SetSpatialBlendCommand, handle, value)
             }
+            Sound::BUS => {
+                make_command!(SetSoundSourceBusCommand, handle, value)
+            }
+            Sound::OCCLUSION_ENABLED => {
+                make_command!(SetSoundSourceOcclusionEnabledCommand, handle, value)
+            }
+            Sound::PRIORITY => {
+                make_command!(SetSoundSourcePriorityCommand, handle, value)
+            }
             _ => None,
         },
         _ => None,
@@ -5,6 +5,7 @@ use crate::{
 use fyrox::{
     core::pool::Handle,
     gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
+    resource::physics_material::PhysicsMaterialResource,
     scene::{node::Node, terrain::Layer, terrain::Terrain},
 };
 use std::any::TypeId;
@@ -35,6 +36,15 @@ pub fn handle_terrain_property_changed(
                                         value: args.cast_value::<String>().cloned()?,
                                     },
                                 )),
+                                Layer::PHYSICS_MATERIAL => {
+                                    Some(SceneCommand::new(SetTerrainLayerPhysicsMaterialCommand {
+                                        handle,
+                                        layer_index: *index,
+                                        value: args
+                                            .cast_value::<Option<PhysicsMaterialResource>>()
+                                            .cloned()?,
+                                    }))
+                                }
                                 _ => None,
                             },
                             _ => None,
@@ -1,10 +1,16 @@
 use crate::{scene::commands::sound_context::*, SceneCommand};
 use fyrox::{
     gui::inspector::{FieldKind, PropertyChanged},
-    scene::sound::context::SoundContext,
+    scene::{
+        collider::InteractionGroups,
+        sound::{context::SoundContext, occlusion::OcclusionSettings},
+    },
 };
 
-pub fn handle_sound_context_property_changed(args: &PropertyChanged) -> Option<SceneCommand> {
+pub fn handle_sound_context_property_changed(
+    args: &PropertyChanged,
+    sound_context: &SoundContext,
+) -> Option<SceneCommand> {
     match args.value {
         FieldKind::Object(ref value) => match args.name.as_ref() {
             SoundContext::PAUSED => Some(SceneCommand::new(SetPausedCommand::new(
@@ -19,6 +25,63 @@ pub fn handle_sound_context_property_changed(args: &PropertyChanged) -> Option<S
             SoundContext::RENDERER => Some(SceneCommand::new(SetRendererCommand::new(
                 value.cast_clone()?,
             ))),
+            SoundContext::DOPPLER_FACTOR => Some(SceneCommand::new(SetDopplerFactorCommand::new(
+                value.cast_clone()?,
+            ))),
+            SoundContext::SPEED_OF_SOUND => Some(SceneCommand::new(SetSpeedOfSoundCommand::new(
+                value.cast_clone()?,
+            ))),
+            SoundContext::MAX_ACTIVE_VOICES => Some(SceneCommand::new(
+                SetMaxActiveVoicesCommand::new(value.cast_clone()?),
+            )),
+            SoundContext::VIRTUALIZATION_THRESHOLD => Some(SceneCommand::new(
+                SetVirtualizationThresholdCommand::new(value.cast_clone()?),
+            )),
+            _ => None,
+        },
+        FieldKind::Inspectable(ref inner) => match args.name.as_ref() {
+            SoundContext::OCCLUSION => {
+                let mut settings = sound_context.occlusion_settings_owned();
+                match inner.value {
+                    FieldKind::Object(ref value) => match inner.name.as_ref() {
+                        OcclusionSettings::UPDATE_INTERVAL => {
+                            settings.update_interval = value.cast_clone()?
+                        }
+                        OcclusionSettings::SMOOTHING_TIME => {
+                            settings.smoothing_time = value.cast_clone()?
+                        }
+                        OcclusionSettings::OCCLUDED_GAIN => {
+                            settings.occluded_gain = value.cast_clone()?
+                        }
+                        OcclusionSettings::OCCLUDED_CUTOFF_FREQUENCY => {
+                            settings.occluded_cutoff_frequency = value.cast_clone()?
+                        }
+                        OcclusionSettings::DEFAULT_TRANSMISSION => {
+                            settings.default_transmission = value.cast_clone()?
+                        }
+                        _ => return None,
+                    },
+                    FieldKind::Inspectable(ref groups) => match inner.name.as_ref() {
+                        OcclusionSettings::GROUPS => match groups.value {
+                            FieldKind::Object(ref value) => match groups.name.as_ref() {
+                                InteractionGroups::MEMBERSHIPS => {
+                                    settings.groups.memberships = value.cast_clone()?
+                                }
+                                InteractionGroups::FILTER => {
+                                    settings.groups.filter = value.cast_clone()?
+                                }
+                                _ => return None,
+                            },
+                            _ => return None,
+                        },
+                        _ => return None,
+                    },
+                    _ => return None,
+                }
+                Some(SceneCommand::new(SetOcclusionSettingsCommand::new(
+                    settings,
+                )))
+            }
             _ => None,
         },
         _ => None,
@@ -3,7 +3,8 @@ use crate::{
     inspector::{
         editors::make_property_editors_container,
         handlers::{
-            effect::handle_reverb_effect_property_changed,
+            audio_bus::handle_audio_bus_property_changed,
+            effect::handle_effect_property_changed,
             node::{particle_system::ParticleSystemHandler, SceneNodePropertyChangedHandler},
             sound_context::handle_sound_context_property_changed,
         },
@@ -194,6 +195,11 @@ impl Inspector {
                         .sound_context
                         .try_get_effect(selection.effects[0])
                         .map(|e| e as &dyn Inspect),
+                    Selection::AudioBus(selection) => scene
+                        .graph
+                        .sound_context
+                        .try_get_bus(selection.buses[0])
+                        .map(|b| b as &dyn Inspect),
                     _ => None,
                 };
 
@@ -265,6 +271,11 @@ impl Inspector {
                         .sound_context
                         .try_get_effect(selection.effects[0])
                         .map(|e| e as &dyn Inspect),
+                    Selection::AudioBus(selection) => scene
+                        .graph
+                        .sound_context
+                        .try_get_bus(selection.buses[0])
+                        .map(|b| b as &dyn Inspect),
                     _ => None,
                 };
 
@@ -343,13 +354,26 @@ impl Inspector {
                             }
                         })
                         .collect::<Vec<_>>(),
-                    Selection::SoundContext => handle_sound_context_property_changed(args)
-                        .map(|c| vec![c])
-                        .unwrap_or_default(),
+                    Selection::SoundContext => {
+                        handle_sound_context_property_changed(args, &scene.graph.sound_context)
+                            .map(|c| vec![c])
+                            .unwrap_or_default()
+                    }
                     Selection::Effect(selection) => selection
                         .effects
                         .iter()
-                        .filter_map(|&handle| handle_reverb_effect_property_changed(args, handle))
+                        .filter_map(|&handle| {
+                            let effect = scene.graph.sound_context.try_get_effect(handle)?;
+                            handle_effect_property_changed(args, handle, effect)
+                        })
+                        .collect::<Vec<_>>(),
+                    Selection::AudioBus(selection) => selection
+                        .buses
+                        .iter()
+                        .filter_map(|&handle| {
+                            let bus = scene.graph.sound_context.try_get_bus(handle)?;
+                            handle_audio_bus_property_changed(args, handle, bus)
+                        })
                         .collect::<Vec<_>>(),
                     _ => vec![],
                 };
@@ -11,6 +11,7 @@
 extern crate lazy_static;
 
 mod absm;
+mod animation;
 mod asset;
 mod audio;
 mod build;
@@ -36,8 +37,9 @@ mod world;
 use crate::build::BuildWindow;
 use crate::{
     absm::AbsmEditor,
+    animation::AnimationEditor,
     asset::{item::AssetItem, item::AssetKind, AssetBrowser},
-    audio::AudioPanel,
+    audio::{AudioBusSelection, AudioPanel},
     command::{panel::CommandStackViewer, Command, CommandStack},
     configurator::Configurator,
     curve_editor::CurveEditorWindow,
@@ -105,6 +107,7 @@ use fyrox::{
         camera::Projection,
         mesh::Mesh,
         node::{Node, TypeUuidProvider},
+        sound::AudioBus,
         Scene, SceneLoader,
     },
     utils::{
@@ -444,6 +447,7 @@ pub struct Editor {
     material_editor: MaterialEditor,
     pub inspector: Inspector,
     curve_editor: CurveEditorWindow,
+    animation_editor: AnimationEditor,
     audio_panel: AudioPanel,
     #[allow(dead_code)] // TODO
     absm_editor: AbsmEditor,
@@ -530,7 +534,7 @@ impl Editor {
 
         let scene_viewer = SceneViewer::new(&mut engine, message_sender.clone());
         let asset_browser = AssetBrowser::new(&mut engine);
-        let menu = Menu::new(&mut engine, message_sender.clone());
+        let menu = Menu::new(&mut engine, message_sender.clone(), &settings);
         let light_panel = LightPanel::new(&mut engine);
         let audio_panel = AudioPanel::new(&mut engine);
 
@@ -677,6 +681,8 @@ impl Editor {
 
         let curve_editor = CurveEditorWindow::new(ctx);
 
+        let animation_editor = AnimationEditor::new(ctx);
+
         let save_scene_dialog = SaveSceneConfirmationDialog::new(ctx);
 
         let build_window = BuildWindow::new(ctx);
@@ -712,6 +718,7 @@ impl Editor {
             material_editor,
             inspector,
             curve_editor,
+            animation_editor,
             audio_panel,
             save_scene_dialog,
             mode: Mode::Edit,
@@ -984,6 +991,7 @@ impl Editor {
                     log_panel: self.log.window,
                     configurator_window: self.configurator.window,
                     path_fixer: self.path_fixer.window,
+                    keyframe_editor: self.animation_editor.window,
                     curve_editor: &self.curve_editor,
                     absm_editor: &self.absm_editor,
                 },
@@ -1016,6 +1024,13 @@ impl Editor {
             self.audio_panel
                 .handle_ui_message(message, editor_scene, &self.message_sender, engine);
 
+            self.animation_editor.handle_ui_message(
+                message,
+                editor_scene,
+                &self.message_sender,
+                engine,
+            );
+
             self.navmesh_panel.handle_message(
                 message,
                 editor_scene,
@@ -1196,6 +1211,7 @@ impl Editor {
         self.command_stack_viewer.on_mode_changed(ui, &self.mode);
         self.inspector.on_mode_changed(ui, &self.mode);
         self.audio_panel.on_mode_changed(ui, &self.mode);
+        self.animation_editor.on_mode_changed(ui, &self.mode);
         self.navmesh_panel.on_mode_changed(ui, &self.mode);
         self.menu.on_mode_changed(ui, &self.mode);
     }
@@ -1215,6 +1231,8 @@ impl Editor {
             self.material_editor
                 .sync_to_model(&mut engine.user_interface);
             self.audio_panel.sync_to_model(editor_scene, engine);
+            self.animation_editor
+                .sync_to_model(editor_scene, engine, &self.message_sender);
             self.command_stack_viewer.sync_to_model(
                 &mut self.command_stack,
                 &SceneContext {
@@ -1229,6 +1247,7 @@ impl Editor {
         } else {
             self.inspector.clear(&engine.user_interface);
             self.world_viewer.clear(&engine.user_interface);
+            self.animation_editor.clear(engine);
         }
     }
 
@@ -1466,6 +1485,10 @@ impl Editor {
                 Some(Selection::Graph(GraphSelection::single_or_empty(
                     handle.into(),
                 )))
+            } else if type_id == TypeId::of::<AudioBus>() {
+                Some(Selection::AudioBus(AudioBusSelection {
+                    buses: vec![handle.into()],
+                }))
             } else {
                 None
             };
@@ -1680,6 +1703,9 @@ impl Editor {
 
             editor_scene.camera_controller.update(graph, dt);
 
+            self.animation_editor
+                .update(editor_scene, &mut self.engine, dt);
+
             if let Some(mode) = self.current_interaction_mode {
                 self.interaction_modes[mode as usize].update(
                     editor_scene,
@@ -46,6 +46,7 @@ pub struct Panels<'b> {
     pub asset_window: Handle<UiNode>,
     pub configurator_window: Handle<UiNode>,
     pub path_fixer: Handle<UiNode>,
+    pub keyframe_editor: Handle<UiNode>,
     pub curve_editor: &'b CurveEditorWindow,
     pub absm_editor: &'b AbsmEditor,
 }
@@ -92,12 +93,16 @@ pub fn create_menu_item_shortcut(
 }
 
 impl Menu {
-    pub fn new(engine: &mut GameEngine, message_sender: Sender<Message>) -> Self {
+    pub fn new(
+        engine: &mut GameEngine,
+        message_sender: Sender<Message>,
+        settings: &Settings,
+    ) -> Self {
         let file_menu = FileMenu::new(engine);
         let ctx = &mut engine.user_interface.build_ctx();
         let create_entity_menu = CreateEntityRootMenu::new(ctx);
         let edit_menu = EditMenu::new(ctx);
-        let view_menu = ViewMenu::new(ctx);
+        let view_menu = ViewMenu::new(ctx, &settings.debugging.physics);
         let utils_menu = UtilsMenu::new(ctx);
 
         let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
@@ -176,8 +181,12 @@ impl Menu {
             ctx.settings,
             ctx.panels.configurator_window,
         );
-        self.view_menu
-            .handle_ui_message(message, &ctx.engine.user_interface, &ctx.panels);
+        self.view_menu.handle_ui_message(
+            message,
+            &ctx.engine.user_interface,
+            &ctx.panels,
+            ctx.settings,
+        );
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
@@ -14,6 +14,7 @@ pub struct UtilsMenu {
     open_path_fixer: Handle<UiNode>,
     open_curve_editor: Handle<UiNode>,
     absm_editor: Handle<UiNode>,
+    keyframe_editor: Handle<UiNode>,
 }
 
 impl UtilsMenu {
@@ -21,6 +22,7 @@ impl UtilsMenu {
         let open_path_fixer;
         let open_curve_editor;
         let absm_editor;
+        let keyframe_editor;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -36,6 +38,10 @@ impl UtilsMenu {
                     absm_editor = create_menu_item("Animation Editor", vec![], ctx);
                     absm_editor
                 },
+                {
+                    keyframe_editor = create_menu_item("Keyframe Editor", vec![], ctx);
+                    keyframe_editor
+                },
             ],
             ctx,
         );
@@ -45,6 +51,7 @@ impl UtilsMenu {
             open_path_fixer,
             open_curve_editor,
             absm_editor,
+            keyframe_editor,
         }
     }
 
@@ -60,6 +67,12 @@ impl UtilsMenu {
                 panels.curve_editor.open(ui);
             } else if message.destination() == self.absm_editor {
                 panels.absm_editor.open(ui);
+            } else if message.destination() == self.keyframe_editor {
+                ui.send_message(WindowMessage::open(
+                    panels.keyframe_editor,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
             }
         }
     }
@@ -1,12 +1,20 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    settings::Settings,
+};
 use fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
     gui::{
-        menu::MenuItemMessage,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
+        text::TextBuilder,
+        widget::WidgetBuilder,
         window::WindowMessage,
-        BuildContext, UiNode, UserInterface,
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
+    scene::graph::physics::PhysicsDebugFlags,
+    utils::log::Log,
 };
 
 pub struct ViewMenu {
@@ -16,6 +24,115 @@ pub struct ViewMenu {
     asset_browser: Handle<UiNode>,
     light_panel: Handle<UiNode>,
     log_panel: Handle<UiNode>,
+    physics_debug: PhysicsDebugMenu,
+}
+
+struct PhysicsDebugMenu {
+    menu: Handle<UiNode>,
+    colliders: Handle<UiNode>,
+    body_states: Handle<UiNode>,
+    contacts: Handle<UiNode>,
+    joints: Handle<UiNode>,
+    center_of_mass: Handle<UiNode>,
+    velocities: Handle<UiNode>,
+    bounding_boxes: Handle<UiNode>,
+}
+
+fn create_check_box_menu_item(
+    text: &str,
+    checked: bool,
+    ctx: &mut BuildContext,
+) -> (Handle<UiNode>, Handle<UiNode>) {
+    let check_box = CheckBoxBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+        .with_content(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(text)
+                .build(ctx),
+        )
+        .checked(Some(checked))
+        .build(ctx);
+    let item = MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
+        .with_content(MenuItemContent::Node(check_box))
+        .build(ctx);
+    (item, check_box)
+}
+
+impl PhysicsDebugMenu {
+    fn new(ctx: &mut BuildContext, flags: &PhysicsDebugFlags) -> Self {
+        let (colliders_item, colliders) =
+            create_check_box_menu_item("Colliders", flags.colliders, ctx);
+        let (body_states_item, body_states) =
+            create_check_box_menu_item("Body States", flags.body_states, ctx);
+        let (contacts_item, contacts) = create_check_box_menu_item("Contacts", flags.contacts, ctx);
+        let (joints_item, joints) = create_check_box_menu_item("Joints", flags.joints, ctx);
+        let (center_of_mass_item, center_of_mass) =
+            create_check_box_menu_item("Center Of Mass", flags.center_of_mass, ctx);
+        let (velocities_item, velocities) =
+            create_check_box_menu_item("Velocities", flags.velocities, ctx);
+        let (bounding_boxes_item, bounding_boxes) =
+            create_check_box_menu_item("Bounding Boxes", flags.bounding_boxes, ctx);
+
+        let menu = create_menu_item(
+            "Physics Debug",
+            vec![
+                colliders_item,
+                body_states_item,
+                contacts_item,
+                joints_item,
+                center_of_mass_item,
+                velocities_item,
+                bounding_boxes_item,
+            ],
+            ctx,
+        );
+
+        Self {
+            menu,
+            colliders,
+            body_states,
+            contacts,
+            joints,
+            center_of_mass,
+            velocities,
+            bounding_boxes,
+        }
+    }
+
+    fn handle_ui_message(&self, message: &UiMessage, settings: &mut Settings) {
+        if let Some(&CheckBoxMessage::Check(Some(value))) = message.data::<CheckBoxMessage>() {
+            if message.direction() != MessageDirection::FromWidget {
+                return;
+            }
+
+            let flags = &mut settings.debugging.physics;
+            let flag = if message.destination() == self.colliders {
+                &mut flags.colliders
+            } else if message.destination() == self.body_states {
+                &mut flags.body_states
+            } else if message.destination() == self.contacts {
+                &mut flags.contacts
+            } else if message.destination() == self.joints {
+                &mut flags.joints
+            } else if message.destination() == self.center_of_mass {
+                &mut flags.center_of_mass
+            } else if message.destination() == self.velocities {
+                &mut flags.velocities
+            } else if message.destination() == self.bounding_boxes {
+                &mut flags.bounding_boxes
+            } else {
+                return;
+            };
+
+            if *flag != value {
+                *flag = value;
+
+                if let Err(e) = settings.save() {
+                    Log::err(format!("Unable to save settings! Reason: {:?}!", e));
+                }
+            }
+        }
+    }
 }
 
 fn switch_window_state(window: Handle<UiNode>, ui: &UserInterface, center: bool) {
@@ -28,12 +145,13 @@ fn switch_window_state(window: Handle<UiNode>, ui: &UserInterface, center: bool)
 }
 
 impl ViewMenu {
-    pub fn new(ctx: &mut BuildContext) -> Self {
+    pub fn new(ctx: &mut BuildContext, physics_debug_flags: &PhysicsDebugFlags) -> Self {
         let sidebar;
         let asset_browser;
         let world_viewer;
         let light_panel;
         let log_panel;
+        let physics_debug = PhysicsDebugMenu::new(ctx, physics_debug_flags);
         let menu = create_root_menu_item(
             "View",
             vec![
@@ -57,6 +175,7 @@ impl ViewMenu {
                     log_panel = create_menu_item("Log Panel", vec![], ctx);
                     log_panel
                 },
+                physics_debug.menu,
             ],
             ctx,
         );
@@ -68,10 +187,19 @@ impl ViewMenu {
             asset_browser,
             light_panel,
             log_panel,
+            physics_debug,
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &UserInterface, panels: &Panels) {
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        panels: &Panels,
+        settings: &mut Settings,
+    ) {
+        self.physics_debug.handle_ui_message(message, settings);
+
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.asset_browser {
                 switch_window_state(panels.asset_window, ui, false);
@@ -0,0 +1,187 @@
+use crate::{Command, SceneContext};
+use fyrox::{
+    animation::{curve_track::CurveTrack, Animation, AnimationSignal, Track},
+    core::pool::{Handle, Ticket},
+};
+
+#[derive(Debug)]
+pub struct AddAnimationCommand {
+    animation: Option<Animation>,
+    handle: Handle<Animation>,
+    ticket: Option<Ticket<Animation>>,
+}
+
+impl AddAnimationCommand {
+    pub fn new(animation: Animation) -> Self {
+        Self {
+            animation: Some(animation),
+            handle: Default::default(),
+            ticket: None,
+        }
+    }
+}
+
+impl Command for AddAnimationCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Add Animation".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        match self.ticket.take() {
+            None => {
+                self.handle = context.scene.animations.add(self.animation.take().unwrap());
+            }
+            Some(ticket) => {
+                self.handle = context
+                    .scene
+                    .animations
+                    .put_back(ticket, self.animation.take().unwrap());
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let (ticket, animation) = context.scene.animations.take_reserve(self.handle);
+        self.animation = Some(animation);
+        self.ticket = Some(ticket);
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        if let Some(ticket) = self.ticket.take() {
+            context.scene.animations.forget_ticket(ticket);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetCurveTracksCommand {
+    animation: Handle<Animation>,
+    tracks: Vec<CurveTrack>,
+}
+
+impl SetCurveTracksCommand {
+    pub fn new(animation: Handle<Animation>, tracks: Vec<CurveTrack>) -> Self {
+        Self { animation, tracks }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let tracks = std::mem::take(&mut self.tracks);
+        self.tracks = context.scene.animations[self.animation].set_curve_tracks(tracks);
+    }
+}
+
+impl Command for SetCurveTracksCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Set Animation Curves".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+/// Replaces key frame tracks of an animation with curve tracks, so they can be edited.
+#[derive(Debug)]
+pub struct ConvertTracksToCurvesCommand {
+    animation: Handle<Animation>,
+    tracks: Vec<Track>,
+    curve_tracks: Vec<CurveTrack>,
+}
+
+impl ConvertTracksToCurvesCommand {
+    pub fn new(animation: Handle<Animation>) -> Self {
+        Self {
+            animation,
+            tracks: Default::default(),
+            curve_tracks: Default::default(),
+        }
+    }
+}
+
+impl Command for ConvertTracksToCurvesCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Convert Key Frames To Curves".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let animation = &mut context.scene.animations[self.animation];
+        let mut curve_tracks = animation.curve_tracks().to_vec();
+        for track in animation.get_tracks() {
+            curve_tracks.extend(track.to_curve_tracks());
+        }
+        self.tracks = animation.set_tracks(Default::default());
+        self.curve_tracks = animation.set_curve_tracks(curve_tracks);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let animation = &mut context.scene.animations[self.animation];
+        animation.set_tracks(std::mem::take(&mut self.tracks));
+        animation.set_curve_tracks(std::mem::take(&mut self.curve_tracks));
+    }
+}
+
+#[derive(Debug)]
+pub struct AddAnimationSignalCommand {
+    animation: Handle<Animation>,
+    signal: Option<AnimationSignal>,
+}
+
+impl AddAnimationSignalCommand {
+    pub fn new(animation: Handle<Animation>, signal: AnimationSignal) -> Self {
+        Self {
+            animation,
+            signal: Some(signal),
+        }
+    }
+}
+
+impl Command for AddAnimationSignalCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Add Animation Signal".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        context.scene.animations[self.animation].add_signal(self.signal.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let animation = &mut context.scene.animations[self.animation];
+        self.signal = Some(animation.remove_signal(animation.signals().len() - 1));
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveAnimationSignalCommand {
+    animation: Handle<Animation>,
+    index: usize,
+    signal: Option<AnimationSignal>,
+}
+
+impl RemoveAnimationSignalCommand {
+    pub fn new(animation: Handle<Animation>, index: usize) -> Self {
+        Self {
+            animation,
+            index,
+            signal: None,
+        }
+    }
+}
+
+impl Command for RemoveAnimationSignalCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Remove Animation Signal".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.signal = Some(context.scene.animations[self.animation].remove_signal(self.index));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        context.scene.animations[self.animation]
+            .insert_signal(self.index, self.signal.take().unwrap());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Command, SceneContext};
+use fyrox::{
+    core::pool::Handle,
+    scene::sound::{context::SoundContext, AudioBus, Ducking},
+};
+
+#[derive(Debug)]
+pub struct AddAudioBusCommand {
+    bus: Option<AudioBus>,
+    handle: Handle<AudioBus>,
+}
+
+impl AddAudioBusCommand {
+    pub fn new(bus: AudioBus) -> Self {
+        Self {
+            bus: Some(bus),
+            handle: Default::default(),
+        }
+    }
+}
+
+impl Command for AddAudioBusCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Add Audio Bus".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.handle = context
+            .scene
+            .graph
+            .sound_context
+            .add_bus(self.bus.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.bus = context.scene.graph.sound_context.remove_bus(self.handle);
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveAudioBusCommand {
+    // Whole graph is stored to restore the bus at the same handle.
+    graph: Option<fyrox::scene::sound::AudioBusGraph>,
+    handle: Handle<AudioBus>,
+}
+
+impl RemoveAudioBusCommand {
+    pub fn new(handle: Handle<AudioBus>) -> Self {
+        Self {
+            graph: None,
+            handle,
+        }
+    }
+}
+
+impl Command for RemoveAudioBusCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Remove Audio Bus".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let sound_context = &mut context.scene.graph.sound_context;
+        self.graph = Some(sound_context.bus_graph().clone());
+        sound_context.remove_bus(self.handle);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(graph) = self.graph.take() {
+            context.scene.graph.sound_context.set_bus_graph(graph);
+        }
+    }
+}
+
+macro_rules! define_audio_bus_command {
+    ($($name:ident($human_readable_name:expr, $value_type:ty): $get:expr, $set:ident;)*) => {
+        $(
+            #[derive(Debug)]
+            pub struct $name {
+                handle: Handle<AudioBus>,
+                value: $value_type,
+            }
+
+            impl $name {
+                pub fn new(handle: Handle<AudioBus>, value: $value_type) -> Self {
+                    Self { handle, value }
+                }
+
+                fn swap(&mut self, sound_context: &mut SoundContext) {
+                    if let Some(bus) = sound_context.try_get_bus(self.handle) {
+                        let old = ($get)(bus);
+                        sound_context.$set(self.handle, self.value.clone());
+                        self.value = old;
+                    }
+                }
+            }
+
+            impl Command for $name {
+                fn name(&mut self, _context: &SceneContext) -> String {
+                    $human_readable_name.to_owned()
+                }
+
+                fn execute(&mut self, context: &mut SceneContext) {
+                    self.swap(&mut context.scene.graph.sound_context);
+                }
+
+                fn revert(&mut self, context: &mut SceneContext) {
+                    self.swap(&mut context.scene.graph.sound_context);
+                }
+            }
+        )*
+    };
+}
+
+define_audio_bus_command! {
+    SetAudioBusNameCommand("Set Audio Bus Name", String): |b: &AudioBus| b.name().to_owned(), set_bus_name;
+    SetAudioBusParentCommand("Set Audio Bus Parent", Handle<AudioBus>): |b: &AudioBus| b.parent(), set_bus_parent;
+    SetAudioBusGainCommand("Set Audio Bus Gain", f32): |b: &AudioBus| b.gain(), set_bus_gain;
+    SetAudioBusMutedCommand("Set Audio Bus Muted", bool): |b: &AudioBus| b.is_muted(), set_bus_muted;
+    SetAudioBusSoloCommand("Set Audio Bus Solo", bool): |b: &AudioBus| b.is_solo(), set_bus_solo;
+    SetAudioBusDuckingCommand("Set Audio Bus Ducking", Option<Ducking>): |b: &AudioBus| b.ducking().cloned(), set_bus_ducking;
+}
@@ -3,6 +3,7 @@ use crate::{
 };
 use fyrox::{
     core::{algebra::Vector3, pool::Handle},
+    resource::physics_material::PhysicsMaterialResource,
     scene::{collider::*, graph::physics::CoefficientCombineRule, graph::Graph, node::Node},
 };
 
@@ -46,6 +47,7 @@ define_swap_command! {
     SetColliderDensityCommand(Option<f32>): density, set_density, "Set Collider Density";
     SetColliderFrictionCombineRule(CoefficientCombineRule): friction_combine_rule, set_friction_combine_rule, "Set Collider Friction Combine Rule";
     SetColliderRestitutionCombineRule(CoefficientCombineRule): restitution_combine_rule, set_restitution_combine_rule, "Set Collider Restitution Combine Rule";
+    SetColliderMaterialCommand(Option<PhysicsMaterialResource>): material, set_material, "Set Collider Material";
     SetColliderCollisionGroupsCommand(InteractionGroups): collision_groups, set_collision_groups, "Set Collider Collision Groups";
     SetColliderSolverGroupsCommand(InteractionGroups): solver_groups, set_solver_groups, "Set Collider Solver Groups";
 }
@@ -65,6 +67,9 @@ define_collider_shape_variant_command! {
     SetTriangleBCommand(Vector3<f32>): Triangle, b, "Set Triangle B";
     SetTriangleCCommand(Vector3<f32>): Triangle, c, "Set Triangle C";
     SetBallRadiusCommand(f32): Ball, radius, "Set Ball Radius";
+    SetConvexDecompositionConcavityCommand(f32): ConvexDecomposition, concavity, "Set Convex Decomposition Concavity";
+    SetConvexDecompositionMaxConvexHullsCommand(u32): ConvexDecomposition, max_convex_hulls, "Set Convex Decomposition Max Convex Hulls";
+    SetConvexDecompositionResolutionCommand(u32): ConvexDecomposition, resolution, "Set Convex Decomposition Resolution";
 }
 
 define_collider_variant_command! {
@@ -75,6 +80,10 @@ define_collider_variant_command! {
     SetPolyhedronSourceCommand("Set Polyhedron Source", Handle<Node>) where fn swap(self, physics, Polyhedron, ph) {
         std::mem::swap(&mut ph.geometry_source.0, &mut self.value);
     }
+
+    SetConvexDecompositionSourceCommand("Set Convex Decomposition Source", Handle<Node>) where fn swap(self, physics, ConvexDecomposition, cd) {
+        std::mem::swap(&mut cd.geometry_source.0, &mut self.value);
+    }
 }
 
 #[derive(Debug)]
@@ -1,6 +1,7 @@
 use crate::{command::Command, define_swap_command, scene::commands::SceneContext};
 use fyrox::{
     core::algebra::Vector2,
+    resource::physics_material::PhysicsMaterialResource,
     scene::{
         collider::InteractionGroups,
         dim2::collider::*,
@@ -35,6 +36,7 @@ define_swap_command! {
     SetColliderDensityCommand(Option<f32>): density, set_density, "Set 2D Collider Density";
     SetColliderFrictionCombineRule(CoefficientCombineRule): friction_combine_rule, set_friction_combine_rule, "Set Collider Friction Combine Rule";
     SetColliderRestitutionCombineRule(CoefficientCombineRule): restitution_combine_rule, set_restitution_combine_rule, "Set Collider Restitution Combine Rule";
+    SetColliderMaterialCommand(Option<PhysicsMaterialResource>): material, set_material, "Set 2D Collider Material";
     SetColliderCollisionGroupsCommand(InteractionGroups): collision_groups, set_collision_groups, "Set 2D Collider Collision Groups";
     SetColliderSolverGroupsCommand(InteractionGroups): solver_groups, set_solver_groups, "Set 2D Collider Solver Groups";
 }
@@ -5,7 +5,7 @@ use fyrox::{
         node::Node,
         sound::{
             context::SoundContext,
-            effect::{Effect, EffectInput},
+            effect::{DistortionShape, Effect, EffectInput, EqualizerBand, TempoSync},
             Biquad,
         },
     },
@@ -167,6 +167,108 @@ define_effect_command!(SetReverbDecayTimeCommand("Set Reverb Decay Time", f32) w
     get_set_swap!(self, effect.as_reverb_mut(), decay_time, set_decay_time);
 });
 
+define_effect_command! {
+    SetEqualizerBandsCommand("Set Equalizer Bands", Vec<EqualizerBand>) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_equalizer_mut(), bands_owned, set_bands);
+    }
+
+    SetCompressorThresholdCommand("Set Compressor Threshold", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), threshold, set_threshold);
+    }
+
+    SetCompressorRatioCommand("Set Compressor Ratio", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), ratio, set_ratio);
+    }
+
+    SetCompressorAttackCommand("Set Compressor Attack", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), attack, set_attack);
+    }
+
+    SetCompressorReleaseCommand("Set Compressor Release", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), release, set_release);
+    }
+
+    SetCompressorMakeupGainCommand("Set Compressor Makeup Gain", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), makeup_gain, set_makeup_gain);
+    }
+
+    SetCompressorLookaheadCommand("Set Compressor Lookahead", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_compressor_mut(), lookahead, set_lookahead);
+    }
+
+    SetLimiterThresholdCommand("Set Limiter Threshold", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_limiter_mut(), threshold, set_threshold);
+    }
+
+    SetLimiterReleaseCommand("Set Limiter Release", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_limiter_mut(), release, set_release);
+    }
+
+    SetLimiterLookaheadCommand("Set Limiter Lookahead", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_limiter_mut(), lookahead, set_lookahead);
+    }
+
+    SetDelayTimeCommand("Set Delay Time", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_delay_mut(), time, set_time);
+    }
+
+    SetDelayFeedbackCommand("Set Delay Feedback", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_delay_mut(), feedback, set_feedback);
+    }
+
+    SetDelayDryCommand("Set Delay Dry", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_delay_mut(), dry, set_dry);
+    }
+
+    SetDelayWetCommand("Set Delay Wet", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_delay_mut(), wet, set_wet);
+    }
+
+    SetDelayTempoSyncCommand("Set Delay Tempo Sync", Option<TempoSync>) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_delay_mut(), tempo_sync, set_tempo_sync);
+    }
+
+    SetChorusRateCommand("Set Chorus Rate", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), rate, set_rate);
+    }
+
+    SetChorusDepthCommand("Set Chorus Depth", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), depth, set_depth);
+    }
+
+    SetChorusDelayCommand("Set Chorus Delay", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), delay, set_delay);
+    }
+
+    SetChorusFeedbackCommand("Set Chorus Feedback", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), feedback, set_feedback);
+    }
+
+    SetChorusDryCommand("Set Chorus Dry", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), dry, set_dry);
+    }
+
+    SetChorusWetCommand("Set Chorus Wet", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_chorus_mut(), wet, set_wet);
+    }
+
+    SetDistortionShapeCommand("Set Distortion Shape", DistortionShape) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_distortion_mut(), shape, set_shape);
+    }
+
+    SetDistortionDriveCommand("Set Distortion Drive", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_distortion_mut(), drive, set_drive);
+    }
+
+    SetDistortionDryCommand("Set Distortion Dry", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_distortion_mut(), dry, set_dry);
+    }
+
+    SetDistortionWetCommand("Set Distortion Wet", f32) where fn swap(self, effect) {
+        get_set_swap!(self, effect.as_distortion_mut(), wet, set_wet);
+    }
+}
+
 define_vec_add_remove_commands!(struct AddInputCommand, RemoveInputCommand<Effect, EffectInput> 
 (self, context) { context.scene.graph.sound_context.effect_mut(self.handle).inputs_mut() });
 
@@ -17,6 +17,8 @@ use std::{
     sync::mpsc::Sender,
 };
 
+pub mod animation;
+pub mod audio_bus;
 pub mod camera;
 pub mod collider;
 pub mod collider2d;
@@ -194,6 +196,7 @@ impl ChangeSelectionCommand {
                 Selection::Navmesh(_) => "Change Selection: Navmesh",
                 Selection::SoundContext => "Change Selection: Sound Context",
                 Selection::Effect(_) => "Change Selection: Effect",
+                Selection::AudioBus(_) => "Change Selection: Audio Bus",
             }
             .to_owned(),
             new_selection,
@@ -18,4 +18,7 @@ define_swap_command! {
     SetBodyCcdEnabledCommand(bool): is_ccd_enabled, enable_ccd, "Set Body Ccd Enabled";
     SetBodyLinDampingCommand(f32): lin_damping, set_lin_damping, "Set Lin Damping";
     SetBodyAngDampingCommand(f32): ang_damping, set_ang_damping, "Set Ang Damping";
+    SetBodyDominanceCommand(i8): dominance, set_dominance, "Set Body Dominance";
+    SetBodyGravityScaleCommand(f32): gravity_scale, set_gravity_scale, "Set Body Gravity Scale";
+    SetBodyAdditionalSolverIterationsCommand(u32): additional_solver_iterations, set_additional_solver_iterations, "Set Body Additional Solver Iterations";
 }
@@ -16,4 +16,7 @@ define_swap_command! {
     SetBodyCcdEnabledCommand(bool): is_ccd_enabled, enable_ccd, "Set 2D Body Ccd Enabled";
     SetBodyLinDampingCommand(f32): lin_damping, set_lin_damping, "Set Lin Damping";
     SetBodyAngDampingCommand(f32): ang_damping, set_ang_damping, "Set Ang Damping";
+    SetBodyDominanceCommand(i8): dominance, set_dominance, "Set 2D Body Dominance";
+    SetBodyGravityScaleCommand(f32): gravity_scale, set_gravity_scale, "Set 2D Body Gravity Scale";
+    SetBodyAdditionalSolverIterationsCommand(u32): additional_solver_iterations, set_additional_solver_iterations, "Set 2D Body Additional Solver Iterations";
 }
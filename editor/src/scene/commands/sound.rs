@@ -1,5 +1,6 @@
 use crate::{command::Command, define_swap_command, scene::commands::SceneContext};
-use fyrox::scene::sound::Status;
+use fyrox::core::pool::Handle;
+use fyrox::scene::sound::{AudioBus, Status};
 use fyrox::scene::{node::Node, sound::SoundBufferResource};
 
 define_swap_command! {
@@ -15,4 +16,7 @@ define_swap_command! {
     SetRolloffFactorCommand(f32): rolloff_factor, set_rolloff_factor, "Set Spatial Sound Source Rolloff Factor";
     SetMaxDistanceCommand(f32): max_distance, set_max_distance, "Set Max Distance";
     SetSpatialBlendCommand(f32): spatial_blend, set_spatial_blend, "Set Spatial Blend";
+    SetSoundSourceBusCommand(Handle<AudioBus>): bus, set_bus, "Set Sound Source Bus";
+    SetSoundSourceOcclusionEnabledCommand(bool): is_occlusion_enabled, set_occlusion_enabled, "Set Sound Source Occlusion Enabled";
+    SetSoundSourcePriorityCommand(u32): priority, set_priority, "Set Sound Source Priority";
 }
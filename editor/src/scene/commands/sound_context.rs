@@ -1,5 +1,7 @@
 use crate::{Command, SceneContext};
-use fyrox::scene::sound::{context::SoundContext, DistanceModel, Renderer};
+use fyrox::scene::sound::{
+    context::SoundContext, occlusion::OcclusionSettings, DistanceModel, Renderer,
+};
 
 macro_rules! define_sound_context_command {
     ($($name:ident($human_readable_name:expr, $value_type:ty, $get:ident, $set:ident); )*) => {
@@ -43,4 +45,9 @@ define_sound_context_command! {
     SetMasterGainCommand("Set Master Gain", f32, master_gain, set_master_gain);
     SetDistanceModelCommand("Set Distance Model", DistanceModel, distance_model, set_distance_model);
     SetRendererCommand("Set Renderer", Renderer, renderer, set_renderer);
+    SetDopplerFactorCommand("Set Doppler Factor", f32, doppler_factor, set_doppler_factor);
+    SetSpeedOfSoundCommand("Set Speed Of Sound", f32, speed_of_sound, set_speed_of_sound);
+    SetMaxActiveVoicesCommand("Set Max Active Voices", u32, max_active_voices, set_max_active_voices);
+    SetVirtualizationThresholdCommand("Set Virtualization Threshold", f32, virtualization_threshold, set_virtualization_threshold);
+    SetOcclusionSettingsCommand("Set Occlusion Settings", OcclusionSettings, occlusion_settings_owned, set_occlusion_settings);
 }
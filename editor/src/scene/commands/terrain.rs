@@ -4,6 +4,7 @@ use crate::{
 };
 use fyrox::{
     core::pool::Handle,
+    resource::physics_material::PhysicsMaterialResource,
     scene::{node::Node, terrain::Layer, terrain::Terrain},
 };
 
@@ -224,3 +225,35 @@ impl Command for SetTerrainLayerMaskPropertyNameCommand {
         self.swap(context);
     }
 }
+
+#[derive(Debug)]
+pub struct SetTerrainLayerPhysicsMaterialCommand {
+    pub handle: Handle<Node>,
+    pub layer_index: usize,
+    pub value: Option<PhysicsMaterialResource>,
+}
+
+impl SetTerrainLayerPhysicsMaterialCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let layer = context.scene.graph[self.handle]
+            .as_terrain_mut()
+            .layers_mut()
+            .get_mut(self.layer_index)
+            .expect("Layer must be presented!");
+        std::mem::swap(&mut layer.physics_material, &mut self.value);
+    }
+}
+
+impl Command for SetTerrainLayerPhysicsMaterialCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Terrain Layer Physics Material".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    audio::EffectSelection,
+    audio::{AudioBusSelection, EffectSelection},
     camera::CameraController,
     interaction::navmesh::{
         data_model::{Navmesh, NavmeshTriangle, NavmeshVertex},
@@ -194,8 +194,14 @@ impl EditorScene {
         }
 
         if settings.show_physics {
-            scene.graph.physics.draw(&mut scene.drawing_context);
-            scene.graph.physics2d.draw(&mut scene.drawing_context);
+            scene
+                .graph
+                .physics
+                .draw_with_flags(&mut scene.drawing_context, &settings.physics);
+            scene
+                .graph
+                .physics2d
+                .draw_with_flags(&mut scene.drawing_context, &settings.physics);
         }
 
         fn draw_recursively(
@@ -300,6 +306,7 @@ pub enum Selection {
     Graph(GraphSelection),
     Navmesh(NavmeshSelection),
     Effect(EffectSelection),
+    AudioBus(AudioBusSelection),
 }
 
 impl Default for Selection {
@@ -316,6 +323,7 @@ impl Selection {
             Selection::Navmesh(navmesh) => navmesh.is_empty(),
             Selection::SoundContext => false,
             Selection::Effect(effect) => effect.is_empty(),
+            Selection::AudioBus(bus) => bus.is_empty(),
         }
     }
 
@@ -326,6 +334,7 @@ impl Selection {
             Selection::Navmesh(navmesh) => navmesh.len(),
             Selection::SoundContext => 1,
             Selection::Effect(effect) => effect.len(),
+            Selection::AudioBus(bus) => bus.len(),
         }
     }
 
@@ -1,7 +1,8 @@
 use fyrox::{
     core::inspect::{Inspect, PropertyInfo},
-    gui::inspector::PropertyChanged,
+    gui::inspector::{FieldKind, PropertyChanged},
     handle_object_property_changed,
+    scene::graph::physics::PhysicsDebugFlags,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,8 @@ pub struct DebuggingSettings {
     pub show_physics: bool,
     pub show_bounds: bool,
     pub show_tbn: bool,
+    #[serde(default)]
+    pub physics: PhysicsDebugFlags,
 }
 
 impl Default for DebuggingSettings {
@@ -18,12 +21,41 @@ impl Default for DebuggingSettings {
             show_physics: true,
             show_bounds: true,
             show_tbn: false,
+            physics: Default::default(),
         }
     }
 }
 
+fn handle_physics_debug_flags_property_changed(
+    flags: &mut PhysicsDebugFlags,
+    property_changed: &PropertyChanged,
+) -> bool {
+    if let FieldKind::Object(ref args) = property_changed.value {
+        return match property_changed.name.as_ref() {
+            PhysicsDebugFlags::COLLIDERS => args.try_override(&mut flags.colliders),
+            PhysicsDebugFlags::BODY_STATES => args.try_override(&mut flags.body_states),
+            PhysicsDebugFlags::CONTACTS => args.try_override(&mut flags.contacts),
+            PhysicsDebugFlags::JOINTS => args.try_override(&mut flags.joints),
+            PhysicsDebugFlags::CENTER_OF_MASS => args.try_override(&mut flags.center_of_mass),
+            PhysicsDebugFlags::VELOCITIES => args.try_override(&mut flags.velocities),
+            PhysicsDebugFlags::BOUNDING_BOXES => args.try_override(&mut flags.bounding_boxes),
+            _ => false,
+        };
+    }
+    false
+}
+
 impl DebuggingSettings {
     pub fn handle_property_changed(&mut self, property_changed: &PropertyChanged) -> bool {
+        if let FieldKind::Inspectable(ref inner) = property_changed.value {
+            return match property_changed.name.as_ref() {
+                Self::PHYSICS => {
+                    handle_physics_debug_flags_property_changed(&mut self.physics, &**inner)
+                }
+                _ => false,
+            };
+        }
+
         handle_object_property_changed!(self, property_changed,
             Self::SHOW_PHYSICS => show_physics,
             Self::SHOW_BOUNDS => show_bounds,
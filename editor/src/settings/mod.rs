@@ -32,6 +32,7 @@ use fyrox::{
         HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     },
     renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
+    scene::graph::physics::PhysicsDebugFlags,
     utils::log::Log,
 };
 use ron::ser::PrettyConfig;
@@ -107,6 +108,7 @@ impl Settings {
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PhysicsDebugFlags>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<
@@ -1,7 +1,11 @@
 use crate::menu::create::CreateEntityMenu;
 use crate::{
-    scene::{commands::make_delete_selection_command, EditorScene, Selection},
-    GameEngine, Message,
+    menu::create_menu_item,
+    scene::{
+        commands::{graph::AddNodeCommand, make_delete_selection_command, CommandGroup},
+        EditorScene, Selection,
+    },
+    GameEngine, Message, SceneCommand,
 };
 use fyrox::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
@@ -13,14 +17,140 @@ use fyrox::{
         widget::WidgetBuilder,
         BuildContext, UiNode,
     },
+    scene::{
+        base::BaseBuilder,
+        collider::{
+            ColliderBuilder, ColliderShape, FittedPrimitive, GeometrySource, PrimitiveFitKind,
+        },
+        graph::Graph,
+        mesh::Mesh,
+        node::Node,
+        transform::TransformBuilder,
+    },
+    utils::log::Log,
 };
 use std::sync::mpsc::Sender;
 
+enum ColliderGenerationKind {
+    ConvexDecomposition,
+    Primitive(PrimitiveFitKind),
+}
+
+struct GenerateColliderMenu {
+    menu: Handle<UiNode>,
+    convex_decomposition: Handle<UiNode>,
+    cuboid: Handle<UiNode>,
+    capsule: Handle<UiNode>,
+    ball: Handle<UiNode>,
+}
+
+impl GenerateColliderMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let convex_decomposition = create_menu_item("Convex Decomposition", vec![], ctx);
+        let cuboid = create_menu_item("Box", vec![], ctx);
+        let capsule = create_menu_item("Capsule", vec![], ctx);
+        let ball = create_menu_item("Sphere", vec![], ctx);
+        let menu = create_menu_item(
+            "Generate Collider",
+            vec![convex_decomposition, cuboid, capsule, ball],
+            ctx,
+        );
+
+        Self {
+            menu,
+            convex_decomposition,
+            cuboid,
+            capsule,
+            ball,
+        }
+    }
+
+    fn kind(&self, destination: Handle<UiNode>) -> Option<ColliderGenerationKind> {
+        if destination == self.convex_decomposition {
+            Some(ColliderGenerationKind::ConvexDecomposition)
+        } else if destination == self.cuboid {
+            Some(ColliderGenerationKind::Primitive(PrimitiveFitKind::Cuboid))
+        } else if destination == self.capsule {
+            Some(ColliderGenerationKind::Primitive(PrimitiveFitKind::Capsule))
+        } else if destination == self.ball {
+            Some(ColliderGenerationKind::Primitive(PrimitiveFitKind::Ball))
+        } else {
+            None
+        }
+    }
+}
+
+// Creates a collider for the given mesh. The collider is attached to the rigid body which is the
+// parent of the mesh, so the mesh will keep its place in the hierarchy.
+fn make_collider(
+    graph: &Graph,
+    mesh_handle: Handle<Node>,
+    kind: &ColliderGenerationKind,
+) -> Option<SceneCommand> {
+    let mesh_node = &graph[mesh_handle];
+    let mesh = match mesh_node.cast::<Mesh>() {
+        Some(mesh) => mesh,
+        None => {
+            Log::warn(format!(
+                "Unable to generate collider for {}, it is not a mesh!",
+                mesh_node.name()
+            ));
+            return None;
+        }
+    };
+
+    let body = mesh_node.parent();
+    if !graph.try_get(body).map_or(false, |b| b.is_rigid_body()) {
+        Log::warn(format!(
+            "Unable to generate collider for {}, the mesh must be a child of a rigid body!",
+            mesh_node.name()
+        ));
+        return None;
+    }
+
+    let collider = match kind {
+        ColliderGenerationKind::ConvexDecomposition => {
+            ColliderBuilder::new(BaseBuilder::new().with_name("Collider"))
+                .with_shape(ColliderShape::convex_decomposition(GeometrySource(
+                    mesh_handle,
+                )))
+                .build_node()
+        }
+        ColliderGenerationKind::Primitive(fit_kind) => {
+            let body_inv_transform = graph[body]
+                .global_transform()
+                .try_inverse()
+                .unwrap_or_default();
+            let fitted = FittedPrimitive::from_mesh(
+                *fit_kind,
+                mesh,
+                &(body_inv_transform * mesh.global_transform()),
+            )?;
+
+            ColliderBuilder::new(
+                BaseBuilder::new()
+                    .with_name("Collider")
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(fitted.position)
+                            .with_local_rotation(fitted.rotation)
+                            .build(),
+                    ),
+            )
+            .with_shape(fitted.shape)
+            .build_node()
+        }
+    };
+
+    Some(SceneCommand::new(AddNodeCommand::new(collider, body)))
+}
+
 pub struct ItemContextMenu {
     pub menu: Handle<UiNode>,
     delete_selection: Handle<UiNode>,
     copy_selection: Handle<UiNode>,
     create_entity_menu: CreateEntityMenu,
+    generate_collider_menu: GenerateColliderMenu,
 }
 
 impl ItemContextMenu {
@@ -29,6 +159,7 @@ impl ItemContextMenu {
         let copy_selection;
 
         let (create_entity_menu, create_entity_menu_root_items) = CreateEntityMenu::new(ctx);
+        let generate_collider_menu = GenerateColliderMenu::new(ctx);
 
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
@@ -67,7 +198,8 @@ impl ItemContextMenu {
                             .with_content(MenuItemContent::text("Create Child"))
                             .with_items(create_entity_menu_root_items)
                             .build(ctx),
-                        ),
+                        )
+                        .with_child(generate_collider_menu.menu),
                 )
                 .build(ctx),
             )
@@ -75,6 +207,7 @@ impl ItemContextMenu {
 
         Self {
             create_entity_menu,
+            generate_collider_menu,
             menu,
             delete_selection,
             copy_selection,
@@ -113,6 +246,21 @@ impl ItemContextMenu {
                         engine,
                     );
                 }
+            } else if let Some(kind) = self.generate_collider_menu.kind(message.destination()) {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    let commands = graph_selection
+                        .nodes()
+                        .iter()
+                        .filter_map(|&node| make_collider(graph, node, &kind))
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender
+                            .send(Message::do_scene_command(CommandGroup::from(commands)))
+                            .unwrap();
+                    }
+                }
             }
         }
     }
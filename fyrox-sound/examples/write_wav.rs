@@ -1,21 +1,17 @@
 use fyrox_sound::{
     buffer::{DataSource, SoundBufferResource},
-    context::SoundContext,
-    engine::SoundEngine,
+    context::{SoundContext, SAMPLE_RATE},
+    offline::{OfflineRenderer, WavSampleFormat},
     pool::Handle,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
+use std::time::Duration;
 
 fn main() {
-    // Initialize sound engine without output device.
-    let engine = SoundEngine::without_device();
-
-    // Create new context.
+    // Create new context. It is not registered in a sound engine, because it will be
+    // rendered offline.
     let context = SoundContext::new();
 
-    // Register context in the engine.
-    engine.lock().unwrap().add_context(context.clone());
-
     // Load sound buffer.
     let door_open_buffer = SoundBufferResource::new_generic(
         fyrox_sound::futures::executor::block_on(DataSource::from_file(
@@ -36,29 +32,14 @@ fn main() {
     // and returns pool handle to it by which it can be accessed later on if needed.
     let _source_handle: Handle<SoundSource> = context.state().add_source(source);
 
-    // Create output wav file. The sample rate is currently fixed.
-    let wav_spec = hound::WavSpec {
-        channels: 2,
-        sample_rate: fyrox_sound::context::SAMPLE_RATE,
-        bits_per_sample: 32,
-        sample_format: hound::SampleFormat::Float,
-    };
-    let mut wav_writer = hound::WavWriter::create("output.wav", wav_spec).unwrap();
-
-    // Create an output buffer.
-    let buf_len = SoundEngine::render_buffer_len();
-    let mut buf = vec![(0.0f32, 0.0f32); buf_len];
-    let mut samples_written = 0;
-
-    // Wait until sound will play completely.
-    while samples_written < 3 * fyrox_sound::context::SAMPLE_RATE {
-        engine.lock().unwrap().render(&mut buf);
-        for &(l, r) in buf.iter() {
-            wav_writer.write_sample(l).unwrap();
-            wav_writer.write_sample(r).unwrap();
-        }
-        samples_written += buf_len as u32;
-    }
-
-    wav_writer.finalize().unwrap();
+    // Render 3 seconds of sound into a wav file. Offline renderer does not need any
+    // output device and produces exactly the same output on each run.
+    let mut renderer = OfflineRenderer::new(SAMPLE_RATE).with_context(context);
+    renderer
+        .render_to_wav_file(
+            "output.wav",
+            Duration::from_secs(3),
+            WavSampleFormat::Float32,
+        )
+        .unwrap();
 }
@@ -42,8 +42,9 @@ pub enum DataSource {
         data: Cursor<Vec<u8>>,
     },
 
-    /// Data source is a memory block. Memory block must be in valid format (wav or vorbis/ogg). This variant can
-    /// be used together with virtual file system.
+    /// Data source is a memory block. Memory block must be in valid format (wav, vorbis/ogg or
+    /// any other format enabled by features). This variant can be used together with virtual
+    /// file system.
     Memory(Cursor<Vec<u8>>),
 
     /// Raw samples in interleaved format with specified sample rate and channel count. Can be used for procedural
@@ -210,6 +211,20 @@ impl SoundBufferState {
     }
 }
 
+impl SoundBufferState {
+    /// Returns total duration of the sound. Can be `None` for streaming buffers if internal
+    /// decoder does not support seeking.
+    pub fn total_duration(&self) -> Option<Duration> {
+        match self {
+            SoundBufferState::Generic(generic) => Some(Duration::from_secs_f64(
+                generic.samples().len() as f64
+                    / (generic.channel_count() * generic.sample_rate()) as f64,
+            )),
+            SoundBufferState::Streaming(streaming) => streaming.duration(),
+        }
+    }
+}
+
 impl Default for SoundBufferState {
     fn default() -> Self {
         SoundBufferState::Generic(Default::default())
@@ -0,0 +1,572 @@
+//! Mixer buses.
+//!
+//! # Overview
+//!
+//! Audio bus is a mixing point for a group of sound sources and other buses. Buses form a tree
+//! with the master bus at its root, for example `Master → Music/SFX/Voice/UI`. Each bus has its
+//! own gain, can be muted or soloed, has an ordered chain of effects which process the mixed
+//! signal of the bus and can be "ducked" when some other bus is loud enough (for example music
+//! could be ducked when dialogue is playing).
+//!
+//! Sound sources are routed to a bus by its handle, see [`crate::source::SoundSource::set_bus`].
+//! Sources with invalid bus handle are routed to the master bus.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     bus::{AudioBus, Ducking},
+//!     context::SoundContext,
+//! };
+//!
+//! fn setup_buses(context: &SoundContext) {
+//!     let mut state = context.state();
+//!     let graph = state.bus_graph_mut();
+//!     let master = graph.master();
+//!     let voice = graph.add_bus(AudioBus::new("Voice").with_parent(master));
+//!     graph.add_bus(
+//!         AudioBus::new("Music")
+//!             .with_parent(master)
+//!             .with_ducking(Ducking::new(voice)),
+//!     );
+//! }
+//! ```
+
+#![allow(clippy::float_cmp)]
+
+use crate::{
+    context::SAMPLE_RATE,
+    effects::{Effect, EffectRenderTrait},
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    math,
+    pool::{Handle, Pool},
+    visitor::prelude::*,
+};
+use std::cmp::Reverse;
+
+/// Ducking lowers gain of a bus when signal level of other (sidechain) bus is above some threshold.
+/// Typical usage is to make music quieter when there is a dialogue.
+#[derive(Debug, Clone, PartialEq, Inspect, Visit)]
+pub struct Ducking {
+    /// A bus which level controls the ducking.
+    pub sidechain: Handle<AudioBus>,
+    /// Peak level (linear) of the sidechain bus above which ducking starts.
+    #[inspect(min_value = 0.0, step = 0.01)]
+    pub threshold: f32,
+    /// Gain of the bus when it is fully ducked.
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub ducked_gain: f32,
+    /// Time (in seconds) that is needed to duck the bus.
+    #[inspect(min_value = 0.0, step = 0.01)]
+    pub attack: f32,
+    /// Time (in seconds) that is needed to restore gain of the bus after the sidechain became quiet.
+    #[inspect(min_value = 0.0, step = 0.01)]
+    pub release: f32,
+}
+
+impl Default for Ducking {
+    fn default() -> Self {
+        Self {
+            sidechain: Default::default(),
+            threshold: 0.01,
+            ducked_gain: 0.3,
+            attack: 0.05,
+            release: 0.5,
+        }
+    }
+}
+
+impl Ducking {
+    /// Creates new ducking with default parameters, which will be controlled by given bus.
+    pub fn new(sidechain: Handle<AudioBus>) -> Self {
+        Self {
+            sidechain,
+            ..Default::default()
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct AudioBus {
+    name: String,
+    parent: Handle<AudioBus>,
+    #[inspect(min_value = 0.0, step = 0.05)]
+    gain: f32,
+    muted: bool,
+    solo: bool,
+    ducking: Option<Ducking>,
+    #[inspect(skip)]
+    effects: Vec<Effect>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    buffer: Vec<(f32, f32)>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    duck_gain: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    last_gain: Option<f32>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    level: f32,
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            parent: Default::default(),
+            gain: 1.0,
+            muted: false,
+            solo: false,
+            ducking: None,
+            effects: Default::default(),
+            buffer: Default::default(),
+            duck_gain: 1.0,
+            last_gain: None,
+            level: 0.0,
+        }
+    }
+}
+
+impl AudioBus {
+    /// Creates new bus with given name. The bus will be attached to the master bus.
+    pub fn new<N: AsRef<str>>(name: N) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets parent bus, the output of the bus will be mixed into the parent bus.
+    pub fn with_parent(mut self, parent: Handle<AudioBus>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Sets gain of the bus.
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Sets ducking of the bus.
+    pub fn with_ducking(mut self, ducking: Ducking) -> Self {
+        self.ducking = Some(ducking);
+        self
+    }
+
+    /// Sets effects chain of the bus.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+        self
+    }
+
+    /// Sets new name of the bus.
+    pub fn set_name<N: AsRef<str>>(&mut self, name: N) {
+        self.name = name.as_ref().to_owned();
+    }
+
+    /// Returns name of the bus.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets new parent of the bus. Invalid handle (or a handle that would create a cycle) means
+    /// that the bus is attached to the master bus.
+    pub fn set_parent(&mut self, parent: Handle<AudioBus>) {
+        self.parent = parent;
+    }
+
+    /// Returns handle of the parent bus.
+    pub fn parent(&self) -> Handle<AudioBus> {
+        self.parent
+    }
+
+    /// Sets gain of the bus. It should be in (0;1) range, but larger values are fine too.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns gain of the bus.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Mutes or unmutes the bus, muted bus produces no sound.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    /// Returns true if the bus is muted.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Enables or disables solo mode of the bus. When there is at least one soloed bus, every other
+    /// bus is silenced except ancestors and descendants of soloed buses.
+    pub fn set_solo(&mut self, solo: bool) {
+        self.solo = solo;
+    }
+
+    /// Returns true if the bus is soloed.
+    pub fn is_solo(&self) -> bool {
+        self.solo
+    }
+
+    /// Sets new ducking settings of the bus, `None` disables ducking.
+    pub fn set_ducking(&mut self, ducking: Option<Ducking>) {
+        self.ducking = ducking;
+    }
+
+    /// Returns ducking settings of the bus.
+    pub fn ducking(&self) -> Option<&Ducking> {
+        self.ducking.as_ref()
+    }
+
+    /// Returns shared reference to the effects chain of the bus.
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    /// Returns mutable reference to the effects chain of the bus. Effects are applied in order.
+    pub fn effects_mut(&mut self) -> &mut Vec<Effect> {
+        &mut self.effects
+    }
+
+    /// Returns peak level of the output of the bus in the last rendered frame.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Copies settings (except effects) of the other bus, runtime state of the bus is preserved.
+    pub fn set_settings_from(&mut self, other: &AudioBus) {
+        self.name = other.name.clone();
+        self.parent = other.parent;
+        self.gain = other.gain;
+        self.muted = other.muted;
+        self.solo = other.solo;
+        self.ducking = other.ducking.clone();
+    }
+
+    fn process(&mut self, audible: bool, sidechain_level: Option<f32>, frame_duration: f32) {
+        for effect in self.effects.iter_mut() {
+            effect.process(&mut self.buffer);
+        }
+
+        match self.ducking.as_ref() {
+            Some(ducking) => {
+                let target = if sidechain_level.unwrap_or(0.0) > ducking.threshold {
+                    ducking.ducked_gain
+                } else {
+                    1.0
+                };
+                let time = if target < self.duck_gain {
+                    ducking.attack
+                } else {
+                    ducking.release
+                };
+                let k = if time > 0.0 {
+                    1.0 - (-frame_duration / time).exp()
+                } else {
+                    1.0
+                };
+                self.duck_gain += (target - self.duck_gain) * k;
+            }
+            None => self.duck_gain = 1.0,
+        }
+
+        let gain = if self.muted || !audible {
+            0.0
+        } else {
+            self.gain * self.duck_gain
+        };
+        let last_gain = self.last_gain.unwrap_or(gain);
+
+        let mut level = 0.0f32;
+        let step = 1.0 / self.buffer.len().max(1) as f32;
+        let mut t = 0.0;
+        for (left, right) in self.buffer.iter_mut() {
+            // Interpolate gain to prevent clicks when it changes significantly between frames.
+            let g = math::lerpf(last_gain, gain, t);
+            *left *= g;
+            *right *= g;
+            level = level.max(left.abs()).max(right.abs());
+            t += step;
+        }
+
+        self.last_gain = Some(gain);
+        self.level = level;
+    }
+}
+
+/// A tree of buses with the master bus at its root. See module docs for more info.
+#[derive(Debug, Clone, Visit)]
+pub struct AudioBusGraph {
+    buses: Pool<AudioBus>,
+    master: Handle<AudioBus>,
+}
+
+impl Default for AudioBusGraph {
+    fn default() -> Self {
+        let mut buses = Pool::new();
+        let master = buses.spawn(AudioBus::new("Master"));
+        Self { buses, master }
+    }
+}
+
+impl AudioBusGraph {
+    /// Creates new bus graph with the master bus only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns handle of the master bus.
+    pub fn master(&self) -> Handle<AudioBus> {
+        self.master
+    }
+
+    /// Adds new bus and returns its handle.
+    pub fn add_bus(&mut self, bus: AudioBus) -> Handle<AudioBus> {
+        self.buses.spawn(bus)
+    }
+
+    /// Removes a bus, the master bus cannot be removed. Children of removed bus and sources
+    /// routed to it will be mixed into the master bus.
+    pub fn remove_bus(&mut self, handle: Handle<AudioBus>) -> Option<AudioBus> {
+        if handle != self.master && self.buses.is_valid_handle(handle) {
+            Some(self.buses.free(handle))
+        } else {
+            None
+        }
+    }
+
+    /// Checks if given handle points to a bus in the graph.
+    pub fn is_valid_handle(&self, handle: Handle<AudioBus>) -> bool {
+        self.buses.is_valid_handle(handle)
+    }
+
+    /// Tries to borrow a bus.
+    pub fn try_get(&self, handle: Handle<AudioBus>) -> Option<&AudioBus> {
+        self.buses.try_borrow(handle)
+    }
+
+    /// Tries to borrow a bus as mutable.
+    pub fn try_get_mut(&mut self, handle: Handle<AudioBus>) -> Option<&mut AudioBus> {
+        self.buses.try_borrow_mut(handle)
+    }
+
+    /// Returns an iterator over all buses.
+    pub fn buses(&self) -> impl Iterator<Item = (Handle<AudioBus>, &AudioBus)> {
+        self.buses.pair_iter()
+    }
+
+    /// Returns total amount of buses (including the master bus).
+    pub fn bus_count(&self) -> u32 {
+        self.buses.alive_count()
+    }
+
+    /// Searches for a bus with given name.
+    pub fn find_by_name(&self, name: &str) -> Option<Handle<AudioBus>> {
+        self.buses
+            .pair_iter()
+            .find(|(_, bus)| bus.name == name)
+            .map(|(handle, _)| handle)
+    }
+
+    /// Returns a bus into which given bus is mixed, `None` is returned for the master bus.
+    /// Invalid parent or a parent that creates a cycle is replaced with the master bus.
+    pub fn resolve_parent(&self, handle: Handle<AudioBus>) -> Option<Handle<AudioBus>> {
+        if handle == self.master {
+            return None;
+        }
+        let parent = match self.buses.try_borrow(handle) {
+            Some(bus) if self.buses.is_valid_handle(bus.parent) => bus.parent,
+            _ => return Some(self.master),
+        };
+        // Make sure that the parent is not a descendant of the bus.
+        let mut ancestor = parent;
+        for _ in 0..self.buses.alive_count() {
+            if ancestor == handle {
+                return Some(self.master);
+            }
+            if ancestor == self.master {
+                return Some(parent);
+            }
+            match self.buses.try_borrow(ancestor) {
+                Some(bus) if self.buses.is_valid_handle(bus.parent) => ancestor = bus.parent,
+                _ => return Some(parent),
+            }
+        }
+        Some(self.master)
+    }
+
+    fn is_ancestor_or_self(&self, ancestor: Handle<AudioBus>, mut bus: Handle<AudioBus>) -> bool {
+        loop {
+            if bus == ancestor {
+                return true;
+            }
+            match self.resolve_parent(bus) {
+                Some(parent) => bus = parent,
+                None => return false,
+            }
+        }
+    }
+
+    fn depth(&self, mut bus: Handle<AudioBus>) -> usize {
+        let mut depth = 0;
+        while let Some(parent) = self.resolve_parent(bus) {
+            bus = parent;
+            depth += 1;
+        }
+        depth
+    }
+
+    pub(crate) fn begin_render(&mut self, amount: usize) {
+        for bus in self.buses.iter_mut() {
+            bus.buffer.clear();
+            bus.buffer.resize(amount, (0.0, 0.0));
+        }
+    }
+
+    /// Returns input buffer of a bus, the master bus is used if the handle is invalid.
+    pub(crate) fn input_buffer_mut(&mut self, bus: Handle<AudioBus>) -> &mut [(f32, f32)] {
+        let bus = if self.buses.is_valid_handle(bus) {
+            bus
+        } else {
+            self.master
+        };
+        &mut self.buses[bus].buffer
+    }
+
+    /// Processes every bus, children are processed before their parents, and mixes the output of
+    /// the master bus into given buffer.
+    pub(crate) fn end_render(&mut self, output: &mut [(f32, f32)]) {
+        let mut order = self
+            .buses
+            .pair_iter()
+            .map(|(handle, _)| (handle, self.depth(handle)))
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&(_, depth)| Reverse(depth));
+
+        let soloed = self
+            .buses
+            .pair_iter()
+            .filter(|(_, bus)| bus.solo)
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        let frame_duration = output.len() as f32 / SAMPLE_RATE as f32;
+
+        for (handle, _) in order {
+            let audible = soloed.is_empty()
+                || soloed.iter().any(|&solo| {
+                    self.is_ancestor_or_self(solo, handle) || self.is_ancestor_or_self(handle, solo)
+                });
+            // Sidechain could be processed after this bus, in this case its level from the
+            // previous frame is used.
+            let sidechain_level = self.buses[handle]
+                .ducking
+                .as_ref()
+                .and_then(|ducking| self.buses.try_borrow(ducking.sidechain))
+                .map(|sidechain| sidechain.level);
+            let parent = self.resolve_parent(handle);
+
+            let bus = &mut self.buses[handle];
+            bus.process(audible, sidechain_level, frame_duration);
+            let samples = std::mem::take(&mut bus.buffer);
+
+            let destination: &mut [(f32, f32)] = match parent {
+                Some(parent) => &mut self.buses[parent].buffer,
+                None => &mut *output,
+            };
+            for ((out_left, out_right), &(left, right)) in destination.iter_mut().zip(&samples) {
+                *out_left += left;
+                *out_right += right;
+            }
+
+            self.buses[handle].buffer = samples;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bus::{AudioBus, AudioBusGraph, Ducking};
+    use fyrox_core::pool::Handle;
+
+    const FRAME: usize = 64;
+
+    fn render(graph: &mut AudioBusGraph, inputs: &[(Handle<AudioBus>, f32)]) -> Vec<(f32, f32)> {
+        graph.begin_render(FRAME);
+        for &(bus, value) in inputs {
+            for sample in graph.input_buffer_mut(bus) {
+                *sample = (value, value);
+            }
+        }
+        let mut output = vec![(0.0, 0.0); FRAME];
+        graph.end_render(&mut output);
+        output
+    }
+
+    #[test]
+    fn test_children_are_processed_before_parents() {
+        let mut graph = AudioBusGraph::new();
+        let master = graph.master();
+        // Spawn the leaf first, so the order of buses in the pool differs from their depth order.
+        let leaf = graph.add_bus(AudioBus::new("Leaf").with_gain(0.5));
+        let group = graph.add_bus(AudioBus::new("Group").with_gain(0.5).with_parent(master));
+        graph.try_get_mut(leaf).unwrap().set_parent(group);
+
+        let output = render(&mut graph, &[(leaf, 1.0), (group, 0.5)]);
+
+        // (1.0 * 0.5 + 0.5) * 0.5
+        for &(left, right) in output.iter() {
+            assert_eq!(left, 0.5);
+            assert_eq!(right, 0.5);
+        }
+        assert_eq!(graph.try_get(leaf).unwrap().level(), 0.5);
+        assert_eq!(graph.try_get(group).unwrap().level(), 0.5);
+    }
+
+    #[test]
+    fn test_ducking() {
+        let mut graph = AudioBusGraph::new();
+        let master = graph.master();
+        let voice = graph.add_bus(AudioBus::new("Voice").with_parent(master));
+        let music = graph.add_bus(AudioBus::new("Music").with_parent(master).with_ducking(
+            Ducking {
+                sidechain: voice,
+                threshold: 0.1,
+                ducked_gain: 0.25,
+                attack: 0.0,
+                release: 0.0,
+            },
+        ));
+
+        // Quiet sidechain must not duck the bus.
+        for _ in 0..3 {
+            render(&mut graph, &[(voice, 0.05), (music, 1.0)]);
+        }
+        assert_eq!(graph.try_get(music).unwrap().level(), 1.0);
+
+        // Level of the sidechain could be taken from the previous frame and the gain is
+        // interpolated across a frame, so the bus is fully ducked in a few frames.
+        for _ in 0..3 {
+            render(&mut graph, &[(voice, 1.0), (music, 1.0)]);
+        }
+        assert_eq!(graph.try_get(music).unwrap().level(), 0.25);
+        assert_eq!(graph.try_get(voice).unwrap().level(), 1.0);
+        let output = render(&mut graph, &[(voice, 1.0), (music, 1.0)]);
+        assert!(output.iter().all(|&(left, _)| left == 1.25));
+
+        // Gain is restored when the sidechain becomes quiet.
+        for _ in 0..3 {
+            render(&mut graph, &[(music, 1.0)]);
+        }
+        assert_eq!(graph.try_get(music).unwrap().level(), 1.0);
+    }
+}
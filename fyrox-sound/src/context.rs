@@ -11,6 +11,7 @@
 //! sounds, only your level will do.
 
 use crate::{
+    bus::AudioBusGraph,
     effects::{Effect, EffectRenderTrait},
     listener::Listener,
     pool::Ticket,
@@ -23,6 +24,8 @@ use fyrox_core::{
     visitor::prelude::*,
 };
 use std::{
+    cmp::Ordering,
+    collections::VecDeque,
     sync::{Arc, Mutex, MutexGuard},
     time::Duration,
 };
@@ -32,6 +35,20 @@ use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 /// TODO: Make this configurable, for now its set to most commonly used sample rate of 44100 Hz.
 pub const SAMPLE_RATE: u32 = 44100;
 
+/// Converts given duration to amount of samples per channel at [`SAMPLE_RATE`].
+pub fn duration_to_samples(duration: Duration) -> u64 {
+    (duration.as_secs_f64() * SAMPLE_RATE as f64).round() as u64
+}
+
+/// Default maximum amount of sources that can be mixed at the same time.
+pub const DEFAULT_MAX_ACTIVE_VOICES: u32 = 128;
+
+/// Default audibility (-60 dB) below which sources are virtualized.
+pub const DEFAULT_VIRTUALIZATION_THRESHOLD: f32 = 0.001;
+
+/// Speed of sound in the air (in meters per second) at 20 degrees Celsius.
+pub const DEFAULT_SPEED_OF_SOUND: f32 = 343.3;
+
 /// Distance model defines how volume of sound will decay when distance to listener changes.
 #[derive(
     Copy, Clone, Debug, Eq, PartialEq, Inspect, Visit, AsRefStr, EnumString, EnumVariantNames,
@@ -85,6 +102,28 @@ impl Default for DistanceModel {
     }
 }
 
+/// Statistics of voice management of a context for the last rendered frame.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct VoiceStatistics {
+    /// Amount of sources that were mixed.
+    pub active_voices: usize,
+
+    /// Amount of playing sources that were not mixed, but their playback time has advanced. See
+    /// [`SoundSource::is_virtual`] for more info.
+    pub virtual_voices: usize,
+
+    /// Amount of time spent on mixing of active sources, it includes effects and buses.
+    pub mixing_duration: Duration,
+}
+
+// A playing source that competes for a place in the mix.
+#[derive(Clone, Debug)]
+struct Voice {
+    source: Handle<SoundSource>,
+    priority: u32,
+    audibility: f32,
+}
+
 /// See module docs.
 #[derive(Clone, Default, Debug, Visit)]
 pub struct SoundContext {
@@ -98,7 +137,7 @@ impl PartialEq for SoundContext {
 }
 
 /// Internal state of context.
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct State {
     sources: Pool<SoundSource>,
     listener: Listener,
@@ -108,6 +147,43 @@ pub struct State {
     effects: Pool<Effect>,
     distance_model: DistanceModel,
     paused: bool,
+    bus_graph: AudioBusGraph,
+    doppler_factor: f32,
+    speed_of_sound: f32,
+    sample_clock: u64,
+    max_active_voices: u32,
+    virtualization_threshold: f32,
+    voice_statistics: VoiceStatistics,
+    voices: Vec<Voice>,
+    // HRTF renderer works with blocks of fixed size, these are used to pass its output to
+    // buffers of any size.
+    hrtf_block: Vec<(f32, f32)>,
+    hrtf_output: VecDeque<(f32, f32)>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            sources: Pool::new(),
+            listener: Listener::new(),
+            master_gain: 1.0,
+            render_duration: Default::default(),
+            renderer: Renderer::Default,
+            effects: Pool::new(),
+            distance_model: DistanceModel::InverseDistance,
+            paused: false,
+            bus_graph: AudioBusGraph::new(),
+            doppler_factor: 1.0,
+            speed_of_sound: DEFAULT_SPEED_OF_SOUND,
+            sample_clock: 0,
+            max_active_voices: DEFAULT_MAX_ACTIVE_VOICES,
+            virtualization_threshold: DEFAULT_VIRTUALIZATION_THRESHOLD,
+            voice_statistics: Default::default(),
+            voices: Default::default(),
+            hrtf_block: Default::default(),
+            hrtf_output: Default::default(),
+        }
+    }
 }
 
 impl State {
@@ -154,6 +230,71 @@ impl State {
         self.distance_model
     }
 
+    /// Sets new doppler factor. It exaggerates (values larger than 1.0) or attenuates (values less
+    /// than 1.0) pitch shift caused by the Doppler effect. Zero disables the effect completely.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.doppler_factor = doppler_factor.max(0.0);
+    }
+
+    /// Returns current doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.doppler_factor
+    }
+
+    /// Sets speed of sound in units per second. Default value is [`DEFAULT_SPEED_OF_SOUND`], which
+    /// is suitable if one unit of your game world is one meter.
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.speed_of_sound = speed_of_sound.max(f32::EPSILON);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.speed_of_sound
+    }
+
+    /// Returns current time of context's render clock, which is amount of samples per channel
+    /// rendered by the context. The clock does not advance while the context is paused. This
+    /// clock is used to schedule playback of sources and fades with sample accuracy.
+    pub fn sample_clock(&self) -> u64 {
+        self.sample_clock
+    }
+
+    /// Returns time of context's render clock after given delay. See [`Self::sample_clock`].
+    pub fn clock_after(&self, delay: Duration) -> u64 {
+        self.sample_clock + duration_to_samples(delay)
+    }
+
+    /// Sets maximum amount of sources that can be mixed at the same time. When there are more
+    /// playing sources, the least important ones (see [`SoundSource::set_priority`]) become
+    /// virtual: they're not mixed, but their playback time is still advancing. Default value is
+    /// [`DEFAULT_MAX_ACTIVE_VOICES`].
+    pub fn set_max_active_voices(&mut self, max_active_voices: u32) {
+        self.max_active_voices = max_active_voices;
+    }
+
+    /// Returns maximum amount of sources that can be mixed at the same time.
+    pub fn max_active_voices(&self) -> u32 {
+        self.max_active_voices
+    }
+
+    /// Sets audibility below which sources become virtual. Audibility is an estimation of loudness
+    /// of a source which takes into account its gain, distance attenuation and occlusion. Zero
+    /// threshold disables virtualization of inaudible sources. Default value is
+    /// [`DEFAULT_VIRTUALIZATION_THRESHOLD`].
+    pub fn set_virtualization_threshold(&mut self, threshold: f32) {
+        self.virtualization_threshold = threshold.max(0.0);
+    }
+
+    /// Returns audibility below which sources become virtual.
+    pub fn virtualization_threshold(&self) -> f32 {
+        self.virtualization_threshold
+    }
+
+    /// Returns statistics of voice management for the last rendered frame.
+    pub fn voice_statistics(&self) -> VoiceStatistics {
+        self.voice_statistics
+    }
+
     /// Adds new effect to effects chain. Each sample from
     pub fn add_effect(&mut self, effect: Effect) -> Handle<Effect> {
         self.effects.spawn(effect)
@@ -164,6 +305,21 @@ impl State {
         self.effects.free(effect);
     }
 
+    /// Returns shared reference to the graph of mixer buses.
+    pub fn bus_graph(&self) -> &AudioBusGraph {
+        &self.bus_graph
+    }
+
+    /// Returns mutable reference to the graph of mixer buses.
+    pub fn bus_graph_mut(&mut self) -> &mut AudioBusGraph {
+        &mut self.bus_graph
+    }
+
+    /// Sets new graph of mixer buses and returns the old one.
+    pub fn set_bus_graph(&mut self, bus_graph: AudioBusGraph) -> AudioBusGraph {
+        std::mem::replace(&mut self.bus_graph, bus_graph)
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -262,41 +418,108 @@ impl State {
     }
 
     pub(crate) fn render(&mut self, master_gain: f32, buf: &mut [(f32, f32)]) {
+        if let Renderer::HrtfRenderer(_) = self.renderer {
+            let mut written = 0;
+            while written < buf.len() {
+                if self.hrtf_output.is_empty() {
+                    let mut block = std::mem::take(&mut self.hrtf_block);
+                    block.clear();
+                    block.resize(SoundContext::SAMPLES_PER_CHANNEL, (0.0, 0.0));
+                    self.render_block(master_gain, &mut block);
+                    self.hrtf_output.extend(block.iter().copied());
+                    self.hrtf_block = block;
+                }
+
+                let count = (buf.len() - written).min(self.hrtf_output.len());
+                for ((out_left, out_right), (left, right)) in buf[written..written + count]
+                    .iter_mut()
+                    .zip(self.hrtf_output.drain(..count))
+                {
+                    *out_left += left;
+                    *out_right += right;
+                }
+                written += count;
+            }
+        } else {
+            // Drop the rest of HRTF output if the renderer was changed.
+            self.hrtf_output.clear();
+            self.render_block(master_gain, buf);
+        }
+    }
+
+    fn render_block(&mut self, master_gain: f32, buf: &mut [(f32, f32)]) {
         let last_time = fyrox_core::instant::Instant::now();
 
         if !self.paused {
             self.sources.retain(|source| {
-                let done = source.is_play_once() && source.status() == Status::Stopped;
+                let done = source.is_play_once()
+                    && source.status() == Status::Stopped
+                    && source.scheduled_play_time().is_none();
                 !done
             });
 
+            self.bus_graph.begin_render(buf.len());
+
+            let clock = self.sample_clock;
+            let frame_end = clock + buf.len() as u64;
+
+            self.update_voices(frame_end);
+
+            let mixing_start = fyrox_core::instant::Instant::now();
+
             for source in self
                 .sources
                 .iter_mut()
-                .filter(|s| s.status() == Status::Playing)
+                .filter(|s| is_playing_in_frame(s, frame_end))
             {
-                source.render(buf.len());
+                if source.is_virtual() {
+                    source.render_virtual(buf.len(), clock);
+                    continue;
+                }
+
+                // Doppler effect is applied by changing playback speed, so it works with any
+                // renderer.
+                let doppler_shift = source.calculate_doppler_shift(
+                    &self.listener,
+                    self.doppler_factor,
+                    self.speed_of_sound,
+                );
+                source.render(buf.len(), doppler_shift, clock);
+
+                // Each source is mixed into its bus, buses are then mixed together.
+                let bus_buf = self.bus_graph.input_buffer_mut(source.bus());
 
                 match self.renderer {
                     Renderer::Default => {
                         // Simple rendering path. Much faster (4-5 times) than HRTF path.
-                        render_source_default(source, &self.listener, self.distance_model, buf);
+                        render_source_default(source, &self.listener, self.distance_model, bus_buf);
                     }
                     Renderer::HrtfRenderer(ref mut hrtf_renderer) => {
                         hrtf_renderer.render_source(
                             source,
                             &self.listener,
                             self.distance_model,
-                            buf,
+                            bus_buf,
                         );
                     }
                 }
             }
 
+            let master_buf = self.bus_graph.input_buffer_mut(self.bus_graph.master());
             for effect in self.effects.iter_mut() {
-                effect.render(&self.sources, &self.listener, self.distance_model, buf);
+                effect.render(
+                    &self.sources,
+                    &self.listener,
+                    self.distance_model,
+                    master_buf,
+                );
             }
 
+            self.bus_graph.end_render(buf);
+
+            self.voice_statistics.mixing_duration =
+                fyrox_core::instant::Instant::now() - mixing_start;
+
             let global_gain = self.master_gain * master_gain;
 
             // Apply master gain to be able to control total sound volume.
@@ -304,10 +527,58 @@ impl State {
                 *left *= global_gain;
                 *right *= global_gain;
             }
+
+            self.sample_clock = frame_end;
         }
 
         self.render_duration = fyrox_core::instant::Instant::now() - last_time;
     }
+
+    // Decides which sources will be mixed in the next frame and which will become virtual.
+    fn update_voices(&mut self, frame_end: u64) {
+        self.voices.clear();
+        for (handle, source) in self.sources.pair_iter() {
+            if is_playing_in_frame(source, frame_end) {
+                self.voices.push(Voice {
+                    source: handle,
+                    priority: source.priority(),
+                    audibility: source.calculate_audibility(&self.listener, self.distance_model),
+                });
+            }
+        }
+
+        // Most important voices go first.
+        self.voices.sort_by(|a, b| {
+            b.priority.cmp(&a.priority).then_with(|| {
+                b.audibility
+                    .partial_cmp(&a.audibility)
+                    .unwrap_or(Ordering::Equal)
+            })
+        });
+
+        let mut active_voices = 0;
+        for voice in self.voices.iter() {
+            let is_virtual = voice.audibility < self.virtualization_threshold
+                || active_voices >= self.max_active_voices as usize;
+            if !is_virtual {
+                active_voices += 1;
+            }
+            self.sources
+                .borrow_mut(voice.source)
+                .set_virtual(is_virtual);
+        }
+
+        self.voice_statistics.active_voices = active_voices;
+        self.voice_statistics.virtual_voices = self.voices.len() - active_voices;
+    }
+}
+
+// Checks whether a source plays in a frame that ends at given time.
+fn is_playing_in_frame(source: &SoundSource, frame_end: u64) -> bool {
+    source.status() == Status::Playing
+        || source
+            .scheduled_play_time()
+            .map_or(false, |time| time < frame_end)
 }
 
 impl SoundContext {
@@ -325,16 +596,7 @@ impl SoundContext {
     /// because separate thread also uses context.
     pub fn new() -> Self {
         Self {
-            state: Some(Arc::new(Mutex::new(State {
-                sources: Pool::new(),
-                listener: Listener::new(),
-                master_gain: 1.0,
-                render_duration: Default::default(),
-                renderer: Renderer::Default,
-                effects: Pool::new(),
-                distance_model: DistanceModel::InverseDistance,
-                paused: false,
-            }))),
+            state: Some(Arc::new(Mutex::new(State::default()))),
         }
     }
 
@@ -389,7 +651,206 @@ impl Visit for State {
         self.renderer.visit("Renderer", &mut region)?;
         self.paused.visit("Paused", &mut region)?;
         self.distance_model.visit("DistanceModel", &mut region)?;
+        let _ = self.bus_graph.visit("BusGraph", &mut region); // Backward compatibility
+        let _ = self.doppler_factor.visit("DopplerFactor", &mut region); // Backward compatibility
+        let _ = self.speed_of_sound.visit("SpeedOfSound", &mut region); // Backward compatibility
+        let _ = self.max_active_voices.visit("MaxActiveVoices", &mut region); // Backward compatibility
+        let _ = self
+            .virtualization_threshold
+            .visit("VirtualizationThreshold", &mut region); // Backward compatibility
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource},
+        context::{State, SAMPLE_RATE},
+        pool::Handle,
+        source::{SoundSource, SoundSourceBuilder, Status},
+    };
+    use std::time::Duration;
+
+    fn add_source(state: &mut State, gain: f32, priority: u32) -> Handle<SoundSource> {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![1.0; SAMPLE_RATE as usize],
+        })
+        .unwrap();
+        state.add_source(
+            SoundSourceBuilder::new()
+                .with_buffer(buffer)
+                .with_gain(gain)
+                .with_priority(priority)
+                .with_spatial_blend_factor(0.0)
+                .with_looping(true)
+                .with_status(Status::Playing)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    // Source which plays samples that are equal to their indices.
+    fn add_ramp_source(state: &mut State, len: usize, looping: bool) -> Handle<SoundSource> {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: (0..len).map(|i| i as f32).collect(),
+        })
+        .unwrap();
+        state.add_source(
+            SoundSourceBuilder::new()
+                .with_buffer(buffer)
+                .with_spatial_blend_factor(0.0)
+                .with_looping(looping)
+                .with_status(Status::Playing)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn render(state: &mut State) {
+        let mut buf = vec![(0.0, 0.0); 441];
+        state.render(1.0, &mut buf);
+    }
+
+    fn virtual_sources(state: &State, sources: &[Handle<SoundSource>]) -> Vec<bool> {
+        sources
+            .iter()
+            .map(|&source| state.source(source).is_virtual())
+            .collect()
+    }
+
+    #[test]
+    fn test_voice_limit() {
+        let mut state = State::default();
+        state.set_max_active_voices(2);
+
+        let sources = [
+            add_source(&mut state, 1.0, 0),
+            // Priority is more important than audibility.
+            add_source(&mut state, 0.1, 1),
+            add_source(&mut state, 0.5, 0),
+            add_source(&mut state, 0.2, 0),
+        ];
+
+        render(&mut state);
+        assert_eq!(
+            virtual_sources(&state, &sources),
+            vec![false, false, true, true]
+        );
+        let statistics = state.voice_statistics();
+        assert_eq!(statistics.active_voices, 2);
+        assert_eq!(statistics.virtual_voices, 2);
+
+        // Virtual sources are still advancing.
+        for &source in sources.iter() {
+            assert_eq!(
+                state.source(source).playback_time(),
+                Duration::from_millis(10)
+            );
+        }
+
+        // The quietest active source is replaced by louder virtual source.
+        state.source_mut(sources[3]).set_gain(2.0);
+        render(&mut state);
+        assert_eq!(
+            virtual_sources(&state, &sources),
+            vec![true, false, true, false]
+        );
+
+        // Stopped sources do not occupy voices.
+        state.source_mut(sources[1]).stop().unwrap();
+        render(&mut state);
+        assert_eq!(
+            virtual_sources(&state, &sources),
+            vec![false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_virtual_voice_resumes_at_right_offset() {
+        let mut state = State::default();
+        state.set_max_active_voices(1);
+
+        let important = add_source(&mut state, 1.0, 1);
+        let ramp = add_ramp_source(&mut state, SAMPLE_RATE as usize, false);
+        let looping_ramp = add_ramp_source(&mut state, 1000, true);
+
+        for _ in 0..3 {
+            render(&mut state);
+            assert!(state.source(ramp).is_virtual());
+            assert!(state.source(looping_ramp).is_virtual());
+            // Virtual sources are not mixed.
+            assert!(state
+                .source(ramp)
+                .frame_samples()
+                .iter()
+                .all(|s| *s == (0.0, 0.0)));
+        }
+        assert_eq!(
+            state.source(ramp).playback_time(),
+            Duration::from_millis(30)
+        );
+
+        // Let the ramp take the only voice, it must continue from the sample it would have reached
+        // if it was playing all the time.
+        state.source_mut(important).stop().unwrap();
+        state.source_mut(looping_ramp).set_priority(1);
+        render(&mut state);
+        assert!(!state.source(looping_ramp).is_virtual());
+        // 3 frames of 441 samples were skipped, looping source has wrapped around.
+        let samples = state.source(looping_ramp).frame_samples();
+        assert_eq!(samples[0].0, 323.0);
+        assert_eq!(samples[1].0, 324.0);
+
+        state.source_mut(looping_ramp).set_priority(0);
+        state.source_mut(ramp).set_priority(1);
+        render(&mut state);
+        assert!(!state.source(ramp).is_virtual());
+        let samples = state.source(ramp).frame_samples();
+        assert_eq!(samples[0].0, 1764.0);
+        assert_eq!(samples[440].0, 2204.0);
+    }
+
+    #[test]
+    fn test_virtual_voice_stops_at_end() {
+        let mut state = State::default();
+        state.set_max_active_voices(1);
+
+        let important = add_source(&mut state, 1.0, 1);
+        let ramp = add_ramp_source(&mut state, 1000, false);
+
+        for _ in 0..3 {
+            render(&mut state);
+        }
+
+        // Non-looping source reached its end while it was virtual.
+        assert_eq!(state.source(ramp).status(), Status::Stopped);
+        assert_eq!(state.source(important).status(), Status::Playing);
+    }
+
+    #[test]
+    fn test_voice_virtualization_threshold() {
+        let mut state = State::default();
+        state.set_virtualization_threshold(0.01);
+
+        let quiet = add_source(&mut state, 0.005, 0);
+        let loud = add_source(&mut state, 0.5, 0);
+
+        render(&mut state);
+        assert_eq!(virtual_sources(&state, &[quiet, loud]), vec![true, false]);
+
+        state.source_mut(quiet).set_gain(0.02);
+        render(&mut state);
+        assert_eq!(virtual_sources(&state, &[quiet, loud]), vec![false, false]);
+
+        state.source_mut(loud).set_gain(0.0);
+        state.set_virtualization_threshold(0.0);
+        render(&mut state);
+        assert_eq!(virtual_sources(&state, &[quiet, loud]), vec![false, false]);
+    }
+}
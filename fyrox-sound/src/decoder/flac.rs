@@ -0,0 +1,252 @@
+use crate::{buffer::DataSource, error::SoundError};
+use claxon::FlacReader;
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Read, Seek, SeekFrom},
+    time::Duration,
+    vec,
+};
+
+/// FLAC decoder. Blocks of samples are decoded one by one when they're needed.
+pub(in crate) struct FlacDecoder {
+    reader: Option<FlacReader<DataSource>>,
+    samples: vec::IntoIter<f32>,
+    // Buffer is reused between blocks to prevent redundant memory allocations.
+    block_buffer: Vec<i32>,
+    scale: f32,
+    total_samples: Option<u64>,
+    pub channel_count: usize,
+    pub sample_rate: usize,
+}
+
+impl Debug for FlacDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FlacDecoder")
+    }
+}
+
+impl Iterator for FlacDecoder {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.samples.next() {
+            Some(sample)
+        } else {
+            self.read_next_block();
+            self.samples.next()
+        }
+    }
+}
+
+fn is_flac(source: &mut DataSource) -> bool {
+    let pos = source.seek(SeekFrom::Current(0)).unwrap();
+
+    let is_flac = FlacReader::new(source.by_ref()).is_ok();
+
+    source.seek(SeekFrom::Start(pos)).unwrap();
+
+    is_flac
+}
+
+impl FlacDecoder {
+    pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
+        if is_flac(&mut source) {
+            let reader = FlacReader::new(source).unwrap();
+            let info = reader.streaminfo();
+
+            let mut decoder = Self {
+                samples: Vec::new().into_iter(),
+                block_buffer: Vec::new(),
+                scale: 1.0 / (1u64 << (info.bits_per_sample - 1)) as f32,
+                total_samples: info.samples,
+                channel_count: info.channels as usize,
+                sample_rate: info.sample_rate as usize,
+                reader: Some(reader),
+            };
+            decoder.read_next_block();
+
+            Ok(decoder)
+        } else {
+            Err(source)
+        }
+    }
+
+    fn read_next_block(&mut self) {
+        if let Some(reader) = self.reader.as_mut() {
+            let buffer = std::mem::take(&mut self.block_buffer);
+            if let Ok(Some(block)) = reader.blocks().read_next_or_eof(buffer) {
+                // Blocks store channels one after another, convert them to interleaved samples.
+                let mut samples = Vec::with_capacity(block.len() as usize);
+                for i in 0..block.duration() {
+                    for channel in 0..block.channels() {
+                        samples.push(block.sample(channel, i) as f32 * self.scale);
+                    }
+                }
+                self.samples = samples.into_iter();
+                self.block_buffer = block.into_buffer();
+            }
+        }
+    }
+
+    pub fn rewind(&mut self) -> Result<(), SoundError> {
+        // claxon does not support seeking, so we have to create new reader.
+        let mut source = self.reader.take().unwrap().into_inner();
+        source.seek(SeekFrom::Start(0))?;
+        *self = match Self::new(source) {
+            Ok(flac_decoder) => flac_decoder,
+            // Drop source here, this will invalidate decoder and it can't produce any
+            // samples anymore. This is unrecoverable error, but *should* never happen
+            // in reality.
+            Err(_) => return Err(SoundError::UnsupportedFormat),
+        };
+        Ok(())
+    }
+
+    pub fn time_seek(&mut self, location: Duration) {
+        if self.rewind().is_ok() {
+            // There is no seek table support, so just skip decoded samples until we
+            // reach desired position.
+            let sample_index =
+                (location.as_secs_f64() * self.sample_rate as f64) as usize * self.channel_count;
+            for _ in 0..sample_index {
+                if self.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        self.total_samples
+            .map(|samples| Duration::from_secs_f64(samples as f64 / self.sample_rate as f64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::DataSource, decoder::flac::FlacDecoder};
+
+    struct BitWriter {
+        bytes: Vec<u8>,
+        bit_count: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u64, bits: usize) {
+            for i in (0..bits).rev() {
+                if self.bit_count % 8 == 0 {
+                    self.bytes.push(0);
+                }
+                let bit = ((value >> i) & 1) as u8;
+                *self.bytes.last_mut().unwrap() |= bit << (7 - self.bit_count % 8);
+                self.bit_count += 1;
+            }
+        }
+    }
+
+    fn crc8(data: &[u8]) -> u8 {
+        let mut crc = 0u8;
+        for byte in data {
+            crc ^= byte;
+            for _ in 0..8 {
+                crc = if crc & 0x80 != 0 {
+                    (crc << 1) ^ 0x07
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc
+    }
+
+    fn crc16(data: &[u8]) -> u16 {
+        let mut crc = 0u16;
+        for byte in data {
+            crc ^= (*byte as u16) << 8;
+            for _ in 0..8 {
+                crc = if crc & 0x8000 != 0 {
+                    (crc << 1) ^ 0x8005
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc
+    }
+
+    // Encodes 16-bit stereo samples as a single frame with verbatim subframes.
+    fn encode(left: &[i16], right: &[i16], sample_rate: u64) -> Vec<u8> {
+        let block_size = left.len() as u64;
+
+        let mut stream = BitWriter {
+            bytes: b"fLaC".to_vec(),
+            bit_count: 32,
+        };
+        // Last metadata block, type STREAMINFO, 34 bytes long.
+        stream.write(1, 1);
+        stream.write(0, 7);
+        stream.write(34, 24);
+        stream.write(block_size, 16);
+        stream.write(block_size, 16);
+        stream.write(0, 24);
+        stream.write(0, 24);
+        stream.write(sample_rate, 20);
+        stream.write(2 - 1, 3);
+        stream.write(16 - 1, 5);
+        stream.write(block_size, 36);
+        stream.write(0, 64);
+        stream.write(0, 64);
+
+        let mut frame = BitWriter {
+            bytes: Vec::new(),
+            bit_count: 0,
+        };
+        // Sync code, fixed block size.
+        frame.write(0b11_1111_1111_1110, 14);
+        frame.write(0, 2);
+        // Block size in 16 bits at the end of header, sample rate from STREAMINFO.
+        frame.write(0b0111, 4);
+        frame.write(0b0000, 4);
+        // Two independent channels, 16 bits per sample.
+        frame.write(0b0001, 4);
+        frame.write(0b100, 3);
+        frame.write(0, 1);
+        // Frame number.
+        frame.write(0, 8);
+        frame.write(block_size - 1, 16);
+        let header_crc = crc8(&frame.bytes);
+        frame.write(header_crc as u64, 8);
+        for channel in [left, right] {
+            // Verbatim subframe without wasted bits.
+            frame.write(0b0000_0010, 8);
+            for &sample in channel {
+                frame.write(sample as u16 as u64, 16);
+            }
+        }
+        let footer_crc = crc16(&frame.bytes);
+        frame.write(footer_crc as u64, 16);
+
+        stream.bytes.extend_from_slice(&frame.bytes);
+        stream.bytes
+    }
+
+    #[test]
+    fn test_flac_round_trip() {
+        let left = (0..16).map(|i| i * 1000 - 8000).collect::<Vec<i16>>();
+        let right = (0..16).map(|i| i16::MAX - i * 2000).collect::<Vec<i16>>();
+
+        let decoder =
+            FlacDecoder::new(DataSource::from_memory(encode(&left, &right, 44100))).unwrap();
+        assert_eq!(decoder.channel_count, 2);
+        assert_eq!(decoder.sample_rate, 44100);
+
+        let expected = left
+            .iter()
+            .zip(right.iter())
+            .flat_map(|(&l, &r)| [l, r])
+            .map(|s| s as f32 / 32768.0)
+            .collect::<Vec<_>>();
+        assert_eq!(decoder.collect::<Vec<_>>(), expected);
+    }
+}
@@ -5,6 +5,19 @@ use crate::{
 };
 use std::time::Duration;
 
+#[cfg(feature = "flac")]
+use crate::decoder::flac::FlacDecoder;
+#[cfg(feature = "mp3")]
+use crate::decoder::mp3::Mp3Decoder;
+#[cfg(feature = "ogg_opus")]
+use crate::decoder::opus::OpusDecoder;
+
+#[cfg(feature = "flac")]
+mod flac;
+#[cfg(feature = "mp3")]
+mod mp3;
+#[cfg(feature = "ogg_opus")]
+mod opus;
 mod vorbis;
 mod wav;
 
@@ -12,6 +25,12 @@ mod wav;
 pub(in crate) enum Decoder {
     Wav(WavDecoder),
     Ogg(OggDecoder),
+    #[cfg(feature = "flac")]
+    Flac(FlacDecoder),
+    #[cfg(feature = "mp3")]
+    Mp3(Mp3Decoder),
+    #[cfg(feature = "ogg_opus")]
+    Opus(OpusDecoder),
 }
 
 impl Iterator for Decoder {
@@ -21,6 +40,12 @@ impl Iterator for Decoder {
         match self {
             Decoder::Wav(wav) => wav.next(),
             Decoder::Ogg(ogg) => ogg.next(),
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.next(),
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.next(),
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.next(),
         }
     }
 }
@@ -37,6 +62,25 @@ impl Decoder {
             Ok(ogg_decoder) => return Ok(Decoder::Ogg(ogg_decoder)),
             Err(source) => source,
         };
+        // Try Opus/Ogg
+        #[cfg(feature = "ogg_opus")]
+        let source = match OpusDecoder::new(source) {
+            Ok(opus_decoder) => return Ok(Decoder::Opus(opus_decoder)),
+            Err(source) => source,
+        };
+        // Try Flac
+        #[cfg(feature = "flac")]
+        let source = match FlacDecoder::new(source) {
+            Ok(flac_decoder) => return Ok(Decoder::Flac(flac_decoder)),
+            Err(source) => source,
+        };
+        // Try Mp3. It must be the last one, because MP3 has no header and the decoder just
+        // searches for the first valid frame.
+        #[cfg(feature = "mp3")]
+        let source = match Mp3Decoder::new(source) {
+            Ok(mp3_decoder) => return Ok(Decoder::Mp3(mp3_decoder)),
+            Err(source) => source,
+        };
         Err(source)
     }
 
@@ -44,6 +88,12 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.rewind(),
             Decoder::Ogg(ogg) => ogg.rewind(),
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.rewind(),
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.rewind(),
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.rewind(),
         }
     }
 
@@ -51,6 +101,12 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.time_seek(location),
             Decoder::Ogg(ogg) => ogg.time_seek(location),
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.time_seek(location),
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.time_seek(location),
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.time_seek(location),
         }
     }
 
@@ -58,6 +114,12 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.channel_count(),
             Decoder::Ogg(ogg) => ogg.channel_count,
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.channel_count,
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.channel_count,
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.channel_count,
         }
     }
 
@@ -65,6 +127,12 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.sample_rate(),
             Decoder::Ogg(ogg) => ogg.sample_rate,
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.sample_rate,
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.sample_rate,
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.sample_rate,
         }
     }
 
@@ -76,6 +144,12 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.duration(),
             Decoder::Ogg(ogg) => ogg.duration(),
+            #[cfg(feature = "flac")]
+            Decoder::Flac(flac) => flac.duration(),
+            #[cfg(feature = "mp3")]
+            Decoder::Mp3(mp3) => mp3.duration(),
+            #[cfg(feature = "ogg_opus")]
+            Decoder::Opus(opus) => opus.duration(),
         }
     }
 }
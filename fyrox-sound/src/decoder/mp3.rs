@@ -0,0 +1,303 @@
+use crate::{buffer::DataSource, error::SoundError};
+use minimp3_sys::{
+    mp3dec_decode_frame, mp3dec_frame_info_t, mp3dec_init, mp3dec_t, MINIMP3_MAX_SAMPLES_PER_FRAME,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Read, Seek, SeekFrom},
+    os::raw::c_int,
+    time::Duration,
+    vec,
+};
+
+// Amount of bytes that is kept in the buffer, it is enough to hold a few frames of any bit rate.
+const BUFFER_SIZE: usize = 16 * 1024;
+
+struct Frame {
+    samples: Vec<f32>,
+    channel_count: usize,
+    sample_rate: usize,
+}
+
+// Thin wrapper over minimp3 decoder that reads data source block by block and feeds it to the
+// decoder frame by frame.
+struct FrameReader {
+    decoder: Box<mp3dec_t>,
+    source: DataSource,
+    buffer: Vec<u8>,
+    end_reached: bool,
+}
+
+impl FrameReader {
+    fn new(source: DataSource) -> Self {
+        // SAFETY: Decoder state consists of plain numbers and arrays, so zeroed memory is valid.
+        let mut decoder = Box::new(unsafe { std::mem::zeroed::<mp3dec_t>() });
+        unsafe { mp3dec_init(&mut *decoder) };
+
+        Self {
+            decoder,
+            source,
+            buffer: Vec::with_capacity(BUFFER_SIZE),
+            end_reached: false,
+        }
+    }
+
+    fn rewind(&mut self) -> Result<(), SoundError> {
+        self.source.seek(SeekFrom::Start(0))?;
+        self.buffer.clear();
+        self.end_reached = false;
+        unsafe { mp3dec_init(&mut *self.decoder) };
+        Ok(())
+    }
+
+    // Reads next block of data source, returns amount of read bytes.
+    fn refill(&mut self) -> usize {
+        let len = self.buffer.len();
+        self.buffer.resize(len + BUFFER_SIZE, 0);
+        let read = self.source.read(&mut self.buffer[len..]).unwrap_or(0);
+        self.buffer.truncate(len + read);
+        if read == 0 {
+            self.end_reached = true;
+        }
+        read
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        let mut pcm = [0i16; MINIMP3_MAX_SAMPLES_PER_FRAME as usize];
+        loop {
+            if self.buffer.len() < BUFFER_SIZE && !self.end_reached {
+                self.refill();
+            }
+            if self.buffer.is_empty() {
+                return None;
+            }
+
+            let mut info = mp3dec_frame_info_t {
+                frame_bytes: 0,
+                frame_offset: 0,
+                channels: 0,
+                hz: 0,
+                layer: 0,
+                bitrate_kbps: 0,
+            };
+            // SAFETY: Pcm buffer can hold maximum amount of samples of a frame and decoder reads
+            // only given amount of bytes from the buffer.
+            let sample_count = unsafe {
+                mp3dec_decode_frame(
+                    &mut *self.decoder,
+                    self.buffer.as_ptr(),
+                    self.buffer.len() as c_int,
+                    pcm.as_mut_ptr(),
+                    &mut info,
+                )
+            } as usize;
+
+            let consumed = (info.frame_bytes.max(0) as usize).min(self.buffer.len());
+            self.buffer.drain(..consumed);
+
+            if sample_count > 0 {
+                let channel_count = info.channels as usize;
+                return Some(Frame {
+                    samples: pcm[..sample_count * channel_count]
+                        .iter()
+                        .map(|s| *s as f32 / i16::MAX as f32)
+                        .collect(),
+                    channel_count,
+                    sample_rate: info.hz as usize,
+                });
+            }
+
+            // Zero consumed bytes means that the buffer contains an incomplete frame.
+            if consumed == 0 && (self.end_reached || self.refill() == 0) {
+                return None;
+            }
+        }
+    }
+}
+
+// MP3 file either starts with ID3v2 tag or with frame header, which begins with 11 set bits of
+// frame sync followed by valid version, layer, bit rate and sample rate.
+fn is_mp3(header: &[u8; 4]) -> bool {
+    if header[..3] == *b"ID3" {
+        return true;
+    }
+    let sync = header[0] == 0xFF && header[1] & 0xE0 == 0xE0;
+    let version = (header[1] >> 3) & 0b11;
+    let layer = (header[1] >> 1) & 0b11;
+    let bit_rate = header[2] >> 4;
+    let sample_rate = (header[2] >> 2) & 0b11;
+    sync && version != 0b01 && layer != 0 && bit_rate != 0b1111 && sample_rate != 0b11
+}
+
+fn check_header(source: &mut DataSource) -> bool {
+    let pos = match source.stream_position() {
+        Ok(pos) => pos,
+        Err(_) => return false,
+    };
+    let mut header = [0; 4];
+    let valid = source.read_exact(&mut header).is_ok() && is_mp3(&header);
+    source.seek(SeekFrom::Start(pos)).is_ok() && valid
+}
+
+/// MP3 decoder. Frames are decoded one by one when they're needed.
+pub(in crate) struct Mp3Decoder {
+    reader: FrameReader,
+    samples: vec::IntoIter<f32>,
+    pub channel_count: usize,
+    pub sample_rate: usize,
+}
+
+impl Debug for Mp3Decoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mp3Decoder")
+    }
+}
+
+impl Iterator for Mp3Decoder {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.samples.next() {
+            Some(sample)
+        } else {
+            if let Some(frame) = self.reader.next_frame() {
+                self.samples = frame.samples.into_iter();
+            }
+            self.samples.next()
+        }
+    }
+}
+
+impl Mp3Decoder {
+    pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
+        // MP3 decoder accepts almost any data, it just skips everything that does not look like
+        // a frame. Check header first to not treat arbitrary files as MP3.
+        if !check_header(&mut source) {
+            return Err(source);
+        }
+
+        let mut reader = FrameReader::new(source);
+        match reader.next_frame() {
+            Some(first_frame) => Ok(Self {
+                reader,
+                channel_count: first_frame.channel_count,
+                sample_rate: first_frame.sample_rate,
+                samples: first_frame.samples.into_iter(),
+            }),
+            None => {
+                let mut source = reader.source;
+                let _ = source.seek(SeekFrom::Start(0));
+                Err(source)
+            }
+        }
+    }
+
+    pub fn rewind(&mut self) -> Result<(), SoundError> {
+        // Decoder does not support seeking, so we have to decode from the beginning.
+        self.reader.rewind()?;
+        let first_frame = self
+            .reader
+            .next_frame()
+            .ok_or(SoundError::UnsupportedFormat)?;
+        self.samples = first_frame.samples.into_iter();
+        Ok(())
+    }
+
+    pub fn time_seek(&mut self, location: Duration) {
+        if self.rewind().is_ok() {
+            // Frames of MP3 could have variable bit rate, so the only reliable way of seeking
+            // is to skip decoded samples until we reach desired position.
+            let sample_index =
+                (location.as_secs_f64() * self.sample_rate as f64) as usize * self.channel_count;
+            for _ in 0..sample_index {
+                if self.next().is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::DataSource, decoder::mp3::Mp3Decoder};
+    use std::{io::Read, time::Duration};
+
+    const FRAME_COUNT: usize = 4;
+    const SAMPLES_PER_FRAME: usize = 1152;
+
+    // Encodes silence as MPEG-1 Layer III mono frames at 128 kbps and 44100 Hz. Zeroed side
+    // information describes frames without any spectral data.
+    fn encode_silence() -> Vec<u8> {
+        let frame_size = 144 * 128000 / 44100;
+        let mut data = Vec::new();
+        for _ in 0..FRAME_COUNT {
+            let mut frame = vec![0; frame_size];
+            frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0xC4]);
+            data.extend_from_slice(&frame);
+        }
+        data
+    }
+
+    #[test]
+    fn test_mp3_round_trip() {
+        let mut decoder = Mp3Decoder::new(DataSource::from_memory(encode_silence())).unwrap();
+        assert_eq!(decoder.channel_count, 1);
+        assert_eq!(decoder.sample_rate, 44100);
+
+        let samples = decoder.by_ref().collect::<Vec<_>>();
+        assert_eq!(samples.len(), FRAME_COUNT * SAMPLES_PER_FRAME);
+        assert!(samples.iter().all(|s| *s == 0.0));
+
+        decoder.rewind().unwrap();
+        assert_eq!(decoder.by_ref().count(), FRAME_COUNT * SAMPLES_PER_FRAME);
+
+        // Rewind must work any number of times.
+        decoder.rewind().unwrap();
+        assert_eq!(decoder.count(), FRAME_COUNT * SAMPLES_PER_FRAME);
+    }
+
+    #[test]
+    fn test_mp3_with_id3_tag() {
+        // ID3v2.4 tag header with empty body.
+        let mut data = vec![b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&encode_silence());
+
+        let decoder = Mp3Decoder::new(DataSource::from_memory(data)).unwrap();
+        assert_eq!(decoder.count(), FRAME_COUNT * SAMPLES_PER_FRAME);
+    }
+
+    #[test]
+    fn test_mp3_time_seek() {
+        let mut decoder = Mp3Decoder::new(DataSource::from_memory(encode_silence())).unwrap();
+        // Skip two frames.
+        decoder.time_seek(Duration::from_secs_f64(
+            (2 * SAMPLES_PER_FRAME) as f64 / 44100.0,
+        ));
+        assert_eq!(decoder.count(), (FRAME_COUNT - 2) * SAMPLES_PER_FRAME);
+    }
+
+    #[test]
+    fn test_non_mp3_data_is_rejected() {
+        // Frames preceded by junk are not accepted, because the data does not start with a
+        // valid header.
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&encode_silence());
+        let mut source = match Mp3Decoder::new(DataSource::from_memory(data.clone())) {
+            Ok(_) => panic!("must be rejected"),
+            Err(source) => source,
+        };
+        // Data source must be returned untouched, so other decoders could try it.
+        let mut content = Vec::new();
+        source.read_to_end(&mut content).unwrap();
+        assert_eq!(content, data);
+
+        // Valid header without any frames.
+        assert!(Mp3Decoder::new(DataSource::from_memory(vec![0xFF, 0xFB, 0x90, 0xC4])).is_err());
+    }
+}
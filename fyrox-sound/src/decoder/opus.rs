@@ -0,0 +1,253 @@
+use crate::{buffer::DataSource, error::SoundError};
+use ogg::PacketReader;
+use opus::{Channels, Decoder};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{Read, Seek, SeekFrom},
+    time::Duration,
+    vec,
+};
+
+/// Opus streams are always decoded at 48 kHz, regardless of sample rate of the original audio.
+const OPUS_SAMPLE_RATE: usize = 48000;
+
+/// Maximum duration of an Opus packet is 120 ms.
+const MAX_PACKET_SAMPLES: usize = OPUS_SAMPLE_RATE * 120 / 1000;
+
+/// Ogg/Opus decoder. Only mono and stereo streams (channel mapping family 0) are supported.
+pub(in crate) struct OpusDecoder {
+    // Option here is because we need to extract data source on rewind.
+    reader: Option<PacketReader<DataSource>>,
+    decoder: Decoder,
+    stream_serial: u32,
+    samples: vec::IntoIter<f32>,
+    // Buffer is reused between packets to prevent redundant memory allocations.
+    packet_buffer: Vec<f32>,
+    // Amount of samples per channel that should be discarded at the beginning of the stream.
+    pre_skip: usize,
+    header_pre_skip: usize,
+    pub channel_count: usize,
+    pub sample_rate: usize,
+}
+
+impl Debug for OpusDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OpusDecoder")
+    }
+}
+
+impl Iterator for OpusDecoder {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.samples.next() {
+            Some(sample)
+        } else {
+            self.read_next_packet();
+            self.samples.next()
+        }
+    }
+}
+
+struct OpusHeader {
+    stream_serial: u32,
+    channel_count: usize,
+    pre_skip: usize,
+}
+
+fn read_header<R: Read + Seek>(reader: &mut PacketReader<R>) -> Option<OpusHeader> {
+    let packet = reader.read_packet().ok()??;
+    let data = &packet.data;
+    // See https://datatracker.ietf.org/doc/html/rfc7845#section-5.1 for header layout.
+    if data.len() >= 19 && &data[0..8] == b"OpusHead" {
+        let channel_count = data[9] as usize;
+        let pre_skip = u16::from_le_bytes([data[10], data[11]]) as usize;
+        let mapping_family = data[18];
+        if mapping_family == 0 && (channel_count == 1 || channel_count == 2) {
+            return Some(OpusHeader {
+                stream_serial: packet.stream_serial(),
+                channel_count,
+                pre_skip,
+            });
+        }
+    }
+    None
+}
+
+fn is_opus(source: &mut DataSource) -> bool {
+    let pos = source.seek(SeekFrom::Current(0)).unwrap();
+
+    let is_opus = read_header(&mut PacketReader::new(source.by_ref())).is_some();
+
+    source.seek(SeekFrom::Start(pos)).unwrap();
+
+    is_opus
+}
+
+impl OpusDecoder {
+    pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
+        if is_opus(&mut source) {
+            let mut reader = PacketReader::new(source);
+            let header = read_header(&mut reader).unwrap();
+            // Skip comment header, it is not needed.
+            let _ = reader.read_packet();
+
+            let channels = if header.channel_count == 1 {
+                Channels::Mono
+            } else {
+                Channels::Stereo
+            };
+
+            Ok(Self {
+                reader: Some(reader),
+                decoder: Decoder::new(OPUS_SAMPLE_RATE as u32, channels).unwrap(),
+                stream_serial: header.stream_serial,
+                samples: Vec::new().into_iter(),
+                packet_buffer: Vec::new(),
+                pre_skip: header.pre_skip,
+                header_pre_skip: header.pre_skip,
+                channel_count: header.channel_count,
+                sample_rate: OPUS_SAMPLE_RATE,
+            })
+        } else {
+            Err(source)
+        }
+    }
+
+    fn read_next_packet(&mut self) {
+        if let Some(reader) = self.reader.as_mut() {
+            while let Ok(Some(packet)) = reader.read_packet() {
+                if packet.stream_serial() != self.stream_serial {
+                    continue;
+                }
+
+                self.packet_buffer
+                    .resize(MAX_PACKET_SAMPLES * self.channel_count, 0.0);
+                if let Ok(count) =
+                    self.decoder
+                        .decode_float(&packet.data, &mut self.packet_buffer, false)
+                {
+                    let skip = self.pre_skip.min(count);
+                    self.pre_skip -= skip;
+                    if skip < count {
+                        self.samples = self.packet_buffer
+                            [skip * self.channel_count..count * self.channel_count]
+                            .to_vec()
+                            .into_iter();
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn rewind(&mut self) -> Result<(), SoundError> {
+        let mut source = self.reader.take().unwrap().into_inner();
+        source.seek(SeekFrom::Start(0))?;
+        *self = match Self::new(source) {
+            Ok(opus_decoder) => opus_decoder,
+            // Drop source here, this will invalidate decoder and it can't produce any
+            // samples anymore. This is unrecoverable error, but *should* never happen
+            // in reality.
+            Err(_) => return Err(SoundError::UnsupportedFormat),
+        };
+        Ok(())
+    }
+
+    pub fn time_seek(&mut self, location: Duration) {
+        // Granule positions of Opus streams are always in 48 kHz samples and include pre-skip.
+        let granule_position =
+            self.header_pre_skip as u64 + (location.as_secs_f64() * OPUS_SAMPLE_RATE as f64) as u64;
+        if let Some(reader) = self.reader.as_mut() {
+            if reader
+                .seek_absgp(Some(self.stream_serial), granule_position)
+                .is_ok()
+            {
+                let _ = self.decoder.reset_state();
+                self.samples = Vec::new().into_iter();
+                self.pre_skip = 0;
+            }
+        }
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::DataSource, decoder::opus::OpusDecoder};
+    use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+    use opus::{Application, Channels, Encoder};
+    use std::io::Cursor;
+
+    const FRAME_SIZE: usize = 960;
+    const FRAME_COUNT: usize = 10;
+    const PRE_SKIP: usize = 312;
+
+    fn encode(samples: &[f32]) -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        let mut writer = PacketWriter::new(&mut data);
+        let serial = 1;
+
+        let mut header = b"OpusHead".to_vec();
+        header.push(1);
+        header.push(1);
+        header.extend_from_slice(&(PRE_SKIP as u16).to_le_bytes());
+        header.extend_from_slice(&48000u32.to_le_bytes());
+        header.extend_from_slice(&0i16.to_le_bytes());
+        header.push(0);
+        writer
+            .write_packet(header, serial, PacketWriteEndInfo::EndPage, 0)
+            .unwrap();
+
+        let mut tags = b"OpusTags".to_vec();
+        tags.extend_from_slice(&0u32.to_le_bytes());
+        tags.extend_from_slice(&0u32.to_le_bytes());
+        writer
+            .write_packet(tags, serial, PacketWriteEndInfo::EndPage, 0)
+            .unwrap();
+
+        let mut encoder = Encoder::new(48000, Channels::Mono, Application::Audio).unwrap();
+        for (i, frame) in samples.chunks(FRAME_SIZE).enumerate() {
+            let packet = encoder.encode_vec_float(frame, 4000).unwrap();
+            let end_info = if i + 1 == FRAME_COUNT {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            let granule_position = ((i + 1) * FRAME_SIZE) as u64;
+            writer
+                .write_packet(packet, serial, end_info, granule_position)
+                .unwrap();
+        }
+
+        drop(writer);
+        data.into_inner()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_opus_round_trip() {
+        let samples = (0..FRAME_SIZE * FRAME_COUNT)
+            .map(|i| 0.5 * (i as f32 * 440.0 * std::f32::consts::TAU / 48000.0).sin())
+            .collect::<Vec<_>>();
+
+        let decoder = OpusDecoder::new(DataSource::from_memory(encode(&samples))).unwrap();
+        assert_eq!(decoder.channel_count, 1);
+        assert_eq!(decoder.sample_rate, 48000);
+
+        // Pre-skip samples are discarded by the decoder.
+        let decoded = decoder.collect::<Vec<_>>();
+        assert_eq!(decoded.len(), samples.len() - PRE_SKIP);
+
+        // Opus is lossy, so only compare loudness of the signals.
+        let expected_rms = rms(&samples);
+        assert!((rms(&decoded) - expected_rms).abs() < expected_rms * 0.2);
+    }
+}
@@ -1,19 +1,20 @@
 use crate::{
-    context::SAMPLE_RATE,
-    device::{Device, FeedCallback, MixContext, NativeSample},
+    device::{
+        Backend, Device, DeviceConfig, DeviceInfo, MixContext, Mixer, NativeSample,
+        SharedFeedCallback,
+    },
     error::SoundError,
 };
 use alsa_sys::*;
 use std::{
     ffi::{CStr, CString},
-    mem::size_of,
-    os::raw::c_int,
+    os::raw::{c_int, c_void},
 };
 
 pub struct AlsaSoundDevice {
     frame_count: u32,
     playback_device: *mut snd_pcm_t,
-    callback: Box<FeedCallback>,
+    mixer: Mixer,
     out_data: Vec<NativeSample>,
     mix_buffer: Vec<(f32, f32)>,
 }
@@ -39,15 +40,122 @@ pub fn check(err_code: c_int) -> Result<(), SoundError> {
     }
 }
 
+// Reads a value of a device hint and frees the memory allocated by ALSA.
+unsafe fn device_hint(hint: *const c_void, id: &str) -> Option<String> {
+    let id = CString::new(id).unwrap();
+    let value = snd_device_name_get_hint(hint, id.as_ptr());
+    if value.is_null() {
+        None
+    } else {
+        let string = CStr::from_ptr(value).to_string_lossy().into_owned();
+        // Strings returned by ALSA are allocated by C runtime.
+        libc::free(value as *mut _);
+        Some(string)
+    }
+}
+
+pub fn enumerate_devices() -> Result<Vec<DeviceInfo>, SoundError> {
+    unsafe {
+        let interface = CString::new("pcm").unwrap();
+        let mut hints = std::ptr::null_mut();
+        check(snd_device_name_hint(-1, interface.as_ptr(), &mut hints))?;
+
+        let mut devices = Vec::new();
+        let mut hint = hints;
+        while !(*hint).is_null() {
+            if let Some(id) = device_hint(*hint, "NAME") {
+                // Missing IOID means that the device supports both input and output.
+                if device_hint(*hint, "IOID").map_or(true, |io| io == "Output") {
+                    let name = device_hint(*hint, "DESC")
+                        .map(|desc| desc.replace('\n', " "))
+                        .unwrap_or_else(|| id.clone());
+                    devices.push(DeviceInfo {
+                        backend: Backend::Alsa,
+                        is_default: id == "default",
+                        id,
+                        name,
+                    });
+                }
+            }
+            hint = hint.add(1);
+        }
+
+        snd_device_name_free_hint(hints);
+
+        Ok(devices)
+    }
+}
+
+// Sets parameters of the device, returns actual sample rate and period size in frames.
+unsafe fn configure(
+    playback_device: *mut snd_pcm_t,
+    config: &DeviceConfig,
+) -> Result<(u32, snd_pcm_uframes_t), SoundError> {
+    let mut hw_params = std::ptr::null_mut();
+    check(snd_pcm_hw_params_malloc(&mut hw_params))?;
+    check(snd_pcm_hw_params_any(playback_device, hw_params))?;
+    let access = SND_PCM_ACCESS_RW_INTERLEAVED;
+    check(snd_pcm_hw_params_set_access(
+        playback_device,
+        hw_params,
+        access,
+    ))?;
+    check(snd_pcm_hw_params_set_format(
+        playback_device,
+        hw_params,
+        SND_PCM_FORMAT_S16_LE,
+    ))?;
+    let mut exact_rate = config.sample_rate;
+    check(snd_pcm_hw_params_set_rate_near(
+        playback_device,
+        hw_params,
+        &mut exact_rate,
+        std::ptr::null_mut(),
+    ))?;
+    check(snd_pcm_hw_params_set_channels(
+        playback_device,
+        hw_params,
+        2,
+    ))?;
+    let mut frame_count = config.buffer_size as snd_pcm_uframes_t;
+    check(snd_pcm_hw_params_set_period_size_near(
+        playback_device,
+        hw_params,
+        &mut frame_count,
+        std::ptr::null_mut(),
+    ))?;
+    let mut exact_size = frame_count * 2;
+    check(snd_pcm_hw_params_set_buffer_size_near(
+        playback_device,
+        hw_params,
+        &mut exact_size,
+    ))?;
+    check(snd_pcm_hw_params(playback_device, hw_params))?;
+    snd_pcm_hw_params_free(hw_params);
+    let mut sw_params = std::ptr::null_mut();
+    check(snd_pcm_sw_params_malloc(&mut sw_params))?;
+    check(snd_pcm_sw_params_current(playback_device, sw_params))?;
+    check(snd_pcm_sw_params_set_avail_min(
+        playback_device,
+        sw_params,
+        frame_count,
+    ))?;
+    check(snd_pcm_sw_params_set_start_threshold(
+        playback_device,
+        sw_params,
+        frame_count,
+    ))?;
+    check(snd_pcm_sw_params(playback_device, sw_params))?;
+    snd_pcm_sw_params_free(sw_params);
+    check(snd_pcm_prepare(playback_device))?;
+    Ok((exact_rate, frame_count))
+}
+
 impl AlsaSoundDevice {
-    pub fn new<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-        buffer_len_bytes: u32,
-        callback: F,
-    ) -> Result<Self, SoundError> {
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Result<Self, SoundError> {
         unsafe {
-            let name = CString::new("default").unwrap();
-            // 16-bit stereo is 4 bytes, so frame count is bufferHalfSize / 4
-            let frame_count = buffer_len_bytes / 4;
+            let name = CString::new(config.device.as_deref().unwrap_or("default"))
+                .map_err(|e| SoundError::FailedToInitializeDevice(e.to_string()))?;
             let mut playback_device = std::ptr::null_mut();
             check(snd_pcm_open(
                 &mut playback_device,
@@ -55,69 +163,22 @@ impl AlsaSoundDevice {
                 SND_PCM_STREAM_PLAYBACK,
                 0,
             ))?;
-            let mut hw_params = std::ptr::null_mut();
-            check(snd_pcm_hw_params_malloc(&mut hw_params))?;
-            check(snd_pcm_hw_params_any(playback_device, hw_params))?;
-            let access = SND_PCM_ACCESS_RW_INTERLEAVED;
-            check(snd_pcm_hw_params_set_access(
-                playback_device,
-                hw_params,
-                access,
-            ))?;
-            check(snd_pcm_hw_params_set_format(
-                playback_device,
-                hw_params,
-                SND_PCM_FORMAT_S16_LE,
-            ))?;
-            let mut exact_rate = SAMPLE_RATE;
-            check(snd_pcm_hw_params_set_rate_near(
-                playback_device,
-                hw_params,
-                &mut exact_rate,
-                std::ptr::null_mut(),
-            ))?;
-            check(snd_pcm_hw_params_set_channels(
-                playback_device,
-                hw_params,
-                2,
-            ))?;
-            check(snd_pcm_hw_params_set_period_size(
-                playback_device,
-                hw_params,
-                frame_count as u64,
-                0,
-            ))?;
-            let mut exact_size = (frame_count * 2) as u64;
-            check(snd_pcm_hw_params_set_buffer_size_near(
-                playback_device,
-                hw_params,
-                &mut exact_size,
-            ))?;
-            check(snd_pcm_hw_params(playback_device, hw_params))?;
-            snd_pcm_hw_params_free(hw_params);
-            let mut sw_params = std::ptr::null_mut();
-            check(snd_pcm_sw_params_malloc(&mut sw_params))?;
-            check(snd_pcm_sw_params_current(playback_device, sw_params))?;
-            check(snd_pcm_sw_params_set_avail_min(
-                playback_device,
-                sw_params,
-                frame_count.into(),
-            ))?;
-            check(snd_pcm_sw_params_set_start_threshold(
-                playback_device,
-                sw_params,
-                frame_count.into(),
-            ))?;
-            check(snd_pcm_sw_params(playback_device, sw_params))?;
-            check(snd_pcm_prepare(playback_device))?;
 
-            let samples_per_channel = buffer_len_bytes as usize / size_of::<NativeSample>();
+            let (sample_rate, frame_count) = match configure(playback_device, config) {
+                Ok(parameters) => parameters,
+                Err(error) => {
+                    // Release the device, so other backends can use it.
+                    snd_pcm_close(playback_device);
+                    return Err(error);
+                }
+            };
+
             Ok(Self {
                 playback_device,
-                frame_count,
-                callback: Box::new(callback),
-                out_data: vec![Default::default(); samples_per_channel],
-                mix_buffer: vec![(0.0, 0.0); samples_per_channel],
+                frame_count: frame_count as u32,
+                mixer: Mixer::new(callback, sample_rate, frame_count as usize),
+                out_data: vec![Default::default(); frame_count as usize],
+                mix_buffer: vec![(0.0, 0.0); frame_count as usize],
             })
         }
     }
@@ -128,7 +189,7 @@ impl Device for AlsaSoundDevice {
         Some(MixContext {
             mix_buffer: self.mix_buffer.as_mut_slice(),
             out_data: &mut self.out_data,
-            callback: &mut self.callback,
+            mixer: &mut self.mixer,
         })
     }
 
@@ -1,5 +1,5 @@
 use crate::{
-    device::{Device, FeedCallback, MixContext, NativeSample},
+    device::{Device, DeviceConfig, MixContext, Mixer, NativeSample, SharedFeedCallback},
     error::SoundError,
 };
 use coreaudio_sys::*;
@@ -14,7 +14,7 @@ unsafe impl Send for CoreaudioSoundDevice {}
 
 struct Inner {
     // MixContext
-    fill_callback: Box<FeedCallback>,
+    mixer: Mixer,
     out_data: Vec<NativeSample>,
     mix_buffer: Vec<(f32, f32)>,
     // else
@@ -62,13 +62,12 @@ unsafe extern "C" fn audio_queue_callback(
 }
 
 impl CoreaudioSoundDevice {
-    pub fn new<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-        buffer_len_bytes: u32,
-        fill_callback: F,
-    ) -> Result<Self, SoundError> {
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Result<Self, SoundError> {
+        let buffer_len_bytes = config.buffer_len_bytes();
+
         // 16-bit linear PCM
         let desc = AudioStreamBasicDescription {
-            mSampleRate: crate::context::SAMPLE_RATE as f64,
+            mSampleRate: config.sample_rate as f64,
             mFormatID: kAudioFormatLinearPCM,
             mFormatFlags: kLinearPCMFormatFlagIsSignedInteger | kLinearPCMFormatFlagIsPacked,
             mBitsPerChannel: 16,
@@ -82,7 +81,7 @@ impl CoreaudioSoundDevice {
         // create data at fixed memory location
         let samples_per_channel = buffer_len_bytes as usize / size_of::<NativeSample>();
         let mut inner = Box::new(Inner {
-            fill_callback: Box::new(fill_callback),
+            mixer: Mixer::new(callback, config.sample_rate, samples_per_channel),
             out_data: vec![Default::default(); samples_per_channel],
             mix_buffer: vec![(0.0, 0.0); samples_per_channel],
             queue: std::ptr::null_mut(),
@@ -160,7 +159,7 @@ impl Device for CoreaudioSoundDevice {
         Some(MixContext {
             mix_buffer: &mut self.inner.mix_buffer,
             out_data: &mut self.inner.out_data,
-            callback: &mut self.inner.fill_callback,
+            mixer: &mut self.inner.mixer,
         })
     }
 
@@ -178,7 +177,7 @@ impl Device for Inner {
         Some(MixContext {
             mix_buffer: &mut self.mix_buffer,
             out_data: &mut self.out_data,
-            callback: &mut self.fill_callback,
+            mixer: &mut self.mixer,
         })
     }
 
@@ -1,8 +1,7 @@
 #![allow(non_snake_case)]
 
 use crate::{
-    context::SAMPLE_RATE,
-    device::{Device, FeedCallback, MixContext, NativeSample},
+    device::{Device, DeviceConfig, MixContext, Mixer, NativeSample, SharedFeedCallback},
     error::SoundError,
 };
 use std::mem::size_of;
@@ -45,7 +44,7 @@ pub struct DirectSoundDevice {
     buffer_len_bytes: u32,
     out_data: Vec<NativeSample>,
     mix_buffer: Vec<(f32, f32)>,
-    callback: Box<FeedCallback>,
+    mixer: Mixer,
 }
 
 unsafe impl Send for DirectSoundDevice {}
@@ -59,10 +58,8 @@ fn check<S: Into<String>>(code: i32, message: S) -> Result<(), SoundError> {
 }
 
 impl DirectSoundDevice {
-    pub fn new<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-        buffer_len_bytes: u32,
-        callback: F,
-    ) -> Result<Self, SoundError> {
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Result<Self, SoundError> {
+        let buffer_len_bytes = config.buffer_len_bytes();
         unsafe {
             let mut direct_sound = std::ptr::null_mut();
             check(
@@ -82,8 +79,8 @@ impl DirectSoundDevice {
             let mut buffer_format = WAVEFORMATEX {
                 wFormatTag: WAVE_FORMAT_PCM,
                 nChannels: channels_count,
-                nSamplesPerSec: SAMPLE_RATE,
-                nAvgBytesPerSec: SAMPLE_RATE * u32::from(block_align),
+                nSamplesPerSec: config.sample_rate,
+                nAvgBytesPerSec: config.sample_rate * u32::from(block_align),
                 nBlockAlign: block_align,
                 wBitsPerSample: 8 * byte_per_sample,
                 cbSize: size_of::<WAVEFORMATEX>() as u16,
@@ -149,7 +146,7 @@ impl DirectSoundDevice {
                 mix_buffer: vec![(0.0, 0.0); samples_per_channel],
                 notify_points,
                 buffer_len_bytes,
-                callback: Box::new(callback),
+                mixer: Mixer::new(callback, config.sample_rate, samples_per_channel),
             })
         }
     }
@@ -193,7 +190,7 @@ impl Device for DirectSoundDevice {
         Some(MixContext {
             mix_buffer: self.mix_buffer.as_mut_slice(),
             out_data: &mut self.out_data,
-            callback: &mut self.callback,
+            mixer: &mut self.mixer,
         })
     }
 
@@ -1,30 +1,44 @@
-use crate::{
-    device::{Device, FeedCallback, MixContext, NativeSample},
-    error::SoundError,
-};
-use std::mem::size_of;
+use crate::device::{Device, DeviceConfig, MixContext, Mixer, NativeSample, SharedFeedCallback};
+use std::time::Duration;
 
-pub struct DummySoundDevice;
+// Dummy device does not produce any sound, but it keeps contexts running in real time.
+pub struct DummySoundDevice {
+    mixer: Mixer,
+    out_data: Vec<NativeSample>,
+    mix_buffer: Vec<(f32, f32)>,
+    buffer_duration: Duration,
+}
 
 impl DummySoundDevice {
-    pub fn new<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-        _buffer_len_bytes: u32,
-        _callback: F,
-    ) -> Result<Self, SoundError> {
-        Ok(Self)
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Self {
+        let samples_per_channel = config.buffer_size as usize;
+        Self {
+            mixer: Mixer::new(callback, config.sample_rate, samples_per_channel),
+            out_data: vec![Default::default(); samples_per_channel],
+            mix_buffer: vec![(0.0, 0.0); samples_per_channel],
+            buffer_duration: config.latency(),
+        }
     }
 }
 
 impl Device for DummySoundDevice {
     fn get_mix_context(&mut self) -> Option<MixContext> {
-        None
+        Some(MixContext {
+            mix_buffer: self.mix_buffer.as_mut_slice(),
+            out_data: &mut self.out_data,
+            mixer: &mut self.mixer,
+        })
     }
 
     fn run(&mut self) {
+        // There is no way to block the main thread on WASM.
+        #[cfg(not(target_arch = "wasm32"))]
         loop {
+            let start = std::time::Instant::now();
+
             self.mix();
 
-            //std::thread::sleep(std::time::Duration::from_millis(100));
+            std::thread::sleep(self.buffer_duration.saturating_sub(start.elapsed()));
         }
     }
 }
@@ -0,0 +1,161 @@
+//! JACK output device. Sample rate and buffer size are defined by JACK server, so values from
+//! device config are ignored. Device identifier is a name of a JACK client with physical playback
+//! ports (usually `system`), the device ports are connected to the first two playback ports of
+//! the client.
+
+use crate::{
+    device::{Backend, Device, DeviceConfig, DeviceInfo, MixContext, Mixer, SharedFeedCallback},
+    error::SoundError,
+};
+use jack::{
+    AsyncClient, AudioOut, Client, ClientOptions, Control, Port, PortFlags, ProcessHandler,
+    ProcessScope,
+};
+use std::time::Duration;
+
+const CLIENT_NAME: &str = "fyrox-sound";
+const DEFAULT_DEVICE: &str = "system";
+
+fn error(message: &str, error: jack::Error) -> SoundError {
+    SoundError::FailedToInitializeDevice(format!("{}. Reason: {}", message, error))
+}
+
+fn open_client() -> Result<Client, SoundError> {
+    // Do not start a server if it isn't running, other backends will be used instead.
+    Client::new(CLIENT_NAME, ClientOptions::NO_START_SERVER)
+        .map(|(client, _)| client)
+        .map_err(|e| error("Unable to connect to JACK server", e))
+}
+
+// Returns names of physical audio playback ports.
+fn playback_ports(client: &Client) -> Vec<String> {
+    client.ports(
+        None,
+        Some("audio"),
+        PortFlags::IS_INPUT | PortFlags::IS_PHYSICAL,
+    )
+}
+
+fn port_device(port: &str) -> &str {
+    port.split(':').next().unwrap_or(port)
+}
+
+pub fn enumerate_devices() -> Result<Vec<DeviceInfo>, SoundError> {
+    let client = open_client()?;
+
+    let mut devices: Vec<DeviceInfo> = Vec::new();
+    for port in playback_ports(&client) {
+        let id = port_device(&port);
+        if !devices.iter().any(|d| d.id == id) {
+            devices.push(DeviceInfo {
+                backend: Backend::Jack,
+                id: id.to_owned(),
+                name: id.to_owned(),
+                is_default: id == DEFAULT_DEVICE,
+            });
+        }
+    }
+
+    Ok(devices)
+}
+
+struct Process {
+    left: Port<AudioOut>,
+    right: Port<AudioOut>,
+    mixer: Mixer,
+    mix_buffer: Vec<(f32, f32)>,
+}
+
+impl ProcessHandler for Process {
+    fn process(&mut self, _: &Client, process_scope: &ProcessScope) -> Control {
+        let left = self.left.as_mut_slice(process_scope);
+        let right = self.right.as_mut_slice(process_scope);
+
+        // Buffer size of the server can be changed at any time.
+        self.mix_buffer.resize(left.len(), (0.0, 0.0));
+        self.mixer.fill(&mut self.mix_buffer);
+
+        for ((out_left, out_right), &(mixed_left, mixed_right)) in left
+            .iter_mut()
+            .zip(right.iter_mut())
+            .zip(self.mix_buffer.iter())
+        {
+            *out_left = mixed_left;
+            *out_right = mixed_right;
+        }
+
+        Control::Continue
+    }
+}
+
+pub struct JackSoundDevice {
+    // Processing stops when the client is dropped.
+    client: AsyncClient<(), Process>,
+}
+
+impl JackSoundDevice {
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Result<Self, SoundError> {
+        let client = open_client()?;
+
+        let left = client
+            .register_port("out_left", AudioOut::default())
+            .map_err(|e| error("Unable to register JACK port", e))?;
+        let right = client
+            .register_port("out_right", AudioOut::default())
+            .map_err(|e| error("Unable to register JACK port", e))?;
+
+        let client_name = client.name().to_owned();
+        let device = config.device.as_deref().unwrap_or(DEFAULT_DEVICE);
+        let targets = playback_ports(&client)
+            .into_iter()
+            .filter(|port| port_device(port) == device)
+            .collect::<Vec<_>>();
+        if targets.is_empty() {
+            return Err(SoundError::FailedToInitializeDevice(format!(
+                "JACK device {} has no playback ports",
+                device
+            )));
+        }
+
+        let process = Process {
+            left,
+            right,
+            mixer: Mixer::new(
+                callback,
+                client.sample_rate() as u32,
+                client.buffer_size() as usize,
+            ),
+            mix_buffer: vec![(0.0, 0.0); client.buffer_size() as usize],
+        };
+
+        let client = client
+            .activate_async((), process)
+            .map_err(|e| error("Unable to activate JACK client", e))?;
+
+        // Mono devices receive both channels.
+        for (port, target) in ["out_left", "out_right"]
+            .iter()
+            .zip(targets.iter().chain(targets.iter()))
+        {
+            client
+                .as_client()
+                .connect_ports_by_name(&format!("{}:{}", client_name, port), target)
+                .map_err(|e| error("Unable to connect JACK ports", e))?;
+        }
+
+        Ok(Self { client })
+    }
+}
+
+impl Device for JackSoundDevice {
+    fn get_mix_context(&mut self) -> Option<MixContext> {
+        // Mixing is done in the process callback of the server.
+        None
+    }
+
+    fn run(&mut self) {
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
@@ -7,7 +7,58 @@
 //! # Overview
 //!
 //! Device is an abstraction over output device which provides unified way of communication with
-//! output device.
+//! output device. Output device is opened by the sound engine (see
+//! [`crate::engine::SoundEngine::with_device`]) using [`DeviceConfig`], which defines a backend,
+//! an output device of the backend, sample rate and buffer size.
+//!
+//! # Backends
+//!
+//! | Backend     | Platform | Cargo feature |
+//! |-------------|----------|---------------|
+//! | DirectSound | Windows  | -             |
+//! | ALSA        | Linux    | -             |
+//! | PulseAudio  | Linux    | `pulseaudio`  |
+//! | JACK        | Linux    | `jack`        |
+//! | CoreAudio   | macOS    | -             |
+//! | WebAudio    | WASM     | -             |
+//! | Dummy       | Any      | -             |
+//!
+//! PulseAudio backend works with PipeWire as well (via `pipewire-pulse`). If a backend cannot be
+//! opened, the next available one is tried (see [`Backend::available`]), the dummy backend is
+//! used as the last resort. Dummy backend does not produce any sound, but keeps the contexts
+//! running in real time, so the game will behave the same as with a real device.
+//!
+//! # Device enumeration
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     device::{self, Backend, DeviceConfig},
+//!     engine::SoundEngine,
+//! };
+//!
+//! for backend in Backend::available() {
+//!     for device in device::enumerate_devices(backend).unwrap_or_default() {
+//!         println!("{:?}: {} ({})", backend, device.name, device.id);
+//!     }
+//! }
+//!
+//! let engine = SoundEngine::with_device(
+//!     DeviceConfig::default()
+//!         .with_backend(Backend::Alsa)
+//!         .with_device("default")
+//!         .with_buffer_size(2048),
+//! );
+//! ```
+
+use crate::{
+    context::{SoundContext, SAMPLE_RATE},
+    dsp::resampler::Resampler,
+    error::SoundError,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 #[cfg(target_os = "windows")]
 mod dsound;
@@ -15,34 +66,263 @@ mod dsound;
 #[cfg(target_os = "linux")]
 mod alsa;
 
+#[cfg(all(target_os = "linux", feature = "pulseaudio"))]
+mod pulse;
+
+#[cfg(all(target_os = "linux", feature = "jack"))]
+mod jack;
+
 #[cfg(target_os = "macos")]
 mod coreaudio;
 
 // The dummy target works on all platforms
-#[cfg(not(any(
-    target_os = "windows",
-    target_os = "linux",
-    target_os = "macos",
-    target_arch = "wasm32"
-)))]
 mod dummy;
 
 #[cfg(target_arch = "wasm32")]
 mod web;
 
+/// Audio backend, it is a platform-specific way of communication with output devices.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Backend {
+    /// DirectSound, Windows only.
+    DirectSound,
+
+    /// Advanced Linux Sound Architecture, Linux only.
+    Alsa,
+
+    /// PulseAudio sound server (or PipeWire with PulseAudio compatibility layer), Linux only.
+    /// Requires `pulseaudio` feature.
+    PulseAudio,
+
+    /// JACK Audio Connection Kit, Linux only. Requires `jack` feature.
+    Jack,
+
+    /// CoreAudio, macOS only.
+    CoreAudio,
+
+    /// WebAudio, WASM only.
+    WebAudio,
+
+    /// Silent backend, available on all platforms.
+    Dummy,
+}
+
+impl Backend {
+    /// Returns a list of backends that are available on current platform with current set of
+    /// features, in order of preference. Optional backends go first, because they're enabled
+    /// explicitly. Dummy backend is always the last one.
+    #[allow(clippy::vec_init_then_push)]
+    pub fn available() -> Vec<Backend> {
+        #[allow(unused_mut)]
+        let mut backends = Vec::new();
+        #[cfg(target_os = "windows")]
+        backends.push(Backend::DirectSound);
+        #[cfg(all(target_os = "linux", feature = "jack"))]
+        backends.push(Backend::Jack);
+        #[cfg(all(target_os = "linux", feature = "pulseaudio"))]
+        backends.push(Backend::PulseAudio);
+        #[cfg(target_os = "linux")]
+        backends.push(Backend::Alsa);
+        #[cfg(target_os = "macos")]
+        backends.push(Backend::CoreAudio);
+        #[cfg(target_arch = "wasm32")]
+        backends.push(Backend::WebAudio);
+        backends.push(Backend::Dummy);
+        backends
+    }
+
+    /// Returns true if the backend is available on current platform with current set of features.
+    pub fn is_available(self) -> bool {
+        Self::available().contains(&self)
+    }
+}
+
+/// Information about an output device.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviceInfo {
+    /// Backend of the device.
+    pub backend: Backend,
+
+    /// Unique (within the backend) identifier of the device, it should be used in
+    /// [`DeviceConfig::with_device`] to select the device.
+    pub id: String,
+
+    /// Human-readable name of the device.
+    pub name: String,
+
+    /// Whether the device is used by the backend by default or not.
+    pub is_default: bool,
+}
+
+impl DeviceInfo {
+    fn default_device(backend: Backend) -> Self {
+        Self {
+            backend,
+            id: "default".to_owned(),
+            name: "Default Device".to_owned(),
+            is_default: true,
+        }
+    }
+}
+
+/// Returns a list of output devices of given backend. Backends without device enumeration
+/// support (DirectSound, CoreAudio, WebAudio and Dummy) have only the default device.
+pub fn enumerate_devices(backend: Backend) -> Result<Vec<DeviceInfo>, SoundError> {
+    match backend {
+        #[cfg(target_os = "linux")]
+        Backend::Alsa => alsa::enumerate_devices(),
+        #[cfg(all(target_os = "linux", feature = "pulseaudio"))]
+        Backend::PulseAudio => pulse::enumerate_devices(),
+        #[cfg(all(target_os = "linux", feature = "jack"))]
+        Backend::Jack => jack::enumerate_devices(),
+        _ if backend.is_available() => Ok(vec![DeviceInfo::default_device(backend)]),
+        _ => Err(SoundError::NoBackend),
+    }
+}
+
+/// Configuration of an output device.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceConfig {
+    /// Backend that should be used, `None` means that every available backend will be tried
+    /// in order of preference (see [`Backend::available`]).
+    pub backend: Option<Backend>,
+
+    /// Identifier of an output device (see [`DeviceInfo::id`]), `None` means that default device
+    /// of a backend will be used.
+    pub device: Option<String>,
+
+    /// Desired sample rate of the device. Contexts are always mixed at [`SAMPLE_RATE`], if the
+    /// device runs at different sample rate, mixed samples are resampled. Device may choose the
+    /// closest supported sample rate.
+    pub sample_rate: u32,
+
+    /// Desired size of the device buffer in samples per channel. Smaller buffers reduce output
+    /// latency, but increase the chance of audible glitches if the mixer can't keep up. Device may
+    /// choose the closest supported size.
+    ///
+    /// # Notes
+    ///
+    /// Contexts are mixed in blocks of the same duration as the device buffer, so changes in
+    /// contexts are applied with the granularity of the buffer. The only exception is the HRTF
+    /// renderer, it works with blocks of fixed size (about 93 ms).
+    pub buffer_size: u32,
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self {
+            backend: None,
+            device: None,
+            sample_rate: SAMPLE_RATE,
+            buffer_size: SoundContext::SAMPLES_PER_CHANNEL as u32,
+        }
+    }
+}
+
+impl DeviceConfig {
+    /// Sets desired backend.
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Sets desired output device, see [`DeviceInfo::id`].
+    pub fn with_device<S: AsRef<str>>(mut self, device: S) -> Self {
+        self.device = Some(device.as_ref().to_owned());
+        self
+    }
+
+    /// Sets desired sample rate.
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.max(1);
+        self
+    }
+
+    /// Sets desired buffer size in samples per channel.
+    pub fn with_buffer_size(mut self, buffer_size: u32) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Sets desired buffer size by given latency.
+    pub fn with_latency(self, latency: Duration) -> Self {
+        let buffer_size = (latency.as_secs_f64() * self.sample_rate as f64).round() as u32;
+        self.with_buffer_size(buffer_size)
+    }
+
+    /// Returns output latency which is caused by the buffer of the device.
+    pub fn latency(&self) -> Duration {
+        Duration::from_secs_f64(self.buffer_size as f64 / self.sample_rate.max(1) as f64)
+    }
+
+    fn buffer_len_bytes(&self) -> u32 {
+        self.buffer_size * std::mem::size_of::<NativeSample>() as u32
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Default)]
-pub struct NativeSample {
+pub(in crate) struct NativeSample {
     pub left: i16,
     pub right: i16,
 }
 
-pub type FeedCallback = dyn FnMut(&mut [(f32, f32)]) + Send;
+pub(in crate) type FeedCallback = dyn FnMut(&mut [(f32, f32)]) + Send;
+
+// Callback is shared, so it can be passed to the next backend if a device failed to open.
+pub(in crate) type SharedFeedCallback = Arc<Mutex<Box<FeedCallback>>>;
 
-pub struct MixContext<'a> {
+/// Mixer adapts the feed callback, which renders blocks at [`SAMPLE_RATE`], to sample rate of a
+/// device. Size of the blocks matches the duration of the device buffer, so there is no
+/// additional latency if the device runs at [`SAMPLE_RATE`].
+pub(in crate) struct Mixer {
+    callback: SharedFeedCallback,
+    block: Vec<(f32, f32)>,
+    resampler: Resampler,
+}
+
+impl Mixer {
+    /// Creates new mixer for a device with given sample rate and buffer size in samples per
+    /// channel.
+    pub(in crate) fn new(
+        callback: SharedFeedCallback,
+        sample_rate: u32,
+        buffer_size: usize,
+    ) -> Self {
+        Self {
+            callback,
+            block: vec![(0.0, 0.0); Self::block_len(sample_rate, buffer_size)],
+            resampler: Resampler::new(sample_rate),
+        }
+    }
+
+    // Amount of samples at SAMPLE_RATE that is needed to fill a buffer of the device.
+    fn block_len(sample_rate: u32, buffer_size: usize) -> usize {
+        let len = buffer_size as f64 * SAMPLE_RATE as f64 / sample_rate.max(1) as f64;
+        (len.ceil() as usize).max(1)
+    }
+
+    pub(in crate) fn fill(&mut self, buffer: &mut [(f32, f32)]) {
+        let Self {
+            callback,
+            block,
+            resampler,
+        } = self;
+
+        resampler.process(buffer, |pending| {
+            block.fill((0.0, 0.0));
+            if let Ok(mut callback) = callback.lock() {
+                (*callback)(block.as_mut_slice());
+            }
+            pending.extend(block.iter().copied());
+        });
+    }
+}
+
+pub(in crate) struct MixContext<'a> {
     mix_buffer: &'a mut [(f32, f32)],
     out_data: &'a mut [NativeSample],
-    callback: &'a mut FeedCallback,
+    mixer: &'a mut Mixer,
 }
 
 trait Device {
@@ -52,14 +332,8 @@ trait Device {
 
     fn mix(&mut self) {
         if let Some(context) = self.get_mix_context() {
-            // Clear mixer buffer.
-            for (left, right) in context.mix_buffer.iter_mut() {
-                *left = 0.0;
-                *right = 0.0;
-            }
-
-            // Fill it.
-            (context.callback)(context.mix_buffer);
+            // Fill mixer buffer.
+            context.mixer.fill(context.mix_buffer);
 
             // Convert to i16 - device expects samples in this format.
             assert_eq!(context.mix_buffer.len(), context.out_data.len());
@@ -85,32 +359,139 @@ trait Device {
     }
 }
 
-/// Transfer ownership of device to separate mixer thread. It will
-/// call the callback with a specified rate to get data to send to a physical device.
+fn open_backend(
+    backend: Backend,
+    config: &DeviceConfig,
+    callback: SharedFeedCallback,
+) -> Result<Box<dyn Device>, SoundError> {
+    match backend {
+        #[cfg(target_os = "windows")]
+        Backend::DirectSound => Ok(Box::new(dsound::DirectSoundDevice::new(config, callback)?)),
+        #[cfg(target_os = "linux")]
+        Backend::Alsa => Ok(Box::new(alsa::AlsaSoundDevice::new(config, callback)?)),
+        #[cfg(all(target_os = "linux", feature = "pulseaudio"))]
+        Backend::PulseAudio => Ok(Box::new(pulse::PulseSoundDevice::new(config, callback)?)),
+        #[cfg(all(target_os = "linux", feature = "jack"))]
+        Backend::Jack => Ok(Box::new(jack::JackSoundDevice::new(config, callback)?)),
+        #[cfg(target_os = "macos")]
+        Backend::CoreAudio => Ok(Box::new(coreaudio::CoreaudioSoundDevice::new(
+            config, callback,
+        )?)),
+        #[cfg(target_arch = "wasm32")]
+        Backend::WebAudio => Ok(Box::new(web::WebAudioDevice::new(config, callback))),
+        Backend::Dummy => Ok(Box::new(dummy::DummySoundDevice::new(config, callback))),
+        #[allow(unreachable_patterns)]
+        _ => Err(SoundError::NoBackend),
+    }
+}
+
+// Opens a device using the first backend that works, falls back to the dummy device. Returns
+// errors of backends that failed to open.
+fn open_device(
+    config: &DeviceConfig,
+    callback: SharedFeedCallback,
+) -> (Backend, Box<dyn Device>, Vec<(Backend, SoundError)>) {
+    let backends = match config.backend {
+        Some(backend) => vec![backend],
+        None => Backend::available(),
+    };
+
+    let mut errors = Vec::new();
+    for backend in backends {
+        match open_backend(backend, config, callback.clone()) {
+            Ok(device) => return (backend, device, errors),
+            Err(error) => errors.push((backend, error)),
+        }
+    }
+
+    (
+        Backend::Dummy,
+        Box::new(dummy::DummySoundDevice::new(config, callback)),
+        errors,
+    )
+}
+
+/// Transfer ownership of device to separate mixer thread. It will call the callback with a
+/// specified rate to get data to send to a physical device. Returns a backend which was used
+/// to open the device and errors of backends that failed to open.
 pub(in crate) fn run_device<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-    #[allow(unused_variables)] buffer_len_bytes: u32,
-    #[allow(unused_variables)] callback: F,
-) {
+    config: DeviceConfig,
+    callback: F,
+) -> (Backend, Vec<(Backend, SoundError)>) {
+    let callback: SharedFeedCallback = Arc::new(Mutex::new(Box::new(callback)));
+
     #[cfg(not(target_arch = "wasm32"))]
     {
+        // Devices are not Send, so they must be created in the mixer thread.
+        let (sender, receiver) = std::sync::mpsc::channel();
         std::thread::spawn(move || {
-            #[cfg(target_os = "windows")]
-            let mut device = dsound::DirectSoundDevice::new(buffer_len_bytes, callback).unwrap();
-            #[cfg(target_os = "linux")]
-            let mut device = alsa::AlsaSoundDevice::new(buffer_len_bytes, callback).unwrap();
-            #[cfg(target_os = "macos")]
-            let mut device =
-                coreaudio::CoreaudioSoundDevice::new(buffer_len_bytes, callback).unwrap();
-            #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
-            let mut device = dummy::DummySoundDevice::new(buffer_len_bytes, callback).unwrap();
+            let (backend, mut device, errors) = open_device(&config, callback);
+            let _ = sender.send((backend, errors));
             device.run()
         });
+        receiver
+            .recv()
+            .unwrap_or_else(|_| (Backend::Dummy, Vec::new()))
     }
 
     #[cfg(target_arch = "wasm32")]
     {
-        let mut device = web::WebAudioDevice::new(buffer_len_bytes, callback);
+        let (backend, mut device, errors) = open_device(&config, callback);
         device.run();
         std::mem::forget(device);
+        (backend, errors)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        device::{Mixer, SharedFeedCallback},
+    };
+    use std::sync::{Arc, Mutex};
+
+    // Returns a mixer which records lengths of requested blocks.
+    fn make_mixer(sample_rate: u32, buffer_size: usize) -> (Mixer, Arc<Mutex<Vec<usize>>>) {
+        let blocks = Arc::new(Mutex::new(Vec::new()));
+        let callback: SharedFeedCallback = Arc::new(Mutex::new(Box::new({
+            let blocks = blocks.clone();
+            move |buf: &mut [(f32, f32)]| {
+                blocks.lock().unwrap().push(buf.len());
+                buf.fill((1.0, 1.0));
+            }
+        })));
+        (Mixer::new(callback, sample_rate, buffer_size), blocks)
+    }
+
+    #[test]
+    fn test_mixer_block_matches_device_buffer() {
+        let (mut mixer, blocks) = make_mixer(SAMPLE_RATE, 256);
+
+        let mut buffer = vec![(0.0, 0.0); 256];
+        for _ in 0..3 {
+            mixer.fill(&mut buffer);
+            assert!(buffer.iter().all(|s| *s == (1.0, 1.0)));
+        }
+
+        // Exactly one block per device buffer, so there is no additional latency.
+        assert_eq!(*blocks.lock().unwrap(), vec![256, 256, 256]);
+    }
+
+    #[test]
+    fn test_mixer_block_with_resampling() {
+        // 10 ms buffer of a device at 48 kHz.
+        let (mut mixer, blocks) = make_mixer(48000, 480);
+
+        let mut buffer = vec![(0.0, 0.0); 480];
+        for _ in 0..10 {
+            mixer.fill(&mut buffer);
+        }
+
+        let blocks = blocks.lock().unwrap();
+        assert!(blocks.iter().all(|len| *len == 441));
+        // Mixer stays at most one block ahead of the device.
+        let rendered = blocks.iter().sum::<usize>();
+        assert!((10 * 441..=11 * 441).contains(&rendered));
     }
 }
@@ -0,0 +1,192 @@
+//! PulseAudio output device. It works with PipeWire as well, because PipeWire implements
+//! PulseAudio protocol.
+
+use crate::{
+    device::{
+        Backend, Device, DeviceConfig, DeviceInfo, MixContext, Mixer, NativeSample,
+        SharedFeedCallback,
+    },
+    error::SoundError,
+};
+use libpulse_binding::{
+    callbacks::ListResult,
+    context::{Context, FlagSet, State},
+    def::BufferAttr,
+    mainloop::standard::{IterateResult, Mainloop},
+    operation::{Operation, State as OperationState},
+    sample::{Format, Spec},
+    stream::Direction,
+};
+use libpulse_simple_binding::Simple;
+use std::{cell::RefCell, mem::size_of, rc::Rc};
+
+const APPLICATION_NAME: &str = "fyrox-sound";
+
+pub struct PulseSoundDevice {
+    simple: Simple,
+    mixer: Mixer,
+    out_data: Vec<NativeSample>,
+    mix_buffer: Vec<(f32, f32)>,
+}
+
+fn error<E: std::fmt::Debug>(message: &str, error: E) -> SoundError {
+    SoundError::FailedToInitializeDevice(format!("{}. Reason: {:?}", message, error))
+}
+
+fn iterate(mainloop: &mut Mainloop) -> Result<(), SoundError> {
+    match mainloop.iterate(true) {
+        IterateResult::Success(_) => Ok(()),
+        IterateResult::Quit(_) => Err(SoundError::FailedToInitializeDevice(
+            "PulseAudio main loop has quit".to_owned(),
+        )),
+        IterateResult::Err(err) => Err(error("PulseAudio main loop has failed", err)),
+    }
+}
+
+fn wait_operation<T: ?Sized>(
+    mainloop: &mut Mainloop,
+    operation: Operation<T>,
+) -> Result<(), SoundError> {
+    while let OperationState::Running = operation.get_state() {
+        iterate(mainloop)?;
+    }
+    Ok(())
+}
+
+// Lists sinks of PulseAudio server. Introspection API is asynchronous, so it is driven by
+// a local main loop until every operation is done.
+pub fn enumerate_devices() -> Result<Vec<DeviceInfo>, SoundError> {
+    let mut mainloop = Mainloop::new().ok_or_else(|| {
+        SoundError::FailedToInitializeDevice("Unable to create PulseAudio main loop".to_owned())
+    })?;
+    let mut context = Context::new(&mainloop, APPLICATION_NAME).ok_or_else(|| {
+        SoundError::FailedToInitializeDevice("Unable to create PulseAudio context".to_owned())
+    })?;
+    context
+        .connect(None, FlagSet::NOFLAGS, None)
+        .map_err(|e| error("Unable to connect to PulseAudio server", e))?;
+
+    loop {
+        iterate(&mut mainloop)?;
+        match context.get_state() {
+            State::Ready => break,
+            State::Failed | State::Terminated => {
+                return Err(SoundError::FailedToInitializeDevice(
+                    "Unable to connect to PulseAudio server".to_owned(),
+                ))
+            }
+            _ => (),
+        }
+    }
+
+    let default_sink = Rc::new(RefCell::new(None));
+    let operation = context.introspect().get_server_info({
+        let default_sink = default_sink.clone();
+        move |info| {
+            *default_sink.borrow_mut() = info.default_sink_name.as_ref().map(|n| n.to_string());
+        }
+    });
+    wait_operation(&mut mainloop, operation)?;
+
+    let devices = Rc::new(RefCell::new(Vec::new()));
+    let operation = context.introspect().get_sink_info_list({
+        let devices = devices.clone();
+        let default_sink = default_sink.borrow().clone();
+        move |result| {
+            if let ListResult::Item(info) = result {
+                if let Some(id) = info.name.as_ref().map(|n| n.to_string()) {
+                    devices.borrow_mut().push(DeviceInfo {
+                        backend: Backend::PulseAudio,
+                        name: info
+                            .description
+                            .as_ref()
+                            .map(|d| d.to_string())
+                            .unwrap_or_else(|| id.clone()),
+                        is_default: default_sink.as_ref() == Some(&id),
+                        id,
+                    });
+                }
+            }
+        }
+    });
+    wait_operation(&mut mainloop, operation)?;
+
+    context.disconnect();
+
+    let devices = devices.borrow().clone();
+    Ok(devices)
+}
+
+impl PulseSoundDevice {
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Result<Self, SoundError> {
+        let spec = Spec {
+            format: Format::S16NE,
+            channels: 2,
+            rate: config.sample_rate,
+        };
+        if !spec.is_valid() {
+            return Err(SoundError::FailedToInitializeDevice(format!(
+                "Unsupported sample rate {}",
+                config.sample_rate
+            )));
+        }
+
+        // Server keeps two device buffers, just like other backends do.
+        let buffer_len_bytes = config.buffer_len_bytes();
+        let attributes = BufferAttr {
+            maxlength: u32::MAX,
+            tlength: 2 * buffer_len_bytes,
+            prebuf: u32::MAX,
+            minreq: buffer_len_bytes,
+            fragsize: u32::MAX,
+        };
+
+        let simple = Simple::new(
+            None,
+            APPLICATION_NAME,
+            Direction::Playback,
+            config.device.as_deref(),
+            "Output",
+            &spec,
+            None,
+            Some(&attributes),
+        )
+        .map_err(|e| error("Unable to open PulseAudio stream", e))?;
+
+        let samples_per_channel = config.buffer_size as usize;
+        Ok(Self {
+            simple,
+            mixer: Mixer::new(callback, config.sample_rate, samples_per_channel),
+            out_data: vec![Default::default(); samples_per_channel],
+            mix_buffer: vec![(0.0, 0.0); samples_per_channel],
+        })
+    }
+}
+
+impl Device for PulseSoundDevice {
+    fn get_mix_context(&mut self) -> Option<MixContext> {
+        Some(MixContext {
+            mix_buffer: self.mix_buffer.as_mut_slice(),
+            out_data: &mut self.out_data,
+            mixer: &mut self.mixer,
+        })
+    }
+
+    fn run(&mut self) {
+        loop {
+            self.mix();
+
+            // Write blocks until the server is ready to accept the data.
+            let data = unsafe {
+                std::slice::from_raw_parts(
+                    self.out_data.as_ptr() as *const u8,
+                    self.out_data.len() * size_of::<NativeSample>(),
+                )
+            };
+            if self.simple.write(data).is_err() {
+                // Connection to the server is lost.
+                return;
+            }
+        }
+    }
+}
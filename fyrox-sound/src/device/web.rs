@@ -1,13 +1,10 @@
-use crate::{
-    context::SAMPLE_RATE,
-    device::{Device, MixContext, NativeSample},
-};
+use crate::device::{Device, DeviceConfig, MixContext, Mixer, SharedFeedCallback};
 use fyrox_core::{
     parking_lot::{Mutex, RwLock},
     wasm_bindgen::{prelude::*, JsCast},
     web_sys::{AudioContext, AudioContextOptions},
 };
-use std::{mem::size_of, sync::Arc};
+use std::sync::Arc;
 
 pub struct WebAudioDevice {
     ctx: Arc<AudioContext>,
@@ -16,24 +13,26 @@ pub struct WebAudioDevice {
 }
 
 impl WebAudioDevice {
-    pub fn new<F: FnMut(&mut [(f32, f32)]) + Send + 'static>(
-        buffer_len_bytes: u32,
-        callback: F,
-    ) -> Self {
-        let callback = Arc::new(Mutex::new(callback));
+    pub fn new(config: &DeviceConfig, callback: SharedFeedCallback) -> Self {
+        let sample_rate = config.sample_rate;
+        let samples_per_channel = config.buffer_size as usize;
+        let mixer = Arc::new(Mutex::new(Mixer::new(
+            callback,
+            sample_rate,
+            samples_per_channel,
+        )));
 
         let mut options = AudioContextOptions::new();
-        options.sample_rate(SAMPLE_RATE as f32);
+        options.sample_rate(sample_rate as f32);
         let ctx = Arc::new(AudioContext::new_with_context_options(&options).unwrap());
-        let samples_per_channel = buffer_len_bytes as usize / size_of::<NativeSample>();
-        let buffer_duration_secs = samples_per_channel as f32 / (SAMPLE_RATE as f32);
+        let buffer_duration_secs = samples_per_channel as f32 / (sample_rate as f32);
         let mut onended: Vec<Arc<RwLock<Option<Closure<dyn FnMut()>>>>> = Vec::new();
 
         let time = Arc::new(RwLock::new(0.0f32));
 
         for _ in 0..2 {
             let buffer = ctx
-                .create_buffer(2, samples_per_channel as u32, SAMPLE_RATE as f32)
+                .create_buffer(2, samples_per_channel as u32, sample_rate as f32)
                 .unwrap();
 
             let onended_closure: Arc<RwLock<Option<Closure<dyn FnMut()>>>> =
@@ -42,17 +41,12 @@ impl WebAudioDevice {
             let ctx_clone = ctx.clone();
             let onended_closure_clone = onended_closure.clone();
             let time = time.clone();
-            let callback = callback.clone();
+            let mixer = mixer.clone();
             let mut mix_buffer = vec![(0.0f32, 0.0f32); samples_per_channel];
             let mut temp_samples = vec![0.0f32; samples_per_channel];
             onended_closure
                 .write()
                 .replace(Closure::wrap(Box::new(move || {
-                    for (l, r) in mix_buffer.iter_mut() {
-                        *r = 0.0;
-                        *l = 0.0;
-                    }
-
                     let current_time = ctx_clone.current_time() as f32;
                     let raw_time = *time.read();
                     let start_time = if raw_time >= current_time {
@@ -61,7 +55,7 @@ impl WebAudioDevice {
                         current_time
                     };
 
-                    callback.lock()(&mut mix_buffer);
+                    mixer.lock().fill(&mut mix_buffer);
 
                     // Fill left channel.
                     for ((l, _), sample) in mix_buffer.iter().zip(temp_samples.iter_mut()) {
@@ -192,6 +192,10 @@ pub enum BiquadKind {
     /// Reduces amplitude of frequencies in a shape like this _/̅  where location of center of /
     /// defined by F_center.
     HighShelf,
+
+    /// Boosts or cuts amplitude of frequencies in some band around F_center, it is the basic block
+    /// of parametric equalizers.
+    Peak,
 }
 
 /// Generic second order digital filter.
@@ -310,6 +314,15 @@ impl Biquad {
                 let a2 = (gain + 1.0) - (gain - 1.0) * w0_cos - sq;
                 (b0, b1, b2, a0, a1, a2)
             }
+            BiquadKind::Peak => {
+                let b0 = 1.0 + alpha * gain;
+                let b1 = -2.0 * w0_cos;
+                let b2 = 1.0 - alpha * gain;
+                let a0 = 1.0 + alpha / gain;
+                let a1 = -2.0 * w0_cos;
+                let a2 = 1.0 - alpha / gain;
+                (b0, b1, b2, a0, a1, a2)
+            }
         };
 
         self.b0 = b0 / a0;
@@ -319,6 +332,16 @@ impl Biquad {
         self.a2 = a2 / a0;
     }
 
+    /// Copies coefficients of other filter, but keeps internal state of the filter intact. It
+    /// allows you to change parameters of a filter while it is processing a signal.
+    pub fn copy_coefficients(&mut self, other: &Biquad) {
+        self.b0 = other.b0;
+        self.b1 = other.b1;
+        self.b2 = other.b2;
+        self.a1 = other.a1;
+        self.a2 = other.a2;
+    }
+
     /// Processes single sample.
     pub fn feed(&mut self, sample: f32) -> f32 {
         let result = sample * self.b0 + self.prev1;
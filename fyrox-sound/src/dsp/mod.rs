@@ -11,6 +11,7 @@
 use fyrox_core::visitor::{Visit, VisitResult, Visitor};
 
 pub mod filters;
+pub(in crate) mod resampler;
 
 /// See more info here <https://ccrma.stanford.edu/~jos/pasp/Delay_Lines.html>
 #[derive(Debug, Clone, Visit)]
@@ -79,3 +80,23 @@ pub fn hann_window(i: usize, sample_count: usize) -> f32 {
 pub fn make_window<W: Fn(usize, usize) -> f32>(sample_count: usize, func: W) -> Vec<f32> {
     (0..sample_count).map(|i| func(i, sample_count)).collect()
 }
+
+/// Converts decibels to linear gain, for example `-6 dB` is `~0.5`.
+pub fn db_to_linear(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+/// Converts linear gain to decibels. Silence (zero gain) is clamped to `-120 dB`.
+pub fn linear_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(0.000_001).log10()
+}
+
+/// Calculates coefficient of one-pole smoothing filter that reaches ~63% of a target value in
+/// given time (in seconds).
+pub fn time_constant(time: f32, sample_rate: u32) -> f32 {
+    if time <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time * sample_rate as f32)).exp()
+    }
+}
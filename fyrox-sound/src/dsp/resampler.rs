@@ -0,0 +1,61 @@
+//! Linear resampler of a stream of stereo samples.
+
+use crate::context::SAMPLE_RATE;
+use std::collections::VecDeque;
+
+/// Converts a stream of samples, which are produced in blocks at [`SAMPLE_RATE`], to a stream at
+/// some other sample rate using linear interpolation. There is no resampling and no additional
+/// latency when the sample rates are equal.
+#[derive(Clone, Debug)]
+pub(in crate) struct Resampler {
+    step: f64,
+    // Samples at the native sample rate that are not yet consumed.
+    pending: VecDeque<(f32, f32)>,
+    // Fractional position in `pending` buffer.
+    position: f64,
+}
+
+impl Resampler {
+    pub(in crate) fn new(sample_rate: u32) -> Self {
+        Self {
+            step: SAMPLE_RATE as f64 / sample_rate.max(1) as f64,
+            pending: Default::default(),
+            position: 0.0,
+        }
+    }
+
+    /// Fills output buffer with resampled samples, `produce` is called every time when more
+    /// input samples are needed and it must push at least one sample into given queue.
+    pub(in crate) fn process<F>(&mut self, output: &mut [(f32, f32)], mut produce: F)
+    where
+        F: FnMut(&mut VecDeque<(f32, f32)>),
+    {
+        for out in output.iter_mut() {
+            let index = self.position as usize;
+            let t = (self.position - index as f64) as f32;
+
+            // Next sample is needed only for interpolation.
+            let required = if t > 0.0 { index + 2 } else { index + 1 };
+            while self.pending.len() < required {
+                produce(&mut self.pending);
+            }
+
+            let (left_a, right_a) = self.pending[index];
+            *out = if t > 0.0 {
+                let (left_b, right_b) = self.pending[index + 1];
+                (
+                    left_a + (left_b - left_a) * t,
+                    right_a + (right_b - right_a) * t,
+                )
+            } else {
+                (left_a, right_a)
+            };
+
+            self.position += self.step;
+        }
+
+        let consumed = (self.position as usize).min(self.pending.len());
+        self.pending.drain(..consumed);
+        self.position -= consumed as f64;
+    }
+}
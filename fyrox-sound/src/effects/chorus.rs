@@ -0,0 +1,276 @@
+//! Chorus and flanger module.
+//!
+//! # Overview
+//!
+//! Chorus mixes input signal with its copy delayed by a time that is modulated by low frequency
+//! oscillator, this gives an impression of multiple voices playing the same sound. Flanger is
+//! the same effect with shorter delay and strong feedback, it gives a "jet plane" sound.
+//! Modulation of right channel is shifted by a quarter of period, which widens stereo image.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::chorus::Chorus;
+//! use fyrox_sound::effects::{BaseEffect, Effect};
+//!
+//! fn add_flanger(context: &mut SoundContext) {
+//!     let flanger = Chorus::flanger(BaseEffect::default());
+//!     context.state().add_effect(Effect::Chorus(flanger));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    effects::{render_processed, BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    visitor::prelude::*,
+};
+use std::{
+    f32::consts::PI,
+    ops::{Deref, DerefMut},
+};
+
+/// Maximum sum of delay and modulation depth in seconds.
+const MAX_DELAY_TIME: f32 = 0.1;
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Chorus {
+    #[inspect(skip)]
+    base: BaseEffect,
+    #[inspect(min_value = 0.0, max_value = 20.0, step = 0.05)]
+    rate: f32,
+    #[inspect(min_value = 0.0, max_value = 0.05, step = 0.0005)]
+    depth: f32,
+    #[inspect(min_value = 0.0, max_value = 0.05, step = 0.0005)]
+    delay: f32,
+    #[inspect(min_value = -0.99, max_value = 0.99, step = 0.01)]
+    feedback: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    dry: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    wet: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    samples: Vec<(f32, f32)>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    pos: usize,
+    #[inspect(skip)]
+    #[visit(skip)]
+    phase: f32,
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Chorus {
+    /// Creates new chorus with 0.8 Hz modulation rate, 20 ms delay and 3 ms modulation depth.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            rate: 0.8,
+            depth: 0.003,
+            delay: 0.02,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.5,
+            samples: Default::default(),
+            pos: 0,
+            phase: 0.0,
+        }
+    }
+
+    /// Creates new flanger with 0.25 Hz modulation rate, 2 ms delay, 1.5 ms modulation depth and
+    /// strong feedback.
+    pub fn flanger(base: BaseEffect) -> Self {
+        Self {
+            rate: 0.25,
+            depth: 0.0015,
+            delay: 0.002,
+            feedback: 0.7,
+            wet: 0.7,
+            ..Self::new(base)
+        }
+    }
+
+    /// Sets frequency (in hertz) of the modulation.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Returns frequency (in hertz) of the modulation.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets modulation depth (in seconds), it defines how much delay time changes.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.clamp(0.0, MAX_DELAY_TIME * 0.5);
+    }
+
+    /// Returns modulation depth (in seconds).
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Sets base delay time (in seconds).
+    pub fn set_delay(&mut self, delay: f32) {
+        self.delay = delay.clamp(0.0, MAX_DELAY_TIME * 0.5);
+    }
+
+    /// Returns base delay time (in seconds).
+    pub fn delay(&self) -> f32 {
+        self.delay
+    }
+
+    /// Sets amount of delayed signal that is fed back, negative values invert phase of the
+    /// feedback.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(-0.99, 0.99);
+    }
+
+    /// Returns feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    fn read(&self, delay: f32) -> (f32, f32) {
+        // Linear interpolation between two nearest samples of the delay line.
+        let len = self.samples.len();
+        let position = (self.pos as f32 - 1.0 - delay * SAMPLE_RATE as f32).rem_euclid(len as f32);
+        let i = position as usize % len;
+        let j = (i + 1) % len;
+        let t = position.fract();
+        let (a_left, a_right) = self.samples[i];
+        let (b_left, b_right) = self.samples[j];
+        (
+            a_left + (b_left - a_left) * t,
+            a_right + (b_right - a_right) * t,
+        )
+    }
+}
+
+impl EffectRenderTrait for Chorus {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let len = (MAX_DELAY_TIME * SAMPLE_RATE as f32) as usize + 2;
+        if self.samples.len() != len {
+            self.samples = vec![(0.0, 0.0); len];
+            self.pos = 0;
+        }
+
+        let phase_step = 2.0 * PI * self.rate / SAMPLE_RATE as f32;
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let left_delay = self.delay + self.depth * (1.0 + self.phase.sin()) * 0.5;
+            let right_delay = self.delay + self.depth * (1.0 + (self.phase + PI * 0.5).sin()) * 0.5;
+
+            let (delayed_left, _) = self.read(left_delay);
+            let (_, delayed_right) = self.read(right_delay);
+
+            self.samples[self.pos] = (
+                *left + delayed_left * self.feedback,
+                *right + delayed_right * self.feedback,
+            );
+            self.pos = (self.pos + 1) % len;
+
+            self.phase += phase_step;
+            if self.phase > 2.0 * PI {
+                self.phase -= 2.0 * PI;
+            }
+
+            *left = gain * (*left * self.dry + delayed_left * self.wet);
+            *right = gain * (*right * self.dry + delayed_right * self.wet);
+        }
+    }
+}
+
+impl Deref for Chorus {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Chorus {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{chorus::Chorus, BaseEffect, EffectRenderTrait},
+    };
+
+    #[test]
+    fn test_chorus_without_modulation_is_delay() {
+        let mut chorus = Chorus::new(BaseEffect::default());
+        chorus.set_depth(0.0);
+        chorus.set_delay(0.01);
+        chorus.set_dry(0.0);
+        chorus.set_wet(1.0);
+
+        let mut buf = vec![(0.0, 0.0); 1024];
+        buf[0] = (1.0, 1.0);
+        chorus.process(&mut buf);
+
+        let delay = (0.01 * SAMPLE_RATE as f32) as usize;
+        let (peak, _) =
+            buf.iter()
+                .enumerate()
+                .fold((0, 0.0f32), |(index, max), (i, &(left, _))| {
+                    if left > max {
+                        (i, left)
+                    } else {
+                        (index, max)
+                    }
+                });
+        assert!((peak as isize - delay as isize).abs() <= 2);
+        // Energy of the impulse must be preserved by interpolation.
+        let sum = buf.iter().map(|(left, _)| left).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1.0e-3);
+    }
+}
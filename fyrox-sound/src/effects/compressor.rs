@@ -0,0 +1,461 @@
+//! Dynamics processing module.
+//!
+//! # Overview
+//!
+//! [`Compressor`] reduces dynamic range of a signal - it makes loud parts quieter, so the overall
+//! level could be raised with makeup gain. [`Limiter`] is a compressor with infinite ratio and
+//! instant attack, it guarantees that output signal never exceeds given threshold.
+//!
+//! Both effects support lookahead - input signal is delayed by a small amount of time, so gain
+//! reduction could start before a loud peak, this removes distortion of transients at the cost
+//! of small latency.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::compressor::{Compressor, Limiter};
+//! use fyrox_sound::effects::{BaseEffect, Effect};
+//!
+//! fn add_dynamics(context: &mut SoundContext) {
+//!     let mut compressor = Compressor::new(BaseEffect::default());
+//!     compressor.set_threshold(-18.0);
+//!     compressor.set_ratio(4.0);
+//!     context.state().add_effect(Effect::Compressor(compressor));
+//!
+//!     let mut limiter = Limiter::new(BaseEffect::default());
+//!     limiter.set_threshold(-0.3);
+//!     context.state().add_effect(Effect::Limiter(limiter));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::{db_to_linear, linear_to_db, time_constant},
+    effects::{render_processed, BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    visitor::prelude::*,
+};
+use std::ops::{Deref, DerefMut};
+
+/// Stereo delay line with variable length that is used to delay signal by lookahead time.
+#[derive(Default, Debug, Clone)]
+struct Lookahead {
+    samples: Vec<(f32, f32)>,
+    pos: usize,
+}
+
+impl Lookahead {
+    fn resize(&mut self, time: f32) {
+        let len = (time.max(0.0) * SAMPLE_RATE as f32) as usize;
+        if len != self.samples.len() {
+            self.samples = vec![(0.0, 0.0); len];
+            self.pos = 0;
+        }
+    }
+
+    fn feed(&mut self, sample: (f32, f32)) -> (f32, f32) {
+        if self.samples.is_empty() {
+            sample
+        } else {
+            let delayed = std::mem::replace(&mut self.samples[self.pos], sample);
+            self.pos = (self.pos + 1) % self.samples.len();
+            delayed
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Compressor {
+    #[inspect(skip)]
+    base: BaseEffect,
+    #[inspect(min_value = -96.0, max_value = 0.0, step = 0.5)]
+    threshold: f32,
+    #[inspect(min_value = 1.0, step = 0.1)]
+    ratio: f32,
+    #[inspect(min_value = 0.0, step = 0.001)]
+    attack: f32,
+    #[inspect(min_value = 0.0, step = 0.01)]
+    release: f32,
+    #[inspect(min_value = 0.0, step = 0.5)]
+    makeup_gain: f32,
+    #[inspect(min_value = 0.0, max_value = 0.1, step = 0.001)]
+    lookahead: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    envelope: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    delay: Lookahead,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Compressor {
+    /// Creates new compressor with -12 dB threshold, 4:1 ratio, 10 ms attack, 100 ms release and
+    /// 5 ms lookahead.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            threshold: -12.0,
+            ratio: 4.0,
+            attack: 0.01,
+            release: 0.1,
+            makeup_gain: 0.0,
+            lookahead: 0.005,
+            envelope: 0.0,
+            delay: Default::default(),
+        }
+    }
+
+    /// Sets level (in decibels) above which compression starts.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.min(0.0);
+    }
+
+    /// Returns level (in decibels) above which compression starts.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Sets compression ratio, for example 4.0 means that 4 dB above threshold will become 1 dB.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets time (in seconds) that is needed to apply gain reduction.
+    pub fn set_attack(&mut self, attack: f32) {
+        self.attack = attack.max(0.0);
+    }
+
+    /// Returns attack time (in seconds).
+    pub fn attack(&self) -> f32 {
+        self.attack
+    }
+
+    /// Sets time (in seconds) that is needed to remove gain reduction.
+    pub fn set_release(&mut self, release: f32) {
+        self.release = release.max(0.0);
+    }
+
+    /// Returns release time (in seconds).
+    pub fn release(&self) -> f32 {
+        self.release
+    }
+
+    /// Sets gain (in decibels) that is applied after compression to restore loudness.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain = makeup_gain;
+    }
+
+    /// Returns makeup gain (in decibels).
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    /// Sets lookahead time (in seconds).
+    pub fn set_lookahead(&mut self, lookahead: f32) {
+        self.lookahead = lookahead.max(0.0);
+    }
+
+    /// Returns lookahead time (in seconds).
+    pub fn lookahead(&self) -> f32 {
+        self.lookahead
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        self.delay.resize(self.lookahead);
+
+        let attack = time_constant(self.attack, SAMPLE_RATE);
+        let release = time_constant(self.release, SAMPLE_RATE);
+        let slope = 1.0 - 1.0 / self.ratio;
+
+        for (left, right) in buf.iter_mut() {
+            // Detector works on undelayed signal, so gain reduction is ahead of the signal.
+            let level = linear_to_db(left.abs().max(right.abs()));
+            let reduction = (level - self.threshold).max(0.0) * slope;
+            let k = if reduction > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = reduction + k * (self.envelope - reduction);
+
+            let gain = self.base.gain * db_to_linear(self.makeup_gain - self.envelope);
+            let (delayed_left, delayed_right) = self.delay.feed((*left, *right));
+            *left = delayed_left * gain;
+            *right = delayed_right * gain;
+        }
+    }
+}
+
+impl Deref for Compressor {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Compressor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Limiter {
+    #[inspect(skip)]
+    base: BaseEffect,
+    #[inspect(min_value = -96.0, max_value = 0.0, step = 0.1)]
+    threshold: f32,
+    #[inspect(min_value = 0.0, step = 0.01)]
+    release: f32,
+    #[inspect(min_value = 0.0, max_value = 0.1, step = 0.001)]
+    lookahead: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    current_gain: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    held_gain: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    hold: usize,
+    #[inspect(skip)]
+    #[visit(skip)]
+    delay: Lookahead,
+}
+
+impl Default for Limiter {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Limiter {
+    /// Creates new limiter with -0.1 dB threshold, 50 ms release and 5 ms lookahead.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            threshold: -0.1,
+            release: 0.05,
+            lookahead: 0.005,
+            current_gain: 1.0,
+            held_gain: 1.0,
+            hold: 0,
+            delay: Default::default(),
+        }
+    }
+
+    /// Sets maximum level (in decibels) of output signal.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.min(0.0);
+    }
+
+    /// Returns maximum level (in decibels) of output signal.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Sets time (in seconds) that is needed to remove gain reduction.
+    pub fn set_release(&mut self, release: f32) {
+        self.release = release.max(0.0);
+    }
+
+    /// Returns release time (in seconds).
+    pub fn release(&self) -> f32 {
+        self.release
+    }
+
+    /// Sets lookahead time (in seconds). Gain reduction is smoothly applied during this time
+    /// before a peak.
+    pub fn set_lookahead(&mut self, lookahead: f32) {
+        self.lookahead = lookahead.max(0.0);
+    }
+
+    /// Returns lookahead time (in seconds).
+    pub fn lookahead(&self) -> f32 {
+        self.lookahead
+    }
+}
+
+impl EffectRenderTrait for Limiter {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        self.delay.resize(self.lookahead);
+
+        let ceiling = db_to_linear(self.threshold);
+        let lookahead_len = self.delay.samples.len();
+        let release = time_constant(self.release, SAMPLE_RATE);
+
+        for (left, right) in buf.iter_mut() {
+            let peak = left.abs().max(right.abs());
+            let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+
+            // Hold the lowest required gain until the peak leaves the lookahead window.
+            if required <= self.held_gain {
+                self.held_gain = required;
+                self.hold = lookahead_len;
+            } else if self.hold > 0 {
+                self.hold -= 1;
+            } else {
+                self.held_gain = required + release * (self.held_gain - required);
+            }
+
+            if self.held_gain < self.current_gain {
+                // Ramp down during lookahead time so the gain reaches the target at the peak.
+                self.current_gain -=
+                    (self.current_gain - self.held_gain) / (self.hold.max(1) as f32);
+            } else {
+                self.current_gain = self.held_gain;
+            }
+
+            let (delayed_left, delayed_right) = self.delay.feed((*left, *right));
+            // Clamping guarantees that nothing passes through, even if envelope is late.
+            *left = (delayed_left * self.current_gain)
+                .max(-ceiling)
+                .min(ceiling)
+                * self.base.gain;
+            *right = (delayed_right * self.current_gain)
+                .max(-ceiling)
+                .min(ceiling)
+                * self.base.gain;
+        }
+    }
+}
+
+impl Deref for Limiter {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Limiter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        dsp::db_to_linear,
+        effects::{
+            compressor::{Compressor, Limiter},
+            BaseEffect, EffectRenderTrait,
+        },
+    };
+
+    fn instant_compressor() -> Compressor {
+        let mut compressor = Compressor::new(BaseEffect::default());
+        compressor.set_threshold(-20.0);
+        compressor.set_ratio(4.0);
+        compressor.set_attack(0.0);
+        compressor.set_release(0.0);
+        compressor.set_lookahead(0.0);
+        compressor
+    }
+
+    #[test]
+    fn test_compressor_reduces_gain_above_threshold() {
+        let mut compressor = instant_compressor();
+
+        let mut buf = vec![(1.0, -1.0); 64];
+        compressor.process(&mut buf);
+
+        // 20 dB above threshold with 4:1 ratio must become 5 dB above threshold.
+        let expected = db_to_linear(-15.0);
+        for &(left, right) in buf.iter() {
+            assert!((left - expected).abs() < 1.0e-4);
+            assert!((right + expected).abs() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_compressor_passes_signal_below_threshold() {
+        let mut compressor = instant_compressor();
+
+        let mut buf = vec![(0.05, 0.05); 64];
+        compressor.process(&mut buf);
+
+        for &(left, right) in buf.iter() {
+            assert!((left - 0.05).abs() < 1.0e-6);
+            assert!((right - 0.05).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn test_compressor_makeup_gain() {
+        let mut compressor = instant_compressor();
+        compressor.set_makeup_gain(15.0);
+
+        let mut buf = vec![(1.0, 1.0); 64];
+        compressor.process(&mut buf);
+
+        for &(left, _) in buf.iter() {
+            assert!((left - 1.0).abs() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_limiter_never_exceeds_threshold() {
+        let mut limiter = Limiter::new(BaseEffect::default());
+        limiter.set_threshold(-6.0);
+
+        let ceiling = db_to_linear(-6.0);
+        let mut buf = (0..2048)
+            .map(|i| {
+                let sample = (i as f32 * 0.05).sin() * 4.0;
+                (sample, -sample)
+            })
+            .collect::<Vec<_>>();
+        limiter.process(&mut buf);
+
+        for &(left, right) in buf.iter() {
+            assert!(left.abs() <= ceiling + f32::EPSILON);
+            assert!(right.abs() <= ceiling + f32::EPSILON);
+        }
+        // Loud signal must still reach the ceiling, limiter should not just mute it.
+        assert!(buf.iter().any(|(left, _)| left.abs() > ceiling * 0.9));
+    }
+}
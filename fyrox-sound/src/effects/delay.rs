@@ -0,0 +1,280 @@
+//! Delay (echo) module.
+//!
+//! # Overview
+//!
+//! Delay repeats input signal after some time, every repetition is quieter than the previous
+//! one, amount of repetitions is defined by feedback. Delay time could be defined in seconds or
+//! synced with a tempo of music, in the latter case the time is defined in beats.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::delay::{Delay, TempoSync};
+//! use fyrox_sound::effects::{BaseEffect, Effect};
+//!
+//! fn add_echo(context: &mut SoundContext) {
+//!     let mut delay = Delay::new(BaseEffect::default());
+//!     delay.set_feedback(0.4);
+//!     // Dotted eighth at 128 BPM.
+//!     delay.set_tempo_sync(Some(TempoSync {
+//!         bpm: 128.0,
+//!         beats: 0.75,
+//!     }));
+//!     context.state().add_effect(Effect::Delay(delay));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    effects::{render_processed, BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    visitor::prelude::*,
+};
+use std::ops::{Deref, DerefMut};
+
+/// Maximum delay time in seconds.
+pub const MAX_DELAY_TIME: f32 = 10.0;
+
+/// Defines delay time using tempo of music.
+#[derive(Debug, Clone, PartialEq, Inspect, Visit)]
+pub struct TempoSync {
+    /// Tempo of music in beats per minute.
+    #[inspect(min_value = 1.0, step = 1.0)]
+    pub bpm: f32,
+    /// Delay time in beats (quarter notes), for example 0.5 is an eighth note.
+    #[inspect(min_value = 0.0, step = 0.125)]
+    pub beats: f32,
+}
+
+impl Default for TempoSync {
+    fn default() -> Self {
+        Self {
+            bpm: 120.0,
+            beats: 1.0,
+        }
+    }
+}
+
+impl TempoSync {
+    /// Returns delay time in seconds.
+    pub fn time(&self) -> f32 {
+        self.beats * 60.0 / self.bpm.max(1.0)
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Delay {
+    #[inspect(skip)]
+    base: BaseEffect,
+    #[inspect(min_value = 0.0, max_value = 10.0, step = 0.01)]
+    time: f32,
+    #[inspect(min_value = 0.0, max_value = 0.99, step = 0.01)]
+    feedback: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    dry: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    wet: f32,
+    tempo_sync: Option<TempoSync>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    samples: Vec<(f32, f32)>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    pos: usize,
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Delay {
+    /// Creates new delay with 0.3 seconds delay time and 0.3 feedback.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            time: 0.3,
+            feedback: 0.3,
+            dry: 1.0,
+            wet: 0.5,
+            tempo_sync: None,
+            samples: Default::default(),
+            pos: 0,
+        }
+    }
+
+    /// Sets delay time in seconds. It is ignored if tempo sync is used.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time.clamp(0.0, MAX_DELAY_TIME);
+    }
+
+    /// Returns delay time in seconds. It is ignored if tempo sync is used.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Sets amount of delayed signal that is fed back into the delay line, defines how many
+    /// repetitions will be audible. Values close to 1.0 give infinite repetitions.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Sets tempo sync, `None` means that delay time in seconds will be used.
+    pub fn set_tempo_sync(&mut self, tempo_sync: Option<TempoSync>) {
+        self.tempo_sync = tempo_sync;
+    }
+
+    /// Returns current tempo sync.
+    pub fn tempo_sync(&self) -> Option<&TempoSync> {
+        self.tempo_sync.as_ref()
+    }
+
+    /// Returns actual delay time in seconds, taking tempo sync into account.
+    pub fn effective_time(&self) -> f32 {
+        self.tempo_sync
+            .as_ref()
+            .map_or(self.time, |sync| sync.time())
+            .clamp(0.0, MAX_DELAY_TIME)
+    }
+}
+
+impl EffectRenderTrait for Delay {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let len = ((self.effective_time() * SAMPLE_RATE as f32) as usize).max(1);
+        if len != self.samples.len() {
+            self.samples = vec![(0.0, 0.0); len];
+            self.pos = 0;
+        }
+
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let (delayed_left, delayed_right) = self.samples[self.pos];
+            self.samples[self.pos] = (
+                *left + delayed_left * self.feedback,
+                *right + delayed_right * self.feedback,
+            );
+            self.pos = (self.pos + 1) % len;
+
+            *left = gain * (*left * self.dry + delayed_left * self.wet);
+            *right = gain * (*right * self.dry + delayed_right * self.wet);
+        }
+    }
+}
+
+impl Deref for Delay {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Delay {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{
+            delay::{Delay, TempoSync},
+            BaseEffect, EffectRenderTrait,
+        },
+    };
+
+    fn impulse_response(delay: &mut Delay, len: usize) -> Vec<(f32, f32)> {
+        let mut buf = vec![(0.0, 0.0); len];
+        buf[0] = (1.0, 1.0);
+        delay.process(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_tempo_sync_time() {
+        let sync = TempoSync {
+            bpm: 120.0,
+            beats: 0.5,
+        };
+        assert_eq!(sync.time(), 0.25);
+
+        let mut delay = Delay::new(BaseEffect::default());
+        delay.set_time(1.0);
+        assert_eq!(delay.effective_time(), 1.0);
+        delay.set_tempo_sync(Some(sync));
+        assert_eq!(delay.effective_time(), 0.25);
+    }
+
+    #[test]
+    fn test_tempo_synced_echo_position() {
+        let mut delay = Delay::new(BaseEffect::default());
+        delay.set_dry(0.0);
+        delay.set_wet(1.0);
+        delay.set_feedback(0.5);
+        // Quarter note at 120 BPM is half of a second.
+        delay.set_tempo_sync(Some(TempoSync {
+            bpm: 120.0,
+            beats: 1.0,
+        }));
+
+        let period = SAMPLE_RATE as usize / 2;
+        let output = impulse_response(&mut delay, period * 2 + 1);
+
+        for (i, &(left, right)) in output.iter().enumerate() {
+            let expected = if i == period {
+                1.0
+            } else if i == period * 2 {
+                0.5
+            } else {
+                0.0
+            };
+            assert_eq!(left, expected);
+            assert_eq!(right, expected);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Distortion module.
+//!
+//! # Overview
+//!
+//! Waveshaper distortion amplifies input signal by drive and then passes it through a non-linear
+//! transfer function (shape), which adds harmonics to the signal. It could be used to emulate
+//! overdriven amplifiers, radios, megaphones and so on.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::distortion::{Distortion, DistortionShape};
+//! use fyrox_sound::effects::{BaseEffect, Effect};
+//!
+//! fn add_distortion(context: &mut SoundContext) {
+//!     let mut distortion = Distortion::new(BaseEffect::default());
+//!     distortion.set_shape(DistortionShape::HardClip);
+//!     distortion.set_drive(8.0);
+//!     context.state().add_effect(Effect::Distortion(distortion));
+//! }
+//! ```
+
+use crate::{
+    context::DistanceModel,
+    effects::{render_processed, BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    visitor::prelude::*,
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Transfer function of the distortion.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Inspect, Visit, AsRefStr, EnumString, EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum DistortionShape {
+    /// Smooth saturation (hyperbolic tangent), gives "warm" tube-like sound.
+    SoftClip = 0,
+    /// Cuts everything above 1.0, gives harsh sound with lots of high harmonics.
+    HardClip = 1,
+    /// Folds the signal back when it exceeds 1.0, gives metallic sound.
+    Foldback = 2,
+}
+
+impl Default for DistortionShape {
+    fn default() -> Self {
+        Self::SoftClip
+    }
+}
+
+impl DistortionShape {
+    fn apply(self, sample: f32) -> f32 {
+        match self {
+            DistortionShape::SoftClip => sample.tanh(),
+            DistortionShape::HardClip => sample.clamp(-1.0, 1.0),
+            DistortionShape::Foldback => {
+                // Triangle wave of the input keeps the signal in [-1; 1] range.
+                let folded = (sample - 1.0).rem_euclid(4.0);
+                (folded - 2.0).abs() - 1.0
+            }
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Distortion {
+    #[inspect(skip)]
+    base: BaseEffect,
+    shape: DistortionShape,
+    #[inspect(min_value = 1.0, max_value = 100.0, step = 0.1)]
+    drive: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    dry: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    wet: f32,
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Distortion {
+    /// Creates new soft clip distortion with drive of 4.0.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            shape: DistortionShape::SoftClip,
+            drive: 4.0,
+            dry: 0.0,
+            wet: 1.0,
+        }
+    }
+
+    /// Sets transfer function of the distortion.
+    pub fn set_shape(&mut self, shape: DistortionShape) {
+        self.shape = shape;
+    }
+
+    /// Returns transfer function of the distortion.
+    pub fn shape(&self) -> DistortionShape {
+        self.shape
+    }
+
+    /// Sets amplification of the signal before shaping, the higher the drive, the more harmonics
+    /// will be in output signal.
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive.max(1.0);
+    }
+
+    /// Returns drive.
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets how much of distorted signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Distortion {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            let shaped_left = self.shape.apply(*left * self.drive);
+            let shaped_right = self.shape.apply(*right * self.drive);
+            *left = gain * (*left * self.dry + shaped_left * self.wet);
+            *right = gain * (*right * self.dry + shaped_right * self.wet);
+        }
+    }
+}
+
+impl Deref for Distortion {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Distortion {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        distortion::{Distortion, DistortionShape},
+        BaseEffect, EffectRenderTrait,
+    };
+
+    fn distort(shape: DistortionShape, drive: f32, input: f32) -> (f32, f32) {
+        let mut distortion = Distortion::new(BaseEffect::default());
+        distortion.set_shape(shape);
+        distortion.set_drive(drive);
+        let mut buf = [(input, -input)];
+        distortion.process(&mut buf);
+        buf[0]
+    }
+
+    #[test]
+    fn test_soft_clip() {
+        let (left, right) = distort(DistortionShape::SoftClip, 4.0, 0.25);
+        assert!((left - 1.0f32.tanh()).abs() < 1.0e-6);
+        assert!((right + 1.0f32.tanh()).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn test_hard_clip() {
+        assert_eq!(distort(DistortionShape::HardClip, 4.0, 0.5), (1.0, -1.0));
+        assert_eq!(distort(DistortionShape::HardClip, 4.0, 0.125), (0.5, -0.5));
+    }
+
+    #[test]
+    fn test_foldback() {
+        // 1.5 exceeds 1.0 by 0.5, so it is folded back to 0.5.
+        assert_eq!(distort(DistortionShape::Foldback, 4.0, 0.375), (0.5, -0.5));
+        assert_eq!(distort(DistortionShape::Foldback, 4.0, 0.125), (0.5, -0.5));
+    }
+
+    #[test]
+    fn test_dry_wet_mix() {
+        let mut distortion = Distortion::new(BaseEffect::default());
+        distortion.set_shape(DistortionShape::HardClip);
+        distortion.set_drive(4.0);
+        distortion.set_dry(0.5);
+        distortion.set_wet(0.5);
+
+        let mut buf = [(0.5, 0.5)];
+        distortion.process(&mut buf);
+
+        assert_eq!(buf[0], (0.75, 0.75));
+    }
+}
@@ -0,0 +1,295 @@
+//! Parametric equalizer module.
+//!
+//! # Overview
+//!
+//! Equalizer is a chain of second order filters (bands), each band boosts or cuts some range of
+//! frequencies. It could be used to make a sound "brighter" or "darker", to remove rumble or to
+//! emulate a telephone line, for example.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::context::SoundContext;
+//! use fyrox_sound::effects::equalizer::{Equalizer, EqualizerBand, EqualizerBandKind};
+//! use fyrox_sound::effects::{BaseEffect, Effect};
+//!
+//! fn add_equalizer(context: &mut SoundContext) {
+//!     let equalizer = Equalizer::new(BaseEffect::default()).with_bands(vec![
+//!         EqualizerBand::new(EqualizerBandKind::HighPass, 80.0, 0.0, 0.707),
+//!         EqualizerBand::new(EqualizerBandKind::Peak, 2500.0, 3.0, 1.0),
+//!     ]);
+//!     context.state().add_effect(Effect::Equalizer(equalizer));
+//! }
+//! ```
+
+use crate::{
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::{Biquad, BiquadKind},
+    effects::{render_processed, BaseEffect, EffectRenderTrait},
+    listener::Listener,
+    source::SoundSource,
+};
+use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
+    pool::Pool,
+    visitor::prelude::*,
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Shape of a single band of the equalizer.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Inspect, Visit, AsRefStr, EnumString, EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum EqualizerBandKind {
+    /// Boosts or cuts frequencies around the frequency of the band.
+    Peak = 0,
+    /// Boosts or cuts frequencies below the frequency of the band.
+    LowShelf = 1,
+    /// Boosts or cuts frequencies above the frequency of the band.
+    HighShelf = 2,
+    /// Removes frequencies above the frequency of the band, gain is ignored.
+    LowPass = 3,
+    /// Removes frequencies below the frequency of the band, gain is ignored.
+    HighPass = 4,
+}
+
+impl Default for EqualizerBandKind {
+    fn default() -> Self {
+        Self::Peak
+    }
+}
+
+/// A single band of the equalizer.
+#[derive(Clone, Debug, PartialEq, Inspect, Visit)]
+pub struct EqualizerBand {
+    /// Shape of the band.
+    pub kind: EqualizerBandKind,
+    /// Center (or cutoff) frequency of the band in hertz.
+    #[inspect(min_value = 20.0, max_value = 20000.0, step = 10.0)]
+    pub frequency: f32,
+    /// Gain of the band in decibels.
+    #[inspect(min_value = -48.0, max_value = 48.0, step = 0.5)]
+    pub gain: f32,
+    /// Quality factor of the band, the higher it is, the narrower the band is.
+    #[inspect(min_value = 0.01, step = 0.05)]
+    pub quality: f32,
+}
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self {
+            kind: EqualizerBandKind::Peak,
+            frequency: 1000.0,
+            gain: 0.0,
+            quality: 0.707,
+        }
+    }
+}
+
+impl EqualizerBand {
+    /// Creates new band using given parameters. See fields docs for more info.
+    pub fn new(kind: EqualizerBandKind, frequency: f32, gain: f32, quality: f32) -> Self {
+        Self {
+            kind,
+            frequency,
+            gain,
+            quality,
+        }
+    }
+
+    fn make_filter(&self) -> Biquad {
+        let kind = match self.kind {
+            EqualizerBandKind::Peak => BiquadKind::Peak,
+            EqualizerBandKind::LowShelf => BiquadKind::LowShelf,
+            EqualizerBandKind::HighShelf => BiquadKind::HighShelf,
+            EqualizerBandKind::LowPass => BiquadKind::LowPass,
+            EqualizerBandKind::HighPass => BiquadKind::HighPass,
+        };
+        // Cookbook formulas use amplitude as square root of linear gain.
+        let amplitude = 10.0f32.powf(self.gain / 40.0);
+        let fc = (self.frequency / SAMPLE_RATE as f32).clamp(0.0, 0.49);
+        Biquad::new(kind, fc, amplitude, self.quality.max(0.01))
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, Inspect, Visit)]
+pub struct Equalizer {
+    #[inspect(skip)]
+    base: BaseEffect,
+    bands: Vec<EqualizerBand>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    filters: Vec<(Biquad, Biquad)>,
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl Equalizer {
+    /// Creates new equalizer without any bands.
+    pub fn new(base: BaseEffect) -> Self {
+        Self {
+            base,
+            bands: Default::default(),
+            filters: Default::default(),
+        }
+    }
+
+    /// Sets desired bands of the equalizer.
+    pub fn with_bands(mut self, bands: Vec<EqualizerBand>) -> Self {
+        self.set_bands(bands);
+        self
+    }
+
+    /// Sets new bands of the equalizer. Filters are re-tuned only for changed bands, so this
+    /// method could be used to automate parameters without clicks.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) {
+        self.filters.truncate(bands.len());
+        for (i, band) in bands.iter().enumerate() {
+            let filter = band.make_filter();
+            if let Some((left, right)) = self.filters.get_mut(i) {
+                if self.bands.get(i) != Some(band) {
+                    left.copy_coefficients(&filter);
+                    right.copy_coefficients(&filter);
+                }
+            } else {
+                self.filters.push((filter.clone(), filter));
+            }
+        }
+        self.bands = bands;
+    }
+
+    /// Returns current bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(
+        &mut self,
+        sources: &Pool<SoundSource>,
+        listener: &Listener,
+        distance_model: DistanceModel,
+        mix_buf: &mut [(f32, f32)],
+    ) {
+        render_processed(self, sources, listener, distance_model, mix_buf)
+    }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        // Filters are not serialized, restore them after deserialization.
+        if self.filters.len() != self.bands.len() {
+            self.filters = self
+                .bands
+                .iter()
+                .map(|band| {
+                    let filter = band.make_filter();
+                    (filter.clone(), filter)
+                })
+                .collect();
+        }
+
+        let gain = self.base.gain;
+        for (left, right) in buf.iter_mut() {
+            for (left_filter, right_filter) in self.filters.iter_mut() {
+                *left = left_filter.feed(*left);
+                *right = right_filter.feed(*right);
+            }
+            *left *= gain;
+            *right *= gain;
+        }
+    }
+}
+
+impl Deref for Equalizer {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Equalizer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+        BaseEffect, EffectRenderTrait,
+    };
+
+    // Feeds enough samples to let filters settle and returns peak amplitude of the rest.
+    fn peak_amplitude(equalizer: &mut Equalizer, signal: impl Fn(usize) -> f32) -> f32 {
+        let mut buf = (0..4096)
+            .map(|i| (signal(i), signal(i)))
+            .collect::<Vec<_>>();
+        equalizer.process(&mut buf);
+        buf[2048..]
+            .iter()
+            .fold(0.0f32, |peak, (left, _)| peak.max(left.abs()))
+    }
+
+    #[test]
+    fn test_equalizer_without_bands_passes_signal() {
+        let mut equalizer = Equalizer::new(BaseEffect::default());
+
+        let mut buf = vec![(0.25, -0.5); 16];
+        equalizer.process(&mut buf);
+
+        assert!(buf.iter().all(|&sample| sample == (0.25, -0.5)));
+    }
+
+    #[test]
+    fn test_equalizer_low_pass() {
+        let mut equalizer =
+            Equalizer::new(BaseEffect::default()).with_bands(vec![EqualizerBand::new(
+                EqualizerBandKind::LowPass,
+                1000.0,
+                0.0,
+                0.707,
+            )]);
+        // Constant signal passes through.
+        assert!((peak_amplitude(&mut equalizer, |_| 1.0) - 1.0).abs() < 1.0e-3);
+
+        let mut equalizer =
+            Equalizer::new(BaseEffect::default()).with_bands(vec![EqualizerBand::new(
+                EqualizerBandKind::LowPass,
+                1000.0,
+                0.0,
+                0.707,
+            )]);
+        // Signal at Nyquist frequency is removed.
+        let nyquist = |i: usize| if i % 2 == 0 { 1.0 } else { -1.0 };
+        assert!(peak_amplitude(&mut equalizer, nyquist) < 1.0e-2);
+    }
+
+    #[test]
+    fn test_equalizer_peak_boost() {
+        let frequency = 1000.0;
+        let sine = |i: usize| {
+            (2.0 * std::f32::consts::PI * frequency * i as f32 / crate::context::SAMPLE_RATE as f32)
+                .sin()
+        };
+
+        let mut equalizer =
+            Equalizer::new(BaseEffect::default()).with_bands(vec![EqualizerBand::new(
+                EqualizerBandKind::Peak,
+                frequency,
+                6.0,
+                1.0,
+            )]);
+
+        // +6 dB at the center frequency doubles amplitude.
+        assert!((peak_amplitude(&mut equalizer, sine) - 1.995).abs() < 0.02);
+    }
+}
@@ -7,22 +7,36 @@
 use crate::{
     context::DistanceModel,
     dsp::filters::Biquad,
-    effects::reverb::Reverb,
+    effects::{
+        chorus::Chorus,
+        compressor::{Compressor, Limiter},
+        delay::Delay,
+        distortion::Distortion,
+        equalizer::Equalizer,
+        reverb::Reverb,
+    },
     listener::Listener,
     source::{SoundSource, Status},
 };
 use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
     math,
     pool::{Handle, Pool},
     visitor::{Visit, VisitResult, Visitor},
 };
 use std::ops::{Deref, DerefMut};
 
+pub mod chorus;
+pub mod compressor;
+pub mod delay;
+pub mod distortion;
+pub mod equalizer;
 pub mod reverb;
 
 /// Stub effect that does nothing.
-#[derive(Default, Debug, Clone, Visit)]
+#[derive(Default, Debug, Clone, Inspect, Visit)]
 pub struct StubEffect {
+    #[inspect(skip)]
     base: BaseEffect,
 }
 
@@ -35,6 +49,8 @@ impl EffectRenderTrait for StubEffect {
         _mix_buf: &mut [(f32, f32)],
     ) {
     }
+
+    fn process(&mut self, _buf: &mut [(f32, f32)]) {}
 }
 
 impl Deref for StubEffect {
@@ -58,6 +74,18 @@ pub enum Effect {
     Stub(StubEffect),
     /// Reverberation effect. See corresponding module for more info.
     Reverb(Reverb),
+    /// Parametric equalizer. See corresponding module for more info.
+    Equalizer(Equalizer),
+    /// Dynamic range compressor. See corresponding module for more info.
+    Compressor(Compressor),
+    /// Brickwall limiter. See corresponding module for more info.
+    Limiter(Limiter),
+    /// Feedback delay (echo). See corresponding module for more info.
+    Delay(Delay),
+    /// Chorus or flanger. See corresponding module for more info.
+    Chorus(Chorus),
+    /// Waveshaper distortion. See corresponding module for more info.
+    Distortion(Distortion),
 }
 
 impl Default for Effect {
@@ -74,6 +102,38 @@ pub(in crate) trait EffectRenderTrait {
         distance_model: DistanceModel,
         mix_buf: &mut [(f32, f32)],
     );
+
+    /// Processes given samples in place, it is used when the effect is a part of effects chain
+    /// of a bus (see [`crate::bus::AudioBus`]). Inputs of the effect are ignored in this case.
+    fn process(&mut self, buf: &mut [(f32, f32)]);
+}
+
+/// Renders inputs of an effect, passes them through [`EffectRenderTrait::process`] and adds the
+/// result to the mixing buffer. Suitable for every effect which output depends only on its input.
+pub(in crate) fn render_processed<E>(
+    effect: &mut E,
+    sources: &Pool<SoundSource>,
+    listener: &Listener,
+    distance_model: DistanceModel,
+    mix_buf: &mut [(f32, f32)],
+) where
+    E: EffectRenderTrait + DerefMut<Target = BaseEffect>,
+{
+    BaseEffect::render(
+        effect,
+        sources,
+        listener,
+        distance_model,
+        mix_buf.len(),
+    );
+
+    let mut frame_samples = std::mem::take(&mut effect.frame_samples);
+    effect.process(&mut frame_samples);
+    for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter()) {
+        *out_left += left;
+        *out_right += right;
+    }
+    effect.frame_samples = frame_samples;
 }
 
 /// Base effect for all other kinds of effects. It contains set of inputs (direct
@@ -258,6 +318,12 @@ macro_rules! static_dispatch {
         match $self {
             Effect::Stub(v) => v.$func($($args),*),
             Effect::Reverb(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Limiter(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::Chorus(v) => v.$func($($args),*),
+            Effect::Distortion(v) => v.$func($($args),*),
         }
     };
 }
@@ -272,6 +338,16 @@ impl EffectRenderTrait for Effect {
     ) {
         static_dispatch!(self, render, sources, listener, distance_model, mix_buf)
     }
+
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        static_dispatch!(self, process, buf)
+    }
+}
+
+impl Inspect for Effect {
+    fn properties(&self) -> Vec<PropertyInfo<'_>> {
+        static_dispatch!(self, properties,)
+    }
 }
 
 impl Deref for Effect {
@@ -281,6 +357,12 @@ impl Deref for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Equalizer(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Limiter(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Distortion(v) => v,
         }
     }
 }
@@ -290,6 +372,12 @@ impl DerefMut for Effect {
         match self {
             Effect::Stub(v) => v,
             Effect::Reverb(v) => v,
+            Effect::Equalizer(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Limiter(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Distortion(v) => v,
         }
     }
 }
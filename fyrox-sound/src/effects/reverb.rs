@@ -33,6 +33,7 @@ use crate::{
     source::SoundSource,
 };
 use fyrox_core::{
+    inspect::{Inspect, PropertyInfo},
     pool::Pool,
     visitor::{Visit, VisitResult, Visitor},
 };
@@ -140,12 +141,17 @@ impl ChannelReverb {
 }
 
 /// See module docs.
-#[derive(Debug, Clone, Visit)]
+#[derive(Debug, Clone, Inspect, Visit)]
 pub struct Reverb {
+    #[inspect(skip)]
     base: BaseEffect,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
     dry: f32,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
     wet: f32,
+    #[inspect(skip)]
     left: ChannelReverb,
+    #[inspect(skip)]
     right: ChannelReverb,
 }
 
@@ -237,6 +243,24 @@ impl Reverb {
     }
 }
 
+impl Reverb {
+    fn feed(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let wet1 = self.wet;
+        let wet2 = 1.0 - self.wet;
+
+        let mid = (left + right) * 0.5;
+        let input = mid * Self::GAIN;
+
+        let processed_left = self.left.feed(input);
+        let processed_right = self.right.feed(input);
+
+        (
+            self.gain * (processed_left * wet1 + processed_right * wet2 + self.dry * left),
+            self.gain * (processed_right * wet1 + processed_left * wet2 + self.dry * right),
+        )
+    }
+}
+
 impl EffectRenderTrait for Reverb {
     fn render(
         &mut self,
@@ -248,22 +272,21 @@ impl EffectRenderTrait for Reverb {
         self.base
             .render(sources, listener, distance_model, mix_buf.len());
 
-        let wet1 = self.wet;
-        let wet2 = 1.0 - self.wet;
-
-        for ((out_left, out_right), &(left, right)) in
-            mix_buf.iter_mut().zip(self.base.frame_samples.iter())
+        let frame_samples = std::mem::take(&mut self.base.frame_samples);
+        for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(frame_samples.iter())
         {
-            let mid = (left + right) * 0.5;
-            let input = mid * Self::GAIN;
-
-            let processed_left = self.left.feed(input);
-            let processed_right = self.right.feed(input);
+            let (processed_left, processed_right) = self.feed(left, right);
+            *out_left += processed_left;
+            *out_right += processed_right;
+        }
+        self.base.frame_samples = frame_samples;
+    }
 
-            *out_left +=
-                self.gain * (processed_left * wet1 + processed_right * wet2 + self.dry * left);
-            *out_right +=
-                self.gain * (processed_right * wet1 + processed_left * wet2 + self.dry * right);
+    fn process(&mut self, buf: &mut [(f32, f32)]) {
+        for (left, right) in buf.iter_mut() {
+            let (processed_left, processed_right) = self.feed(*left, *right);
+            *left = processed_left;
+            *right = processed_right;
         }
     }
 }
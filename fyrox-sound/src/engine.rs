@@ -4,7 +4,11 @@
 //!
 //! Sound engine manages contexts, feeds output device with data.
 
-use crate::{context::SoundContext, device};
+use crate::{
+    context::SoundContext,
+    device::{self, Backend, DeviceConfig},
+    error::SoundError,
+};
 use fyrox_core::visitor::{Visit, VisitResult, Visitor};
 use std::sync::{Arc, Mutex};
 
@@ -13,6 +17,8 @@ use std::sync::{Arc, Mutex};
 pub struct SoundEngine {
     contexts: Vec<SoundContext>,
     master_gain: f32,
+    backend: Option<Backend>,
+    device_errors: Vec<(Backend, SoundError)>,
 }
 
 impl SoundEngine {
@@ -20,15 +26,26 @@ impl SoundEngine {
     /// the same time, but you shouldn't do this because you can create multiple contexts which
     /// should cover 99% of use cases.
     pub fn new() -> Arc<Mutex<Self>> {
+        Self::with_device(Default::default())
+    }
+
+    /// Creates new instance of a sound engine, which uses an output device with given
+    /// configuration. If the device cannot be opened, the engine falls back to other available
+    /// backends and to the dummy device as the last resort, see [`device`] module docs for more
+    /// info. Use [`Self::backend`] to check which backend is used and [`Self::device_errors`] to
+    /// find out why other backends have failed.
+    pub fn with_device(config: DeviceConfig) -> Arc<Mutex<Self>> {
         let engine = Arc::new(Mutex::new(Self {
             contexts: Default::default(),
             master_gain: 1.0,
+            backend: None,
+            device_errors: Default::default(),
         }));
 
-        // Run the default output device. Internally it creates separate thread, so we have
+        // Run the output device. Internally it creates separate thread, so we have
         // to share sound engine instance with it, this is the only reason why it is wrapped
         // in Arc<Mutex<>>
-        device::run_device(4 * SoundContext::SAMPLES_PER_CHANNEL as u32, {
+        let (backend, device_errors) = device::run_device(config, {
             let state = engine.clone();
             move |buf| {
                 if let Ok(mut state) = state.lock() {
@@ -37,6 +54,11 @@ impl SoundEngine {
             }
         });
 
+        if let Ok(mut state) = engine.lock() {
+            state.backend = Some(backend);
+            state.device_errors = device_errors;
+        }
+
         engine
     }
 
@@ -46,9 +68,23 @@ impl SoundEngine {
         Arc::new(Mutex::new(Self {
             contexts: Default::default(),
             master_gain: 1.0,
+            backend: None,
+            device_errors: Default::default(),
         }))
     }
 
+    /// Returns a backend of the output device, `None` if the engine was created via
+    /// [`Self::without_device`].
+    pub fn backend(&self) -> Option<Backend> {
+        self.backend
+    }
+
+    /// Returns errors of backends that failed to open before the engine has settled on
+    /// [`Self::backend`], the list is empty if the first backend was opened successfully.
+    pub fn device_errors(&self) -> &[(Backend, SoundError)] {
+        &self.device_errors
+    }
+
     /// Adds new context to the engine. Each context must be added to the engine to emit
     /// sounds.
     pub fn add_context(&mut self, context: SoundContext) {
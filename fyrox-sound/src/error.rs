@@ -73,6 +73,16 @@ impl From<lewton::VorbisError> for SoundError {
     }
 }
 
+impl From<hound::Error> for SoundError {
+    fn from(e: hound::Error) -> Self {
+        match e {
+            hound::Error::IoError(e) => SoundError::Io(e),
+            hound::Error::Unsupported => SoundError::UnsupportedFormat,
+            _ => SoundError::DecoderError(DecoderError::Wav),
+        }
+    }
+}
+
 impl Display for SoundError {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self {
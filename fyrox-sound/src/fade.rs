@@ -0,0 +1,267 @@
+//! Gain fades module.
+//!
+//! # Overview
+//!
+//! Fade smoothly changes gain envelope of a sound source from its current value to a target
+//! value during some time. Envelope is an additional gain multiplier of a source, it does not
+//! change [`crate::source::SoundSource::gain`], so fades can be used together with usual gain
+//! control. Fades are processed sample by sample, they can start at exact time of context's
+//! render clock (see [`crate::context::State::sample_clock`]).
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     fade::{Fade, FadeCurve},
+//!     pool::Handle,
+//!     source::SoundSource,
+//! };
+//! use std::time::Duration;
+//!
+//! fn fade_out(context: &SoundContext, source: Handle<SoundSource>) {
+//!     let mut state = context.state();
+//!     // Start fading in one second and stop the source when it is done.
+//!     let start_time = state.clock_after(Duration::from_secs(1));
+//!     state.source_mut(source).fade(
+//!         Fade::new(0.0, Duration::from_secs(2))
+//!             .with_curve(FadeCurve::EqualPower)
+//!             .with_start_time(start_time)
+//!             .with_stop_when_done(true),
+//!     );
+//! }
+//! ```
+
+use crate::context::duration_to_samples;
+use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+/// Curve defines how gain changes during a fade.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FadeCurve {
+    /// Gain changes linearly. Linear crossfade has a noticeable loudness dip in the middle.
+    Linear,
+
+    /// Sine/cosine curve, keeps total power constant during a crossfade of two uncorrelated
+    /// sounds (music tracks for example). This is the best choice for most crossfades.
+    EqualPower,
+
+    /// Smooth start and smooth end (smoothstep).
+    SCurve,
+
+    /// Gain changes slowly at the beginning of a fade in and quickly at the beginning of a
+    /// fade out, this sounds natural because loudness perception is logarithmic.
+    Exponential,
+}
+
+impl Default for FadeCurve {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl FadeCurve {
+    /// Returns gain at normalized time `t` (in `[0; 1]` range) of a fade from `from` to `to`.
+    pub fn evaluate(self, from: f32, to: f32, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        let rising = to >= from;
+        let k = match self {
+            FadeCurve::Linear => t,
+            FadeCurve::EqualPower => {
+                if rising {
+                    (t * FRAC_PI_2).sin()
+                } else {
+                    1.0 - (t * FRAC_PI_2).cos()
+                }
+            }
+            FadeCurve::SCurve => t * t * (3.0 - 2.0 * t),
+            FadeCurve::Exponential => {
+                if rising {
+                    t * t
+                } else {
+                    1.0 - (1.0 - t) * (1.0 - t)
+                }
+            }
+        };
+        from + (to - from) * k
+    }
+}
+
+/// Fade of gain envelope of a sound source. See module docs for more info.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fade {
+    /// Value of gain envelope at the end of the fade.
+    pub target_gain: f32,
+
+    /// Duration of the fade.
+    pub duration: Duration,
+
+    /// Shape of the fade.
+    pub curve: FadeCurve,
+
+    /// Time (in samples of context's render clock) at which the fade starts. `None` means that
+    /// the fade starts with the next rendered sample of a source.
+    pub start_time: Option<u64>,
+
+    /// Whether a source should be stopped when the fade is done or not.
+    pub stop_when_done: bool,
+}
+
+impl Fade {
+    /// Creates new linear fade to given gain, which starts immediately.
+    pub fn new(target_gain: f32, duration: Duration) -> Self {
+        Self {
+            target_gain,
+            duration,
+            curve: Default::default(),
+            start_time: None,
+            stop_when_done: false,
+        }
+    }
+
+    /// Sets desired curve of the fade.
+    pub fn with_curve(mut self, curve: FadeCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Sets start time (in samples of context's render clock) of the fade.
+    pub fn with_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    /// Sets whether a source should be stopped when the fade is done or not.
+    pub fn with_stop_when_done(mut self, stop_when_done: bool) -> Self {
+        self.stop_when_done = stop_when_done;
+        self
+    }
+}
+
+/// Fade that is being applied to a source.
+#[derive(Clone, Debug)]
+pub(in crate) struct ActiveFade {
+    fade: Fade,
+    // Envelope gain at the moment when the fade has started.
+    start_gain: Option<f32>,
+    length: u64,
+}
+
+/// Result of processing of a single sample of an envelope.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(in crate) enum FadeStatus {
+    Active,
+    Done,
+    DoneStop,
+}
+
+impl ActiveFade {
+    pub(in crate) fn new(fade: Fade) -> Self {
+        Self {
+            length: duration_to_samples(fade.duration).max(1),
+            fade,
+            start_gain: None,
+        }
+    }
+
+    pub(in crate) fn target_gain(&self) -> f32 {
+        self.fade.target_gain
+    }
+
+    pub(in crate) fn resolve_start_time(&mut self, time: u64) {
+        self.fade.start_time.get_or_insert(time);
+    }
+
+    /// Calculates envelope gain at given time.
+    pub(in crate) fn process(&mut self, envelope_gain: &mut f32, time: u64) -> FadeStatus {
+        let start_time = *self.fade.start_time.get_or_insert(time);
+        if time < start_time {
+            return FadeStatus::Active;
+        }
+
+        let start_gain = *self.start_gain.get_or_insert(*envelope_gain);
+        let t = (time - start_time + 1) as f32 / self.length as f32;
+        *envelope_gain = self
+            .fade
+            .curve
+            .evaluate(start_gain, self.fade.target_gain, t);
+
+        if t < 1.0 {
+            FadeStatus::Active
+        } else if self.fade.stop_when_done {
+            FadeStatus::DoneStop
+        } else {
+            FadeStatus::Done
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fade::{ActiveFade, Fade, FadeCurve, FadeStatus};
+    use std::time::Duration;
+
+    #[test]
+    fn test_fade_curves() {
+        for curve in [
+            FadeCurve::Linear,
+            FadeCurve::EqualPower,
+            FadeCurve::SCurve,
+            FadeCurve::Exponential,
+        ] {
+            for (from, to) in [(0.0, 1.0), (1.0, 0.0), (0.5, 0.25)] {
+                assert!((curve.evaluate(from, to, 0.0) - from).abs() < 1.0e-6);
+                assert!((curve.evaluate(from, to, 1.0) - to).abs() < 1.0e-6);
+
+                // Every curve is monotonic.
+                let mut prev = from;
+                for i in 1..=10 {
+                    let value = curve.evaluate(from, to, i as f32 / 10.0);
+                    if to > from {
+                        assert!(value >= prev);
+                    } else {
+                        assert!(value <= prev);
+                    }
+                    prev = value;
+                }
+            }
+        }
+
+        // Equal power crossfade keeps total power constant.
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let fade_in = FadeCurve::EqualPower.evaluate(0.0, 1.0, t);
+            let fade_out = FadeCurve::EqualPower.evaluate(1.0, 0.0, t);
+            assert!((fade_in * fade_in + fade_out * fade_out - 1.0).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn test_active_fade() {
+        // 100 samples at 44100 Hz.
+        let duration = Duration::from_secs_f64(100.0 / 44100.0);
+        let mut fade = ActiveFade::new(
+            Fade::new(0.0, duration)
+                .with_start_time(50)
+                .with_stop_when_done(true),
+        );
+
+        // Envelope is untouched until the fade starts.
+        let mut gain = 0.8;
+        assert_eq!(fade.process(&mut gain, 10), FadeStatus::Active);
+        assert_eq!(gain, 0.8);
+
+        assert_eq!(fade.process(&mut gain, 99), FadeStatus::Active);
+        assert!((gain - 0.4).abs() < 1.0e-6);
+
+        assert_eq!(fade.process(&mut gain, 149), FadeStatus::DoneStop);
+        assert_eq!(gain, 0.0);
+
+        // Fade without explicit start time starts at the first processed sample.
+        let mut fade = ActiveFade::new(Fade::new(1.0, duration));
+        let mut gain = 0.0;
+        assert_eq!(fade.process(&mut gain, 1000), FadeStatus::Active);
+        assert!((gain - 0.01).abs() < 1.0e-6);
+        assert_eq!(fade.process(&mut gain, 1099), FadeStatus::Done);
+        assert_eq!(gain, 1.0);
+    }
+}
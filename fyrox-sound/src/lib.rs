@@ -4,9 +4,15 @@
 //!
 //! - Generic and spatial sounds.
 //! - WAV and OGG/Vorbis formats support.
+//! - FLAC, MP3 and OGG/Opus formats support via `flac`, `mp3` and `ogg_opus` features.
 //! - Streaming.
 //! - Head-related transfer function support ([HRTF](https://en.wikipedia.org/wiki/Head-related_transfer_function)).
 //! - Reverb effect.
+//! - Deterministic offline rendering to WAV.
+//! - Sample-accurate playback scheduling, fades and music playlists with crossfades.
+//! - Voice limiting with priorities and virtualization of inaudible sources.
+//! - Output device enumeration and selection, PulseAudio and JACK backends via `pulseaudio` and
+//!   `jack` features.
 //!
 //! ## Examples
 //!
@@ -67,13 +73,17 @@
 extern crate winapi;
 
 pub mod buffer;
+pub mod bus;
 pub mod context;
-
+pub mod device;
 pub mod dsp;
 pub mod effects;
 pub mod engine;
 pub mod error;
+pub mod fade;
 pub mod listener;
+pub mod offline;
+pub mod playlist;
 pub mod renderer;
 pub mod source;
 
@@ -85,4 +95,3 @@ pub use fyrox_core::pool;
 pub use hrtf;
 
 mod decoder;
-mod device;
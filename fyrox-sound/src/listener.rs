@@ -14,6 +14,8 @@ use fyrox_core::visitor::{Visit, VisitResult, Visitor};
 pub struct Listener {
     basis: Matrix3<f32>,
     position: Vector3<f32>,
+    #[visit(optional)] // Backward compatibility
+    velocity: Vector3<f32>,
 }
 
 impl Default for Listener {
@@ -27,6 +29,7 @@ impl Listener {
         Self {
             basis: Matrix3::identity(),
             position: Vector3::new(0.0, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
         }
     }
 
@@ -83,6 +86,17 @@ impl Listener {
         self.position
     }
 
+    /// Sets current velocity in world space (in units per second). It is used to calculate pitch
+    /// shift caused by the Doppler effect.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) {
+        self.velocity = velocity;
+    }
+
+    /// Returns velocity of listener.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
     /// Returns up axis from basis.
     pub fn up_axis(&self) -> Vector3<f32> {
         self.basis.up()
@@ -0,0 +1,335 @@
+//! Offline rendering module.
+//!
+//! ## Overview
+//!
+//! Offline renderer mixes one or more sound contexts without any output device, as fast as
+//! possible. It could be used to export audio (for example for cutscenes) or to write regression
+//! tests for mixing, spatialization and effects by comparing rendered samples with "golden"
+//! files.
+//!
+//! Rendering is fully deterministic - the same contexts rendered for the same amount of samples
+//! will always produce exactly the same output. Make sure that all sound buffers are loaded before
+//! rendering, otherwise sources that use them will be silent.
+//!
+//! ## Sample rate
+//!
+//! Contexts are always mixed at [`SAMPLE_RATE`], if a renderer has different sample rate, mixed
+//! samples are resampled using linear interpolation. Output at [`SAMPLE_RATE`] is sample-accurate,
+//! no resampling is done in this case.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     offline::{OfflineRenderer, WavSampleFormat},
+//! };
+//! use std::time::Duration;
+//!
+//! let context = SoundContext::new();
+//!
+//! // Add some sources to the context here.
+//!
+//! let mut renderer = OfflineRenderer::new(48000).with_context(context);
+//! renderer
+//!     .render_to_wav_file("output.wav", Duration::from_secs(3), WavSampleFormat::Int16)
+//!     .unwrap();
+//! ```
+
+use crate::{
+    context::{SoundContext, SAMPLE_RATE},
+    dsp::resampler::Resampler,
+    error::SoundError,
+};
+use std::{
+    io::{Read, Seek, Write},
+    path::Path,
+    time::Duration,
+};
+
+/// Format of samples in a WAV file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WavSampleFormat {
+    /// 16-bit signed integer samples. Most compatible format.
+    Int16,
+    /// 24-bit signed integer samples.
+    Int24,
+    /// 32-bit floating point samples. Samples are stored as is, without clamping.
+    Float32,
+}
+
+/// See module docs.
+pub struct OfflineRenderer {
+    contexts: Vec<SoundContext>,
+    master_gain: f32,
+    sample_rate: u32,
+    resampler: Resampler,
+    mix_buffer: Vec<(f32, f32)>,
+    context_buffer: Vec<(f32, f32)>,
+}
+
+impl Default for OfflineRenderer {
+    fn default() -> Self {
+        Self::new(SAMPLE_RATE)
+    }
+}
+
+impl OfflineRenderer {
+    /// Creates new offline renderer with given output sample rate. Zero sample rate is replaced
+    /// with [`SAMPLE_RATE`].
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = if sample_rate == 0 {
+            SAMPLE_RATE
+        } else {
+            sample_rate
+        };
+        Self {
+            contexts: Default::default(),
+            master_gain: 1.0,
+            sample_rate,
+            resampler: Resampler::new(sample_rate),
+            mix_buffer: vec![(0.0, 0.0); SoundContext::SAMPLES_PER_CHANNEL],
+            context_buffer: vec![(0.0, 0.0); SoundContext::SAMPLES_PER_CHANNEL],
+        }
+    }
+
+    /// Adds new context to the renderer.
+    pub fn with_context(mut self, context: SoundContext) -> Self {
+        self.add_context(context);
+        self
+    }
+
+    /// Adds new context to the renderer. Contexts must not be registered in a sound engine that
+    /// runs an output device, otherwise they will be rendered twice as fast.
+    pub fn add_context(&mut self, context: SoundContext) {
+        self.contexts.push(context);
+    }
+
+    /// Removes a context from the renderer.
+    pub fn remove_context(&mut self, context: &SoundContext) {
+        if let Some(position) = self.contexts.iter().position(|c| c == context) {
+            self.contexts.remove(position);
+        }
+    }
+
+    /// Returns a reference to context container.
+    pub fn contexts(&self) -> &[SoundContext] {
+        &self.contexts
+    }
+
+    /// Sets global gain of all contexts.
+    pub fn set_master_gain(&mut self, master_gain: f32) {
+        self.master_gain = master_gain;
+    }
+
+    /// Returns global gain of all contexts.
+    pub fn master_gain(&self) -> f32 {
+        self.master_gain
+    }
+
+    /// Returns output sample rate of the renderer.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns amount of samples per channel that will be rendered for given duration.
+    pub fn duration_to_sample_count(&self, duration: Duration) -> usize {
+        (duration.as_secs_f64() * self.sample_rate as f64).round() as usize
+    }
+
+    /// Renders exactly `count` samples per channel. Consecutive calls continue rendering from
+    /// the point where previous call has stopped.
+    ///
+    /// ## Deadlocks
+    ///
+    /// This method internally locks added sound contexts so it must be called when all the
+    /// contexts are unlocked or you'll get a deadlock.
+    pub fn render_samples(&mut self, count: usize) -> Vec<(f32, f32)> {
+        let mut output = vec![(0.0, 0.0); count];
+
+        let Self {
+            contexts,
+            master_gain,
+            resampler,
+            mix_buffer,
+            context_buffer,
+            ..
+        } = self;
+
+        resampler.process(&mut output, |pending| {
+            mix_buffer.fill((0.0, 0.0));
+            for context in contexts.iter() {
+                // Each context is rendered separately, because context applies its master gain
+                // to the whole buffer.
+                context_buffer.fill((0.0, 0.0));
+                context.state().render(*master_gain, context_buffer);
+                for ((out_left, out_right), &(left, right)) in
+                    mix_buffer.iter_mut().zip(context_buffer.iter())
+                {
+                    *out_left += left;
+                    *out_right += right;
+                }
+            }
+            pending.extend(mix_buffer.iter().copied());
+        });
+
+        output
+    }
+
+    /// Renders given duration of sound. See [`Self::render_samples`] for more info.
+    pub fn render(&mut self, duration: Duration) -> Vec<(f32, f32)> {
+        let count = self.duration_to_sample_count(duration);
+        self.render_samples(count)
+    }
+
+    /// Renders given duration of sound into a WAV file.
+    pub fn render_to_wav_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        duration: Duration,
+        format: WavSampleFormat,
+    ) -> Result<(), SoundError> {
+        let samples = self.render(duration);
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write_wav(file, &samples, self.sample_rate, format)
+    }
+}
+
+/// Writes stereo samples into a WAV stream. Integer formats are clamped to `[-1; 1]` range.
+pub fn write_wav<W: Write + Seek>(
+    writer: W,
+    samples: &[(f32, f32)],
+    sample_rate: u32,
+    format: WavSampleFormat,
+) -> Result<(), SoundError> {
+    let (bits_per_sample, sample_format) = match format {
+        WavSampleFormat::Int16 => (16, hound::SampleFormat::Int),
+        WavSampleFormat::Int24 => (24, hound::SampleFormat::Int),
+        WavSampleFormat::Float32 => (32, hound::SampleFormat::Float),
+    };
+
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate,
+        bits_per_sample,
+        sample_format,
+    };
+    let mut wav_writer = hound::WavWriter::new(writer, spec)?;
+
+    let scale = ((1u64 << (bits_per_sample - 1)) - 1) as f32;
+    for &(left, right) in samples {
+        for sample in [left, right] {
+            match format {
+                WavSampleFormat::Int16 => {
+                    wav_writer.write_sample((sample.clamp(-1.0, 1.0) * scale) as i16)?
+                }
+                WavSampleFormat::Int24 => {
+                    wav_writer.write_sample((sample.clamp(-1.0, 1.0) * scale) as i32)?
+                }
+                WavSampleFormat::Float32 => wav_writer.write_sample(sample)?,
+            }
+        }
+    }
+
+    wav_writer.finalize()?;
+
+    Ok(())
+}
+
+/// Reads samples of a WAV stream, could be used to load "golden" files. Mono streams are
+/// converted to stereo, streams with more than two channels are not supported. Returns sample
+/// rate and samples.
+pub fn read_wav<R: Read>(reader: R) -> Result<(u32, Vec<(f32, f32)>), SoundError> {
+    let mut wav_reader = hound::WavReader::new(reader)?;
+    let spec = wav_reader.spec();
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => wav_reader.samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            wav_reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+
+    let samples = match spec.channels {
+        1 => samples.into_iter().map(|s| (s, s)).collect(),
+        2 => samples.chunks_exact(2).map(|s| (s[0], s[1])).collect(),
+        _ => return Err(SoundError::UnsupportedFormat),
+    };
+
+    Ok((spec.sample_rate, samples))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource},
+        context::SoundContext,
+        offline::{read_wav, write_wav, OfflineRenderer, WavSampleFormat},
+        source::{SoundSourceBuilder, Status},
+    };
+    use std::{io::Cursor, time::Duration};
+
+    fn make_renderer(sample_rate: u32) -> OfflineRenderer {
+        let samples = (0..4410)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 44100.0).sin())
+            .collect();
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples,
+        })
+        .unwrap();
+
+        let context = SoundContext::new();
+        context.state().add_source(
+            SoundSourceBuilder::new()
+                .with_buffer(buffer)
+                .with_looping(true)
+                .with_panning(0.3)
+                .with_pitch(1.25)
+                .with_status(Status::Playing)
+                .build()
+                .unwrap(),
+        );
+
+        OfflineRenderer::new(sample_rate).with_context(context)
+    }
+
+    #[test]
+    fn test_offline_rendering_is_deterministic() {
+        for sample_rate in [44100, 48000] {
+            let first = make_renderer(sample_rate).render(Duration::from_millis(250));
+            let second = make_renderer(sample_rate).render(Duration::from_millis(250));
+
+            assert_eq!(first.len(), sample_rate as usize / 4);
+            assert!(first
+                .iter()
+                .any(|&(left, right)| left != 0.0 && right != 0.0));
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_wav_round_trip() {
+        let samples = make_renderer(48000).render(Duration::from_millis(100));
+
+        let mut data = Cursor::new(Vec::new());
+        write_wav(&mut data, &samples, 48000, WavSampleFormat::Float32).unwrap();
+        let (sample_rate, read_samples) = read_wav(Cursor::new(data.into_inner())).unwrap();
+        assert_eq!(sample_rate, 48000);
+        assert_eq!(read_samples, samples);
+
+        let mut data = Cursor::new(Vec::new());
+        write_wav(&mut data, &samples, 48000, WavSampleFormat::Int16).unwrap();
+        let (_, read_samples) = read_wav(Cursor::new(data.into_inner())).unwrap();
+        assert_eq!(read_samples.len(), samples.len());
+        for (&(left, right), &(read_left, read_right)) in samples.iter().zip(read_samples.iter()) {
+            assert!((left.clamp(-1.0, 1.0) - read_left).abs() < 1.0e-4);
+            assert!((right.clamp(-1.0, 1.0) - read_right).abs() < 1.0e-4);
+        }
+    }
+}
@@ -0,0 +1,643 @@
+//! Music playlist module.
+//!
+//! # Overview
+//!
+//! Playlist is a sequencer for music tracks, it crossfades between tracks, plays intro sections
+//! before looped sections and synchronizes transitions with beats or bars of current track.
+//! Playlist creates sound sources in a context by itself and uses scheduled playback and fades
+//! of sources (see [`crate::fade`]), so all transitions are sample-accurate.
+//!
+//! Playlist must be updated periodically (once per frame for example) by calling
+//! [`Playlist::update`], it is needed to advance to the next track automatically.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     buffer::SoundBufferResource,
+//!     context::SoundContext,
+//!     playlist::{MusicTrack, Playlist, Transition, TransitionSync},
+//! };
+//! use std::time::Duration;
+//!
+//! fn play_music(context: &SoundContext, intro: SoundBufferResource, main: SoundBufferResource) {
+//!     let mut playlist = Playlist::new().with_track(
+//!         MusicTrack::new(main)
+//!             .with_intro(intro)
+//!             .with_looping(true)
+//!             .with_tempo(120.0, 4),
+//!     );
+//!
+//!     let mut state = context.state();
+//!     playlist
+//!         .play(
+//!             &mut state,
+//!             0,
+//!             &Transition::new(Duration::from_secs(2)).with_sync(TransitionSync::NextBar),
+//!         )
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! # Notes
+//!
+//! It is recommended to use streaming buffers for music. Keep in mind that only one source can
+//! use a streaming buffer at a time, so a track with streaming buffers can't be crossfaded with
+//! itself.
+
+use crate::{
+    buffer::SoundBufferResource,
+    bus::AudioBus,
+    context::{duration_to_samples, State, SAMPLE_RATE},
+    error::SoundError,
+    fade::{Fade, FadeCurve},
+    pool::Handle,
+    source::{SoundSource, SoundSourceBuilder, Status},
+};
+use fyrox_resource::ResourceState;
+use std::time::Duration;
+
+/// Music track of a playlist. Track consists of an optional intro section which is played once,
+/// and main section which can be looped.
+#[derive(Clone, Debug)]
+pub struct MusicTrack {
+    /// Name of the track.
+    pub name: String,
+
+    /// Intro section, it is played once before main section.
+    pub intro: Option<SoundBufferResource>,
+
+    /// Main section of the track.
+    pub main: SoundBufferResource,
+
+    /// Whether main section should be looped or not.
+    pub looping: bool,
+
+    /// Gain of the track.
+    pub gain: f32,
+
+    /// Tempo of the track in beats per minute. Zero means that the track has no tempo, in this
+    /// case transitions from the track are never synchronized.
+    pub bpm: f32,
+
+    /// Amount of beats in a bar.
+    pub beats_per_bar: u32,
+}
+
+impl MusicTrack {
+    /// Creates new non-looping track without intro section.
+    pub fn new(main: SoundBufferResource) -> Self {
+        Self {
+            name: Default::default(),
+            intro: None,
+            main,
+            looping: false,
+            gain: 1.0,
+            bpm: 0.0,
+            beats_per_bar: 4,
+        }
+    }
+
+    /// Sets desired name of the track.
+    pub fn with_name<N: AsRef<str>>(mut self, name: N) -> Self {
+        self.name = name.as_ref().to_owned();
+        self
+    }
+
+    /// Sets desired intro section of the track.
+    pub fn with_intro(mut self, intro: SoundBufferResource) -> Self {
+        self.intro = Some(intro);
+        self
+    }
+
+    /// Sets whether main section should be looped or not.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Sets desired gain of the track.
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Sets tempo and time signature of the track.
+    pub fn with_tempo(mut self, bpm: f32, beats_per_bar: u32) -> Self {
+        self.bpm = bpm.max(0.0);
+        self.beats_per_bar = beats_per_bar.max(1);
+        self
+    }
+
+    fn beat_length(&self) -> Option<f64> {
+        if self.bpm > 0.0 {
+            Some(60.0 / self.bpm as f64 * SAMPLE_RATE as f64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Defines when a transition to other track starts.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransitionSync {
+    /// Transition starts immediately.
+    Immediate,
+
+    /// Transition starts at the next beat of current track.
+    NextBeat,
+
+    /// Transition starts at the next bar of current track.
+    NextBar,
+}
+
+impl Default for TransitionSync {
+    fn default() -> Self {
+        Self::Immediate
+    }
+}
+
+/// Transition between two tracks of a playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transition {
+    /// Duration of crossfade between tracks. Zero means that tracks are switched instantly.
+    pub crossfade: Duration,
+
+    /// Curve of crossfade.
+    pub curve: FadeCurve,
+
+    /// Defines when the transition starts.
+    pub sync: TransitionSync,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2))
+    }
+}
+
+impl Transition {
+    /// Creates new equal-power crossfade which starts immediately.
+    pub fn new(crossfade: Duration) -> Self {
+        Self {
+            crossfade,
+            curve: FadeCurve::EqualPower,
+            sync: Default::default(),
+        }
+    }
+
+    /// Sets desired crossfade curve.
+    pub fn with_curve(mut self, curve: FadeCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Sets desired synchronization of the transition.
+    pub fn with_sync(mut self, sync: TransitionSync) -> Self {
+        self.sync = sync;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ActiveTrack {
+    index: usize,
+    intro: Handle<SoundSource>,
+    main: Handle<SoundSource>,
+    // Time at which the track has started, it is the origin of beat grid.
+    start_time: u64,
+    // Main section waits until intro section ends, it happens when duration of intro is unknown.
+    main_pending: bool,
+}
+
+/// See module docs.
+#[derive(Clone, Debug)]
+pub struct Playlist {
+    tracks: Vec<MusicTrack>,
+    bus: Handle<AudioBus>,
+    transition: Transition,
+    auto_advance: bool,
+    current: Option<ActiveTrack>,
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Playlist {
+    /// Creates new empty playlist. Tracks are advanced automatically using default transition.
+    pub fn new() -> Self {
+        Self {
+            tracks: Default::default(),
+            bus: Default::default(),
+            transition: Default::default(),
+            auto_advance: true,
+            current: None,
+        }
+    }
+
+    /// Adds new track to the playlist.
+    pub fn with_track(mut self, track: MusicTrack) -> Self {
+        self.add_track(track);
+        self
+    }
+
+    /// Adds new track to the playlist.
+    pub fn add_track(&mut self, track: MusicTrack) {
+        self.tracks.push(track);
+    }
+
+    /// Returns a reference to tracks container.
+    pub fn tracks(&self) -> &[MusicTrack] {
+        &self.tracks
+    }
+
+    /// Sets a bus to which all sources of the playlist will be attached.
+    pub fn set_bus(&mut self, bus: Handle<AudioBus>) {
+        self.bus = bus;
+    }
+
+    /// Returns current output bus of the playlist.
+    pub fn bus(&self) -> Handle<AudioBus> {
+        self.bus
+    }
+
+    /// Sets transition that will be used when the playlist advances to the next track
+    /// automatically.
+    pub fn set_default_transition(&mut self, transition: Transition) {
+        self.transition = transition;
+    }
+
+    /// Returns transition that is used when the playlist advances to the next track automatically.
+    pub fn default_transition(&self) -> &Transition {
+        &self.transition
+    }
+
+    /// Enables or disables automatic advance to the next track, when non-looping track ends.
+    /// Playlist wraps around when last track ends.
+    pub fn set_auto_advance(&mut self, auto_advance: bool) {
+        self.auto_advance = auto_advance;
+    }
+
+    /// Returns true if automatic advance to the next track is enabled.
+    pub fn is_auto_advance(&self) -> bool {
+        self.auto_advance
+    }
+
+    /// Returns index of current track, if any.
+    pub fn current_track(&self) -> Option<usize> {
+        self.current.as_ref().map(|current| current.index)
+    }
+
+    /// Returns time (in samples of context's render clock) at which a transition with given
+    /// synchronization will start.
+    pub fn transition_time(&self, state: &State, sync: TransitionSync) -> u64 {
+        let now = state.sample_clock();
+
+        let current = match self.current.as_ref() {
+            Some(current) => current,
+            None => return now,
+        };
+        let track = &self.tracks[current.index];
+        let period = match (sync, track.beat_length()) {
+            (TransitionSync::NextBeat, Some(beat_length)) => beat_length,
+            (TransitionSync::NextBar, Some(beat_length)) => {
+                beat_length * track.beats_per_bar as f64
+            }
+            _ => return now,
+        };
+
+        let elapsed = now.saturating_sub(current.start_time) as f64;
+        current.start_time + ((elapsed / period).ceil() * period).round() as u64
+    }
+
+    /// Starts playing a track with given index. Current track (if any) fades out, and new track
+    /// fades in.
+    pub fn play(
+        &mut self,
+        state: &mut State,
+        index: usize,
+        transition: &Transition,
+    ) -> Result<(), SoundError> {
+        if index >= self.tracks.len() {
+            return Ok(());
+        }
+
+        let time = self.transition_time(state, transition.sync);
+
+        self.fade_out_current(state, time, transition);
+
+        let track = &self.tracks[index];
+        let (intro, main, main_pending) = if let Some(intro_buffer) = track.intro.as_ref() {
+            let mut intro = self.make_source(intro_buffer, track.gain, false)?;
+            intro.schedule_play(time).fade(
+                Fade::new(1.0, transition.crossfade)
+                    .with_curve(transition.curve)
+                    .with_start_time(time),
+            );
+            intro.set_envelope_gain(0.0);
+
+            let intro_duration = buffer_duration(intro_buffer);
+            let intro = state.add_source(intro);
+
+            if let Some(intro_duration) = intro_duration {
+                // Duration of intro is known, so main section can be scheduled right after it.
+                let mut main = self.make_source(&track.main, track.gain, track.looping)?;
+                main.schedule_play(time + duration_to_samples(intro_duration));
+                (intro, state.add_source(main), false)
+            } else {
+                (intro, Handle::NONE, true)
+            }
+        } else {
+            let mut main = self.make_source(&track.main, track.gain, track.looping)?;
+            main.schedule_play(time).fade(
+                Fade::new(1.0, transition.crossfade)
+                    .with_curve(transition.curve)
+                    .with_start_time(time),
+            );
+            main.set_envelope_gain(0.0);
+            (Handle::NONE, state.add_source(main), false)
+        };
+
+        self.current = Some(ActiveTrack {
+            index,
+            intro,
+            main,
+            start_time: time,
+            main_pending,
+        });
+
+        Ok(())
+    }
+
+    /// Starts playing next track, wraps around when current track is the last one.
+    pub fn next(&mut self, state: &mut State, transition: &Transition) -> Result<(), SoundError> {
+        if self.tracks.is_empty() {
+            return Ok(());
+        }
+        let index = self
+            .current_track()
+            .map_or(0, |index| (index + 1) % self.tracks.len());
+        self.play(state, index, transition)
+    }
+
+    /// Fades out current track and stops the playlist.
+    pub fn stop(&mut self, state: &mut State, transition: &Transition) {
+        let time = self.transition_time(state, transition.sync);
+        self.fade_out_current(state, time, transition);
+    }
+
+    /// Updates the playlist. Starts main sections of tracks with intro of unknown duration and
+    /// advances to the next track if needed.
+    pub fn update(&mut self, state: &mut State) -> Result<(), SoundError> {
+        let current = match self.current.as_ref() {
+            Some(current) => current.clone(),
+            None => return Ok(()),
+        };
+        let track = &self.tracks[current.index];
+
+        if current.main_pending {
+            if !is_playing(state, current.intro) {
+                let mut main = self.make_source(&track.main, track.gain, track.looping)?;
+                main.play();
+                let main = state.add_source(main);
+                if let Some(current) = self.current.as_mut() {
+                    current.main = main;
+                    current.main_pending = false;
+                }
+            }
+            return Ok(());
+        }
+
+        if self.auto_advance && !track.looping {
+            let main = match state.try_get_source_mut(current.main) {
+                Some(main) => main,
+                None => {
+                    // Main section has ended already.
+                    let transition = self.transition.clone();
+                    return self.next(state, &transition);
+                }
+            };
+
+            let remaining = match main.status() {
+                Status::Playing => {
+                    let playback_time = main.playback_time();
+                    main.buffer()
+                        .as_ref()
+                        .and_then(buffer_duration)
+                        .map(|duration| duration.saturating_sub(playback_time))
+                }
+                Status::Stopped if main.scheduled_play_time().is_none() => Some(Duration::ZERO),
+                _ => None,
+            };
+
+            if remaining.map_or(false, |remaining| remaining <= self.transition.crossfade) {
+                // Transition to the next track must start right now to finish crossfade in time.
+                let transition = self.transition.clone().with_sync(TransitionSync::Immediate);
+                return self.next(state, &transition);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fade_out_current(&mut self, state: &mut State, time: u64, transition: &Transition) {
+        if let Some(current) = self.current.take() {
+            for handle in [current.intro, current.main] {
+                if let Some(source) = state.try_get_source_mut(handle) {
+                    let starts_before_transition = source.status() == Status::Playing
+                        || source
+                            .scheduled_play_time()
+                            .map_or(false, |play_time| play_time < time);
+                    if starts_before_transition {
+                        source.fade(
+                            Fade::new(0.0, transition.crossfade)
+                                .with_curve(transition.curve)
+                                .with_start_time(time)
+                                .with_stop_when_done(true),
+                        );
+                    } else {
+                        // The source won't start before transition, it is not needed anymore.
+                        let _ = source.stop();
+                    }
+                }
+            }
+        }
+    }
+
+    fn make_source(
+        &self,
+        buffer: &SoundBufferResource,
+        gain: f32,
+        looping: bool,
+    ) -> Result<SoundSource, SoundError> {
+        SoundSourceBuilder::new()
+            .with_buffer(buffer.clone())
+            .with_gain(gain)
+            .with_spatial_blend_factor(0.0)
+            .with_looping(looping)
+            .with_play_once(true)
+            .with_bus(self.bus)
+            .build()
+    }
+}
+
+fn is_playing(state: &State, handle: Handle<SoundSource>) -> bool {
+    state.sources().try_borrow(handle).map_or(false, |source| {
+        source.status() == Status::Playing || source.scheduled_play_time().is_some()
+    })
+}
+
+fn buffer_duration(buffer: &SoundBufferResource) -> Option<Duration> {
+    if let ResourceState::Ok(ref buffer) = *buffer.state() {
+        buffer.total_duration()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource},
+        context::{State, SAMPLE_RATE},
+        playlist::{MusicTrack, Playlist, Transition, TransitionSync},
+        source::Status,
+    };
+    use std::time::Duration;
+
+    fn make_buffer(length: usize) -> SoundBufferResource {
+        SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![0.5; length],
+        })
+        .unwrap()
+    }
+
+    fn render(state: &mut State, length: usize) {
+        let mut buf = vec![(0.0, 0.0); length];
+        state.render(1.0, &mut buf);
+    }
+
+    fn playing_sources(state: &State) -> usize {
+        state
+            .sources()
+            .iter()
+            .filter(|source| source.status() == Status::Playing)
+            .count()
+    }
+
+    #[test]
+    fn test_transition_sync() {
+        let mut state = State::default();
+        // 120 BPM - beat is 22050 samples long, bar is 88200 samples long.
+        let mut playlist = Playlist::new()
+            .with_track(
+                MusicTrack::new(make_buffer(44100))
+                    .with_looping(true)
+                    .with_tempo(120.0, 4),
+            )
+            .with_track(MusicTrack::new(make_buffer(44100)).with_looping(true));
+
+        assert_eq!(playlist.transition_time(&state, TransitionSync::NextBar), 0);
+
+        playlist
+            .play(&mut state, 0, &Transition::new(Duration::ZERO))
+            .unwrap();
+        assert_eq!(playlist.current_track(), Some(0));
+
+        render(&mut state, 1000);
+        assert_eq!(playing_sources(&state), 1);
+        assert_eq!(
+            playlist.transition_time(&state, TransitionSync::Immediate),
+            1000
+        );
+        assert_eq!(
+            playlist.transition_time(&state, TransitionSync::NextBeat),
+            22050
+        );
+        assert_eq!(
+            playlist.transition_time(&state, TransitionSync::NextBar),
+            88200
+        );
+
+        // Next track is scheduled at the next bar and crossfaded with current track.
+        playlist
+            .play(
+                &mut state,
+                1,
+                &Transition::new(Duration::from_secs(1)).with_sync(TransitionSync::NextBar),
+            )
+            .unwrap();
+        assert_eq!(playlist.current_track(), Some(1));
+        assert_eq!(
+            state
+                .sources()
+                .iter()
+                .filter_map(|source| source.scheduled_play_time())
+                .collect::<Vec<_>>(),
+            vec![88200]
+        );
+        assert_eq!(
+            state
+                .sources()
+                .iter()
+                .filter(|source| source.is_fading())
+                .count(),
+            2
+        );
+
+        // Both tracks play during crossfade, first one stops when crossfade is done.
+        render(&mut state, 88200 - 1000);
+        assert_eq!(playing_sources(&state), 1);
+        render(&mut state, 1000);
+        assert_eq!(playing_sources(&state), 2);
+        render(&mut state, 44100);
+        assert_eq!(playing_sources(&state), 1);
+    }
+
+    #[test]
+    fn test_intro_and_auto_advance() {
+        let mut state = State::default();
+        let mut playlist = Playlist::new()
+            .with_track(MusicTrack::new(make_buffer(4410)).with_intro(make_buffer(2205)))
+            .with_track(MusicTrack::new(make_buffer(4410)));
+        playlist.set_default_transition(Transition::new(Duration::ZERO));
+
+        playlist
+            .play(&mut state, 0, &Transition::new(Duration::ZERO))
+            .unwrap();
+
+        // Main section is scheduled right after intro.
+        assert_eq!(
+            state
+                .sources()
+                .iter()
+                .filter_map(|source| source.scheduled_play_time())
+                .collect::<Vec<_>>(),
+            vec![0, 2205]
+        );
+
+        render(&mut state, 1000);
+        playlist.update(&mut state).unwrap();
+        assert_eq!(playlist.current_track(), Some(0));
+
+        // Playlist advances to the next track when main section ends. Source stops in a frame
+        // that follows its last sample, so render a bit more.
+        render(&mut state, 2205 + 4410 - 1000 + 100);
+        assert_eq!(playing_sources(&state), 0);
+        playlist.update(&mut state).unwrap();
+        assert_eq!(playlist.current_track(), Some(1));
+
+        render(&mut state, 1000);
+        assert_eq!(playing_sources(&state), 1);
+
+        // And wraps around at the end.
+        render(&mut state, 4410);
+        playlist.update(&mut state).unwrap();
+        assert_eq!(playlist.current_track(), Some(0));
+    }
+}
@@ -31,17 +31,22 @@
 
 use crate::{
     buffer::{streaming::StreamingBuffer, SoundBufferResource, SoundBufferState},
-    context::DistanceModel,
+    bus::AudioBus,
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::{Biquad, BiquadKind},
     error::SoundError,
+    fade::{ActiveFade, Fade, FadeCurve, FadeStatus},
     listener::Listener,
 };
 use fyrox_core::{
     algebra::Vector3,
     inspect::{Inspect, PropertyInfo},
+    math::lerpf,
+    pool::Handle,
     visitor::{Visit, VisitResult, Visitor},
 };
 use fyrox_resource::ResourceState;
-use std::time::Duration;
+use std::{f32::consts::FRAC_1_SQRT_2, time::Duration};
 
 /// Status (state) of sound source.
 #[derive(Eq, PartialEq, Copy, Clone, Debug, Inspect, Visit)]
@@ -116,10 +121,16 @@ pub struct SoundSource {
     #[inspect(min_value = 0.0, step = 0.05)]
     radius: f32,
     position: Vector3<f32>,
+    #[visit(optional)] // Backward compatibility
+    velocity: Vector3<f32>,
     #[inspect(min_value = 0.0, step = 0.05)]
     max_distance: f32,
     #[inspect(min_value = 0.0, step = 0.05)]
     rolloff_factor: f32,
+    #[visit(optional)] // Backward compatibility
+    bus: Handle<AudioBus>,
+    #[visit(optional)] // Backward compatibility
+    priority: u32,
     // Some data that needed for iterative overlap-save convolution.
     #[inspect(skip)]
     #[visit(skip)]
@@ -133,6 +144,49 @@ pub struct SoundSource {
     #[inspect(skip)]
     #[visit(skip)]
     pub(in crate) prev_distance_gain: Option<f32>,
+    // Current pitch multiplier caused by the Doppler effect. It is changed smoothly sample by
+    // sample from its value at the previous frame to the new one, sudden changes of playback
+    // speed would produce audible "zipper" noise.
+    #[inspect(skip)]
+    #[visit(skip)]
+    doppler_shift: f64,
+    #[inspect(skip)]
+    #[visit(skip)]
+    doppler_shift_step: f64,
+    // Gain and low-pass filter of the direct path between the source and the listener, they're
+    // used to simulate occlusion by obstacles.
+    #[inspect(skip)]
+    #[visit(skip)]
+    occlusion_gain: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    occlusion_cutoff_frequency: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    occlusion_filter: Option<(Biquad, Biquad)>,
+    // Scheduled playback start and stop in samples of context's render clock.
+    #[inspect(skip)]
+    #[visit(skip)]
+    scheduled_play: Option<u64>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    scheduled_stop: Option<u64>,
+    // Additional gain multiplier which is controlled by fades.
+    #[inspect(skip)]
+    #[visit(skip)]
+    envelope_gain: f32,
+    #[inspect(skip)]
+    #[visit(skip)]
+    fade: Option<ActiveFade>,
+    // Virtual source is not mixed, but its playback position is still advancing.
+    #[inspect(skip)]
+    #[visit(skip)]
+    is_virtual: bool,
+    // Whether the read position must be synced with playback position before rendering. It is
+    // set when a virtual source has advanced its playback position.
+    #[inspect(skip)]
+    #[visit(skip)]
+    needs_seek: bool,
 }
 
 impl Default for SoundSource {
@@ -156,17 +210,37 @@ impl Default for SoundSource {
             prev_buffer_sample: (0.0, 0.0),
             radius: 1.0,
             position: Vector3::new(0.0, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
+            bus: Default::default(),
+            priority: 0,
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
             prev_distance_gain: None,
+            doppler_shift: 1.0,
+            doppler_shift_step: 0.0,
+            occlusion_gain: 1.0,
+            occlusion_cutoff_frequency: Self::MAX_OCCLUSION_CUTOFF_FREQUENCY,
+            occlusion_filter: None,
+            scheduled_play: None,
+            scheduled_stop: None,
+            envelope_gain: 1.0,
+            fade: None,
+            is_virtual: false,
+            needs_seek: false,
         }
     }
 }
 
 impl SoundSource {
+    /// Maximum pitch multiplier that can be caused by the Doppler effect.
+    pub const MAX_DOPPLER_SHIFT: f32 = 4.0;
+
+    /// Cut-off frequency (in hertz) of occlusion low-pass filter at which the filter is disabled.
+    pub const MAX_OCCLUSION_CUTOFF_FREQUENCY: f32 = 20000.0;
+
     /// Sets new name of the sound source.
     pub fn set_name<N: AsRef<str>>(&mut self, name: N) {
         self.name = name.as_ref().to_owned();
@@ -325,10 +399,19 @@ impl SoundSource {
         self.pitch
     }
 
-    /// Stops sound source. Automatically rewinds streaming buffers.
+    /// Stops sound source. Automatically rewinds streaming buffers. Scheduled playback and fades
+    /// are cancelled and gain envelope is reset to 1.0.
     pub fn stop(&mut self) -> Result<(), SoundError> {
         self.status = Status::Stopped;
 
+        self.scheduled_play = None;
+        self.scheduled_stop = None;
+        self.fade = None;
+        self.envelope_gain = 1.0;
+
+        self.is_virtual = false;
+        self.needs_seek = false;
+
         self.buf_read_pos = 0.0;
         self.playback_pos = 0.0;
 
@@ -341,6 +424,86 @@ impl SoundSource {
 
         Ok(())
     }
+
+    /// Schedules playback start at given time (in samples of context's render clock, see
+    /// [`crate::context::State::sample_clock`]). Playback starts exactly at the given sample,
+    /// if the time is in the past, playback starts immediately. Has no effect on a source that
+    /// is already playing.
+    pub fn schedule_play(&mut self, time: u64) -> &mut Self {
+        self.scheduled_play = Some(time);
+        self
+    }
+
+    /// Schedules stop at given time (in samples of context's render clock, see
+    /// [`crate::context::State::sample_clock`]).
+    pub fn schedule_stop(&mut self, time: u64) -> &mut Self {
+        self.scheduled_stop = Some(time);
+        self
+    }
+
+    /// Cancels scheduled playback start and stop.
+    pub fn cancel_schedule(&mut self) -> &mut Self {
+        self.scheduled_play = None;
+        self.scheduled_stop = None;
+        self
+    }
+
+    /// Returns time of scheduled playback start if any.
+    pub fn scheduled_play_time(&self) -> Option<u64> {
+        self.scheduled_play
+    }
+
+    /// Returns time of scheduled stop if any.
+    pub fn scheduled_stop_time(&self) -> Option<u64> {
+        self.scheduled_stop
+    }
+
+    /// Starts a fade of gain envelope, previous fade (if any) is replaced. See [`crate::fade`]
+    /// module docs for more info.
+    pub fn fade(&mut self, fade: Fade) -> &mut Self {
+        self.fade = Some(ActiveFade::new(fade));
+        self
+    }
+
+    /// Sets gain envelope to zero and fades it to 1.0 when the source starts playing. Does not
+    /// change status of the source.
+    pub fn fade_in(&mut self, duration: Duration, curve: FadeCurve) -> &mut Self {
+        self.envelope_gain = 0.0;
+        self.fade(Fade::new(1.0, duration).with_curve(curve))
+    }
+
+    /// Fades gain envelope to zero and stops the source.
+    pub fn fade_out(&mut self, duration: Duration, curve: FadeCurve) -> &mut Self {
+        self.fade(
+            Fade::new(0.0, duration)
+                .with_curve(curve)
+                .with_stop_when_done(true),
+        )
+    }
+
+    /// Cancels current fade, gain envelope stays at its current value.
+    pub fn cancel_fade(&mut self) -> &mut Self {
+        self.fade = None;
+        self
+    }
+
+    /// Returns true if the source has a fade in progress.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// Sets current value of gain envelope. Gain envelope is a multiplier of source's gain that
+    /// is controlled by fades.
+    pub fn set_envelope_gain(&mut self, gain: f32) -> &mut Self {
+        self.envelope_gain = gain.max(0.0);
+        self
+    }
+
+    /// Returns current value of gain envelope.
+    pub fn envelope_gain(&self) -> f32 {
+        self.envelope_gain
+    }
+
     /// Sets position of source in world space.
     pub fn set_position(&mut self, position: Vector3<f32>) -> &mut Self {
         self.position = position;
@@ -352,6 +515,40 @@ impl SoundSource {
         self.position
     }
 
+    /// Sets velocity of source in world space (in units per second). Velocity is used only to
+    /// calculate pitch shift caused by the Doppler effect, it does not move the source.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) -> &mut Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Returns velocity of source.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
+    /// Sets gain and cut-off frequency (in hertz) of low-pass filter that is applied to the
+    /// source to simulate occlusion - when there are obstacles between the source and the
+    /// listener, the sound becomes quieter and muffled. The gain is applied only to spatial
+    /// part of the sound (see [`Self::set_spatial_blend`]), the filter is applied to the whole
+    /// sound. Cut-off frequency equal or higher than [`Self::MAX_OCCLUSION_CUTOFF_FREQUENCY`]
+    /// disables the filter.
+    pub fn set_occlusion(&mut self, gain: f32, cutoff_frequency: f32) -> &mut Self {
+        self.occlusion_gain = gain.clamp(0.0, 1.0);
+        self.occlusion_cutoff_frequency = cutoff_frequency.max(1.0);
+        self
+    }
+
+    /// Returns gain of occlusion. See [`Self::set_occlusion`].
+    pub fn occlusion_gain(&self) -> f32 {
+        self.occlusion_gain
+    }
+
+    /// Returns cut-off frequency of occlusion low-pass filter. See [`Self::set_occlusion`].
+    pub fn occlusion_cutoff_frequency(&self) -> f32 {
+        self.occlusion_cutoff_frequency
+    }
+
     /// Sets radius of imaginable sphere around source in which no distance attenuation is applied.
     pub fn set_radius(&mut self, radius: f32) -> &mut Self {
         self.radius = radius;
@@ -390,6 +587,70 @@ impl SoundSource {
         self.max_distance
     }
 
+    /// Sets a bus to which the source will be routed. Invalid handle means that the source is
+    /// routed to the master bus. See [`crate::bus`] docs for more info.
+    pub fn set_bus(&mut self, bus: Handle<AudioBus>) -> &mut Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Returns a bus to which the source is routed.
+    pub fn bus(&self) -> Handle<AudioBus> {
+        self.bus
+    }
+
+    /// Sets priority of the source. When there are more playing sources than context can mix
+    /// (see [`crate::context::State::set_max_active_voices`]), sources with higher priority
+    /// are mixed first, sources with the same priority are sorted by their audibility. Default
+    /// priority is 0.
+    pub fn set_priority(&mut self, priority: u32) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns priority of the source.
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    /// Returns true if the source is virtual. Virtual source is not mixed, but its playback
+    /// time is still advancing, so it will continue playing from the right position once it
+    /// becomes active again. Sources are virtualized by the context when they're inaudible or
+    /// when there are too many playing sources.
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
+    pub(in crate) fn set_virtual(&mut self, is_virtual: bool) {
+        if is_virtual && !self.is_virtual {
+            // Make sure that the source will smoothly fade in when it becomes active again.
+            self.last_left_gain = Some(0.0);
+            self.last_right_gain = Some(0.0);
+            self.prev_distance_gain = Some(0.0);
+        }
+        self.is_virtual = is_virtual;
+    }
+
+    /// Estimates how loud the source is for given listener, it takes into account gain, gain
+    /// envelope, distance attenuation and occlusion. Panning and effects are ignored.
+    pub(in crate) fn calculate_audibility(
+        &self,
+        listener: &Listener,
+        distance_model: DistanceModel,
+    ) -> f32 {
+        let distance_gain = lerpf(
+            1.0,
+            self.calculate_distance_gain(listener, distance_model) * self.occlusion_gain,
+            self.spatial_blend,
+        );
+        // Source that fades in must not be virtualized just because its envelope starts from
+        // silence, otherwise the beginning of the fade would be lost.
+        let envelope_gain = self.fade.as_ref().map_or(self.envelope_gain, |fade| {
+            fade.target_gain().max(self.envelope_gain)
+        });
+        (distance_gain * self.gain * envelope_gain).abs()
+    }
+
     // Distance models were taken from OpenAL Specification because it looks like they're
     // standard in industry and there is no need to reinvent it.
     // https://www.openal.org/documentation/openal-1.1-specification.pdf
@@ -424,6 +685,38 @@ impl SoundSource {
             .dot(&listener.ear_axis())
     }
 
+    // Doppler shift formula was taken from OpenAL Specification as well. Velocities are clamped
+    // to speed of sound, so the sound won't be played backwards for supersonic sources.
+    pub(in crate) fn calculate_doppler_shift(
+        &self,
+        listener: &Listener,
+        doppler_factor: f32,
+        speed_of_sound: f32,
+    ) -> f32 {
+        if doppler_factor <= 0.0 || speed_of_sound <= 0.0 {
+            return 1.0;
+        }
+
+        let source_to_listener = listener.position() - self.position;
+        let distance = source_to_listener.norm();
+        if distance <= f32::EPSILON {
+            return 1.0;
+        }
+
+        let max_speed = speed_of_sound / doppler_factor;
+        let listener_speed = (listener.velocity().dot(&source_to_listener) / distance)
+            .max(-max_speed)
+            .min(max_speed);
+        let source_speed = (self.velocity.dot(&source_to_listener) / distance)
+            .max(-max_speed)
+            .min(max_speed);
+
+        let shift = (speed_of_sound - doppler_factor * listener_speed)
+            / (speed_of_sound - doppler_factor * source_speed).max(f32::EPSILON);
+
+        lerpf(1.0, shift.min(Self::MAX_DOPPLER_SHIFT), self.spatial_blend)
+    }
+
     pub(in crate) fn calculate_sampling_vector(&self, listener: &Listener) -> Vector3<f32> {
         let to_self = self.position - listener.position();
 
@@ -446,14 +739,24 @@ impl SoundSource {
 
     /// Sets playback duration.
     pub fn set_playback_time(&mut self, time: Duration) {
+        if let Some(buffer) = self.buffer.as_ref() {
+            let sample_rate = buffer.data_ref().sample_rate as f64;
+            self.set_playback_pos(time.as_secs_f64() * sample_rate);
+        }
+    }
+
+    // Sets playback position in samples. Unlike `set_playback_time` it does not lose precision
+    // on conversion to `Duration`.
+    fn set_playback_pos(&mut self, playback_pos: f64) {
         if let Some(buffer) = self.buffer.as_ref() {
             let mut buffer = buffer.data_ref();
+            let sample_rate = buffer.sample_rate as f64;
             if let SoundBufferState::Streaming(ref mut streaming) = *buffer {
                 // Make sure decoder is at right position.
-                streaming.time_seek(time);
+                streaming.time_seek(Duration::from_secs_f64(playback_pos / sample_rate));
             }
             // Set absolute position first.
-            self.playback_pos = time.as_secs_f64() * buffer.sample_rate as f64;
+            self.playback_pos = playback_pos;
             // Then adjust buffer read position.
             self.buf_read_pos = match *buffer {
                 SoundBufferState::Streaming(ref mut streaming) => {
@@ -471,23 +774,105 @@ impl SoundSource {
         }
     }
 
-    pub(in crate) fn render(&mut self, amount: usize) {
-        if self.frame_samples.capacity() < amount {
-            self.frame_samples = Vec::with_capacity(amount);
+    pub(in crate) fn render(&mut self, amount: usize, doppler_shift: f32, clock: u64) {
+        // Change doppler shift linearly during the frame.
+        let doppler_shift = doppler_shift as f64;
+        self.doppler_shift_step = (doppler_shift - self.doppler_shift) / amount.max(1) as f64;
+
+        if self.needs_seek {
+            self.needs_seek = false;
+            self.set_playback_pos(self.playback_pos);
         }
 
-        self.frame_samples.clear();
+        let start = self.begin_frame(amount, clock);
 
         if let Some(buffer) = self.buffer.clone() {
             let mut state = buffer.state();
             if let ResourceState::Ok(ref mut buffer) = *state {
                 if self.status == Status::Playing && !buffer.is_empty() {
-                    self.render_playing(buffer, amount);
+                    self.render_playing(buffer, amount - start);
                 }
             }
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_envelope(clock, start);
+
+        self.apply_occlusion_filter();
+
+        // Prevent accumulation of errors.
+        self.doppler_shift = doppler_shift;
+        self.doppler_shift_step = 0.0;
+    }
+
+    // Advances playback of a virtual source without rendering any samples. Scheduled playback,
+    // fades and scheduled stops are processed as usual.
+    pub(in crate) fn render_virtual(&mut self, amount: usize, clock: u64) {
+        let start = self.begin_frame(amount, clock);
+
+        if self.status == Status::Playing {
+            self.skip_playing(amount - start);
+        }
+        self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_envelope(clock, start);
+    }
+
+    // Clears frame samples and starts scheduled playback, returns an index of the first sample
+    // that should be played.
+    fn begin_frame(&mut self, amount: usize, clock: u64) -> usize {
+        if self.frame_samples.capacity() < amount {
+            self.frame_samples = Vec::with_capacity(amount);
+        }
+
+        self.frame_samples.clear();
+
+        // Scheduled playback starts in the middle of the frame, so fill the beginning with silence.
+        if let Some(time) = self.scheduled_play {
+            if time < clock + amount as u64 {
+                self.scheduled_play = None;
+                if self.status != Status::Playing {
+                    self.status = Status::Playing;
+                    let offset = time.saturating_sub(clock) as usize;
+                    self.frame_samples.resize(offset, (0.0, 0.0));
+                }
+            }
+        }
+
+        self.frame_samples.len()
+    }
+
+    // Moves playback position as if `amount` samples were rendered.
+    fn skip_playing(&mut self, amount: usize) {
+        let length = match self.buffer.as_ref() {
+            Some(buffer) => match *buffer.state() {
+                // Length of generic buffer is known exactly, do not lose precision on
+                // conversion to `Duration`.
+                ResourceState::Ok(SoundBufferState::Generic(ref generic)) => {
+                    Some((generic.samples().len() / generic.channel_count()) as f64)
+                }
+                ResourceState::Ok(SoundBufferState::Streaming(ref streaming)) => streaming
+                    .duration()
+                    .map(|duration| duration.as_secs_f64() * streaming.sample_rate() as f64),
+                _ => return,
+            },
+            None => return,
+        };
+
+        self.playback_pos +=
+            amount as f64 * self.pitch * self.resampling_multiplier * self.doppler_shift;
+        self.needs_seek = true;
+
+        if let Some(length) = length {
+            if self.playback_pos >= length {
+                if self.looping && length > 0.0 {
+                    self.playback_pos %= length;
+                } else {
+                    let _ = self.stop();
+                }
+            }
+        }
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBufferState, amount: usize) {
@@ -531,8 +916,8 @@ impl SoundSource {
         buffer: &mut SoundBufferState,
         mut amount: usize,
     ) -> usize {
-        let step = self.pitch * self.resampling_multiplier;
-        if step == 1.0 {
+        let step = self.pitch * self.resampling_multiplier * self.doppler_shift;
+        if step == 1.0 && self.doppler_shift_step == 0.0 {
             if self.buf_read_pos < 0.0 {
                 // This can theoretically happen if we change pitch on the fly.
                 self.frame_samples.push(self.prev_buffer_sample);
@@ -558,16 +943,19 @@ impl SoundSource {
             self.playback_pos += rendered as f64;
             rendered
         } else {
-            self.render_until_block_end_resample(buffer, amount, step)
+            let step_delta = self.pitch * self.resampling_multiplier * self.doppler_shift_step;
+            self.render_until_block_end_resample(buffer, amount, step, step_delta)
         }
     }
 
-    // Does linear resampling while rendering until the end of the block.
+    // Does linear resampling while rendering until the end of the block. Step is changed by
+    // `step_delta` after each sample.
     fn render_until_block_end_resample(
         &mut self,
         buffer: &mut SoundBufferState,
         amount: usize,
-        step: f64,
+        mut step: f64,
+        step_delta: f64,
     ) -> usize {
         let mut rendered = 0;
 
@@ -586,6 +974,7 @@ impl SoundSource {
             self.frame_samples.push((l, r));
             self.buf_read_pos += step;
             self.playback_pos += step;
+            step += step_delta;
             rendered += 1;
         }
 
@@ -595,7 +984,8 @@ impl SoundSource {
         let buffer_base_idx = self.buf_read_pos as usize;
         let mut buffer_rel_pos = (self.buf_read_pos - buffer_base_idx as f64) as f32;
         let start_buffer_rel_pos = buffer_rel_pos;
-        let rel_step = step as f32;
+        let mut rel_step = step as f32;
+        let rel_step_delta = step_delta as f32;
         // We skip one last element because the hot loop resampling between current and next
         // element. Last elements are appended after the hot loop.
         let buffer_last = buffer.samples.len() / buffer.channel_count - 1;
@@ -614,6 +1004,7 @@ impl SoundSource {
                 let r = buffer.samples[idx * 2 + 1] * (1.0 - w) + buffer.samples[idx * 2 + 3] * w;
                 self.frame_samples.push((l, r));
                 buffer_rel_pos += rel_step;
+                rel_step += rel_step_delta;
                 rendered += 1;
             }
         } else {
@@ -629,15 +1020,80 @@ impl SoundSource {
                 let v = buffer.samples[idx] * (1.0 - w) + buffer.samples[idx + 1] * w;
                 self.frame_samples.push((v, v));
                 buffer_rel_pos += rel_step;
+                rel_step += rel_step_delta;
                 rendered += 1;
             }
         }
 
         self.buf_read_pos += (buffer_rel_pos - start_buffer_rel_pos) as f64;
         self.playback_pos += (buffer_rel_pos - start_buffer_rel_pos) as f64;
+        self.doppler_shift += self.doppler_shift_step * rendered as f64;
         rendered
     }
 
+    // Applies gain envelope and scheduled stop, `start` is an index of the first sample that was
+    // actually played.
+    fn apply_envelope(&mut self, clock: u64, start: usize) {
+        let mut stop_at = self
+            .scheduled_stop
+            .filter(|&time| time < clock + self.frame_samples.len() as u64)
+            .map(|time| (time.saturating_sub(clock) as usize).max(start));
+
+        if let Some(fade) = self.fade.as_mut() {
+            fade.resolve_start_time(clock + start as u64);
+        }
+
+        if self.fade.is_some() || self.envelope_gain != 1.0 {
+            let end = stop_at.unwrap_or(self.frame_samples.len());
+            for i in start..end {
+                if let Some(fade) = self.fade.as_mut() {
+                    match fade.process(&mut self.envelope_gain, clock + i as u64) {
+                        FadeStatus::Active => (),
+                        FadeStatus::Done => self.fade = None,
+                        FadeStatus::DoneStop => {
+                            self.fade = None;
+                            stop_at = Some(i + 1);
+                        }
+                    }
+                }
+                let (left, right) = &mut self.frame_samples[i];
+                *left *= self.envelope_gain;
+                *right *= self.envelope_gain;
+                if stop_at == Some(i + 1) {
+                    break;
+                }
+            }
+        }
+
+        if let Some(stop_at) = stop_at {
+            for sample in self.frame_samples[stop_at..].iter_mut() {
+                *sample = (0.0, 0.0);
+            }
+            let _ = self.stop();
+        }
+    }
+
+    fn apply_occlusion_filter(&mut self) {
+        if self.occlusion_cutoff_frequency >= Self::MAX_OCCLUSION_CUTOFF_FREQUENCY {
+            self.occlusion_filter = None;
+            return;
+        }
+
+        let fc = self.occlusion_cutoff_frequency / SAMPLE_RATE as f32;
+        let (left_filter, right_filter) = self
+            .occlusion_filter
+            .get_or_insert_with(|| (Biquad::default(), Biquad::default()));
+        // Coefficients are changed once per frame, smoothing of cut-off frequency is done by
+        // the caller.
+        left_filter.tune(BiquadKind::LowPass, fc, 1.0, FRAC_1_SQRT_2);
+        right_filter.copy_coefficients(left_filter);
+
+        for (left, right) in self.frame_samples.iter_mut() {
+            *left = left_filter.feed(*left);
+            *right = right_filter.feed(*right);
+        }
+    }
+
     pub(in crate) fn frame_samples(&self) -> &[(f32, f32)] {
         &self.frame_samples
     }
@@ -699,9 +1155,12 @@ pub struct SoundSourceBuilder {
     playback_time: Duration,
     radius: f32,
     position: Vector3<f32>,
+    velocity: Vector3<f32>,
     max_distance: f32,
     rolloff_factor: f32,
     spatial_blend: f32,
+    bus: Handle<AudioBus>,
+    priority: u32,
 }
 
 impl Default for SoundSourceBuilder {
@@ -725,9 +1184,12 @@ impl SoundSourceBuilder {
             playback_time: Default::default(),
             radius: 1.0,
             position: Vector3::new(0.0, 0.0, 0.0),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
+            bus: Default::default(),
+            priority: 0,
         }
     }
 
@@ -803,6 +1265,12 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// See [`SoundSource::set_velocity`]
+    pub fn with_velocity(mut self, velocity: Vector3<f32>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
     /// See `set_radius` of SpatialSource.
     pub fn with_radius(mut self, radius: f32) -> Self {
         self.radius = radius;
@@ -821,27 +1289,38 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// See [`SoundSource::set_bus`]
+    pub fn with_bus(mut self, bus: Handle<AudioBus>) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// See [`SoundSource::set_priority`]
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Creates new instance of generic sound source. May fail if buffer is invalid.
     pub fn build(self) -> Result<SoundSource, SoundError> {
-        let mut source = SoundSource {
-            buffer: self.buffer.clone(),
-            gain: self.gain,
-            pitch: self.pitch,
-            play_once: self.play_once,
-            panning: self.panning,
-            status: self.status,
-            looping: self.looping,
-            name: self.name,
-            frame_samples: Default::default(),
-            radius: self.radius,
-            position: self.position,
-            max_distance: self.max_distance,
-            rolloff_factor: self.rolloff_factor,
-            spatial_blend: self.spatial_blend,
-            prev_left_samples: Default::default(),
-            prev_right_samples: Default::default(),
-            ..Default::default()
-        };
+        // SoundSource implements Drop, so functional update syntax cannot be used here.
+        let mut source = SoundSource::default();
+        source.buffer = self.buffer.clone();
+        source.gain = self.gain;
+        source.pitch = self.pitch;
+        source.play_once = self.play_once;
+        source.panning = self.panning;
+        source.status = self.status;
+        source.looping = self.looping;
+        source.name = self.name;
+        source.radius = self.radius;
+        source.position = self.position;
+        source.velocity = self.velocity;
+        source.max_distance = self.max_distance;
+        source.rolloff_factor = self.rolloff_factor;
+        source.spatial_blend = self.spatial_blend;
+        source.bus = self.bus;
+        source.priority = self.priority;
 
         source.set_buffer(self.buffer)?;
         source.set_playback_time(self.playback_time);
@@ -849,3 +1328,216 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource},
+        context::{State, DEFAULT_SPEED_OF_SOUND, SAMPLE_RATE},
+        fade::{Fade, FadeCurve},
+        listener::Listener,
+        pool::Handle,
+        source::{SoundSource, SoundSourceBuilder, Status},
+    };
+    use fyrox_core::algebra::Vector3;
+    use std::time::Duration;
+
+    // Source at the origin and listener 10 units away along Z axis.
+    fn doppler_shift(source_velocity: Vector3<f32>, listener_velocity: Vector3<f32>) -> f32 {
+        let source = SoundSourceBuilder::new()
+            .with_velocity(source_velocity)
+            .build()
+            .unwrap();
+        let mut listener = Listener::new();
+        listener.set_position(Vector3::new(0.0, 0.0, 10.0));
+        listener.set_velocity(listener_velocity);
+        source.calculate_doppler_shift(&listener, 1.0, DEFAULT_SPEED_OF_SOUND)
+    }
+
+    #[test]
+    fn test_doppler_shift_of_static_source() {
+        assert_eq!(doppler_shift(Vector3::default(), Vector3::default()), 1.0);
+        // Movement perpendicular to line of sight does not change pitch.
+        assert_eq!(
+            doppler_shift(Vector3::new(50.0, 0.0, 0.0), Vector3::default()),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_doppler_shift_of_moving_source() {
+        let speed = DEFAULT_SPEED_OF_SOUND * 0.1;
+
+        let approaching = doppler_shift(Vector3::new(0.0, 0.0, speed), Vector3::default());
+        assert!((approaching - 1.0 / 0.9).abs() < 1.0e-5);
+
+        let receding = doppler_shift(Vector3::new(0.0, 0.0, -speed), Vector3::default());
+        assert!((receding - 1.0 / 1.1).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_doppler_shift_of_moving_listener() {
+        let speed = DEFAULT_SPEED_OF_SOUND * 0.1;
+
+        let approaching = doppler_shift(Vector3::default(), Vector3::new(0.0, 0.0, -speed));
+        assert!((approaching - 1.1).abs() < 1.0e-5);
+
+        let receding = doppler_shift(Vector3::default(), Vector3::new(0.0, 0.0, speed));
+        assert!((receding - 0.9).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_doppler_shift_is_clamped() {
+        let supersonic = Vector3::new(0.0, 0.0, DEFAULT_SPEED_OF_SOUND * 2.0);
+        assert_eq!(
+            doppler_shift(supersonic, Vector3::default()),
+            SoundSource::MAX_DOPPLER_SHIFT
+        );
+        assert!(doppler_shift(-supersonic, Vector3::default()) >= 0.0);
+    }
+
+    #[test]
+    fn test_doppler_shift_disabled() {
+        let mut source = SoundSourceBuilder::new()
+            .with_velocity(Vector3::new(0.0, 0.0, 100.0))
+            .build()
+            .unwrap();
+        let mut listener = Listener::new();
+        listener.set_position(Vector3::new(0.0, 0.0, 10.0));
+
+        assert_eq!(
+            source.calculate_doppler_shift(&listener, 0.0, DEFAULT_SPEED_OF_SOUND),
+            1.0
+        );
+
+        // 2D sounds are not affected.
+        source.set_spatial_blend(0.0);
+        assert_eq!(
+            source.calculate_doppler_shift(&listener, 1.0, DEFAULT_SPEED_OF_SOUND),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_doppler_shift_changes_playback_speed_smoothly() {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![0.0; SAMPLE_RATE as usize],
+        })
+        .unwrap();
+        let mut source = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+
+        // Shift is ramped from 1.0 to 2.0 during the first frame.
+        source.render(100, 2.0, 0);
+        let first = source.playback_pos;
+        assert!(first > 100.0 && first < 200.0);
+        assert!((first - 150.0).abs() < 1.0);
+
+        // Then it stays constant.
+        source.render(100, 2.0, 100);
+        assert!((source.playback_pos - first - 200.0).abs() < 1.0e-3);
+    }
+
+    const FRAME_LEN: usize = 64;
+
+    fn make_state(status: Status) -> (State, Handle<SoundSource>) {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![1.0; SAMPLE_RATE as usize],
+        })
+        .unwrap();
+
+        let mut state = State::default();
+        let handle = state.add_source(
+            SoundSourceBuilder::new()
+                .with_buffer(buffer)
+                .with_spatial_blend_factor(0.0)
+                .with_looping(true)
+                .with_status(status)
+                .build()
+                .unwrap(),
+        );
+        (state, handle)
+    }
+
+    fn render_frame(state: &mut State) -> Vec<f32> {
+        let mut buf = vec![(0.0, 0.0); FRAME_LEN];
+        state.render(1.0, &mut buf);
+        buf.into_iter().map(|(left, _)| left).collect()
+    }
+
+    fn samples(count: u64) -> Duration {
+        Duration::from_secs_f64(count as f64 / SAMPLE_RATE as f64)
+    }
+
+    #[test]
+    fn test_scheduled_playback() {
+        let (mut state, handle) = make_state(Status::Stopped);
+        state
+            .source_mut(handle)
+            .schedule_play(100)
+            .schedule_stop(200);
+
+        assert!(render_frame(&mut state).iter().all(|s| *s == 0.0));
+        assert_eq!(state.source(handle).status(), Status::Stopped);
+
+        // Playback starts exactly at the scheduled sample.
+        let frame = render_frame(&mut state);
+        assert!(frame[..100 - FRAME_LEN].iter().all(|s| *s == 0.0));
+        assert!(frame[100 - FRAME_LEN..].iter().all(|s| *s > 0.0));
+        assert_eq!(state.source(handle).status(), Status::Playing);
+        assert_eq!(state.source(handle).scheduled_play_time(), None);
+
+        assert!(render_frame(&mut state).iter().all(|s| *s > 0.0));
+
+        // And stops exactly at the scheduled sample.
+        let frame = render_frame(&mut state);
+        assert!(frame[..200 - 3 * FRAME_LEN].iter().all(|s| *s > 0.0));
+        assert!(frame[200 - 3 * FRAME_LEN..].iter().all(|s| *s == 0.0));
+        assert_eq!(state.source(handle).status(), Status::Stopped);
+    }
+
+    #[test]
+    fn test_fades() {
+        let (mut state, handle) = make_state(Status::Playing);
+
+        let level = render_frame(&mut state)[0];
+        assert!(level > 0.0);
+
+        // Fade in starts from silence and reaches full gain at the end of the frame.
+        state
+            .source_mut(handle)
+            .fade_in(samples(FRAME_LEN as u64), FadeCurve::Linear);
+        let frame = render_frame(&mut state);
+        for (i, sample) in frame.iter().enumerate() {
+            let expected = level * (i + 1) as f32 / FRAME_LEN as f32;
+            assert!((sample - expected).abs() < 1.0e-5);
+        }
+        assert!(!state.source(handle).is_fading());
+        assert_eq!(state.source(handle).envelope_gain(), 1.0);
+
+        // Delayed fade out stops the source when it is done.
+        let start_time = state.sample_clock() + FRAME_LEN as u64 / 2;
+        state.source_mut(handle).fade(
+            Fade::new(0.0, samples(FRAME_LEN as u64 / 4))
+                .with_start_time(start_time)
+                .with_stop_when_done(true),
+        );
+        let frame = render_frame(&mut state);
+        assert!(frame[..FRAME_LEN / 2]
+            .iter()
+            .all(|s| (s - level).abs() < 1.0e-5));
+        assert!(frame[FRAME_LEN / 2..]
+            .windows(2)
+            .all(|pair| pair[1] <= pair[0]));
+        assert!(frame[3 * FRAME_LEN / 4 - 1..].iter().all(|s| *s == 0.0));
+        assert_eq!(state.source(handle).status(), Status::Stopped);
+        assert_eq!(state.source(handle).envelope_gain(), 1.0);
+    }
+}
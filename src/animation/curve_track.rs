@@ -0,0 +1,377 @@
+//! Curve tracks animate node transform or arbitrary properties of a node using a set of independent
+//! curves (channels), see [`CurveTrack`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        math::{self, RotationOrder},
+        pool::Handle,
+        sstorage::ImmutableString,
+        visitor::prelude::*,
+    },
+    gui::inspector::{FieldKind, PropertyChanged},
+    material::PropertyValue,
+    scene::{light::BaseLight, mesh::Mesh, node::Node},
+    utils::log::Log,
+};
+use std::any::Any;
+
+/// Defines what is animated by a [`CurveTrack`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Visit)]
+pub enum TrackBinding {
+    /// Local position of a node, requires 3 curves (X, Y, Z).
+    Position,
+    /// Local rotation of a node, requires 3 curves of Euler angles in radians (XYZ order).
+    Rotation,
+    /// Local scale of a node, requires 3 curves (X, Y, Z).
+    Scale,
+    /// Weight of blend shapes with given name of a mesh node, requires 1 curve.
+    BlendShape(String),
+    /// Arbitrary property of a node. Supported paths are:
+    ///
+    /// - `light.intensity` - intensity of a light source, requires 1 curve.
+    /// - `light.color` - color of a light source, requires 3 (RGB) or 4 (RGBA) curves in `[0; 1]`
+    ///   range.
+    /// - `material.<name>` - property with given name of the materials of each surface of a mesh,
+    ///   requires 1 to 4 curves (`float`, `vec2`, `vec3` or `vec4` property respectively).
+    /// - `script.<field>` - field of a script of a node, the path of the field is the same as
+    ///   the one reported by [`crate::core::inspect::Inspect`]. Requires 1 to 4 curves (`f32`,
+    ///   `Vector2<f32>`, `Vector3<f32>` or `Vector4<f32>` field respectively). The value is passed
+    ///   to the script via [`crate::script::ScriptTrait::on_property_changed`].
+    Property(String),
+}
+
+impl Default for TrackBinding {
+    fn default() -> Self {
+        Self::Position
+    }
+}
+
+/// A value of a [`CurveTrack`] at some time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TrackValue {
+    Real(f32),
+    Vector2(Vector2<f32>),
+    Vector3(Vector3<f32>),
+    Vector4(Vector4<f32>),
+}
+
+impl TrackValue {
+    fn from_channels(channels: &[f32]) -> Option<Self> {
+        match *channels {
+            [x] => Some(Self::Real(x)),
+            [x, y] => Some(Self::Vector2(Vector2::new(x, y))),
+            [x, y, z] => Some(Self::Vector3(Vector3::new(x, y, z))),
+            [x, y, z, w] => Some(Self::Vector4(Vector4::new(x, y, z, w))),
+            _ => None,
+        }
+    }
+
+    /// Returns the value multiplied by given weight.
+    pub fn scale(&self, weight: f32) -> Self {
+        match *self {
+            Self::Real(v) => Self::Real(v * weight),
+            Self::Vector2(v) => Self::Vector2(v.scale(weight)),
+            Self::Vector3(v) => Self::Vector3(v.scale(weight)),
+            Self::Vector4(v) => Self::Vector4(v.scale(weight)),
+        }
+    }
+
+    /// Adds other value multiplied by given weight to this value. Values of different kinds are
+    /// ignored.
+    pub fn add_scaled(&mut self, other: &TrackValue, weight: f32) {
+        match (self, other) {
+            (Self::Real(a), Self::Real(b)) => *a += b * weight,
+            (Self::Vector2(a), Self::Vector2(b)) => *a += b.scale(weight),
+            (Self::Vector3(a), Self::Vector3(b)) => *a += b.scale(weight),
+            (Self::Vector4(a), Self::Vector4(b)) => *a += b.scale(weight),
+            _ => (),
+        }
+    }
+
+    /// Linearly interpolates this value towards the other one. Values of different kinds are
+    /// ignored.
+    pub fn lerp(&mut self, other: &TrackValue, t: f32) {
+        let mut delta = *other;
+        delta.add_scaled(self, -1.0);
+        self.add_scaled(&delta, t);
+    }
+
+    pub fn as_real(&self) -> Option<f32> {
+        match *self {
+            Self::Real(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_vector3(&self) -> Option<Vector3<f32>> {
+        match *self {
+            Self::Vector3(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn to_property_value(self) -> PropertyValue {
+        match self {
+            Self::Real(v) => PropertyValue::Float(v),
+            Self::Vector2(v) => PropertyValue::Vector2(v),
+            Self::Vector3(v) => PropertyValue::Vector3(v),
+            Self::Vector4(v) => PropertyValue::Vector4(v),
+        }
+    }
+
+    fn to_field_kind(self) -> FieldKind {
+        match self {
+            Self::Real(v) => FieldKind::object(v),
+            Self::Vector2(v) => FieldKind::object(v),
+            Self::Vector3(v) => FieldKind::object(v),
+            Self::Vector4(v) => FieldKind::object(v),
+        }
+    }
+
+    fn to_color(self) -> Option<Color> {
+        match self {
+            Self::Vector3(v) => Some(Color::from(v)),
+            Self::Vector4(v) => Some(Color::from(v)),
+            _ => None,
+        }
+    }
+}
+
+/// Curve track animates a single [`TrackBinding`] of a node. Unlike [`super::Track`], which
+/// stores position, rotation and scale together in each key frame, the curve track has a separate
+/// [`Curve`] for each channel (component) of the animated value. Each channel has its own set of
+/// keys and each key could have its own interpolation mode (constant, linear or cubic), this
+/// allows to store animations in a compact form.
+#[derive(Clone, Debug, Visit)]
+pub struct CurveTrack {
+    node: Handle<Node>,
+    binding: TrackBinding,
+    curves: Vec<Curve>,
+    enabled: bool,
+}
+
+impl Default for CurveTrack {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            binding: Default::default(),
+            curves: Default::default(),
+            enabled: true,
+        }
+    }
+}
+
+impl CurveTrack {
+    /// Creates new curve track. The amount of curves must match the binding, see
+    /// [`TrackBinding`] docs for more info.
+    pub fn new(node: Handle<Node>, binding: TrackBinding, curves: Vec<Curve>) -> Self {
+        Self {
+            node,
+            binding,
+            curves,
+            enabled: true,
+        }
+    }
+
+    pub fn set_node(&mut self, node: Handle<Node>) {
+        self.node = node;
+    }
+
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    pub fn binding(&self) -> &TrackBinding {
+        &self.binding
+    }
+
+    pub fn curves(&self) -> &[Curve] {
+        &self.curves
+    }
+
+    pub fn curves_mut(&mut self) -> &mut [Curve] {
+        &mut self.curves
+    }
+
+    pub fn enable(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns location of the last key among all curves.
+    pub fn max_time(&self) -> f32 {
+        self.curves
+            .iter()
+            .filter_map(|curve| curve.keys().last())
+            .fold(0.0, |max, key| key.location().max(max))
+    }
+
+    /// Returns value of the track at given time. `None` is returned if there is no curves or there
+    /// are more than 4 curves.
+    pub fn value_at(&self, time: f32) -> Option<TrackValue> {
+        if self.curves.len() > 4 {
+            return None;
+        }
+        let mut channels = [0.0; 4];
+        for (channel, curve) in channels.iter_mut().zip(self.curves.iter()) {
+            *channel = curve.value_at(time);
+        }
+        TrackValue::from_channels(&channels[..self.curves.len()])
+    }
+
+    /// Sets values of the channels at given time, a key at the same time is modified (if any),
+    /// otherwise a new linear key is added. Extra values are ignored.
+    pub fn set_key(&mut self, time: f32, values: &[f32]) {
+        for (curve, &value) in self.curves.iter_mut().zip(values) {
+            let mut keys = curve.keys().to_vec();
+            if let Some(key) = keys
+                .iter_mut()
+                .find(|key| (key.location() - time).abs() <= f32::EPSILON)
+            {
+                key.value = value;
+            } else {
+                keys.push(CurveKey::new(time, value, CurveKeyKind::Linear));
+            }
+            *curve = Curve::from(keys);
+        }
+    }
+}
+
+/// Sets a value of given binding for a node. Transform bindings are handled by the caller.
+pub(in crate::animation) fn apply_property(node: &mut Node, path: &str, value: TrackValue) {
+    let applied = if let Some(property) = path.strip_prefix("light.") {
+        match node.query_component_mut::<BaseLight>() {
+            Some(light) => match property {
+                "intensity" => value.as_real().map(|v| light.set_intensity(v)).is_some(),
+                "color" => value.to_color().map(|v| light.set_color(v)).is_some(),
+                _ => false,
+            },
+            None => false,
+        }
+    } else if let Some(property) = path.strip_prefix("material.") {
+        match node.cast_mut::<Mesh>() {
+            Some(mesh) => {
+                let name = ImmutableString::new(property);
+                mesh.surfaces().iter().all(|surface| {
+                    surface
+                        .material()
+                        .lock()
+                        .set_property(&name, value.to_property_value())
+                        .is_ok()
+                })
+            }
+            None => false,
+        }
+    } else if let Some(field) = path.strip_prefix("script.") {
+        match node.script_mut() {
+            Some(script) => {
+                let owner_type_id = Any::type_id(script.as_any());
+                script.on_property_changed(&PropertyChanged {
+                    name: field.to_owned(),
+                    owner_type_id,
+                    value: value.to_field_kind(),
+                })
+            }
+            None => false,
+        }
+    } else {
+        false
+    };
+
+    if !applied {
+        Log::warn(format!(
+            "Unable to set animated property {} of node {} to {:?}!",
+            path,
+            node.name(),
+            value
+        ));
+    }
+}
+
+/// Sets a transform component of a node, returns `false` if the value does not fit the binding.
+pub(in crate::animation) fn apply_transform(
+    node: &mut Node,
+    binding: &TrackBinding,
+    value: TrackValue,
+) -> bool {
+    let value = match value.as_vector3() {
+        Some(value) => value,
+        None => return false,
+    };
+    let transform = node.local_transform_mut();
+    match binding {
+        TrackBinding::Position => {
+            transform.set_position(value);
+        }
+        TrackBinding::Rotation => {
+            transform.set_rotation(math::quat_from_euler(value, RotationOrder::XYZ));
+        }
+        TrackBinding::Scale => {
+            transform.set_scale(value);
+        }
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            curve_track::{CurveTrack, TrackBinding, TrackValue},
+            Animation,
+        },
+        core::{
+            algebra::Vector3,
+            curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+    };
+
+    #[test]
+    fn test_curve_track() {
+        let node = Handle::new(1, 1);
+
+        let x = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(2.0, 2.0, CurveKeyKind::Linear),
+        ]);
+        // Each channel has its own keys.
+        let y = Curve::from(vec![
+            CurveKey::new(0.0, 1.0, CurveKeyKind::Constant),
+            CurveKey::new(1.0, 3.0, CurveKeyKind::Constant),
+        ]);
+        let z = Curve::default();
+
+        let mut animation = Animation::default();
+        animation.add_curve_track(CurveTrack::new(
+            node,
+            TrackBinding::Position,
+            vec![x.clone(), y, z],
+        ));
+        animation.add_curve_track(CurveTrack::new(
+            node,
+            TrackBinding::Property("light.intensity".to_owned()),
+            vec![x],
+        ));
+        assert_eq!(animation.length(), 2.0);
+
+        animation.set_time_position(0.5);
+        animation.update_pose();
+        let pose = animation.get_pose();
+        assert_eq!(
+            pose.value(node, &TrackBinding::Position),
+            Some(TrackValue::Vector3(Vector3::new(0.5, 1.0, 0.0)))
+        );
+        assert_eq!(
+            pose.value(node, &TrackBinding::Property("light.intensity".to_owned())),
+            Some(TrackValue::Real(0.5))
+        );
+    }
+}
@@ -0,0 +1,974 @@
+//! Inverse kinematics (IK) allows you to post-process a pose produced by animations so that some
+//! bones will reach desired positions or look at desired points. Typical use cases are foot
+//! placement on uneven ground, hands reaching for objects and heads tracking targets.
+//!
+//! There are three kinds of solvers:
+//!
+//! - [`TwoBoneIk`] - analytic solver for limbs that consist of two bones (arms, legs), bending
+//!   direction is controlled by optional pole target. It can also use [`FootPlacement`] helper to
+//!   put feet on the ground.
+//! - [`ChainIk`] - iterative solver (FABRIK or CCD) for arbitrary chains of bones (tails,
+//!   tentacles, spines, etc.) with per-joint angle limits.
+//! - [`LookAtIk`] - rotates a bone (head, eye) towards a target.
+//!
+//! Each solver has a weight that can be either constant or driven by a weight parameter of an
+//! animation blending state machine. Solvers are usually added to a [`Machine`](super::machine::Machine)
+//! and applied automatically, but they could also be used directly on any [`AnimationPose`] via
+//! [`IkSolver::solve`], which must be done before the pose is applied to a graph.
+//!
+//! All solvers work in world space and write local rotations of bones to the pose, bones that are
+//! not in the pose yet will be added to it using their current local transform.
+
+use crate::{
+    animation::{
+        machine::{Parameter, ParameterContainer, PoseWeight},
+        AnimationPose, LocalPose,
+    },
+    core::{
+        algebra::{Matrix4, Point3, Unit, UnitQuaternion, Vector3},
+        math::Matrix4Ext,
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::InteractionGroups,
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph,
+        },
+        node::Node,
+    },
+};
+use std::f32::consts::PI;
+
+/// A point that a solver should reach.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub enum IkTarget {
+    /// Fixed position in world coordinates.
+    Position(Vector3<f32>),
+
+    /// Global position of a node.
+    Node(Handle<Node>),
+}
+
+impl Default for IkTarget {
+    fn default() -> Self {
+        Self::Position(Default::default())
+    }
+}
+
+impl IkTarget {
+    /// Returns world-space position of the target. Node targets use global transform of the node
+    /// calculated at the last graph update.
+    pub fn position(&self, graph: &Graph) -> Option<Vector3<f32>> {
+        match self {
+            IkTarget::Position(position) => Some(*position),
+            IkTarget::Node(node) => graph.try_get(*node).map(|node| node.global_position()),
+        }
+    }
+
+    fn remap<F>(&mut self, func: &mut F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        if let IkTarget::Node(node) = self {
+            *node = func(*node);
+        }
+    }
+}
+
+/// Foot placement helper, it casts a ray down from the animated foot position and moves the
+/// target of a [`TwoBoneIk`] solver to the ground. The height of the foot relative to the
+/// `reference` node (usually the root of a character which is placed at the feet level) is
+/// preserved, so lifted feet will still be lifted over the ground.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub struct FootPlacement {
+    /// Distance above the foot from which the ray is cast. Defines max height of a step.
+    pub ray_offset: f32,
+    /// Distance below the foot that will be checked for ground.
+    pub ray_length: f32,
+    /// Distance between the foot bone and the sole.
+    pub foot_height: f32,
+    /// A node that defines ground level of the animation. If it is not set, feet are always
+    /// planted on the ground.
+    pub reference: Handle<Node>,
+    /// Collision groups for the ray cast, use them to filter out colliders of the character.
+    pub groups: InteractionGroups,
+}
+
+impl Default for FootPlacement {
+    fn default() -> Self {
+        Self {
+            ray_offset: 0.5,
+            ray_length: 0.5,
+            foot_height: 0.1,
+            reference: Handle::NONE,
+            groups: Default::default(),
+        }
+    }
+}
+
+impl FootPlacement {
+    /// Casts a ray down from given foot position and returns the closest intersection with the
+    /// ground.
+    pub fn find_ground(&self, graph: &Graph, foot_position: Vector3<f32>) -> Option<Intersection> {
+        let mut query_buffer = Vec::new();
+        graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(foot_position + Vector3::y().scale(self.ray_offset)),
+                ray_direction: -Vector3::y(),
+                max_len: self.ray_offset + self.ray_length,
+                groups: self.groups,
+                sort_results: true,
+            },
+            &mut query_buffer,
+        );
+        query_buffer.into_iter().next()
+    }
+
+    /// Calculates a new position of a foot at given (animated) position. Returns `None` if there
+    /// is no ground under the foot.
+    pub fn foot_target(&self, graph: &Graph, foot_position: Vector3<f32>) -> Option<Vector3<f32>> {
+        let ground = self.find_ground(graph, foot_position)?;
+
+        let lift = graph
+            .try_get(self.reference)
+            .map_or(0.0, |reference| {
+                foot_position.y - reference.global_position().y - self.foot_height
+            })
+            .max(0.0);
+
+        Some(Vector3::new(
+            foot_position.x,
+            ground.position.y + self.foot_height + lift,
+            foot_position.z,
+        ))
+    }
+}
+
+/// Analytic solver for two-bone chains (arms, legs). See module docs for more info.
+#[derive(Default, Debug, Visit, Clone, PartialEq)]
+pub struct TwoBoneIk {
+    /// First bone of the chain (upper arm, thigh).
+    pub root: Handle<Node>,
+    /// Second bone of the chain (forearm, calf).
+    pub middle: Handle<Node>,
+    /// End of the chain (hand, foot), it will be moved to the target.
+    pub end: Handle<Node>,
+    pub target: IkTarget,
+    /// A point that defines bending direction of the chain (elbow, knee). If it is not set,
+    /// the chain bends in its current plane.
+    pub pole: Option<IkTarget>,
+    /// If set, the target is ignored and calculated by foot placement.
+    pub foot_placement: Option<FootPlacement>,
+}
+
+impl TwoBoneIk {
+    pub fn new(root: Handle<Node>, middle: Handle<Node>, end: Handle<Node>) -> Self {
+        Self {
+            root,
+            middle,
+            end,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_target(mut self, target: IkTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_pole(mut self, pole: IkTarget) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    pub fn with_foot_placement(mut self, foot_placement: FootPlacement) -> Self {
+        self.foot_placement = Some(foot_placement);
+        self
+    }
+
+    fn solve(&self, pose: &mut AnimationPose, graph: &Graph) {
+        let target = if let Some(foot_placement) = self.foot_placement.as_ref() {
+            match foot_placement.foot_target(graph, global_position(graph, pose, self.end)) {
+                Some(target) => target,
+                None => return,
+            }
+        } else {
+            match self.target.position(graph) {
+                Some(target) => target,
+                None => return,
+            }
+        };
+
+        let pole = self.pole.as_ref().and_then(|pole| pole.position(graph));
+
+        let a = global_position(graph, pose, self.root);
+        let b = global_position(graph, pose, self.middle);
+        let c = global_position(graph, pose, self.end);
+
+        let lab = (b - a).norm();
+        let lcb = (c - b).norm();
+        if lab <= f32::EPSILON || lcb <= f32::EPSILON {
+            return;
+        }
+
+        let eps = 1.0e-4;
+        let lat = (target - a).norm().max(eps).min(lab + lcb - eps);
+
+        // Current and desired angles at the root and the middle joints.
+        let ac_ab_0 = angle_between(c - a, b - a);
+        let ba_bc_0 = angle_between(a - b, c - b);
+        let ac_at_0 = angle_between(c - a, target - a);
+        let ac_ab_1 = ((lcb * lcb - lab * lab - lat * lat) / (-2.0 * lab * lat))
+            .clamp(-1.0, 1.0)
+            .acos();
+        let ba_bc_1 = ((lat * lat - lab * lab - lcb * lcb) / (-2.0 * lab * lcb))
+            .clamp(-1.0, 1.0)
+            .acos();
+
+        // Bending plane is defined by current middle joint or by pole if chain is straight.
+        let bend_axis = (c - a)
+            .cross(&(b - a))
+            .try_normalize(f32::EPSILON)
+            .or_else(|| {
+                pole.and_then(|pole| (c - a).cross(&(pole - a)).try_normalize(f32::EPSILON))
+            })
+            .or_else(|| (c - a).cross(&Vector3::x()).try_normalize(f32::EPSILON))
+            .or_else(|| (c - a).cross(&Vector3::y()).try_normalize(f32::EPSILON));
+        let bend_axis = match bend_axis {
+            Some(axis) => Unit::new_unchecked(axis),
+            None => return,
+        };
+
+        rotate_bone(
+            graph,
+            pose,
+            self.middle,
+            UnitQuaternion::from_axis_angle(&bend_axis, ba_bc_1 - ba_bc_0),
+        );
+        rotate_bone(
+            graph,
+            pose,
+            self.root,
+            UnitQuaternion::from_axis_angle(&bend_axis, ac_ab_1 - ac_ab_0),
+        );
+        if let Some(axis) = (c - a).cross(&(target - a)).try_normalize(f32::EPSILON) {
+            rotate_bone(
+                graph,
+                pose,
+                self.root,
+                UnitQuaternion::from_axis_angle(&Unit::new_unchecked(axis), ac_at_0),
+            );
+        }
+
+        // Twist the chain around the root-target axis to make the middle joint face the pole.
+        if let (Some(pole), Some(axis)) = (pole, (target - a).try_normalize(f32::EPSILON)) {
+            let b = global_position(graph, pose, self.middle);
+            let project = |v: Vector3<f32>| v - axis.scale(v.dot(&axis));
+            let current = project(b - a);
+            let desired = project(pole - a);
+            if current.norm() > f32::EPSILON && desired.norm() > f32::EPSILON {
+                let mut angle = angle_between(current, desired);
+                if current.cross(&desired).dot(&axis) < 0.0 {
+                    angle = -angle;
+                }
+                rotate_bone(
+                    graph,
+                    pose,
+                    self.root,
+                    UnitQuaternion::from_axis_angle(&Unit::new_unchecked(axis), angle),
+                );
+            }
+        }
+    }
+
+    fn bones(&self) -> Vec<Handle<Node>> {
+        vec![self.root, self.middle]
+    }
+
+    fn remap<F>(&mut self, func: &mut F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        self.root = func(self.root);
+        self.middle = func(self.middle);
+        self.end = func(self.end);
+        self.target.remap(func);
+        if let Some(pole) = self.pole.as_mut() {
+            pole.remap(func);
+        }
+        if let Some(foot_placement) = self.foot_placement.as_mut() {
+            foot_placement.reference = func(foot_placement.reference);
+        }
+    }
+}
+
+/// Algorithm of a [`ChainIk`] solver.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Visit)]
+pub enum ChainIkAlgorithm {
+    /// Forward And Backward Reaching Inverse Kinematics. Converges fast and produces natural
+    /// looking results for long chains.
+    Fabrik,
+
+    /// Cyclic Coordinate Descent. Rotates each joint towards the target starting from the end
+    /// of the chain, tends to curl chains, but respects joint limits better.
+    Ccd,
+}
+
+impl Default for ChainIkAlgorithm {
+    fn default() -> Self {
+        Self::Fabrik
+    }
+}
+
+/// A joint of a [`ChainIk`] solver.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub struct IkJoint {
+    pub bone: Handle<Node>,
+    /// Max angle (in radians) by which the solver can rotate the joint from its animated
+    /// rotation.
+    pub max_angle: f32,
+}
+
+impl Default for IkJoint {
+    fn default() -> Self {
+        Self {
+            bone: Handle::NONE,
+            max_angle: PI,
+        }
+    }
+}
+
+impl IkJoint {
+    /// Creates a joint without angle limits.
+    pub fn new(bone: Handle<Node>) -> Self {
+        Self {
+            bone,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_angle(mut self, max_angle: f32) -> Self {
+        self.max_angle = max_angle;
+        self
+    }
+}
+
+/// Iterative solver for chains of arbitrary length. See module docs for more info.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub struct ChainIk {
+    /// Joints of the chain starting from the root, last joint is the end effector (its rotation
+    /// is not changed).
+    pub joints: Vec<IkJoint>,
+    pub target: IkTarget,
+    pub algorithm: ChainIkAlgorithm,
+    pub iterations: u32,
+    /// Solver stops when the distance between the end effector and the target is less than
+    /// this value.
+    pub tolerance: f32,
+}
+
+impl Default for ChainIk {
+    fn default() -> Self {
+        Self {
+            joints: Default::default(),
+            target: Default::default(),
+            algorithm: Default::default(),
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+impl ChainIk {
+    pub fn new(joints: Vec<IkJoint>) -> Self {
+        Self {
+            joints,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_target(mut self, target: IkTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: ChainIkAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn solve(&self, pose: &mut AnimationPose, graph: &Graph) {
+        if self.joints.len() < 2 {
+            return;
+        }
+
+        let target = match self.target.position(graph) {
+            Some(target) => target,
+            None => return,
+        };
+
+        // Animated rotations are used as reference for joint limits.
+        let reference = self
+            .joints
+            .iter()
+            .map(|joint| local_pose(graph, pose, joint.bone).rotation)
+            .collect::<Vec<_>>();
+
+        match self.algorithm {
+            ChainIkAlgorithm::Fabrik => self.solve_fabrik(pose, graph, target, &reference),
+            ChainIkAlgorithm::Ccd => self.solve_ccd(pose, graph, target, &reference),
+        }
+    }
+
+    fn solve_ccd(
+        &self,
+        pose: &mut AnimationPose,
+        graph: &Graph,
+        target: Vector3<f32>,
+        reference: &[UnitQuaternion<f32>],
+    ) {
+        let end = self.joints.last().unwrap().bone;
+
+        for _ in 0..self.iterations {
+            for (i, joint) in self.joints.iter().enumerate().rev().skip(1) {
+                let joint_position = global_position(graph, pose, joint.bone);
+                let end_position = global_position(graph, pose, end);
+                if let Some(delta) = UnitQuaternion::rotation_between(
+                    &(end_position - joint_position),
+                    &(target - joint_position),
+                ) {
+                    rotate_bone(graph, pose, joint.bone, delta);
+                    limit_rotation(pose, joint, reference[i]);
+                }
+            }
+
+            if (global_position(graph, pose, end) - target).norm() <= self.tolerance {
+                break;
+            }
+        }
+    }
+
+    fn solve_fabrik(
+        &self,
+        pose: &mut AnimationPose,
+        graph: &Graph,
+        target: Vector3<f32>,
+        reference: &[UnitQuaternion<f32>],
+    ) {
+        let mut positions = self
+            .joints
+            .iter()
+            .map(|joint| global_position(graph, pose, joint.bone))
+            .collect::<Vec<_>>();
+
+        fabrik(&mut positions, target, self.iterations, self.tolerance);
+
+        // Convert positions back to rotations, starting from the root of the chain.
+        for (i, joint) in self.joints.iter().enumerate().take(self.joints.len() - 1) {
+            let joint_position = global_position(graph, pose, joint.bone);
+            let child_position = global_position(graph, pose, self.joints[i + 1].bone);
+            if let Some(delta) = UnitQuaternion::rotation_between(
+                &(child_position - joint_position),
+                &(positions[i + 1] - joint_position),
+            ) {
+                rotate_bone(graph, pose, joint.bone, delta);
+                limit_rotation(pose, joint, reference[i]);
+            }
+        }
+    }
+
+    fn bones(&self) -> Vec<Handle<Node>> {
+        self.joints.iter().map(|joint| joint.bone).collect()
+    }
+
+    fn remap<F>(&mut self, func: &mut F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        for joint in self.joints.iter_mut() {
+            joint.bone = func(joint.bone);
+        }
+        self.target.remap(func);
+    }
+}
+
+/// Rotates a bone to make its forward axis point at a target. See module docs for more info.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub struct LookAtIk {
+    pub bone: Handle<Node>,
+    pub target: IkTarget,
+    /// Local axis of the bone that should point at the target.
+    pub forward_axis: Vector3<f32>,
+    /// Max angle (in radians) by which the solver can rotate the bone from its animated rotation.
+    pub max_angle: f32,
+}
+
+impl Default for LookAtIk {
+    fn default() -> Self {
+        Self {
+            bone: Default::default(),
+            target: Default::default(),
+            forward_axis: Vector3::z(),
+            max_angle: PI,
+        }
+    }
+}
+
+impl LookAtIk {
+    pub fn new(bone: Handle<Node>) -> Self {
+        Self {
+            bone,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_target(mut self, target: IkTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_forward_axis(mut self, forward_axis: Vector3<f32>) -> Self {
+        self.forward_axis = forward_axis;
+        self
+    }
+
+    pub fn with_max_angle(mut self, max_angle: f32) -> Self {
+        self.max_angle = max_angle;
+        self
+    }
+
+    fn solve(&self, pose: &mut AnimationPose, graph: &Graph) {
+        let target = match self.target.position(graph) {
+            Some(target) => target,
+            None => return,
+        };
+
+        let global = global_transform(graph, pose, self.bone);
+        let forward = rotation_of(&global) * self.forward_axis;
+        if let Some(delta) =
+            UnitQuaternion::rotation_between(&forward, &(target - global.position()))
+        {
+            rotate_bone(graph, pose, self.bone, clamp_angle(delta, self.max_angle));
+        }
+    }
+
+    fn remap<F>(&mut self, func: &mut F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        self.bone = func(self.bone);
+        self.target.remap(func);
+    }
+}
+
+/// Kind of an IK solver.
+#[derive(Debug, Visit, Clone, PartialEq)]
+pub enum IkSolverKind {
+    TwoBone(TwoBoneIk),
+    Chain(ChainIk),
+    LookAt(LookAtIk),
+}
+
+impl Default for IkSolverKind {
+    fn default() -> Self {
+        Self::TwoBone(Default::default())
+    }
+}
+
+/// IK solver with its weight. See module docs for more info.
+#[derive(Debug, Visit, Clone)]
+pub struct IkSolver {
+    pub kind: IkSolverKind,
+    /// Defines how much of the solution will be blended with the animated pose.
+    pub weight: PoseWeight,
+    pub enabled: bool,
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self {
+            kind: Default::default(),
+            weight: PoseWeight::Constant(1.0),
+            enabled: true,
+        }
+    }
+}
+
+impl From<IkSolverKind> for IkSolver {
+    fn from(kind: IkSolverKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<TwoBoneIk> for IkSolver {
+    fn from(solver: TwoBoneIk) -> Self {
+        IkSolverKind::TwoBone(solver).into()
+    }
+}
+
+impl From<ChainIk> for IkSolver {
+    fn from(solver: ChainIk) -> Self {
+        IkSolverKind::Chain(solver).into()
+    }
+}
+
+impl From<LookAtIk> for IkSolver {
+    fn from(solver: LookAtIk) -> Self {
+        IkSolverKind::LookAt(solver).into()
+    }
+}
+
+impl IkSolver {
+    pub fn with_weight(mut self, weight: PoseWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Calculates actual weight of the solver using given set of parameters.
+    pub fn actual_weight(&self, params: &ParameterContainer) -> f32 {
+        match self.weight {
+            PoseWeight::Constant(value) => value,
+            PoseWeight::Parameter(ref param_id) => {
+                if let Some(Parameter::Weight(weight)) = params.get(param_id) {
+                    *weight
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Modifies given pose so the bones will reach the target of the solver. The solution is
+    /// blended with the original pose using the weight of the solver.
+    pub fn solve(&self, pose: &mut AnimationPose, graph: &Graph, params: &ParameterContainer) {
+        let weight = self.actual_weight(params).min(1.0);
+        if !self.enabled || weight <= 0.0 {
+            return;
+        }
+
+        let bones = match self.kind {
+            IkSolverKind::TwoBone(ref solver) => solver.bones(),
+            IkSolverKind::Chain(ref solver) => solver.bones(),
+            IkSolverKind::LookAt(ref solver) => vec![solver.bone],
+        };
+
+        if bones.iter().any(|bone| graph.try_get(*bone).is_none()) {
+            return;
+        }
+
+        let original = bones
+            .iter()
+            .map(|bone| local_pose(graph, pose, *bone).rotation)
+            .collect::<Vec<_>>();
+
+        match self.kind {
+            IkSolverKind::TwoBone(ref solver) => solver.solve(pose, graph),
+            IkSolverKind::Chain(ref solver) => solver.solve(pose, graph),
+            IkSolverKind::LookAt(ref solver) => solver.solve(pose, graph),
+        }
+
+        if weight < 1.0 {
+            for (bone, original) in bones.iter().zip(original) {
+                if let Some(local_pose) = pose.local_poses.get_mut(bone) {
+                    local_pose.rotation = original.nlerp(&local_pose.rotation, weight);
+                }
+            }
+        }
+    }
+
+    /// Maps every node used by the solver using given function.
+    pub fn remap<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        match self.kind {
+            IkSolverKind::TwoBone(ref mut solver) => solver.remap(&mut func),
+            IkSolverKind::Chain(ref mut solver) => solver.remap(&mut func),
+            IkSolverKind::LookAt(ref mut solver) => solver.remap(&mut func),
+        }
+    }
+}
+
+/// Moves a chain of points (with fixed distances between adjacent points) so that the last point
+/// reaches the target, the first point stays in place.
+fn fabrik(positions: &mut [Vector3<f32>], target: Vector3<f32>, iterations: u32, tolerance: f32) {
+    let count = positions.len();
+    let lengths = positions
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).norm())
+        .collect::<Vec<_>>();
+    let root = positions[0];
+
+    if (target - root).norm() >= lengths.iter().sum::<f32>() {
+        // Target is out of reach - stretch the chain towards it.
+        for i in 0..count - 1 {
+            let dir = (target - positions[i])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            positions[i + 1] = positions[i] + dir.scale(lengths[i]);
+        }
+        return;
+    }
+
+    for _ in 0..iterations {
+        if (positions[count - 1] - target).norm() <= tolerance {
+            break;
+        }
+
+        // Backward pass - from the end to the root.
+        positions[count - 1] = target;
+        for i in (0..count - 1).rev() {
+            let dir = (positions[i] - positions[i + 1])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            positions[i] = positions[i + 1] + dir.scale(lengths[i]);
+        }
+
+        // Forward pass - from the root to the end.
+        positions[0] = root;
+        for i in 0..count - 1 {
+            let dir = (positions[i + 1] - positions[i])
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            positions[i + 1] = positions[i] + dir.scale(lengths[i]);
+        }
+    }
+}
+
+fn angle_between(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
+    match (a.try_normalize(f32::EPSILON), b.try_normalize(f32::EPSILON)) {
+        (Some(a), Some(b)) => a.dot(&b).clamp(-1.0, 1.0).acos(),
+        _ => 0.0,
+    }
+}
+
+fn clamp_angle(rotation: UnitQuaternion<f32>, max_angle: f32) -> UnitQuaternion<f32> {
+    let angle = rotation.angle();
+    if angle > max_angle {
+        UnitQuaternion::identity().slerp(&rotation, max_angle / angle)
+    } else {
+        rotation
+    }
+}
+
+fn limit_rotation(pose: &mut AnimationPose, joint: &IkJoint, reference: UnitQuaternion<f32>) {
+    if let Some(local_pose) = pose.local_poses.get_mut(&joint.bone) {
+        let delta = reference.inverse() * local_pose.rotation;
+        local_pose.rotation = reference * clamp_angle(delta, joint.max_angle);
+    }
+}
+
+fn rotation_of(matrix: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_matrix(&matrix.basis())
+}
+
+/// Returns local pose of a node from the pose, or its current local transform if the pose does not
+/// have it.
+fn local_pose(graph: &Graph, pose: &AnimationPose, node: Handle<Node>) -> LocalPose {
+    pose.local_poses.get(&node).cloned().unwrap_or_else(|| {
+        let transform = graph[node].local_transform();
+        LocalPose {
+            node,
+            position: **transform.position(),
+            scale: **transform.scale(),
+            rotation: **transform.rotation(),
+        }
+    })
+}
+
+fn local_transform(graph: &Graph, pose: &AnimationPose, node: Handle<Node>) -> Matrix4<f32> {
+    let transform = graph[node].local_transform();
+    if let Some(local_pose) = pose.local_poses.get(&node) {
+        let mut transform = transform.clone();
+        transform
+            .set_position(local_pose.position)
+            .set_rotation(local_pose.rotation)
+            .set_scale(local_pose.scale);
+        transform.matrix()
+    } else {
+        transform.matrix()
+    }
+}
+
+/// Calculates global transform of a node as if the pose was applied to the graph.
+fn global_transform(graph: &Graph, pose: &AnimationPose, node: Handle<Node>) -> Matrix4<f32> {
+    let mut matrix = Matrix4::identity();
+    let mut current = node;
+    while let Some(current_ref) = graph.try_get(current) {
+        matrix = local_transform(graph, pose, current) * matrix;
+        current = current_ref.parent();
+    }
+    matrix
+}
+
+fn global_position(graph: &Graph, pose: &AnimationPose, node: Handle<Node>) -> Vector3<f32> {
+    global_transform(graph, pose, node).position()
+}
+
+/// Applies world-space rotation to a bone (around its origin) by changing its local rotation.
+fn rotate_bone(
+    graph: &Graph,
+    pose: &mut AnimationPose,
+    node: Handle<Node>,
+    delta: UnitQuaternion<f32>,
+) {
+    let node_ref = &graph[node];
+    let parent_rotation = rotation_of(&global_transform(graph, pose, node_ref.parent()));
+    let frame = parent_rotation * **node_ref.local_transform().pre_rotation();
+
+    let mut local_pose = local_pose(graph, pose, node);
+    local_pose.rotation = frame.inverse() * delta * frame * local_pose.rotation;
+    pose.add_local_pose(local_pose);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            ik::{fabrik, ChainIk, ChainIkAlgorithm, IkJoint, IkSolver, IkTarget, TwoBoneIk},
+            machine::{Parameter, PoseWeight},
+            AnimationPose,
+        },
+        core::{algebra::Vector3, pool::Handle},
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+    use fxhash::FxHashMap;
+
+    fn approx_eq(a: Vector3<f32>, b: Vector3<f32>) -> bool {
+        (a - b).norm() < 1.0e-3
+    }
+
+    // Creates a straight chain of bones along X axis, each bone is one unit long.
+    fn make_chain(graph: &mut Graph, count: usize) -> Vec<Handle<Node>> {
+        let mut bones = Vec::new();
+        let mut parent = graph.get_root();
+        for i in 0..count {
+            let bone = PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(if i == 0 {
+                            Vector3::default()
+                        } else {
+                            Vector3::new(1.0, 0.0, 0.0)
+                        })
+                        .build(),
+                ),
+            )
+            .build(graph);
+            graph.link_nodes(bone, parent);
+            bones.push(bone);
+            parent = bone;
+        }
+        bones
+    }
+
+    #[test]
+    fn test_fabrik() {
+        let mut positions = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+        ];
+        fabrik(&mut positions, Vector3::new(1.0, 1.0, 0.0), 32, 1.0e-4);
+        assert!(approx_eq(positions[0], Vector3::default()));
+        assert!(approx_eq(positions[2], Vector3::new(1.0, 1.0, 0.0)));
+        assert!(((positions[1] - positions[0]).norm() - 1.0).abs() < 1.0e-4);
+
+        // Unreachable target.
+        fabrik(&mut positions, Vector3::new(0.0, 10.0, 0.0), 32, 1.0e-4);
+        assert!(approx_eq(positions[2], Vector3::new(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn test_two_bone_ik() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+
+        let target = Vector3::new(1.0, 1.0, 0.0);
+        let solver = IkSolver::from(
+            TwoBoneIk::new(bones[0], bones[1], bones[2])
+                .with_target(IkTarget::Position(target))
+                .with_pole(IkTarget::Position(Vector3::new(0.0, 0.0, 5.0))),
+        );
+
+        let mut pose = AnimationPose::default();
+        solver.solve(&mut pose, &graph, &FxHashMap::default());
+
+        assert!(approx_eq(
+            super::global_position(&graph, &pose, bones[2]),
+            target
+        ));
+        // Middle joint must bend towards the pole.
+        assert!(super::global_position(&graph, &pose, bones[1]).z > 0.5);
+    }
+
+    #[test]
+    fn test_chain_ik() {
+        for algorithm in [ChainIkAlgorithm::Fabrik, ChainIkAlgorithm::Ccd] {
+            let mut graph = Graph::new();
+            let bones = make_chain(&mut graph, 4);
+
+            let target = Vector3::new(1.0, 1.5, 0.0);
+            let solver = IkSolver::from(
+                ChainIk::new(bones.iter().map(|bone| IkJoint::new(*bone)).collect())
+                    .with_target(IkTarget::Position(target))
+                    .with_algorithm(algorithm)
+                    .with_iterations(64),
+            );
+
+            let mut pose = AnimationPose::default();
+            solver.solve(&mut pose, &graph, &FxHashMap::default());
+
+            assert!(
+                (super::global_position(&graph, &pose, bones[3]) - target).norm() < 0.01,
+                "{:?} must reach the target",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_joint_limits_and_weight() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 2);
+
+        let solver = IkSolver::from(
+            ChainIk::new(vec![
+                IkJoint::new(bones[0]).with_max_angle(0.5),
+                IkJoint::new(bones[1]),
+            ])
+            .with_target(IkTarget::Position(Vector3::new(0.0, 1.0, 0.0)))
+            .with_algorithm(ChainIkAlgorithm::Ccd),
+        );
+
+        let mut pose = AnimationPose::default();
+        solver.solve(&mut pose, &graph, &FxHashMap::default());
+        assert!((pose.local_poses[&bones[0]].rotation.angle() - 0.5).abs() < 1.0e-4);
+
+        // Weight is taken from the parameters.
+        let solver = solver.with_weight(PoseWeight::Parameter("IK".to_owned()));
+        let mut params = FxHashMap::default();
+        params.insert("IK".to_owned(), Parameter::Weight(0.0));
+        let mut pose = AnimationPose::default();
+        solver.solve(&mut pose, &graph, &params);
+        assert!(pose.local_poses.is_empty());
+    }
+}
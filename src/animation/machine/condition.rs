@@ -0,0 +1,591 @@
+//! Transition conditions.
+//!
+//! Condition is a boolean expression that defines whether a transition between two states should
+//! be activated or not. Expressions are written in a simple C-like syntax, for example:
+//!
+//! ```text
+//! speed > 0.1 && !crouch
+//! (jump || fall) && source_time() >= 0.9
+//! "Walk Speed" <= 0.5
+//! ```
+//!
+//! Supported constructions are:
+//!
+//! - Parameter names - `crouch`. Names with spaces or other special characters must be quoted -
+//!   `"Walk Speed"`. Rule and Trigger parameters evaluate to their value, Weight and Index
+//!   parameters are `true` if they're non-zero. Missing parameters are always `false`.
+//! - Numbers - `0.1`, `2`.
+//! - Boolean constants - `true`, `false`.
+//! - Comparisons - `==`, `!=`, `<`, `<=`, `>`, `>=`. Both sides could be either a parameter or a
+//!   number, Weight and Index parameters are compared by their values, Rule and Trigger are treated
+//!   as `1.0` or `0.0`.
+//! - Logical operators - `&&`, `||`, `!` and parentheses.
+//! - `source_time()` - normalized (in `[0; 1]` range) playback position of an animation used by
+//!   the source state of the transition.
+//!
+//! Trigger parameters that contributed to the `true` result of a condition are automatically reset
+//! to `false` when the transition is activated. Other triggers are left intact, this includes
+//! triggers in a branch of `||` that was not taken and negated triggers (`!jump`), because they do
+//! not make the condition `true` by being set.
+
+use crate::animation::machine::{Parameter, ParameterContainer};
+use std::{
+    fmt::{Display, Formatter},
+    iter::Peekable,
+    str::CharIndices,
+};
+
+/// Comparison operator of a [`TransitionCondition::Compare`] condition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+impl ComparisonOperator {
+    /// Applies the operator to the given values.
+    pub fn apply(self, left: f32, right: f32) -> bool {
+        match self {
+            ComparisonOperator::Equal => (left - right).abs() <= f32::EPSILON,
+            ComparisonOperator::NotEqual => (left - right).abs() > f32::EPSILON,
+            ComparisonOperator::Less => left < right,
+            ComparisonOperator::LessOrEqual => left <= right,
+            ComparisonOperator::Greater => left > right,
+            ComparisonOperator::GreaterOrEqual => left >= right,
+        }
+    }
+}
+
+/// An operand of a comparison.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    /// Value of a parameter with the given name.
+    Parameter(String),
+    /// Numeric constant.
+    Constant(f32),
+    /// Normalized playback position of source state animation.
+    SourceTime,
+}
+
+/// A set of values that may be used by a condition.
+pub struct ConditionContext<'a> {
+    /// Parameters of a machine.
+    pub parameters: &'a ParameterContainer,
+    /// Normalized (in `[0; 1]` range) playback position of an animation of the source state.
+    pub source_time: f32,
+}
+
+impl<'a> ConditionContext<'a> {
+    fn operand_value(&self, operand: &Operand) -> f32 {
+        match operand {
+            Operand::Parameter(name) => match self.parameters.get(name) {
+                Some(Parameter::Weight(weight)) => *weight,
+                Some(Parameter::Index(index)) => *index as f32,
+                Some(Parameter::Rule(true)) | Some(Parameter::Trigger(true)) => 1.0,
+                Some(Parameter::Rule(false)) | Some(Parameter::Trigger(false)) | None => 0.0,
+            },
+            Operand::Constant(value) => *value,
+            Operand::SourceTime => self.source_time,
+        }
+    }
+
+    fn is_set_trigger(&self, name: &str) -> bool {
+        matches!(self.parameters.get(name), Some(Parameter::Trigger(true)))
+    }
+}
+
+/// Boolean expression that defines whether a transition should be activated or not. See module
+/// docs for syntax.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransitionCondition {
+    /// Constant value.
+    Constant(bool),
+    /// Value of a parameter with the given name.
+    Parameter(String),
+    /// Compares two operands.
+    Compare {
+        /// Left operand.
+        left: Operand,
+        /// Comparison operator.
+        op: ComparisonOperator,
+        /// Right operand.
+        right: Operand,
+    },
+    /// Logical negation of the inner condition.
+    Not(Box<TransitionCondition>),
+    /// `true` if both conditions are `true`.
+    And(Box<TransitionCondition>, Box<TransitionCondition>),
+    /// `true` if any of conditions is `true`.
+    Or(Box<TransitionCondition>, Box<TransitionCondition>),
+}
+
+impl Default for TransitionCondition {
+    fn default() -> Self {
+        Self::Constant(false)
+    }
+}
+
+impl TransitionCondition {
+    /// Parses a condition from its textual representation. See module docs for syntax.
+    pub fn parse(expression: &str) -> Result<Self, ConditionParseError> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let condition = parser.parse_or()?;
+        if let Some((position, token)) = parser.peek_with_position() {
+            Err(ConditionParseError {
+                position,
+                message: format!("Unexpected token {:?}", token),
+            })
+        } else {
+            Ok(condition)
+        }
+    }
+
+    /// Evaluates the condition using the given context.
+    pub fn evaluate(&self, context: &ConditionContext) -> bool {
+        match self {
+            TransitionCondition::Constant(value) => *value,
+            TransitionCondition::Parameter(name) => match context.parameters.get(name) {
+                Some(Parameter::Rule(value)) | Some(Parameter::Trigger(value)) => *value,
+                Some(Parameter::Weight(weight)) => *weight != 0.0,
+                Some(Parameter::Index(index)) => *index != 0,
+                None => false,
+            },
+            TransitionCondition::Compare { left, op, right } => {
+                op.apply(context.operand_value(left), context.operand_value(right))
+            }
+            TransitionCondition::Not(inner) => !inner.evaluate(context),
+            TransitionCondition::And(left, right) => {
+                left.evaluate(context) && right.evaluate(context)
+            }
+            TransitionCondition::Or(left, right) => {
+                left.evaluate(context) || right.evaluate(context)
+            }
+        }
+    }
+
+    /// Evaluates the condition using the given context and, if the condition is `true`, adds names
+    /// of set Trigger parameters that contributed to the result to `triggers`. Nothing is added if
+    /// the condition is `false`. See module docs for more info.
+    pub fn evaluate_with_triggers(
+        &self,
+        context: &ConditionContext,
+        triggers: &mut Vec<String>,
+    ) -> bool {
+        // Every branch leaves `triggers` untouched if its result is `false`.
+        match self {
+            TransitionCondition::Constant(value) => *value,
+            TransitionCondition::Parameter(name) => {
+                let result = self.evaluate(context);
+                if result && context.is_set_trigger(name) {
+                    triggers.push(name.clone());
+                }
+                result
+            }
+            TransitionCondition::Compare { left, right, .. } => {
+                let result = self.evaluate(context);
+                if result {
+                    for operand in [left, right] {
+                        if let Operand::Parameter(name) = operand {
+                            if context.is_set_trigger(name) {
+                                triggers.push(name.clone());
+                            }
+                        }
+                    }
+                }
+                result
+            }
+            // Negated triggers never make the condition `true` by being set.
+            TransitionCondition::Not(inner) => !inner.evaluate(context),
+            TransitionCondition::And(left, right) => {
+                let count = triggers.len();
+                let result = left.evaluate_with_triggers(context, triggers)
+                    && right.evaluate_with_triggers(context, triggers);
+                if !result {
+                    triggers.truncate(count);
+                }
+                result
+            }
+            TransitionCondition::Or(left, right) => {
+                left.evaluate_with_triggers(context, triggers)
+                    || right.evaluate_with_triggers(context, triggers)
+            }
+        }
+    }
+
+    /// Calls the given closure for every parameter name used by the condition.
+    pub fn for_each_parameter<F: FnMut(&str)>(&self, func: &mut F) {
+        match self {
+            TransitionCondition::Constant(_) => {}
+            TransitionCondition::Parameter(name) => func(name),
+            TransitionCondition::Compare { left, right, .. } => {
+                for operand in [left, right] {
+                    if let Operand::Parameter(name) = operand {
+                        func(name)
+                    }
+                }
+            }
+            TransitionCondition::Not(inner) => inner.for_each_parameter(func),
+            TransitionCondition::And(left, right) | TransitionCondition::Or(left, right) => {
+                left.for_each_parameter(func);
+                right.for_each_parameter(func);
+            }
+        }
+    }
+}
+
+/// An error that may occur during condition parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionParseError {
+    /// Position (in bytes) in the source expression where the error has occurred.
+    pub position: usize,
+    /// Description of the error.
+    pub message: String,
+}
+
+impl Display for ConditionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    QuotedName(String),
+    Number(f32),
+    And,
+    Or,
+    Not,
+    OpenParen,
+    CloseParen,
+    Comparison(ComparisonOperator),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<(usize, Token)>, ConditionParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '&' if next_is(&mut chars, '&') => Token::And,
+            '|' if next_is(&mut chars, '|') => Token::Or,
+            '!' if next_is(&mut chars, '=') => Token::Comparison(ComparisonOperator::NotEqual),
+            '!' => Token::Not,
+            '=' if next_is(&mut chars, '=') => Token::Comparison(ComparisonOperator::Equal),
+            '<' if next_is(&mut chars, '=') => Token::Comparison(ComparisonOperator::LessOrEqual),
+            '<' => Token::Comparison(ComparisonOperator::Less),
+            '>' if next_is(&mut chars, '=') => {
+                Token::Comparison(ComparisonOperator::GreaterOrEqual)
+            }
+            '>' => Token::Comparison(ComparisonOperator::Greater),
+            '"' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => name.push(c),
+                        None => {
+                            return Err(ConditionParseError {
+                                position,
+                                message: "Unterminated quoted name".to_owned(),
+                            })
+                        }
+                    }
+                }
+                Token::QuotedName(name)
+            }
+            c if c.is_ascii_digit() || c == '.' || c == '-' => {
+                let mut number = c.to_string();
+                while let Some((_, n)) = chars.peek() {
+                    if n.is_ascii_digit() || *n == '.' {
+                        number.push(*n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Number(number.parse().map_err(|_| ConditionParseError {
+                    position,
+                    message: format!("Invalid number {}", number),
+                })?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = c.to_string();
+                while let Some((_, n)) = chars.peek() {
+                    if n.is_alphanumeric() || *n == '_' {
+                        identifier.push(*n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Identifier(identifier)
+            }
+            _ => {
+                return Err(ConditionParseError {
+                    position,
+                    message: format!("Unexpected character {}", c),
+                })
+            }
+        };
+
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+fn next_is(chars: &mut Peekable<CharIndices>, expected: char) -> bool {
+    if chars.peek().map_or(false, |(_, n)| *n == expected) {
+        chars.next();
+        true
+    } else {
+        false
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek_with_position(&self) -> Option<(usize, &'a Token)> {
+        self.tokens.get(self.position).map(|(p, t)| (*p, t))
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position).map(|(_, t)| t)
+    }
+
+    fn next(&mut self) -> Result<&'a Token, ConditionParseError> {
+        match self.tokens.get(self.position) {
+            Some((_, token)) => {
+                self.position += 1;
+                Ok(token)
+            }
+            None => Err(self.error("Unexpected end of expression")),
+        }
+    }
+
+    fn error(&self, message: &str) -> ConditionParseError {
+        ConditionParseError {
+            position: self
+                .tokens
+                .get(self.position)
+                .or_else(|| self.tokens.last())
+                .map_or(0, |(p, _)| *p),
+            message: message.to_owned(),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<TransitionCondition, ConditionParseError> {
+        let mut left = self.parse_and()?;
+        while let Some(Token::Or) = self.peek() {
+            self.position += 1;
+            let right = self.parse_and()?;
+            left = TransitionCondition::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<TransitionCondition, ConditionParseError> {
+        let mut left = self.parse_unary()?;
+        while let Some(Token::And) = self.peek() {
+            self.position += 1;
+            let right = self.parse_unary()?;
+            left = TransitionCondition::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<TransitionCondition, ConditionParseError> {
+        if let Some(Token::Not) = self.peek() {
+            self.position += 1;
+            Ok(TransitionCondition::Not(Box::new(self.parse_unary()?)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<TransitionCondition, ConditionParseError> {
+        if let Some(Token::OpenParen) = self.peek() {
+            self.position += 1;
+            let inner = self.parse_or()?;
+            return match self.next()? {
+                Token::CloseParen => Ok(inner),
+                _ => Err(self.error("Expected )")),
+            };
+        }
+
+        let left = self.parse_operand()?;
+
+        if let Some(Token::Comparison(op)) = self.peek() {
+            self.position += 1;
+            let right = self.parse_operand()?;
+            return Ok(TransitionCondition::Compare {
+                left,
+                op: *op,
+                right,
+            });
+        }
+
+        match left {
+            Operand::Parameter(name) => match name.as_str() {
+                "true" => Ok(TransitionCondition::Constant(true)),
+                "false" => Ok(TransitionCondition::Constant(false)),
+                _ => Ok(TransitionCondition::Parameter(name)),
+            },
+            Operand::Constant(_) | Operand::SourceTime => {
+                Err(self.error("Expected comparison operator"))
+            }
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, ConditionParseError> {
+        match self.next()? {
+            Token::Number(value) => Ok(Operand::Constant(*value)),
+            Token::QuotedName(name) => Ok(Operand::Parameter(name.clone())),
+            Token::Identifier(identifier) => {
+                if let Some(Token::OpenParen) = self.peek() {
+                    self.position += 1;
+                    match self.next()? {
+                        Token::CloseParen => (),
+                        _ => return Err(self.error("Expected )")),
+                    }
+                    match identifier.as_str() {
+                        "source_time" => Ok(Operand::SourceTime),
+                        _ => Err(ConditionParseError {
+                            position: self.tokens[self.position - 3].0,
+                            message: format!("Unknown function {}", identifier),
+                        }),
+                    }
+                } else {
+                    Ok(Operand::Parameter(identifier.clone()))
+                }
+            }
+            _ => {
+                self.position -= 1;
+                Err(self.error("Expected parameter name or number"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::animation::machine::{
+        condition::{ComparisonOperator, ConditionContext, Operand, TransitionCondition},
+        Parameter, ParameterContainer,
+    };
+
+    fn parameters() -> ParameterContainer {
+        let mut parameters = ParameterContainer::default();
+        parameters.insert("speed".to_owned(), Parameter::Weight(0.5));
+        parameters.insert("crouch".to_owned(), Parameter::Rule(false));
+        parameters.insert("jump".to_owned(), Parameter::Trigger(true));
+        parameters.insert("Weapon Index".to_owned(), Parameter::Index(2));
+        parameters
+    }
+
+    fn evaluate(expression: &str, source_time: f32) -> bool {
+        let parameters = parameters();
+        TransitionCondition::parse(expression)
+            .unwrap()
+            .evaluate(&ConditionContext {
+                parameters: &parameters,
+                source_time,
+            })
+    }
+
+    #[test]
+    fn test_condition_parse() {
+        assert_eq!(
+            TransitionCondition::parse("speed > 0.1 && !crouch").unwrap(),
+            TransitionCondition::And(
+                Box::new(TransitionCondition::Compare {
+                    left: Operand::Parameter("speed".to_owned()),
+                    op: ComparisonOperator::Greater,
+                    right: Operand::Constant(0.1),
+                }),
+                Box::new(TransitionCondition::Not(Box::new(
+                    TransitionCondition::Parameter("crouch".to_owned())
+                ))),
+            )
+        );
+
+        assert!(TransitionCondition::parse("speed >").is_err());
+        assert!(TransitionCondition::parse("(speed > 1").is_err());
+        assert!(TransitionCondition::parse("foo() > 1").is_err());
+        assert!(TransitionCondition::parse("\"unterminated").is_err());
+        assert!(TransitionCondition::parse("speed crouch").is_err());
+    }
+
+    #[test]
+    fn test_condition_evaluate() {
+        assert!(evaluate("speed > 0.1 && !crouch", 0.0));
+        assert!(!evaluate("speed > 0.1 && crouch", 0.0));
+        assert!(evaluate("crouch || jump", 0.0));
+        assert!(evaluate("\"Weapon Index\" == 2", 0.0));
+        assert!(!evaluate("missing", 0.0));
+        assert!(evaluate("!(speed <= 0.5) || true", 0.0));
+        assert!(evaluate("source_time() >= 0.9", 0.95));
+        assert!(!evaluate("source_time() >= 0.9", 0.5));
+    }
+
+    fn consumed_triggers(expression: &str) -> Vec<String> {
+        let mut parameters = parameters();
+        parameters.insert("fall".to_owned(), Parameter::Trigger(true));
+        parameters.insert("idle".to_owned(), Parameter::Trigger(false));
+        let mut triggers = Vec::new();
+        TransitionCondition::parse(expression)
+            .unwrap()
+            .evaluate_with_triggers(
+                &ConditionContext {
+                    parameters: &parameters,
+                    source_time: 0.0,
+                },
+                &mut triggers,
+            );
+        triggers
+    }
+
+    #[test]
+    fn test_condition_triggers() {
+        assert_eq!(consumed_triggers("jump && fall"), vec!["jump", "fall"]);
+        // Right branch is not taken.
+        assert_eq!(consumed_triggers("jump || fall"), vec!["jump"]);
+        // Left branch is false, so `jump` did not contribute.
+        assert_eq!(consumed_triggers("(jump && crouch) || fall"), vec!["fall"]);
+        // Negated triggers are never consumed.
+        assert_eq!(consumed_triggers("!idle && fall"), vec!["fall"]);
+        assert_eq!(consumed_triggers("jump == 1"), vec!["jump"]);
+        // Nothing is consumed if the condition is false.
+        assert!(consumed_triggers("jump && fall && crouch").is_empty());
+    }
+
+    #[test]
+    fn test_condition_parameters() {
+        let condition = TransitionCondition::parse("jump && speed > 0.1").unwrap();
+        let mut names = Vec::new();
+        condition.for_each_parameter(&mut |name| names.push(name.to_owned()));
+        assert_eq!(names, vec!["jump".to_owned(), "speed".to_owned()]);
+    }
+}
@@ -68,6 +68,10 @@ impl AnimationMachineContainer {
         self.pool.iter_mut()
     }
 
+    pub fn pair_iter(&self) -> impl Iterator<Item = (Handle<Machine>, &Machine)> {
+        self.pool.pair_iter()
+    }
+
     pub async fn resolve(
         &mut self,
         resource_manager: ResourceManager,
@@ -75,18 +79,20 @@ impl AnimationMachineContainer {
         animations: &mut AnimationContainer,
     ) {
         let mut animation_paths = Vec::new();
+        let mut skeleton_mapping_paths = Vec::new();
         for machine in self.pool.iter() {
             if let Some(resource) = machine.resource() {
-                animation_paths.extend(
-                    resource
-                        .data_ref()
-                        .absm_definition
-                        .collect_animation_paths(),
-                )
+                let data = resource.data_ref();
+                animation_paths.extend(data.absm_definition.collect_animation_paths());
+                skeleton_mapping_paths
+                    .extend(data.absm_definition.collect_skeleton_mapping_paths());
             }
         }
 
-        let pack = AnimationsPack::load(&animation_paths, resource_manager).await;
+        let pack = AnimationsPack::load(&animation_paths, resource_manager.clone())
+            .await
+            .with_skeleton_mappings(&skeleton_mapping_paths, resource_manager)
+            .await;
 
         for machine in self.pool.iter_mut() {
             machine.resolve(&pack, graph, animations);
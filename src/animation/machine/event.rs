@@ -18,12 +18,20 @@ pub enum Event {
 
     /// Occurs when active transition was changed.
     ActiveTransitionChanged(Handle<Transition>),
+
+    /// Occurs when a transition starts blending from its source state to its destination state.
+    TransitionStarted(Handle<Transition>),
+
+    /// Occurs when a transition is done, right before its destination state becomes active.
+    TransitionEnded(Handle<Transition>),
 }
 
 #[derive(Debug, Clone)]
 pub struct LimitedEventQueue {
     queue: VecDeque<Event>,
     limit: u32,
+    // Events since last `clear_recent` call, they're not consumed by `pop`.
+    recent: Vec<Event>,
 }
 
 impl Default for LimitedEventQueue {
@@ -31,6 +39,7 @@ impl Default for LimitedEventQueue {
         Self {
             queue: Default::default(),
             limit: u32::MAX,
+            recent: Default::default(),
         }
     }
 }
@@ -40,15 +49,27 @@ impl LimitedEventQueue {
         Self {
             queue: VecDeque::with_capacity(limit as usize),
             limit,
+            recent: Default::default(),
         }
     }
 
     pub fn push(&mut self, event: Event) {
+        self.recent.push(event.clone());
         if self.queue.len() < (self.limit as usize) {
             self.queue.push_back(event);
         }
     }
 
+    /// Returns events that were pushed since last [`Self::clear_recent`] call, regardless of
+    /// whether they were popped or not.
+    pub fn recent(&self) -> &[Event] {
+        &self.recent
+    }
+
+    pub fn clear_recent(&mut self) {
+        self.recent.clear();
+    }
+
     pub fn pop(&mut self) -> Option<Event> {
         self.queue.pop_front()
     }
@@ -0,0 +1,398 @@
+//! Animation layers allows you to play multiple state graphs at once and blend their results
+//! together. For example, you can play an upper-body "shoot" animation on top of lower-body
+//! locomotion. Each layer has its own set of states and transitions, a bone mask that defines
+//! which nodes are affected by the layer, a weight and a blend mode.
+//!
+//! Layers are applied in order of their creation on top of the pose produced by base state graph
+//! of the machine (the states that does not belong to any layer).
+
+use crate::{
+    animation::{
+        machine::{
+            node::PoseNodeDefinition, state::StateDefinition, Parameter, ParameterContainer,
+            PoseNode, PoseWeight, State, Transition,
+        },
+        AnimationPose,
+    },
+    core::{
+        inspect::{Inspect, PropertyInfo},
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how the pose of a layer is combined with the pose produced by the layers below.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Visit, Inspect, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum LayerBlendMode {
+    /// Pose of the layer replaces the pose below it (weighted by layer weight).
+    Override,
+
+    /// Difference between pose of the layer and the additive reference pose is added to the pose
+    /// below it (weighted by layer weight).
+    Additive,
+}
+
+impl Default for LayerBlendMode {
+    fn default() -> Self {
+        Self::Override
+    }
+}
+
+// Handles are not ordered, so the mask is sorted by index and generation of each handle.
+fn sort_key(handle: &Handle<Node>) -> (u32, u32) {
+    (handle.index(), handle.generation())
+}
+
+/// A set of nodes affected by a layer. Empty mask affects every node.
+#[derive(Default, Debug, Visit, Clone, PartialEq)]
+pub struct LayerMask {
+    // Sorted to be able to use binary search.
+    bones: Vec<Handle<Node>>,
+}
+
+impl LayerMask {
+    /// Creates a mask from a set of node names, each name defines a subtree of nodes (the node
+    /// with the name and all its descendants). Names are searched in the hierarchy that starts
+    /// from `root`, names that cannot be found are ignored.
+    pub fn from_names(graph: &Graph, root: Handle<Node>, names: &[String]) -> Self {
+        let mut mask = Self::default();
+        for name in names {
+            let node = graph.find_by_name(root, name);
+            if node.is_some() {
+                mask.add_subtree(graph, node);
+            }
+        }
+        mask
+    }
+
+    /// Adds a single node to the mask.
+    pub fn add_bone(&mut self, bone: Handle<Node>) {
+        if let Err(index) = self.bones.binary_search_by_key(&sort_key(&bone), sort_key) {
+            self.bones.insert(index, bone);
+        }
+    }
+
+    /// Adds a node and all its descendants to the mask.
+    pub fn add_subtree(&mut self, graph: &Graph, root: Handle<Node>) {
+        for bone in graph.traverse_handle_iter(root) {
+            self.add_bone(bone);
+        }
+    }
+
+    /// Returns `true` if the mask affects given node.
+    pub fn contains(&self, bone: Handle<Node>) -> bool {
+        self.bones.is_empty()
+            || self
+                .bones
+                .binary_search_by_key(&sort_key(&bone), sort_key)
+                .is_ok()
+    }
+
+    /// Returns `true` if the mask has no nodes, such mask affects every node.
+    pub fn is_empty(&self) -> bool {
+        self.bones.is_empty()
+    }
+
+    /// Returns a sorted list of nodes of the mask.
+    pub fn bones(&self) -> &[Handle<Node>] {
+        &self.bones
+    }
+
+    /// Maps every node of the mask using given function, nodes mapped to [`Handle::NONE`] are
+    /// removed from the mask.
+    pub fn remap<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Handle<Node>) -> Handle<Node>,
+    {
+        let bones = std::mem::take(&mut self.bones);
+        for bone in bones {
+            let new_bone = func(bone);
+            if new_bone.is_some() {
+                self.add_bone(new_bone);
+            }
+        }
+    }
+}
+
+/// Animation layer of a machine. See module docs for more info.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct MachineLayer {
+    pub definition: Handle<LayerDefinition>,
+    pub(crate) name: String,
+    pub(crate) weight: PoseWeight,
+    pub(crate) blend_mode: LayerBlendMode,
+    pub(crate) mask: LayerMask,
+    pub(crate) additive_reference: Handle<PoseNode>,
+    pub(crate) entry_state: Handle<State>,
+    pub(crate) active_state: Handle<State>,
+    pub(crate) active_transition: Handle<Transition>,
+
+    #[visit(skip)]
+    pub(crate) pose: AnimationPose,
+}
+
+impl MachineLayer {
+    /// Creates new layer with full weight and override blend mode, that affects every node.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            weight: PoseWeight::Constant(1.0),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_weight(mut self, weight: PoseWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: LayerBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: LayerMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Sets a pose node that produces reference pose for additive blending. Difference between
+    /// the pose of the layer and the reference pose will be added to the poses below the layer.
+    /// Usually it is a single-frame animation of the "neutral" pose of the additive animation.
+    pub fn with_additive_reference(mut self, additive_reference: Handle<PoseNode>) -> Self {
+        self.additive_reference = additive_reference;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn weight(&self) -> &PoseWeight {
+        &self.weight
+    }
+
+    pub fn set_weight(&mut self, weight: PoseWeight) {
+        self.weight = weight;
+    }
+
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
+    pub fn mask(&self) -> &LayerMask {
+        &self.mask
+    }
+
+    pub fn mask_mut(&mut self) -> &mut LayerMask {
+        &mut self.mask
+    }
+
+    pub fn additive_reference(&self) -> Handle<PoseNode> {
+        self.additive_reference
+    }
+
+    pub fn set_entry_state(&mut self, entry_state: Handle<State>) {
+        self.active_state = entry_state;
+        self.entry_state = entry_state;
+    }
+
+    pub fn entry_state(&self) -> Handle<State> {
+        self.entry_state
+    }
+
+    pub fn active_state(&self) -> Handle<State> {
+        self.active_state
+    }
+
+    pub fn active_transition(&self) -> Handle<Transition> {
+        self.active_transition
+    }
+
+    /// Returns last pose produced by the state graph of the layer.
+    pub fn pose(&self) -> &AnimationPose {
+        &self.pose
+    }
+
+    /// Calculates actual weight of the layer using given set of parameters.
+    pub fn actual_weight(&self, params: &ParameterContainer) -> f32 {
+        match self.weight {
+            PoseWeight::Constant(value) => value,
+            PoseWeight::Parameter(ref param_id) => {
+                if let Some(Parameter::Weight(weight)) = params.get(param_id) {
+                    *weight
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.active_state = self.entry_state;
+        self.active_transition = Handle::NONE;
+    }
+
+    /// Blends the pose of the layer into a given pose using layer's mask, weight and blend mode.
+    /// `reference` is used only for additive blending.
+    pub(crate) fn blend_into(
+        &self,
+        dest: &mut AnimationPose,
+        reference: Option<&AnimationPose>,
+        params: &ParameterContainer,
+    ) {
+        let weight = self.actual_weight(params);
+        if weight <= 0.0 {
+            return;
+        }
+
+        let mask = &self.mask;
+        match self.blend_mode {
+            LayerBlendMode::Override => {
+                dest.override_with(&self.pose, weight.min(1.0), |node| mask.contains(node))
+            }
+            LayerBlendMode::Additive => {
+                dest.add_additive(&self.pose, reference, weight, |node| mask.contains(node))
+            }
+        }
+    }
+}
+
+/// Entry of a layer mask definition, it is a name of a node that will be included in the mask
+/// together with all its descendants.
+#[derive(Default, Debug, Visit, Clone, PartialEq, Inspect)]
+pub struct LayerMaskEntryDefinition {
+    pub node_name: String,
+}
+
+#[derive(Debug, Visit, Clone, Inspect)]
+pub struct LayerDefinition {
+    pub name: String,
+    pub weight: PoseWeight,
+    pub blend_mode: LayerBlendMode,
+    /// A set of subtrees affected by the layer. Empty mask means that the layer affects every node.
+    pub mask: Vec<LayerMaskEntryDefinition>,
+    #[inspect(skip)]
+    pub additive_reference: Handle<PoseNodeDefinition>,
+    #[inspect(skip)]
+    pub entry_state: Handle<StateDefinition>,
+}
+
+impl Default for LayerDefinition {
+    fn default() -> Self {
+        Self {
+            name: "Layer".to_owned(),
+            weight: PoseWeight::Constant(1.0),
+            blend_mode: Default::default(),
+            mask: Default::default(),
+            additive_reference: Default::default(),
+            entry_state: Default::default(),
+        }
+    }
+}
+
+impl LayerDefinition {
+    pub(crate) fn mask_names(&self) -> Vec<String> {
+        self.mask.iter().map(|e| e.node_name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{
+                layer::{LayerBlendMode, LayerMask, MachineLayer},
+                PoseWeight,
+            },
+            AnimationPose, LocalPose,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::node::Node,
+    };
+    use fxhash::FxHashMap;
+
+    fn pose_of(nodes: &[(Handle<Node>, Vector3<f32>)]) -> AnimationPose {
+        let mut pose = AnimationPose::default();
+        for (node, position) in nodes {
+            pose.add_local_pose(LocalPose {
+                node: *node,
+                position: *position,
+                ..Default::default()
+            });
+        }
+        pose
+    }
+
+    fn position_of(pose: &AnimationPose, node: Handle<Node>) -> Vector3<f32> {
+        pose.local_poses[&node].position
+    }
+
+    #[test]
+    fn test_layer_mask() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let c = Handle::new(3, 1);
+
+        let mut mask = LayerMask::default();
+        assert!(mask.contains(a));
+
+        mask.add_bone(b);
+        mask.add_bone(a);
+        mask.add_bone(b);
+        assert_eq!(mask.bones(), &[a, b]);
+        assert!(mask.contains(a));
+        assert!(!mask.contains(c));
+
+        mask.remap(|bone| if bone == a { c } else { Handle::NONE });
+        assert_eq!(mask.bones(), &[c]);
+    }
+
+    #[test]
+    fn test_override_layer() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+
+        let mut layer = MachineLayer::new("Layer");
+        layer.mask.add_bone(a);
+        layer.pose = pose_of(&[
+            (a, Vector3::new(2.0, 0.0, 0.0)),
+            (b, Vector3::new(2.0, 0.0, 0.0)),
+        ]);
+        layer.weight = PoseWeight::Constant(0.5);
+
+        let mut pose = pose_of(&[(a, Vector3::default()), (b, Vector3::default())]);
+        layer.blend_into(&mut pose, None, &FxHashMap::default());
+
+        assert_eq!(position_of(&pose, a), Vector3::new(1.0, 0.0, 0.0));
+        // Not in the mask.
+        assert_eq!(position_of(&pose, b), Vector3::default());
+    }
+
+    #[test]
+    fn test_additive_layer() {
+        let a = Handle::new(1, 1);
+
+        let mut layer = MachineLayer::new("Layer").with_blend_mode(LayerBlendMode::Additive);
+        layer.pose = pose_of(&[(a, Vector3::new(3.0, 1.0, 0.0))]);
+        layer.pose.local_poses.get_mut(&a).unwrap().rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 0.5);
+        let reference = pose_of(&[(a, Vector3::new(1.0, 1.0, 0.0))]);
+
+        let mut pose = pose_of(&[(a, Vector3::new(0.0, 0.0, 5.0))]);
+        layer.blend_into(&mut pose, Some(&reference), &FxHashMap::default());
+
+        assert_eq!(position_of(&pose, a), Vector3::new(2.0, 0.0, 5.0));
+        assert!(
+            (pose.local_poses[&a].rotation.angle() - 0.5).abs() < 1.0e-5,
+            "Rotation delta must be added"
+        );
+    }
+}
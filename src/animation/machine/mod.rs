@@ -40,7 +40,8 @@
 //!
 //! There are four transitions between three states each with its own rule. Rule
 //! is just Rule parameter which can have boolean value that indicates that transition
-//! should be activated.
+//! should be activated. More complex rules could be defined using condition expressions,
+//! see [`condition`] module docs for more info.
 //!
 //! Example:
 //!
@@ -86,7 +87,9 @@
 //!
 //! You can use multiple machines to animation single model - for example one machine can be for
 //! locomotion and other is for combat. This means that locomotion machine will take control over
-//! lower body and combat machine will control upper body.
+//! lower body and combat machine will control upper body. Alternatively, you can use animation
+//! layers of a single machine, they have their own state graphs and bone masks and can be blended
+//! in override or additive mode, see [`layer`] module docs for more info.
 
 use fxhash::FxHashMap;
 use std::cmp::Ordering;
@@ -94,14 +97,16 @@ use std::sync::Arc;
 
 use crate::{
     animation::{
+        ik::IkSolver,
         machine::{
             event::LimitedEventQueue,
+            layer::LayerDefinition,
             node::{BasePoseNode, PoseNodeDefinition},
             parameter::ParameterContainerDefinition,
             state::StateDefinition,
             transition::TransitionDefinition,
         },
-        AnimationContainer, AnimationPose,
+        Animation, AnimationContainer, AnimationPose, RootMotion,
     },
     core::futures::future::join_all,
     core::{
@@ -114,14 +119,18 @@ use crate::{
     resource::{
         absm::AbsmResource,
         model::{Model, ModelLoadError},
+        skeleton_mapping::SkeletonMappingResource,
     },
     scene::{graph::Graph, node::Node, Scene},
     utils::log::{Log, MessageKind},
 };
+use condition::ConditionContext;
 pub use event::Event;
 use fyrox_resource::ResourceState;
+pub use layer::{LayerBlendMode, LayerMask, MachineLayer};
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
+    blend_space::{BlendSpace, BlendSpacePoint},
     play::PlayAnimation,
     EvaluatePose, PoseNode,
 };
@@ -129,8 +138,10 @@ pub use parameter::{Parameter, ParameterContainer, PoseWeight};
 pub use state::State;
 pub use transition::Transition;
 
+pub mod condition;
 pub mod container;
 pub mod event;
+pub mod layer;
 pub mod node;
 pub mod parameter;
 pub mod state;
@@ -147,6 +158,10 @@ pub struct Machine {
     active_state: Handle<State>,
     entry_state: Handle<State>,
     active_transition: Handle<Transition>,
+    #[visit(optional)] // Backward compatibility
+    pub(crate) layers: Pool<MachineLayer>,
+    #[visit(optional)] // Backward compatibility
+    pub(crate) ik_solvers: Vec<IkSolver>,
 
     #[visit(skip)]
     final_pose: AnimationPose,
@@ -163,6 +178,8 @@ pub struct MachineDefinition {
     pub transitions: Pool<TransitionDefinition>,
     pub states: Pool<StateDefinition>,
     pub entry_state: Handle<StateDefinition>,
+    #[visit(optional)] // Backward compatibility
+    pub layers: Pool<LayerDefinition>,
 }
 
 /// An error that may occur during ABSM resource loading.
@@ -220,8 +237,14 @@ fn instantiate_node(
                 .unwrap();
 
             let animation = if matches!(*resource.state(), ResourceState::Ok(_)) {
-                *resource
-                    .retarget_animations_internal(root, graph, animations)
+                *animations_pack
+                    .retarget(
+                        resource,
+                        &play_animation.skeleton_mapping,
+                        root,
+                        graph,
+                        animations,
+                    )
                     .first()
                     .ok_or(MachineInstantiationError::InvalidAnimation)?
             } else {
@@ -263,6 +286,19 @@ fn instantiate_node(
                     .collect(),
             )
         }
+        PoseNodeDefinition::BlendSpace(blend_space) => PoseNode::make_blend_space(
+            blend_space.x_parameter.clone(),
+            blend_space.y_parameter.clone(),
+            blend_space
+                .points
+                .iter()
+                .map(|p| BlendSpacePoint {
+                    position: p.position,
+                    // Will be assigned on the next stage.
+                    pose_source: Default::default(),
+                })
+                .collect(),
+        ),
     };
 
     node.definition = definition_handle;
@@ -272,6 +308,7 @@ fn instantiate_node(
 
 pub struct AnimationsPack {
     animations: FxHashMap<String, Model>,
+    skeleton_mappings: FxHashMap<String, SkeletonMappingResource>,
 }
 
 impl AnimationsPack {
@@ -283,12 +320,61 @@ impl AnimationsPack {
 
         join_all(animations.values().cloned()).await;
 
-        Self { animations }
+        Self {
+            animations,
+            skeleton_mappings: Default::default(),
+        }
+    }
+
+    /// Loads skeleton mappings that are used to retarget animations of the pack.
+    pub async fn with_skeleton_mappings(
+        mut self,
+        paths: &[String],
+        resource_manager: ResourceManager,
+    ) -> Self {
+        self.skeleton_mappings = paths
+            .iter()
+            .map(|path| {
+                (
+                    path.clone(),
+                    resource_manager.request_skeleton_mapping(path),
+                )
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        join_all(self.skeleton_mappings.values().cloned()).await;
+
+        self
     }
 
     pub fn animations(&self) -> &FxHashMap<String, Model> {
         &self.animations
     }
+
+    pub fn skeleton_mappings(&self) -> &FxHashMap<String, SkeletonMappingResource> {
+        &self.skeleton_mappings
+    }
+
+    /// Retargets animations of given resource to a hierarchy starting from `root`, using skeleton
+    /// mapping with given path. Empty path means that no skeleton mapping will be used.
+    fn retarget(
+        &self,
+        resource: &Model,
+        skeleton_mapping: &str,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        animations: &mut AnimationContainer,
+    ) -> Vec<Handle<Animation>> {
+        match self.skeleton_mappings.get(skeleton_mapping) {
+            Some(skeleton_mapping) => resource.retarget_animations_with_mapping_internal(
+                root,
+                graph,
+                animations,
+                skeleton_mapping,
+            ),
+            None => resource.retarget_animations_internal(root, graph, animations),
+        }
+    }
 }
 
 impl MachineDefinition {
@@ -305,8 +391,28 @@ impl MachineDefinition {
             .collect()
     }
 
+    pub(crate) fn collect_skeleton_mapping_paths(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter_map(|node| {
+                if let PoseNodeDefinition::PlayAnimation(play_animation) = node {
+                    if play_animation.skeleton_mapping.is_empty() {
+                        None
+                    } else {
+                        Some(play_animation.skeleton_mapping.clone())
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub async fn animations(&self, resource_manager: ResourceManager) -> AnimationsPack {
-        AnimationsPack::load(&self.collect_animation_paths(), resource_manager).await
+        AnimationsPack::load(&self.collect_animation_paths(), resource_manager.clone())
+            .await
+            .with_skeleton_mappings(&self.collect_skeleton_mapping_paths(), resource_manager)
+            .await
     }
 
     /// Instantiates animation blending state machine to the specified scene for a given root node.
@@ -402,9 +508,50 @@ impl MachineDefinition {
                         unreachable!()
                     }
                 }
+                PoseNode::BlendSpace(blend_space) => {
+                    if let PoseNodeDefinition::BlendSpace(blend_space_definition) = definition {
+                        for (point, point_definition) in blend_space
+                            .points
+                            .iter_mut()
+                            .zip(blend_space_definition.points.iter())
+                        {
+                            point.pose_source = node_map
+                                .get(&point_definition.pose_source)
+                                .cloned()
+                                .unwrap_or_default();
+                        }
+                    } else {
+                        unreachable!()
+                    }
+                }
             }
         }
 
+        // Instantiate layers.
+        let mut layer_map = FxHashMap::default();
+        for (definition_handle, layer_definition) in self.layers.pair_iter() {
+            let mut layer = MachineLayer::new(&layer_definition.name)
+                .with_weight(layer_definition.weight.clone())
+                .with_blend_mode(layer_definition.blend_mode)
+                .with_mask(LayerMask::from_names(
+                    &scene.graph,
+                    root,
+                    &layer_definition.mask_names(),
+                ))
+                .with_additive_reference(
+                    node_map
+                        .get(&layer_definition.additive_reference)
+                        .cloned()
+                        .unwrap_or_default(),
+                );
+
+            layer.definition = definition_handle;
+
+            let instance_handle = machine.add_layer(layer);
+
+            layer_map.insert(definition_handle, instance_handle);
+        }
+
         // Instantiate states.
         let mut state_map = FxHashMap::default();
         for (definition_handle, state_definition) in self.states.pair_iter() {
@@ -414,6 +561,12 @@ impl MachineDefinition {
                     .get(&state_definition.root)
                     .cloned()
                     .unwrap_or_default(),
+            )
+            .with_layer(
+                layer_map
+                    .get(&state_definition.layer)
+                    .cloned()
+                    .unwrap_or_default(),
             );
 
             state.definition = definition_handle;
@@ -441,6 +594,8 @@ impl MachineDefinition {
                 rule: transition_definition.rule.clone(),
                 invert_rule: transition_definition.invert_rule,
                 blend_factor: 0.0,
+                condition: transition_definition.condition.clone(),
+                compiled_condition: None,
             });
         }
 
@@ -451,6 +606,15 @@ impl MachineDefinition {
                 .unwrap_or_default(),
         );
 
+        for (definition_handle, instance_handle) in layer_map.iter() {
+            machine.layer_mut(*instance_handle).set_entry_state(
+                state_map
+                    .get(&self.layers[*definition_handle].entry_state)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+        }
+
         Ok(scene.animation_machines.add(machine))
     }
 }
@@ -487,6 +651,22 @@ fn find_node_by_definition(
         .unwrap_or_default()
 }
 
+fn find_layer_by_definition(
+    layers: &Pool<MachineLayer>,
+    definition: Handle<LayerDefinition>,
+) -> Handle<MachineLayer> {
+    layers
+        .pair_iter()
+        .find_map(|(h, l)| {
+            if l.definition == definition {
+                Some(h)
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
 impl Machine {
     #[inline]
     pub fn new(root: Handle<Node>) -> Self {
@@ -500,6 +680,8 @@ impl Machine {
             active_state: Default::default(),
             entry_state: Default::default(),
             active_transition: Default::default(),
+            layers: Default::default(),
+            ik_solvers: Default::default(),
             parameters: Default::default(),
             events: LimitedEventQueue::new(2048),
             debug: false,
@@ -543,13 +725,52 @@ impl Machine {
 
     #[inline]
     pub fn add_state(&mut self, state: State) -> Handle<State> {
+        let layer = state.layer;
         let state = self.states.spawn(state);
-        if self.active_state.is_none() {
+        if let Some(layer) = self.layers.try_borrow_mut(layer) {
+            if layer.active_state.is_none() {
+                layer.active_state = state;
+            }
+        } else if self.active_state.is_none() {
             self.active_state = state;
         }
         state
     }
 
+    /// Adds new animation layer to the machine. Layers are blended on top of the base layer in
+    /// order of their creation. Use [`State::with_layer`] to add states to the layer.
+    #[inline]
+    pub fn add_layer(&mut self, layer: MachineLayer) -> Handle<MachineLayer> {
+        self.layers.spawn(layer)
+    }
+
+    #[inline]
+    pub fn layers(&self) -> &Pool<MachineLayer> {
+        &self.layers
+    }
+
+    #[inline]
+    pub fn layer_mut(&mut self, handle: Handle<MachineLayer>) -> &mut MachineLayer {
+        &mut self.layers[handle]
+    }
+
+    /// Adds new IK solver, solvers are applied in order of their addition by [`Self::solve_ik`].
+    #[inline]
+    pub fn add_ik_solver(&mut self, solver: IkSolver) -> usize {
+        self.ik_solvers.push(solver);
+        self.ik_solvers.len() - 1
+    }
+
+    #[inline]
+    pub fn ik_solvers(&self) -> &[IkSolver] {
+        &self.ik_solvers
+    }
+
+    #[inline]
+    pub fn ik_solvers_mut(&mut self) -> &mut Vec<IkSolver> {
+        &mut self.ik_solvers
+    }
+
     #[inline]
     pub fn add_transition(&mut self, transition: Transition) -> Handle<Transition> {
         self.transitions.spawn(transition)
@@ -570,6 +791,14 @@ impl Machine {
         self.events.pop()
     }
 
+    /// Returns events that occurred during last [`Self::evaluate_pose`] call. Unlike
+    /// [`Self::pop_event`], it does not consume the events. The engine uses it to pass the events
+    /// to scripts, see [`crate::script::ScriptTrait::on_animation_event`] for more info.
+    #[inline]
+    pub fn recent_events(&self) -> &[Event] {
+        self.events.recent()
+    }
+
     #[inline]
     pub fn resource(&self) -> Option<AbsmResource> {
         self.resource.clone()
@@ -581,6 +810,16 @@ impl Machine {
             transition.reset();
         }
 
+        for layer in self.layers.iter_mut() {
+            layer.reset();
+        }
+
+        for node in self.nodes.iter() {
+            if let PoseNode::BlendSpace(blend_space) = node {
+                blend_space.reset_phase();
+            }
+        }
+
         self.active_state = self.entry_state;
     }
 
@@ -689,6 +928,47 @@ impl Machine {
                     .unwrap_or_default()
             };
 
+            match definition
+                .layers
+                .alive_count()
+                .cmp(&self.layers.alive_count())
+            {
+                Ordering::Less => {
+                    // Some layers were deleted in definition, remove respective instances.
+                    let mut layers_to_remove = Vec::new();
+                    for (handle, layer) in self.layers.pair_iter() {
+                        if !definition.layers.is_valid_handle(layer.definition) {
+                            layers_to_remove.push(handle);
+                        }
+                    }
+
+                    for layer_to_remove in layers_to_remove {
+                        self.layers.free(layer_to_remove);
+                    }
+                }
+                Ordering::Equal => {
+                    // Do nothing.
+                }
+                Ordering::Greater => {
+                    // Some layers were added in definition, create respective instances.
+                    for (layer_definition_handle, layer_definition) in definition.layers.pair_iter()
+                    {
+                        if self
+                            .layers
+                            .iter()
+                            .all(|l| l.definition != layer_definition_handle)
+                        {
+                            // Rest of the properties will be synced on the next step.
+                            let mut layer = MachineLayer::new(&layer_definition.name);
+
+                            layer.definition = layer_definition_handle;
+
+                            let _ = self.layers.spawn(layer);
+                        }
+                    }
+                }
+            }
+
             match definition
                 .states
                 .alive_count()
@@ -721,7 +1001,10 @@ impl Machine {
                         {
                             let root = find_node_by_definition(&self.nodes, state_definition.root);
 
-                            let mut state = State::new(state_definition.name.as_ref(), root);
+                            let mut state =
+                                State::new(state_definition.name.as_ref(), root).with_layer(
+                                    find_layer_by_definition(&self.layers, state_definition.layer),
+                                );
 
                             state.definition = state_definition_handle;
 
@@ -777,6 +1060,7 @@ impl Machine {
                             );
 
                             transition.definition = transition_definition_handle;
+                            transition.condition = transition_definition.condition.clone();
 
                             let _ = self.transitions.spawn(transition);
                         }
@@ -796,11 +1080,16 @@ impl Machine {
                             let definition_animation = animations_pack
                                 .animations()
                                 .get(&play_animation_definition.animation);
+                            let definition_skeleton_mapping = animations_pack
+                                .skeleton_mappings()
+                                .get(&play_animation_definition.skeleton_mapping);
 
                             if animations.try_get(play_animation.animation).map_or(
                                 true,
                                 |current_animation| {
                                     definition_animation != current_animation.resource.as_ref()
+                                        || definition_skeleton_mapping
+                                            != current_animation.skeleton_mapping.as_ref()
                                 },
                             ) {
                                 animations.remove(play_animation.animation);
@@ -810,9 +1099,13 @@ impl Machine {
                                 {
                                     if matches!(*definition_animation.state(), ResourceState::Ok(_))
                                     {
-                                        definition_animation
-                                            .retarget_animations_internal(
-                                                self.root, graph, animations,
+                                        animations_pack
+                                            .retarget(
+                                                definition_animation,
+                                                &play_animation_definition.skeleton_mapping,
+                                                self.root,
+                                                graph,
+                                                animations,
                                             )
                                             .first()
                                             .cloned()
@@ -893,6 +1186,32 @@ impl Machine {
                             }
                         }
                     }
+                    PoseNode::BlendSpace(blend_space) => {
+                        if let PoseNodeDefinition::BlendSpace(blend_space_definition) =
+                            node_definition
+                        {
+                            *blend_space = BlendSpace {
+                                base: BasePoseNode {
+                                    definition: blend_space.definition,
+                                },
+                                x_parameter: blend_space_definition.x_parameter.clone(),
+                                y_parameter: blend_space_definition.y_parameter.clone(),
+                                points: blend_space_definition
+                                    .points
+                                    .iter()
+                                    .map(|p| BlendSpacePoint {
+                                        position: p.position,
+                                        pose_source: fetch_node_by_definition(p.pose_source),
+                                    })
+                                    .collect(),
+                                phase: blend_space.phase.clone(),
+                                last_step: blend_space.last_step.clone(),
+                                // Points could be changed, so triangulation must be rebuilt.
+                                triangles: Default::default(),
+                                output_pose: std::mem::take(&mut blend_space.output_pose),
+                            }
+                        }
+                    }
                 }
             }
 
@@ -904,6 +1223,7 @@ impl Machine {
                     definition: state.definition,
                     name: state_definition.name.clone(),
                     root: find_node_by_definition(&self.nodes, state_definition.root),
+                    layer: find_layer_by_definition(&self.layers, state_definition.layer),
                 };
             }
 
@@ -920,6 +1240,50 @@ impl Machine {
                     rule: transition_definition.rule.clone(),
                     invert_rule: transition_definition.invert_rule,
                     blend_factor: transition.blend_factor,
+                    // Keep compiled condition if the expression wasn't changed.
+                    compiled_condition: if transition.condition == transition_definition.condition {
+                        transition.compiled_condition.take()
+                    } else {
+                        None
+                    },
+                    condition: transition_definition.condition.clone(),
+                };
+            }
+
+            for layer in self.layers.iter_mut() {
+                let layer_definition = &definition.layers[layer.definition];
+
+                let entry_state =
+                    find_state_by_definition(&self.states, layer_definition.entry_state);
+                let active_transition = if self.transitions.is_valid_handle(layer.active_transition)
+                {
+                    layer.active_transition
+                } else {
+                    Handle::NONE
+                };
+                // Keep current state (if it still exists), otherwise restart from the entry state.
+                let active_state = if self.states.is_valid_handle(layer.active_state)
+                    || active_transition.is_some()
+                {
+                    layer.active_state
+                } else {
+                    entry_state
+                };
+
+                *layer = MachineLayer {
+                    definition: layer.definition,
+                    name: layer_definition.name.clone(),
+                    weight: layer_definition.weight.clone(),
+                    blend_mode: layer_definition.blend_mode,
+                    mask: LayerMask::from_names(graph, self.root, &layer_definition.mask_names()),
+                    additive_reference: find_node_by_definition(
+                        &self.nodes,
+                        layer_definition.additive_reference,
+                    ),
+                    entry_state,
+                    active_state,
+                    active_transition,
+                    pose: std::mem::take(&mut layer.pose),
                 };
             }
 
@@ -932,110 +1296,246 @@ impl Machine {
     }
 
     pub fn evaluate_pose(&mut self, animations: &AnimationContainer, dt: f32) -> &AnimationPose {
-        self.final_pose.reset();
+        let mut final_pose = std::mem::take(&mut self.final_pose);
+        final_pose.reset();
 
-        if self.active_state.is_some() || self.active_transition.is_some() {
+        self.events.clear_recent();
+
+        if self.active_state.is_some()
+            || self.active_transition.is_some()
+            || self.layers.alive_count() > 0
+        {
             // Gather actual poses for each state.
             for state in self.states.iter_mut() {
                 state.update(&self.nodes, &self.parameters, animations, dt);
             }
 
-            if self.active_transition.is_none() {
-                // Find transition.
-                for (handle, transition) in self.transitions.pair_iter_mut() {
-                    if transition.dest() == self.active_state
-                        || transition.source() != self.active_state
-                    {
-                        continue;
-                    }
-                    if let Some(Parameter::Rule(mut active)) =
-                        self.parameters.get(transition.rule()).cloned()
-                    {
-                        if transition.invert_rule {
-                            active = !active;
-                        }
-
-                        if active {
-                            self.events.push(Event::StateLeave(self.active_state));
-                            if self.debug {
-                                Log::writeln(
-                                    MessageKind::Information,
-                                    format!(
-                                        "Leaving state: {}",
-                                        self.states[self.active_state].name()
-                                    ),
-                                );
-                            }
+            // Base layer.
+            let (active_state, active_transition) = self.update_state_graph(
+                self.active_state,
+                self.active_transition,
+                animations,
+                dt,
+                &mut final_pose,
+            );
+            self.active_state = active_state;
+            self.active_transition = active_transition;
+
+            // Each layer is blended on top of the result of the layers below it.
+            let mut layers = std::mem::take(&mut self.layers);
+            for layer in layers.iter_mut() {
+                let mut layer_pose = std::mem::take(&mut layer.pose);
+                layer_pose.reset();
+
+                let (active_state, active_transition) = self.update_state_graph(
+                    layer.active_state,
+                    layer.active_transition,
+                    animations,
+                    dt,
+                    &mut layer_pose,
+                );
+                layer.active_state = active_state;
+                layer.active_transition = active_transition;
+                layer.pose = layer_pose;
+
+                let reference = if layer.blend_mode == LayerBlendMode::Additive {
+                    self.nodes
+                        .try_borrow(layer.additive_reference)
+                        .map(|node| node.eval_pose(&self.nodes, &self.parameters, animations, 0.0))
+                } else {
+                    None
+                };
 
-                            self.events.push(Event::StateEnter(transition.source()));
-                            if self.debug {
-                                Log::writeln(
-                                    MessageKind::Information,
-                                    format!(
-                                        "Entering state: {}",
-                                        self.states[transition.source()].name()
-                                    ),
-                                );
-                            }
+                layer.blend_into(&mut final_pose, reference.as_deref(), &self.parameters);
+            }
+            self.layers = layers;
+        }
 
-                            self.active_state = Handle::NONE;
+        self.final_pose = final_pose;
 
-                            self.active_transition = handle;
-                            self.events
-                                .push(Event::ActiveTransitionChanged(self.active_transition));
+        &self.final_pose
+    }
 
-                            break;
-                        }
-                    }
-                }
+    /// Drives playback position of animations that are sampled by blend spaces of the machine, so
+    /// signals of the animations are emitted in sync with the poses of the blend spaces. Such
+    /// animations are not ticked by [`AnimationContainer::update_animations`] at the next update.
+    /// Must be called after [`Self::evaluate_pose`], the engine does this automatically for
+    /// machines of a scene.
+    pub fn sync_animations(&self, animations: &mut AnimationContainer) {
+        for node in self.nodes.iter() {
+            if let PoseNode::BlendSpace(blend_space) = node {
+                blend_space.sync_animations(&self.nodes, animations);
             }
+        }
+    }
 
-            // Double check for active transition because we can have empty machine.
-            if self.active_transition.is_some() {
-                let transition = &mut self.transitions[self.active_transition];
+    /// Applies IK solvers of the machine to the pose produced by last [`Self::evaluate_pose`]
+    /// call. Weights of the solvers are taken from the parameters of the machine. Must be called
+    /// before the pose is applied to the graph.
+    pub fn solve_ik(&mut self, graph: &Graph) -> &AnimationPose {
+        for solver in self.ik_solvers.iter() {
+            solver.solve(&mut self.final_pose, graph, &self.parameters);
+        }
 
-                // Blend between source and dest states.
-                if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&source_pose, 1.0 - transition.blend_factor());
-                }
-                if let Some(dest_pose) = self.states[transition.dest()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&dest_pose, transition.blend_factor());
-                }
+        &self.final_pose
+    }
 
-                transition.update(dt);
+    /// Returns the pose produced by last [`Self::evaluate_pose`] call.
+    pub fn pose(&self) -> &AnimationPose {
+        &self.final_pose
+    }
 
-                if transition.is_done() {
-                    transition.reset();
+    /// Returns root motion of the pose produced by last [`Self::evaluate_pose`] call, it is
+    /// blended from root motions of every animation that contributed to the pose. Apply it to a
+    /// rigid body or a character controller to move the character.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.final_pose.root_motion()
+    }
 
-                    self.active_transition = Handle::NONE;
-                    self.events
-                        .push(Event::ActiveTransitionChanged(self.active_transition));
+    /// Performs a step of a state graph (either the base one or a graph of a layer) that is
+    /// defined by given active state and transition, writes resulting pose to `pose` and returns
+    /// new active state and transition.
+    fn update_state_graph(
+        &mut self,
+        mut active_state: Handle<State>,
+        mut active_transition: Handle<Transition>,
+        animations: &AnimationContainer,
+        dt: f32,
+        pose: &mut AnimationPose,
+    ) -> (Handle<State>, Handle<Transition>) {
+        if active_state.is_none() && active_transition.is_none() {
+            return (active_state, active_transition);
+        }
 
-                    self.active_state = transition.dest();
-                    self.events
-                        .push(Event::ActiveStateChanged(self.active_state));
+        if active_transition.is_none() {
+            // Triggers that were consumed by activated transition.
+            let mut consumed_triggers = Vec::new();
+
+            // Find transition.
+            for (handle, transition) in self.transitions.pair_iter_mut() {
+                if transition.dest() == active_state || transition.source() != active_state {
+                    continue;
+                }
+
+                let parameters = &self.parameters;
+                let is_trigger =
+                    |name: &str| matches!(parameters.get(name), Some(Parameter::Trigger(_)));
+
+                let active = if !transition.condition().is_empty() {
+                    let source_time =
+                        self.states[transition.source()].normalized_time(&self.nodes, animations);
+                    match transition.compiled_condition() {
+                        Some(condition) => condition.evaluate_with_triggers(
+                            &ConditionContext {
+                                parameters,
+                                source_time,
+                            },
+                            &mut consumed_triggers,
+                        ),
+                        None => false,
+                    }
+                } else {
+                    match parameters.get(transition.rule()) {
+                        Some(Parameter::Rule(active)) | Some(Parameter::Trigger(active)) => {
+                            let active = *active != transition.invert_rule;
+                            if active && is_trigger(transition.rule()) {
+                                consumed_triggers.push(transition.rule().to_owned());
+                            }
+                            active
+                        }
+                        _ => false,
+                    }
+                };
 
+                if active {
+                    self.events.push(Event::StateLeave(active_state));
                     if self.debug {
                         Log::writeln(
                             MessageKind::Information,
-                            format!(
-                                "Active state changed: {}",
-                                self.states[self.active_state].name()
-                            ),
+                            format!("Leaving state: {}", self.states[active_state].name()),
                         );
                     }
+
+                    self.events.push(Event::StateEnter(transition.dest()));
+                    reset_blend_spaces(&self.nodes, self.states[transition.dest()].root);
+                    if self.debug {
+                        Log::writeln(
+                            MessageKind::Information,
+                            format!("Entering state: {}", self.states[transition.dest()].name()),
+                        );
+                    }
+
+                    active_state = Handle::NONE;
+
+                    active_transition = handle;
+                    self.events
+                        .push(Event::ActiveTransitionChanged(active_transition));
+                    self.events
+                        .push(Event::TransitionStarted(active_transition));
+
+                    break;
                 }
-            } else {
-                // We must have active state all the time when we do not have any active transition.
-                // Just get pose from active state.
-                if let Some(active_state_pose) = self.states[self.active_state].pose(&self.nodes) {
-                    active_state_pose.clone_into(&mut self.final_pose);
+            }
+
+            for name in consumed_triggers {
+                self.set_parameter(&name, Parameter::Trigger(false));
+            }
+        }
+
+        // Double check for active transition because we can have empty machine.
+        if active_transition.is_some() {
+            let transition = &mut self.transitions[active_transition];
+
+            // Blend between source and dest states.
+            if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
+                pose.blend_with(&source_pose, 1.0 - transition.blend_factor());
+            }
+            if let Some(dest_pose) = self.states[transition.dest()].pose(&self.nodes) {
+                pose.blend_with(&dest_pose, transition.blend_factor());
+            }
+
+            transition.update(dt);
+
+            if transition.is_done() {
+                transition.reset();
+
+                self.events.push(Event::TransitionEnded(active_transition));
+
+                active_transition = Handle::NONE;
+                self.events
+                    .push(Event::ActiveTransitionChanged(active_transition));
+
+                active_state = transition.dest();
+                self.events.push(Event::ActiveStateChanged(active_state));
+
+                if self.debug {
+                    Log::writeln(
+                        MessageKind::Information,
+                        format!("Active state changed: {}", self.states[active_state].name()),
+                    );
                 }
             }
+        } else {
+            // We must have active state all the time when we do not have any active transition.
+            // Just get pose from active state.
+            if let Some(active_state_pose) = self.states[active_state].pose(&self.nodes) {
+                active_state_pose.clone_into(pose);
+            }
         }
 
-        &self.final_pose
+        (active_state, active_transition)
+    }
+}
+
+// Rewinds every blend space in the tree of nodes that starts from `root`.
+fn reset_blend_spaces(nodes: &Pool<PoseNode>, root: Handle<PoseNode>) {
+    if let Some(node) = nodes.try_borrow(root) {
+        if let PoseNode::BlendSpace(blend_space) = node {
+            blend_space.reset_phase();
+        }
+
+        for child in node.children() {
+            reset_blend_spaces(nodes, child);
+        }
     }
 }
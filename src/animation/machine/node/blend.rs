@@ -125,6 +125,10 @@ impl BlendAnimations {
             output_pose: Default::default(),
         }
     }
+
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        self.pose_sources.iter().map(|s| s.pose_source).collect()
+    }
 }
 
 impl EvaluatePose for BlendAnimations {
@@ -231,6 +235,10 @@ impl DerefMut for BlendAnimationsByIndexDefinition {
 }
 
 impl BlendAnimationsByIndex {
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        self.inputs.iter().map(|s| s.pose_source).collect()
+    }
+
     pub fn new(index_parameter: String, inputs: Vec<IndexedBlendInput>) -> Self {
         Self {
             base: Default::default(),
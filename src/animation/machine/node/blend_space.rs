@@ -0,0 +1,666 @@
+//! Blend space is a pose node that mixes its input poses depending on the position of a sample
+//! point in one or two dimensional parametric space. See [`BlendSpace`] docs for more info.
+
+use crate::{
+    animation::{
+        machine::{
+            node::{BasePoseNode, BasePoseNodeDefinition, PoseNodeDefinition},
+            EvaluatePose, Parameter, ParameterContainer, PoseNode,
+        },
+        AnimationContainer, AnimationPose,
+    },
+    core::{
+        algebra::{Vector2, Vector3},
+        inspect::{Inspect, PropertyInfo},
+        math::{get_barycentric_coords_2d, triangulator::triangulate},
+        pool::{Handle, Pool},
+        visitor::prelude::*,
+    },
+};
+use std::{
+    cell::{Cell, Ref, RefCell},
+    cmp::Ordering,
+    ops::{Deref, DerefMut},
+};
+
+const EPSILON: f32 = 1.0e-5;
+
+/// A sample of a blend space - a pose source placed at some position in the parametric space.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct BlendSpacePoint {
+    pub position: Vector2<f32>,
+    pub pose_source: Handle<PoseNode>,
+}
+
+#[derive(Default, Debug, Visit, Clone, Inspect)]
+pub struct BlendSpacePointDefinition {
+    pub position: Vector2<f32>,
+    #[inspect(skip)]
+    pub pose_source: Handle<PoseNodeDefinition>,
+}
+
+/// Machine node that blends its input poses using position of a sample point in a parametric
+/// space. The space could be one dimensional (only `x_parameter` is set) or two dimensional (both
+/// `x_parameter` and `y_parameter` are set); coordinates of the sample point are taken from Weight
+/// parameters of the machine with respective names.
+///
+/// In 1D mode the node finds two samples closest to the sample point from both sides and linearly
+/// interpolates between them. In 2D mode the samples are triangulated and weights are barycentric
+/// coordinates of the sample point in a triangle that contains it. If the sample point is outside
+/// of the space, it is projected onto the closest edge.
+///
+/// A typical use case is locomotion: place walk/run animations for different directions at
+/// respective velocities and feed the actual velocity of a character to the parameters.
+///
+/// ## Time synchronization
+///
+/// Input animations usually have different length (for example a walk cycle is longer than a run
+/// cycle), blending them as is will cause feet to slide. To prevent this, the node keeps its own
+/// normalized playback position (phase) that advances with the speed defined by weighted average of
+/// durations of input animations. Every input that is a PlayAnimation node is sampled at this
+/// phase, other inputs are evaluated as usual. Playback position of animations of such inputs is
+/// driven by the phase as well (see [`crate::animation::machine::Machine::sync_animations`]), so
+/// their signals are emitted in sync with the blended pose. The phase is reset when a state of the
+/// node is entered.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct BlendSpace {
+    pub base: BasePoseNode,
+    pub(crate) x_parameter: String,
+    pub(crate) y_parameter: String,
+    pub(crate) points: Vec<BlendSpacePoint>,
+    pub(crate) phase: Cell<f32>,
+    // Unwrapped phases at the beginning and at the end of the last update.
+    #[visit(skip)]
+    pub(crate) last_step: Cell<(f32, f32)>,
+    #[visit(skip)]
+    pub(crate) triangles: RefCell<Option<Vec<[usize; 3]>>>,
+    #[visit(skip)]
+    pub(crate) output_pose: RefCell<AnimationPose>,
+}
+
+impl Deref for BlendSpace {
+    type Target = BasePoseNode;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpace {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+#[derive(Default, Debug, Visit, Clone, Inspect)]
+pub struct BlendSpaceDefinition {
+    pub base: BasePoseNodeDefinition,
+    pub x_parameter: String,
+    /// Leave empty to make one dimensional blend space.
+    pub y_parameter: String,
+    pub points: Vec<BlendSpacePointDefinition>,
+}
+
+impl BlendSpaceDefinition {
+    pub fn children(&self) -> Vec<Handle<PoseNodeDefinition>> {
+        self.points.iter().map(|p| p.pose_source).collect()
+    }
+
+    /// Returns true if the blend space has two dimensions.
+    pub fn is_2d(&self) -> bool {
+        !self.y_parameter.is_empty()
+    }
+}
+
+impl Deref for BlendSpaceDefinition {
+    type Target = BasePoseNodeDefinition;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendSpaceDefinition {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl BlendSpace {
+    /// Creates new blend space. Pass empty `y_parameter` to create one dimensional blend space.
+    pub fn new(x_parameter: String, y_parameter: String, points: Vec<BlendSpacePoint>) -> Self {
+        Self {
+            base: Default::default(),
+            x_parameter,
+            y_parameter,
+            points,
+            phase: Cell::new(0.0),
+            last_step: Default::default(),
+            triangles: Default::default(),
+            output_pose: Default::default(),
+        }
+    }
+
+    /// Returns true if the blend space has two dimensions.
+    pub fn is_2d(&self) -> bool {
+        !self.y_parameter.is_empty()
+    }
+
+    pub fn x_parameter(&self) -> &str {
+        &self.x_parameter
+    }
+
+    pub fn y_parameter(&self) -> &str {
+        &self.y_parameter
+    }
+
+    pub fn points(&self) -> &[BlendSpacePoint] {
+        &self.points
+    }
+
+    /// Replaces samples of the blend space.
+    pub fn set_points(&mut self, points: Vec<BlendSpacePoint>) {
+        self.points = points;
+        self.triangles.replace(None);
+    }
+
+    /// Returns current normalized (in `[0; 1]` range) playback position of the blend space.
+    pub fn phase(&self) -> f32 {
+        self.phase.get()
+    }
+
+    /// Rewinds the blend space to the beginning. The machine does this automatically when a state
+    /// of the node is entered.
+    pub fn reset_phase(&self) {
+        self.phase.set(0.0);
+        self.last_step.set((0.0, 0.0));
+    }
+
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        self.points.iter().map(|p| p.pose_source).collect()
+    }
+
+    /// Moves playback position of animations of PlayAnimation inputs by the last step of the
+    /// phase, emitting their signals.
+    pub(crate) fn sync_animations(
+        &self,
+        nodes: &Pool<PoseNode>,
+        animations: &mut AnimationContainer,
+    ) {
+        let (from, to) = self.last_step.get();
+        for point in self.points.iter() {
+            if let Some(PoseNode::PlayAnimation(play_animation)) =
+                nodes.try_borrow(point.pose_source)
+            {
+                if let Some(animation) = animations.try_get_mut(play_animation.animation) {
+                    if !animation.is_enabled() {
+                        continue;
+                    }
+
+                    let (from, to) = if animation.get_speed() < 0.0 {
+                        (1.0 - from, 1.0 - to)
+                    } else {
+                        (from, to)
+                    };
+                    let range = animation.playback_range();
+                    let length = range.end - range.start;
+                    animation.drive(range.start + from * length, range.start + to * length);
+                }
+            }
+        }
+    }
+
+    /// Returns position of the sample point in the space, coordinates are taken from respective
+    /// Weight parameters. Missing parameters are treated as zero.
+    pub fn sample_position(&self, params: &ParameterContainer) -> Vector2<f32> {
+        let fetch = |name: &str| {
+            if let Some(Parameter::Weight(weight)) = params.get(name) {
+                *weight
+            } else {
+                0.0
+            }
+        };
+
+        Vector2::new(
+            fetch(&self.x_parameter),
+            if self.is_2d() {
+                fetch(&self.y_parameter)
+            } else {
+                0.0
+            },
+        )
+    }
+
+    /// Calculates weights of samples for the given sample point. Returns a list of pairs
+    /// `(point index, weight)`, weights sum up to one.
+    pub fn weights(&self, sample: Vector2<f32>) -> Vec<(usize, f32)> {
+        if self.is_2d() {
+            let positions = self.points.iter().map(|p| p.position).collect::<Vec<_>>();
+            let mut triangles = self.triangles.borrow_mut();
+            let triangles = triangles.get_or_insert_with(|| triangulate_blend_space(&positions));
+            blend_space_weights_2d(&positions, triangles, sample)
+        } else {
+            let positions = self.points.iter().map(|p| p.position.x).collect::<Vec<_>>();
+            blend_space_weights_1d(&positions, sample.x)
+        }
+    }
+
+    fn sample_duration(
+        &self,
+        point: &BlendSpacePoint,
+        nodes: &Pool<PoseNode>,
+        animations: &AnimationContainer,
+    ) -> Option<f32> {
+        if let Some(PoseNode::PlayAnimation(play_animation)) = nodes.try_borrow(point.pose_source) {
+            animations
+                .try_get(play_animation.animation)
+                .and_then(|animation| {
+                    let range = animation.playback_range();
+                    let speed = animation.get_speed().abs();
+                    if speed > 0.0 && range.end > range.start {
+                        Some((range.end - range.start) / speed)
+                    } else {
+                        None
+                    }
+                })
+        } else {
+            None
+        }
+    }
+}
+
+impl EvaluatePose for BlendSpace {
+    fn eval_pose(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+    ) -> Ref<AnimationPose> {
+        self.output_pose.borrow_mut().reset();
+
+        let weights = self.weights(self.sample_position(params));
+
+        // Advance the phase using weighted average duration of the animations.
+        let mut duration = 0.0;
+        let mut total_weight = 0.0;
+        for &(index, weight) in weights.iter() {
+            if let Some(sample_duration) =
+                self.sample_duration(&self.points[index], nodes, animations)
+            {
+                duration += sample_duration * weight;
+                total_weight += weight;
+            }
+        }
+        let prev_phase = self.phase.get();
+        let mut phase_delta = 0.0;
+        if total_weight > 0.0 && duration > 0.0 {
+            phase_delta = dt * total_weight / duration;
+            let phase = prev_phase + phase_delta;
+            self.phase.set(phase - phase.floor());
+        }
+        self.last_step.set((prev_phase, prev_phase + phase_delta));
+
+        for &(index, weight) in weights.iter() {
+            if weight <= 0.0 {
+                continue;
+            }
+
+            match nodes.try_borrow(self.points[index].pose_source) {
+                Some(PoseNode::PlayAnimation(play_animation)) => {
+                    if let Some(animation) = animations.try_get(play_animation.animation) {
+                        let time = if animation.get_speed() < 0.0 {
+                            1.0 - self.phase.get()
+                        } else {
+                            self.phase.get()
+                        };
+                        let mut output_pose = play_animation.output_pose.borrow_mut();
+                        animation.evaluate_pose_at_normalized(time, &mut output_pose);
+
+                        // Root motion is synchronized with the phase as well.
+                        let root_motion = if animation.get_speed() < 0.0 {
+                            animation.root_motion_between_normalized(
+                                1.0 - prev_phase,
+                                1.0 - prev_phase - phase_delta,
+                            )
+                        } else {
+                            animation.root_motion_between_normalized(
+                                prev_phase,
+                                prev_phase + phase_delta,
+                            )
+                        };
+                        output_pose.set_root_motion(root_motion);
+                    }
+                    self.output_pose
+                        .borrow_mut()
+                        .blend_with(&play_animation.output_pose.borrow(), weight);
+                }
+                Some(pose_source) => {
+                    self.output_pose.borrow_mut().blend_with(
+                        &pose_source.eval_pose(nodes, params, animations, dt),
+                        weight,
+                    );
+                }
+                None => (),
+            }
+        }
+
+        self.output_pose.borrow()
+    }
+
+    fn pose(&self) -> Ref<AnimationPose> {
+        self.output_pose.borrow()
+    }
+}
+
+fn cross(o: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    (a - o).perp(&(b - o))
+}
+
+/// Returns indices of points that form counter-clockwise convex hull (monotone chain algorithm).
+fn convex_hull(points: &[Vector2<f32>]) -> Vec<usize> {
+    let mut indices = (0..points.len()).collect::<Vec<_>>();
+    indices.sort_by(|a, b| {
+        let (a, b) = (points[*a], points[*b]);
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+
+    let mut hull: Vec<usize> = Vec::with_capacity(points.len() + 1);
+
+    // Lower part.
+    for &i in indices.iter() {
+        while hull.len() >= 2
+            && cross(
+                points[hull[hull.len() - 2]],
+                points[hull[hull.len() - 1]],
+                points[i],
+            ) <= EPSILON
+        {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+
+    // Upper part.
+    let lower_len = hull.len() + 1;
+    for &i in indices.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && cross(
+                points[hull[hull.len() - 2]],
+                points[hull[hull.len() - 1]],
+                points[i],
+            ) <= EPSILON
+        {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+
+    // Last point is the same as the first one.
+    hull.pop();
+
+    hull
+}
+
+fn triangle_area(points: &[Vector2<f32>], triangle: [usize; 3]) -> f32 {
+    cross(
+        points[triangle[0]],
+        points[triangle[1]],
+        points[triangle[2]],
+    )
+    .abs()
+        * 0.5
+}
+
+/// Triangulates samples of a two dimensional blend space. The convex hull of the samples is
+/// triangulated first, then each inner sample splits the triangles it lies in. Returns an empty
+/// list if there is less than three samples or all the samples are collinear.
+pub fn triangulate_blend_space(points: &[Vector2<f32>]) -> Vec<[usize; 3]> {
+    let hull = convex_hull(points);
+    if hull.len() < 3 {
+        return Vec::new();
+    }
+
+    let polygon = hull
+        .iter()
+        .map(|i| Vector3::new(points[*i].x, points[*i].y, 0.0))
+        .collect::<Vec<_>>();
+    let mut hull_triangles = Vec::new();
+    triangulate(&polygon, &mut hull_triangles);
+
+    let mut triangles = hull_triangles
+        .into_iter()
+        .map(|t| [hull[t[0]], hull[t[1]], hull[t[2]]])
+        .collect::<Vec<_>>();
+
+    for (i, point) in points.iter().enumerate() {
+        if hull.contains(&i) {
+            continue;
+        }
+
+        let mut split = Vec::with_capacity(triangles.len() + 2);
+        for triangle in triangles {
+            let [a, b, c] = triangle;
+            let (u, v, w) = get_barycentric_coords_2d(*point, points[a], points[b], points[c]);
+            if u >= -EPSILON && v >= -EPSILON && w >= -EPSILON {
+                // Points on edges produce degenerate triangles, skip them.
+                for sub_triangle in [[i, b, c], [a, i, c], [a, b, i]] {
+                    if triangle_area(points, sub_triangle) > EPSILON {
+                        split.push(sub_triangle);
+                    }
+                }
+            } else {
+                split.push(triangle);
+            }
+        }
+        triangles = split;
+    }
+
+    triangles
+}
+
+/// Calculates weights of samples of a two dimensional blend space. `triangles` must be produced
+/// by [`triangulate_blend_space`] from the same set of points.
+pub fn blend_space_weights_2d(
+    points: &[Vector2<f32>],
+    triangles: &[[usize; 3]],
+    sample: Vector2<f32>,
+) -> Vec<(usize, f32)> {
+    for &[a, b, c] in triangles {
+        let (u, v, w) = get_barycentric_coords_2d(sample, points[a], points[b], points[c]);
+        if u >= -EPSILON && v >= -EPSILON && w >= -EPSILON {
+            let (u, v, w) = (u.max(0.0), v.max(0.0), w.max(0.0));
+            let sum = u + v + w;
+            return vec![(a, u / sum), (b, v / sum), (c, w / sum)];
+        }
+    }
+
+    // Sample point is outside of the space (or the space is degenerated), project it on the
+    // closest edge.
+    let mut closest: Option<(f32, usize, usize, f32)> = None;
+    let mut check_edge = |a: usize, b: usize| {
+        let edge = points[b] - points[a];
+        let len_sqr = edge.norm_squared();
+        let t = if len_sqr > EPSILON {
+            ((sample - points[a]).dot(&edge) / len_sqr).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let distance = (points[a] + edge.scale(t)).metric_distance(&sample);
+        if closest.map_or(true, |(min_distance, ..)| distance < min_distance) {
+            closest = Some((distance, a, b, t));
+        }
+    };
+
+    if triangles.is_empty() {
+        for a in 0..points.len() {
+            for b in (a + 1)..points.len() {
+                check_edge(a, b);
+            }
+        }
+    } else {
+        for &[a, b, c] in triangles {
+            check_edge(a, b);
+            check_edge(b, c);
+            check_edge(c, a);
+        }
+    }
+
+    if let Some((_, a, b, t)) = closest {
+        vec![(a, 1.0 - t), (b, t)]
+    } else if points.len() == 1 {
+        vec![(0, 1.0)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Calculates weights of samples of a one dimensional blend space.
+pub fn blend_space_weights_1d(positions: &[f32], sample: f32) -> Vec<(usize, f32)> {
+    let mut left: Option<usize> = None;
+    let mut right: Option<usize> = None;
+    for (i, position) in positions.iter().enumerate() {
+        if *position <= sample && left.map_or(true, |l| *position > positions[l]) {
+            left = Some(i);
+        }
+        if *position >= sample && right.map_or(true, |r| *position < positions[r]) {
+            right = Some(i);
+        }
+    }
+
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            let span = positions[right] - positions[left];
+            if span > EPSILON {
+                let t = (sample - positions[left]) / span;
+                vec![(left, 1.0 - t), (right, t)]
+            } else {
+                vec![(left, 1.0)]
+            }
+        }
+        (Some(index), None) | (None, Some(index)) => vec![(index, 1.0)],
+        (None, None) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{
+                node::blend_space::{
+                    blend_space_weights_1d, blend_space_weights_2d, triangulate_blend_space,
+                    BlendSpace, BlendSpacePoint,
+                },
+                EvaluatePose, ParameterContainer, PoseNode,
+            },
+            Animation, AnimationContainer, AnimationSignal,
+        },
+        core::{algebra::Vector2, pool::Pool},
+    };
+
+    fn weight_of(weights: &[(usize, f32)], index: usize) -> f32 {
+        weights
+            .iter()
+            .filter(|(i, _)| *i == index)
+            .map(|(_, w)| *w)
+            .sum()
+    }
+
+    #[test]
+    fn test_blend_space_1d_weights() {
+        let positions = [2.0, 0.0, 1.0];
+
+        let weights = blend_space_weights_1d(&positions, 0.25);
+        assert!((weight_of(&weights, 1) - 0.75).abs() < 1.0e-5);
+        assert!((weight_of(&weights, 2) - 0.25).abs() < 1.0e-5);
+
+        assert_eq!(blend_space_weights_1d(&positions, -1.0), vec![(1, 1.0)]);
+        assert_eq!(blend_space_weights_1d(&positions, 3.0), vec![(0, 1.0)]);
+        assert_eq!(blend_space_weights_1d(&positions, 1.0), vec![(2, 1.0)]);
+    }
+
+    #[test]
+    fn test_blend_space_2d_triangulation() {
+        // Square with a point in the center.
+        let points = [
+            Vector2::new(-1.0, -1.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(-1.0, 1.0),
+            Vector2::new(0.0, 0.0),
+        ];
+
+        let triangles = triangulate_blend_space(&points);
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|t| t.contains(&4)));
+
+        // Sample in the center must be fully defined by the center point.
+        let weights = blend_space_weights_2d(&points, &triangles, Vector2::new(0.0, 0.0));
+        assert!((weight_of(&weights, 4) - 1.0).abs() < 1.0e-5);
+
+        // Weights must sum up to one.
+        let weights = blend_space_weights_2d(&points, &triangles, Vector2::new(0.3, -0.6));
+        let sum = weights.iter().map(|(_, w)| *w).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1.0e-5);
+
+        // Sample outside must be projected on the closest edge.
+        let weights = blend_space_weights_2d(&points, &triangles, Vector2::new(3.0, 0.0));
+        assert!((weight_of(&weights, 1) - 0.5).abs() < 1.0e-5);
+        assert!((weight_of(&weights, 2) - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_blend_space_degenerated() {
+        let points = [Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)];
+        assert!(triangulate_blend_space(&points).is_empty());
+
+        let weights = blend_space_weights_2d(&points, &[], Vector2::new(0.5, 1.0));
+        assert!((weight_of(&weights, 0) - 0.75).abs() < 1.0e-5);
+        assert!((weight_of(&weights, 1) - 0.25).abs() < 1.0e-5);
+
+        assert_eq!(
+            blend_space_weights_2d(&points[..1], &[], Vector2::new(5.0, 5.0)),
+            vec![(0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_blend_space_drives_animations() {
+        let mut animations = AnimationContainer::new();
+        let mut animation = Animation::default();
+        animation.set_time_slice(Some(0.0..2.0));
+        animation.add_signal(AnimationSignal::new(1, 0.5));
+        let animation = animations.add(animation);
+
+        let mut nodes = Pool::new();
+        let input = nodes.spawn(PoseNode::make_play_animation(animation));
+        let blend_space = BlendSpace::new(
+            "X".to_owned(),
+            Default::default(),
+            vec![BlendSpacePoint {
+                position: Vector2::default(),
+                pose_source: input,
+            }],
+        );
+        let params = ParameterContainer::default();
+
+        blend_space.eval_pose(&nodes, &params, &animations, 0.6);
+        blend_space.sync_animations(&nodes, &mut animations);
+        assert!((blend_space.phase() - 0.3).abs() < 1.0e-5);
+        assert!((animations[animation].get_time_position() - 0.6).abs() < 1.0e-5);
+        assert_eq!(animations[animation].recent_events().len(), 1);
+
+        // Driven animation must not be ticked by the container.
+        animations.update_animations(1.0);
+        assert!((animations[animation].get_time_position() - 0.6).abs() < 1.0e-5);
+        assert!(animations[animation].recent_events().is_empty());
+
+        blend_space.reset_phase();
+        assert_eq!(blend_space.phase(), 0.0);
+        blend_space.sync_animations(&nodes, &mut animations);
+        assert_eq!(animations[animation].get_time_position(), 0.0);
+    }
+}
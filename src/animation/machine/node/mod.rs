@@ -5,6 +5,7 @@ use crate::{
                 blend::{
                     BlendAnimations, BlendAnimationsByIndexDefinition, BlendAnimationsDefinition,
                 },
+                blend_space::{BlendSpace, BlendSpaceDefinition, BlendSpacePoint},
                 play::{PlayAnimation, PlayAnimationDefinition},
             },
             state::StateDefinition,
@@ -25,6 +26,7 @@ use std::{
 };
 
 pub mod blend;
+pub mod blend_space;
 pub mod play;
 
 #[derive(Debug, Visit, Clone, Default)]
@@ -43,6 +45,9 @@ pub enum PoseNode {
 
     /// See docs for `BlendAnimationsByIndex`.
     BlendAnimationsByIndex(BlendAnimationsByIndex),
+
+    /// See docs for `BlendSpace`.
+    BlendSpace(BlendSpace),
 }
 
 impl Default for PoseNode {
@@ -69,6 +74,28 @@ impl PoseNode {
     ) -> Self {
         Self::BlendAnimationsByIndex(BlendAnimationsByIndex::new(index_parameter, inputs))
     }
+
+    /// Creates new node that blends poses using position of a point in a blend space.
+    pub fn make_blend_space(
+        x_parameter: String,
+        y_parameter: String,
+        points: Vec<BlendSpacePoint>,
+    ) -> Self {
+        Self::BlendSpace(BlendSpace::new(x_parameter, y_parameter, points))
+    }
+
+    /// Returns handles of input nodes of the node.
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        match self {
+            PoseNode::PlayAnimation(_) => {
+                // No children nodes.
+                vec![]
+            }
+            PoseNode::BlendAnimations(blend_animations) => blend_animations.children(),
+            PoseNode::BlendAnimationsByIndex(blend_animations) => blend_animations.children(),
+            PoseNode::BlendSpace(blend_space) => blend_space.children(),
+        }
+    }
 }
 
 macro_rules! static_dispatch {
@@ -77,6 +104,7 @@ macro_rules! static_dispatch {
             PoseNode::PlayAnimation(v) => v.$func($($args),*),
             PoseNode::BlendAnimations(v) => v.$func($($args),*),
             PoseNode::BlendAnimationsByIndex(v) => v.$func($($args),*),
+            PoseNode::BlendSpace(v) => v.$func($($args),*),
         }
     };
 }
@@ -135,6 +163,7 @@ pub enum PoseNodeDefinition {
     PlayAnimation(PlayAnimationDefinition),
     BlendAnimations(BlendAnimationsDefinition),
     BlendAnimationsByIndex(BlendAnimationsByIndexDefinition),
+    BlendSpace(BlendSpaceDefinition),
 }
 
 impl PoseNodeDefinition {
@@ -146,6 +175,7 @@ impl PoseNodeDefinition {
             }
             PoseNodeDefinition::BlendAnimations(definition) => definition.children(),
             PoseNodeDefinition::BlendAnimationsByIndex(definition) => definition.children(),
+            PoseNodeDefinition::BlendSpace(definition) => definition.children(),
         }
     }
 }
@@ -164,6 +194,7 @@ impl Deref for PoseNodeDefinition {
             PoseNodeDefinition::PlayAnimation(v) => v,
             PoseNodeDefinition::BlendAnimations(v) => v,
             PoseNodeDefinition::BlendAnimationsByIndex(v) => v,
+            PoseNodeDefinition::BlendSpace(v) => v,
         }
     }
 }
@@ -174,6 +205,7 @@ impl DerefMut for PoseNodeDefinition {
             PoseNodeDefinition::PlayAnimation(v) => v,
             PoseNodeDefinition::BlendAnimations(v) => v,
             PoseNodeDefinition::BlendAnimationsByIndex(v) => v,
+            PoseNodeDefinition::BlendSpace(v) => v,
         }
     }
 }
@@ -184,6 +216,7 @@ impl Inspect for PoseNodeDefinition {
             PoseNodeDefinition::PlayAnimation(v) => v.properties(),
             PoseNodeDefinition::BlendAnimations(v) => v.properties(),
             PoseNodeDefinition::BlendAnimationsByIndex(v) => v.properties(),
+            PoseNodeDefinition::BlendSpace(v) => v.properties(),
         }
     }
 }
@@ -52,6 +52,11 @@ pub struct PlayAnimationDefinition {
     pub speed: f32,
     #[visit(optional)] // Backward compatibility
     pub time_slice: Option<TimeSlice>,
+    /// Path to a skeleton mapping resource, that will be used to retarget the animation to a
+    /// skeleton with different bone names and proportions. Empty path means that bones of the
+    /// animation will be matched by their names.
+    #[visit(optional)] // Backward compatibility
+    pub skeleton_mapping: String,
 }
 
 impl Default for PlayAnimationDefinition {
@@ -61,6 +66,7 @@ impl Default for PlayAnimationDefinition {
             animation: "".to_string(),
             speed: 1.0,
             time_slice: None,
+            skeleton_mapping: Default::default(),
         }
     }
 }
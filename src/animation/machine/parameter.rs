@@ -18,6 +18,11 @@ pub enum Parameter {
 
     /// An index of pose.
     Index(u32),
+
+    /// Trigger parameter is a Rule parameter that is automatically reset to `false` when a
+    /// transition that uses it in its condition is activated. It is useful for one-shot actions
+    /// like jump, attack, etc.
+    Trigger(bool),
 }
 
 impl Default for Parameter {
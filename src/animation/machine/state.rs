@@ -1,6 +1,10 @@
 use crate::{
     animation::{
-        machine::{node::PoseNodeDefinition, EvaluatePose, ParameterContainer, PoseNode},
+        machine::{
+            layer::{LayerDefinition, MachineLayer},
+            node::PoseNodeDefinition,
+            EvaluatePose, ParameterContainer, PoseNode,
+        },
         AnimationContainer, AnimationPose,
     },
     core::{
@@ -19,6 +23,9 @@ pub struct State {
     pub definition: Handle<StateDefinition>,
     pub(crate) name: String,
     pub(crate) root: Handle<PoseNode>,
+    /// A layer the state belongs to, [`Handle::NONE`] means the base layer of the machine.
+    #[visit(optional)] // Backward compatibility
+    pub(crate) layer: Handle<MachineLayer>,
 }
 
 #[derive(Default, Debug, Visit, Clone, Inspect)]
@@ -27,6 +34,9 @@ pub struct StateDefinition {
     pub name: String,
     #[inspect(skip)]
     pub root: Handle<PoseNodeDefinition>,
+    #[inspect(skip)]
+    #[visit(optional)] // Backward compatibility
+    pub layer: Handle<LayerDefinition>,
 }
 
 impl State {
@@ -36,13 +46,53 @@ impl State {
             definition: Default::default(),
             name: name.to_owned(),
             root,
+            layer: Default::default(),
         }
     }
 
+    /// Moves the state to a given layer of the machine.
+    pub fn with_layer(mut self, layer: Handle<MachineLayer>) -> Self {
+        self.layer = layer;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn layer(&self) -> Handle<MachineLayer> {
+        self.layer
+    }
+
+    /// Returns normalized (in `[0; 1]` range) playback position of the first animation found in
+    /// the pose nodes of the state (blend spaces report their synchronized phase). Returns `0.0`
+    /// if the state does not play any animation.
+    pub fn normalized_time(&self, nodes: &Pool<PoseNode>, animations: &AnimationContainer) -> f32 {
+        let mut stack = vec![self.root];
+        while let Some(handle) = stack.pop() {
+            match nodes.try_borrow(handle) {
+                Some(PoseNode::PlayAnimation(play_animation)) => {
+                    if let Some(animation) = animations.try_get(play_animation.animation) {
+                        return animation.get_normalized_time_position();
+                    }
+                }
+                Some(PoseNode::BlendAnimations(blend_animations)) => stack.extend(
+                    blend_animations
+                        .pose_sources
+                        .iter()
+                        .rev()
+                        .map(|s| s.pose_source),
+                ),
+                Some(PoseNode::BlendAnimationsByIndex(blend_animations)) => {
+                    stack.extend(blend_animations.inputs.iter().rev().map(|i| i.pose_source))
+                }
+                Some(PoseNode::BlendSpace(blend_space)) => return blend_space.phase(),
+                None => (),
+            }
+        }
+        0.0
+    }
+
     pub fn pose<'a>(&self, nodes: &'a Pool<PoseNode>) -> Option<Ref<'a, AnimationPose>> {
         nodes.try_borrow(self.root).map(|root| root.pose())
     }
@@ -1,10 +1,15 @@
 use crate::{
-    animation::machine::{state::StateDefinition, State},
+    animation::machine::{
+        condition::{ConditionParseError, TransitionCondition},
+        state::StateDefinition,
+        State,
+    },
     core::{
         inspect::{Inspect, PropertyInfo},
         pool::Handle,
         visitor::prelude::*,
     },
+    utils::log::Log,
 };
 
 /// Transition is a connection between two states with a rule that defines possibility
@@ -26,6 +31,12 @@ pub struct Transition {
     pub(crate) invert_rule: bool,
     /// 0 - evaluates `src` pose, 1 - `dest`, 0..1 - blends `src` and `dest`
     pub(crate) blend_factor: f32,
+    /// Condition expression, if not empty it is used instead of `rule`. See
+    /// [`crate::animation::machine::condition`] module docs for syntax.
+    #[visit(optional)] // Backward compatibility
+    pub(crate) condition: String,
+    #[visit(skip)]
+    pub(crate) compiled_condition: Option<Result<TransitionCondition, ConditionParseError>>,
 }
 
 #[derive(Default, Debug, Visit, Clone, Inspect)]
@@ -55,6 +66,13 @@ pub struct TransitionDefinition {
       but have different directions (A -> B, B -> A)."
     )]
     pub invert_rule: bool,
+    /// Condition expression, if not empty it is used instead of `rule`.
+    #[inspect(
+        description = "Condition expression, if not empty it is used instead of `rule`. \
+        Example: speed > 0.1 && !crouch && source_time() >= 0.9"
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub condition: String,
 }
 
 impl Transition {
@@ -75,9 +93,25 @@ impl Transition {
             rule: rule.to_owned(),
             invert_rule: false,
             blend_factor: 0.0,
+            condition: Default::default(),
+            compiled_condition: None,
         }
     }
 
+    /// Creates new transition that is activated by the given condition expression. See
+    /// [`crate::animation::machine::condition`] module docs for syntax.
+    pub fn with_condition(
+        name: &str,
+        src: Handle<State>,
+        dest: Handle<State>,
+        time: f32,
+        condition: &str,
+    ) -> Result<Transition, ConditionParseError> {
+        let mut transition = Self::new(name, src, dest, time, "");
+        transition.set_condition(condition)?;
+        Ok(transition)
+    }
+
     #[inline]
     pub fn name(&self) -> &str {
         self.name.as_str()
@@ -123,6 +157,45 @@ impl Transition {
         self.invert_rule
     }
 
+    /// Sets new condition expression. Empty expression means that `rule` will be used instead.
+    pub fn set_condition(&mut self, condition: &str) -> Result<(), ConditionParseError> {
+        self.compiled_condition = if condition.is_empty() {
+            None
+        } else {
+            Some(Ok(TransitionCondition::parse(condition)?))
+        };
+        self.condition = condition.to_owned();
+        Ok(())
+    }
+
+    #[inline]
+    pub fn condition(&self) -> &str {
+        &self.condition
+    }
+
+    /// Returns compiled condition of the transition, the condition is compiled on first access.
+    /// Returns `None` if the condition is empty or it has syntax errors.
+    pub fn compiled_condition(&mut self) -> Option<&TransitionCondition> {
+        if self.condition.is_empty() {
+            return None;
+        }
+
+        if self.compiled_condition.is_none() {
+            let result = TransitionCondition::parse(&self.condition);
+            if let Err(ref err) = result {
+                Log::err(format!(
+                    "Failed to parse condition \"{}\" of transition {}. Reason: {}",
+                    self.condition, self.name, err
+                ));
+            }
+            self.compiled_condition = Some(result);
+        }
+
+        self.compiled_condition
+            .as_ref()
+            .and_then(|result| result.as_ref().ok())
+    }
+
     pub(super) fn reset(&mut self) {
         self.elapsed_time = 0.0;
         self.blend_factor = 0.0;
@@ -1,16 +1,21 @@
+pub mod curve_track;
+pub mod ik;
 pub mod machine;
+pub mod retarget;
 
 use crate::{
+    animation::curve_track::{CurveTrack, TrackBinding, TrackValue},
     asset::ResourceState,
     core::{
         algebra::{UnitQuaternion, Vector3},
+        curve::{Curve, CurveKey, CurveKeyKind},
         math::wrapf,
         pool::{Handle, Pool, Ticket},
         visitor::{Visit, VisitResult, Visitor},
     },
     engine::resource_manager::ResourceManager,
-    resource::model::Model,
-    scene::{graph::Graph, node::Node},
+    resource::{model::Model, skeleton_mapping::SkeletonMappingResource},
+    scene::{graph::Graph, mesh::Mesh, node::Node},
     utils::log::{Log, MessageKind},
 };
 use fxhash::FxHashMap;
@@ -153,6 +158,51 @@ impl Track {
         &self.frames
     }
 
+    /// Removes key frames that could be restored (with given tolerance) by interpolation between
+    /// neighbour key frames. It significantly reduces memory usage of imported animations, which
+    /// usually have a key frame for every frame. Position and scale tolerance is in units, rotation
+    /// tolerance is in radians.
+    pub fn reduce_key_frames(&mut self, tolerance: f32) {
+        if self.frames.len() < 3 {
+            return;
+        }
+
+        let mut reduced = Vec::with_capacity(self.frames.len());
+        reduced.push(self.frames[0]);
+        let mut left = 0;
+        for right in 2..self.frames.len() {
+            // Check if every key frame between left and right could be restored without the
+            // key frame before right one.
+            let left_frame = &self.frames[left];
+            let right_frame = &self.frames[right];
+            let restorable = self.frames[(left + 1)..right].iter().all(|frame| {
+                let t = (frame.time - left_frame.time) / (right_frame.time - left_frame.time);
+                left_frame
+                    .position
+                    .lerp(&right_frame.position, t)
+                    .metric_distance(&frame.position)
+                    <= tolerance
+                    && left_frame
+                        .scale
+                        .lerp(&right_frame.scale, t)
+                        .metric_distance(&frame.scale)
+                        <= tolerance
+                    && left_frame
+                        .rotation
+                        .nlerp(&right_frame.rotation, t)
+                        .angle_to(&frame.rotation)
+                        <= tolerance
+            });
+            if !restorable {
+                left = right - 1;
+                reduced.push(self.frames[left]);
+            }
+        }
+        reduced.extend(self.frames.last().cloned());
+
+        self.frames = reduced;
+    }
+
     pub fn get_local_pose(&self, mut time: f32) -> Option<LocalPose> {
         if self.frames.is_empty() {
             return None;
@@ -214,21 +264,211 @@ impl Track {
         self.flags
     }
 
+    /// Converts key frames of the track to a set of curve tracks (position, rotation and scale)
+    /// with linear keys, components that are ignored by the flags of the track are skipped.
+    pub fn to_curve_tracks(&self) -> Vec<CurveTrack> {
+        let make_curves = |component: &dyn Fn(&KeyFrame) -> Vector3<f32>| {
+            (0..3)
+                .map(|i| {
+                    Curve::from(
+                        self.frames
+                            .iter()
+                            .map(|k| CurveKey::new(k.time, component(k)[i], CurveKeyKind::Linear))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut tracks = Vec::new();
+        if !self.flags.ignore_position {
+            tracks.push((TrackBinding::Position, make_curves(&|k| k.position)));
+        }
+        if !self.flags.ignore_rotation {
+            tracks.push((
+                TrackBinding::Rotation,
+                make_curves(&|k| {
+                    let (x, y, z) = k.rotation.euler_angles();
+                    Vector3::new(x, y, z)
+                }),
+            ));
+        }
+        if !self.flags.ignore_scale {
+            tracks.push((TrackBinding::Scale, make_curves(&|k| k.scale)));
+        }
+
+        tracks
+            .into_iter()
+            .map(|(binding, curves)| {
+                let mut track = CurveTrack::new(self.node, binding, curves);
+                track.enable(self.enabled);
+                track
+            })
+            .collect()
+    }
+
     pub fn set_flags(&mut self, flags: PoseEvaluationFlags) {
         self.flags = flags;
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// A key of [`BlendShapeTrack`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Visit)]
+pub struct BlendShapeKeyFrame {
+    pub time: f32,
+    pub weight: f32,
+}
+
+impl BlendShapeKeyFrame {
+    pub fn new(time: f32, weight: f32) -> Self {
+        Self { time, weight }
+    }
+}
+
+/// Animates weight of blend shapes with given name of a mesh node, see
+/// [`Mesh::set_blend_shape_weight`] for more info.
+#[derive(Clone, Debug, Visit)]
+pub struct BlendShapeTrack {
+    // Frames are not serialized, they will be taken from resource on Resolve stage.
+    #[visit(skip)]
+    frames: Vec<BlendShapeKeyFrame>,
+    enabled: bool,
+    max_time: f32,
+    node: Handle<Node>,
+    blend_shape: String,
+}
+
+impl Default for BlendShapeTrack {
+    fn default() -> Self {
+        Self {
+            frames: Default::default(),
+            enabled: true,
+            max_time: 0.0,
+            node: Default::default(),
+            blend_shape: Default::default(),
+        }
+    }
+}
+
+impl BlendShapeTrack {
+    pub fn new(node: Handle<Node>, blend_shape: &str) -> Self {
+        Self {
+            node,
+            blend_shape: blend_shape.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_node(&mut self, node: Handle<Node>) {
+        self.node = node;
+    }
+
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+
+    /// Returns name of animated blend shapes.
+    pub fn blend_shape(&self) -> &str {
+        &self.blend_shape
+    }
+
+    pub fn add_key_frame(&mut self, key_frame: BlendShapeKeyFrame) {
+        let index = self
+            .frames
+            .iter()
+            .position(|other| key_frame.time < other.time)
+            .unwrap_or(self.frames.len());
+        self.frames.insert(index, key_frame);
+        self.max_time = self.max_time.max(key_frame.time);
+    }
+
+    pub fn set_key_frames(&mut self, key_frames: &[BlendShapeKeyFrame]) {
+        self.frames = key_frames.to_vec();
+        self.max_time = self.frames.iter().fold(0.0, |max, k| k.time.max(max));
+    }
+
+    pub fn key_frames(&self) -> &[BlendShapeKeyFrame] {
+        &self.frames
+    }
+
+    pub fn enable(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Converts key frames of the track to a curve track with linear keys.
+    pub fn to_curve_track(&self) -> CurveTrack {
+        let curve = Curve::from(
+            self.frames
+                .iter()
+                .map(|k| CurveKey::new(k.time, k.weight, CurveKeyKind::Linear))
+                .collect::<Vec<_>>(),
+        );
+        let mut track = CurveTrack::new(
+            self.node,
+            TrackBinding::BlendShape(self.blend_shape.clone()),
+            vec![curve],
+        );
+        track.enable(self.enabled);
+        track
+    }
+
+    /// Returns interpolated weight at given time, `None` if the track has no key frames.
+    pub fn weight_at(&self, time: f32) -> Option<f32> {
+        let right_index = self.frames.iter().position(|k| k.time >= time);
+
+        match right_index {
+            Some(0) => self.frames.first().map(|k| k.weight),
+            Some(right_index) => {
+                let left = &self.frames[right_index - 1];
+                let right = &self.frames[right_index];
+                let interpolator = (time - left.time) / (right.time - left.time);
+                Some(left.weight + (right.weight - left.weight) * interpolator)
+            }
+            None => self.frames.last().map(|k| k.weight),
+        }
+    }
+}
+
+/// Additional data of an [`AnimationSignal`], it is passed with each event of the signal.
+#[derive(Clone, Debug, PartialEq, Visit)]
+pub enum SignalPayload {
+    None,
+    String(String),
+    Number(f32),
+    Vector(Vector3<f32>),
+}
+
+impl Default for SignalPayload {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct AnimationEvent {
     pub signal_id: u64,
+    /// Name of the signal that produced the event.
+    pub name: String,
+    /// Payload of the signal that produced the event.
+    pub payload: SignalPayload,
 }
 
+/// Signal is a marker on the timeline of an animation, an [`AnimationEvent`] is produced each
+/// time when playback passes the signal. Signals outside of the time slice of the animation are
+/// never emitted, each signal is emitted once per loop.
 #[derive(Clone, Debug, Visit)]
 pub struct AnimationSignal {
     id: u64,
     time: f32,
     enabled: bool,
+    #[visit(optional)] // Backward compatibility
+    name: String,
+    #[visit(optional)] // Backward compatibility
+    payload: SignalPayload,
 }
 
 impl AnimationSignal {
@@ -237,9 +477,41 @@ impl AnimationSignal {
             id,
             time,
             enabled: true,
+            name: Default::default(),
+            payload: Default::default(),
         }
     }
 
+    pub fn with_name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.name = name.as_ref().to_owned();
+        self
+    }
+
+    pub fn with_payload(mut self, payload: SignalPayload) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    pub fn set_name<S: AsRef<str>>(&mut self, name: S) {
+        self.name = name.as_ref().to_owned();
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_payload(&mut self, payload: SignalPayload) {
+        self.payload = payload;
+    }
+
+    pub fn payload(&self) -> &SignalPayload {
+        &self.payload
+    }
+
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+    }
+
     pub fn set_enabled(&mut self, value: bool) {
         self.enabled = value;
     }
@@ -263,10 +535,67 @@ impl Default for AnimationSignal {
             id: 0,
             time: 0.0,
             enabled: true,
+            name: Default::default(),
+            payload: Default::default(),
+        }
+    }
+}
+
+/// Defines how root motion is extracted from an animation. Root motion is the movement of a root
+/// node of an animated hierarchy (usually a hips bone of a character), which is removed from the
+/// pose and exposed as per-frame deltas via [`AnimationPose::root_motion`], so it could be applied
+/// to a rigid body or a character controller.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Visit)]
+pub struct RootMotionSettings {
+    /// A node which movement will be extracted.
+    pub node: Handle<Node>,
+    /// Do not extract movement along X axis, it stays in the pose.
+    pub ignore_x_movement: bool,
+    /// Do not extract movement along Y axis, it stays in the pose. Usually it is vertical
+    /// movement of a character.
+    pub ignore_y_movement: bool,
+    /// Do not extract movement along Z axis, it stays in the pose.
+    pub ignore_z_movement: bool,
+    /// Do not extract rotations, they stay in the pose.
+    pub ignore_rotations: bool,
+}
+
+/// Movement of a root node between two frames, see [`RootMotionSettings`] for more info.
+///
+/// Deltas are relative to the current orientation of the root node (excluding its orientation at
+/// the beginning of the animation), so to apply root motion to a body you need to do:
+/// `position += body_rotation * delta_position; body_rotation *= delta_rotation`. Keep in mind
+/// that the position delta is in the coordinate system of the parent of the root node, so it
+/// includes its scale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootMotion {
+    pub delta_position: Vector3<f32>,
+    pub delta_rotation: UnitQuaternion<f32>,
+}
+
+impl Default for RootMotion {
+    fn default() -> Self {
+        Self {
+            delta_position: Default::default(),
+            delta_rotation: UnitQuaternion::identity(),
         }
     }
 }
 
+impl RootMotion {
+    /// Interpolates this motion towards the other one, `weight` is an interpolation coefficient.
+    pub fn blend_with(&mut self, other: &RootMotion, weight: f32) {
+        self.delta_position = self.delta_position.lerp(&other.delta_position, weight);
+        self.delta_rotation = self.delta_rotation.nlerp(&other.delta_rotation, weight);
+    }
+
+    /// Appends other motion (that happened after this one) to this motion.
+    pub fn append(&mut self, other: &RootMotion) {
+        self.delta_position += self.delta_rotation * other.delta_position;
+        self.delta_rotation *= other.delta_rotation;
+    }
+}
+
 #[derive(Debug, Visit)]
 pub struct Animation {
     // TODO: Extract into separate struct AnimationTimeline
@@ -285,6 +614,21 @@ pub struct Animation {
     signals: Vec<AnimationSignal>,
     #[visit(skip)]
     events: VecDeque<AnimationEvent>,
+    #[visit(skip)]
+    recent_events: Vec<AnimationEvent>,
+    #[visit(optional)] // Backward compatibility
+    root_motion_settings: Option<RootMotionSettings>,
+    // Skeleton mapping which was used to retarget the animation from its resource.
+    #[visit(optional)] // Backward compatibility
+    pub(in crate) skeleton_mapping: Option<SkeletonMappingResource>,
+    #[visit(optional)] // Backward compatibility
+    blend_shape_tracks: Vec<BlendShapeTrack>,
+    #[visit(optional)] // Backward compatibility
+    curve_tracks: Vec<CurveTrack>,
+    // Set when the playback position was driven by a blend space of a machine, such animation is
+    // not ticked by its container at the next update.
+    #[visit(skip)]
+    driven: bool,
 }
 
 /// Snapshot of scene node local transform state.
@@ -339,6 +683,9 @@ impl LocalPose {
 #[derive(Default, Debug, Clone)]
 pub struct AnimationPose {
     local_poses: FxHashMap<Handle<Node>, LocalPose>,
+    root_motion: Option<RootMotion>,
+    blend_shape_weights: FxHashMap<(Handle<Node>, String), f32>,
+    values: FxHashMap<(Handle<Node>, TrackBinding), TrackValue>,
 }
 
 impl AnimationPose {
@@ -347,9 +694,66 @@ impl AnimationPose {
         for (handle, local_pose) in self.local_poses.iter() {
             dest.local_poses.insert(*handle, local_pose.clone());
         }
+        dest.root_motion = self.root_motion.clone();
+        dest.blend_shape_weights = self.blend_shape_weights.clone();
+        dest.values = self.values.clone();
+    }
+
+    /// Returns root motion of the pose (if any), see [`RootMotionSettings`] for more info.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.root_motion.as_ref()
+    }
+
+    pub fn set_root_motion(&mut self, root_motion: Option<RootMotion>) {
+        self.root_motion = root_motion;
+    }
+
+    /// Returns weight of blend shapes with given name of a mesh node in the pose (if any).
+    pub fn blend_shape_weight(&self, node: Handle<Node>, blend_shape: &str) -> Option<f32> {
+        self.blend_shape_weights
+            .get(&(node, blend_shape.to_owned()))
+            .cloned()
+    }
+
+    pub fn set_blend_shape_weight(&mut self, node: Handle<Node>, blend_shape: &str, weight: f32) {
+        self.blend_shape_weights
+            .insert((node, blend_shape.to_owned()), weight);
+    }
+
+    /// Returns a value of given binding of a node in the pose (if any), see [`CurveTrack`] for
+    /// more info.
+    pub fn value(&self, node: Handle<Node>, binding: &TrackBinding) -> Option<TrackValue> {
+        match binding {
+            TrackBinding::BlendShape(blend_shape) => self
+                .blend_shape_weight(node, blend_shape)
+                .map(TrackValue::Real),
+            _ => self.values.get(&(node, binding.clone())).cloned(),
+        }
+    }
+
+    pub fn set_value(&mut self, node: Handle<Node>, binding: &TrackBinding, value: TrackValue) {
+        match binding {
+            TrackBinding::BlendShape(blend_shape) => {
+                if let Some(weight) = value.as_real() {
+                    self.set_blend_shape_weight(node, blend_shape, weight);
+                }
+            }
+            _ => {
+                self.values.insert((node, binding.clone()), value);
+            }
+        }
     }
 
     pub fn blend_with(&mut self, other: &AnimationPose, weight: f32) {
+        if let Some(other_motion) = other.root_motion.as_ref() {
+            if let Some(current_motion) = self.root_motion.as_mut() {
+                current_motion.blend_with(other_motion, weight);
+            } else if weight > 0.0 {
+                // There is nothing to interpolate from, so the first motion is taken as is.
+                self.root_motion = Some(other_motion.clone());
+            }
+        }
+
         for (handle, other_pose) in other.local_poses.iter() {
             if let Some(current_pose) = self.local_poses.get_mut(handle) {
                 current_pose.blend_with(other_pose, weight);
@@ -359,6 +763,139 @@ impl AnimationPose {
                 self.add_local_pose(other_pose.weighted_clone(weight));
             }
         }
+
+        for (key, other_weight) in other.blend_shape_weights.iter() {
+            *self.blend_shape_weights.entry(key.clone()).or_insert(0.0) += other_weight * weight;
+        }
+
+        for (key, other_value) in other.values.iter() {
+            if let Some(current_value) = self.values.get_mut(key) {
+                current_value.add_scaled(other_value, weight);
+            } else {
+                self.values.insert(key.clone(), other_value.scale(weight));
+            }
+        }
+    }
+
+    /// Blends other pose over this pose, `weight` defines how much of the other pose will be in
+    /// the result (`0.0` - this pose is unchanged, `1.0` - the other pose fully replaces this pose).
+    /// Only the nodes accepted by the `filter` are affected.
+    pub fn override_with<F>(&mut self, other: &AnimationPose, weight: f32, mut filter: F)
+    where
+        F: FnMut(Handle<Node>) -> bool,
+    {
+        if let Some(other_motion) = other.root_motion.as_ref() {
+            let current_motion = self.root_motion.get_or_insert_with(Default::default);
+            current_motion.delta_position = current_motion
+                .delta_position
+                .lerp(&other_motion.delta_position, weight);
+            current_motion.delta_rotation = current_motion
+                .delta_rotation
+                .nlerp(&other_motion.delta_rotation, weight);
+        }
+
+        for (handle, other_pose) in other.local_poses.iter() {
+            if !filter(*handle) {
+                continue;
+            }
+
+            if let Some(current_pose) = self.local_poses.get_mut(handle) {
+                current_pose.position = current_pose.position.lerp(&other_pose.position, weight);
+                current_pose.rotation = current_pose.rotation.nlerp(&other_pose.rotation, weight);
+                current_pose.scale = current_pose.scale.lerp(&other_pose.scale, weight);
+            } else if weight > 0.0 {
+                // There is nothing to blend with, so just take the other pose.
+                self.add_local_pose(other_pose.clone());
+            }
+        }
+
+        for (key, other_weight) in other.blend_shape_weights.iter() {
+            if !filter(key.0) {
+                continue;
+            }
+
+            if let Some(current_weight) = self.blend_shape_weights.get_mut(key) {
+                *current_weight += (other_weight - *current_weight) * weight;
+            } else if weight > 0.0 {
+                self.blend_shape_weights.insert(key.clone(), *other_weight);
+            }
+        }
+
+        for (key, other_value) in other.values.iter() {
+            if !filter(key.0) {
+                continue;
+            }
+
+            if let Some(current_value) = self.values.get_mut(key) {
+                current_value.lerp(other_value, weight);
+            } else if weight > 0.0 {
+                self.values.insert(key.clone(), *other_value);
+            }
+        }
+    }
+
+    /// Adds the difference between other pose and the reference pose to this pose. If there is
+    /// no reference pose, identity transform is used as reference. Only the nodes that are
+    /// already in this pose and accepted by the `filter` are affected.
+    pub fn add_additive<F>(
+        &mut self,
+        other: &AnimationPose,
+        reference: Option<&AnimationPose>,
+        weight: f32,
+        mut filter: F,
+    ) where
+        F: FnMut(Handle<Node>) -> bool,
+    {
+        let identity = LocalPose::default();
+
+        for (handle, current_pose) in self.local_poses.iter_mut() {
+            if !filter(*handle) {
+                continue;
+            }
+
+            if let Some(other_pose) = other.local_poses.get(handle) {
+                let reference_pose = reference
+                    .and_then(|reference| reference.local_poses.get(handle))
+                    .unwrap_or(&identity);
+
+                let delta_rotation = reference_pose.rotation.inverse() * other_pose.rotation;
+
+                current_pose.position +=
+                    (other_pose.position - reference_pose.position).scale(weight);
+                current_pose.rotation *= UnitQuaternion::identity().nlerp(&delta_rotation, weight);
+                current_pose.scale += (other_pose.scale - reference_pose.scale).scale(weight);
+            }
+        }
+
+        for (key, current_weight) in self.blend_shape_weights.iter_mut() {
+            if !filter(key.0) {
+                continue;
+            }
+
+            if let Some(other_weight) = other.blend_shape_weights.get(key) {
+                let reference_weight = reference
+                    .and_then(|reference| reference.blend_shape_weights.get(key))
+                    .cloned()
+                    .unwrap_or_default();
+
+                *current_weight += (other_weight - reference_weight) * weight;
+            }
+        }
+
+        for (key, current_value) in self.values.iter_mut() {
+            if !filter(key.0) {
+                continue;
+            }
+
+            if let Some(other_value) = other.values.get(key) {
+                current_value.add_scaled(other_value, weight);
+                if let Some(reference_value) =
+                    reference.and_then(|reference| reference.values.get(key))
+                {
+                    current_value.add_scaled(reference_value, -weight);
+                }
+            }
+        }
     }
 
     fn add_local_pose(&mut self, local_pose: LocalPose) {
@@ -367,6 +904,35 @@ impl AnimationPose {
 
     pub fn reset(&mut self) {
         self.local_poses.clear();
+        self.root_motion = None;
+        self.blend_shape_weights.clear();
+        self.values.clear();
+    }
+
+    fn apply_blend_shape_weights(&self, graph: &mut Graph) {
+        for ((node, blend_shape), weight) in self.blend_shape_weights.iter() {
+            if let Some(mesh) = graph
+                .try_get_mut(*node)
+                .and_then(|node| node.cast_mut::<Mesh>())
+            {
+                mesh.set_blend_shape_weight(blend_shape, *weight);
+            }
+        }
+    }
+
+    fn apply_values(&self, graph: &mut Graph) {
+        for ((node, binding), value) in self.values.iter() {
+            if let Some(node) = graph.try_get_mut(*node) {
+                match binding {
+                    TrackBinding::Property(path) => {
+                        curve_track::apply_property(node, path, *value);
+                    }
+                    _ => {
+                        curve_track::apply_transform(node, binding, *value);
+                    }
+                }
+            }
+        }
     }
 
     pub fn apply(&self, graph: &mut Graph) {
@@ -381,6 +947,8 @@ impl AnimationPose {
                     .set_scale(local_pose.scale);
             }
         }
+        self.apply_blend_shape_weights(graph);
+        self.apply_values(graph);
     }
 
     /// Calls given callback function for each node and allows you to apply pose with your own
@@ -396,6 +964,26 @@ impl AnimationPose {
                 callback(&mut graph[*node], *node, local_pose);
             }
         }
+        self.apply_blend_shape_weights(graph);
+        self.apply_values(graph);
+    }
+}
+
+// A part of playback between two time positions, `end` could be less than `begin` if the
+// animation is played backwards.
+struct PlaybackSegment {
+    begin: f32,
+    end: f32,
+    inclusive_begin: bool,
+}
+
+impl PlaybackSegment {
+    fn contains(&self, time: f32) -> bool {
+        if time == self.begin {
+            self.inclusive_begin
+        } else {
+            time >= self.begin.min(self.end) && time <= self.begin.max(self.end)
+        }
     }
 }
 
@@ -412,7 +1000,13 @@ impl Clone for Animation {
             pose: Default::default(),
             signals: self.signals.clone(),
             events: Default::default(),
+            recent_events: Default::default(),
             time_slice: self.time_slice.clone(),
+            root_motion_settings: self.root_motion_settings,
+            skeleton_mapping: self.skeleton_mapping.clone(),
+            blend_shape_tracks: self.blend_shape_tracks.clone(),
+            curve_tracks: self.curve_tracks.clone(),
+            driven: false,
         }
     }
 }
@@ -432,11 +1026,87 @@ impl Animation {
         &self.tracks
     }
 
+    /// Returns a handle of the first node animated by the animation (if any).
+    pub fn first_animated_node(&self) -> Option<Handle<Node>> {
+        self.tracks
+            .iter()
+            .map(|track| track.node)
+            .chain(self.blend_shape_tracks.iter().map(|track| track.node))
+            .chain(self.curve_tracks.iter().map(|track| track.node()))
+            .next()
+    }
+
+    pub fn add_blend_shape_track(&mut self, track: BlendShapeTrack) {
+        self.length = self.length.max(track.max_time);
+        self.blend_shape_tracks.push(track);
+    }
+
+    pub fn blend_shape_tracks(&self) -> &[BlendShapeTrack] {
+        &self.blend_shape_tracks
+    }
+
+    pub fn blend_shape_tracks_mut(&mut self) -> &mut [BlendShapeTrack] {
+        &mut self.blend_shape_tracks
+    }
+
+    pub fn retain_blend_shape_tracks<F>(&mut self, filter: F)
+    where
+        F: FnMut(&BlendShapeTrack) -> bool,
+    {
+        self.blend_shape_tracks.retain(filter)
+    }
+
+    /// Replaces key frame tracks of the animation, returns previous tracks.
+    pub fn set_tracks(&mut self, tracks: Vec<Track>) -> Vec<Track> {
+        let prev = std::mem::replace(&mut self.tracks, tracks);
+        self.fit_length_to_content();
+        prev
+    }
+
+    /// Replaces curve tracks of the animation, returns previous tracks.
+    pub fn set_curve_tracks(&mut self, tracks: Vec<CurveTrack>) -> Vec<CurveTrack> {
+        let prev = std::mem::replace(&mut self.curve_tracks, tracks);
+        self.fit_length_to_content();
+        prev
+    }
+
+    /// Sets length of the animation to the time of the last key or signal among all tracks and
+    /// signals of the animation.
+    pub fn fit_length_to_content(&mut self) {
+        self.length = self
+            .tracks
+            .iter()
+            .map(|track| track.max_time)
+            .chain(self.blend_shape_tracks.iter().map(|track| track.max_time))
+            .chain(self.curve_tracks.iter().map(|track| track.max_time()))
+            .chain(self.signals.iter().map(|signal| signal.time))
+            .fold(0.0, f32::max);
+        // Ensure time position is in new range.
+        self.set_time_position(self.time_position);
+    }
+
+    pub fn add_curve_track(&mut self, track: CurveTrack) {
+        self.length = self.length.max(track.max_time());
+        self.curve_tracks.push(track);
+    }
+
+    pub fn curve_tracks(&self) -> &[CurveTrack] {
+        &self.curve_tracks
+    }
+
+    pub fn curve_tracks_mut(&mut self) -> &mut [CurveTrack] {
+        &mut self.curve_tracks
+    }
+
+    pub fn retain_curve_tracks<F>(&mut self, filter: F)
+    where
+        F: FnMut(&CurveTrack) -> bool,
+    {
+        self.curve_tracks.retain(filter)
+    }
+
     pub fn set_time_position(&mut self, time: f32) -> &mut Self {
-        let time_slice = self.time_slice.clone().unwrap_or(Range {
-            start: 0.0,
-            end: self.length,
-        });
+        let time_slice = self.playback_range();
 
         if self.looped {
             self.time_position = wrapf(time, time_slice.start, time_slice.end);
@@ -458,6 +1128,15 @@ impl Animation {
         self.set_time_position(self.time_position);
     }
 
+    /// Returns time range in which the animation is played - it is either the time slice (if any)
+    /// or the whole `[0; length]` range.
+    pub fn playback_range(&self) -> Range<f32> {
+        self.time_slice.clone().unwrap_or(Range {
+            start: 0.0,
+            end: self.length,
+        })
+    }
+
     pub fn rewind(&mut self) -> &mut Self {
         self.set_time_position(0.0)
     }
@@ -467,22 +1146,41 @@ impl Animation {
     }
 
     fn tick(&mut self, dt: f32) {
+        let current_time_position = self.get_time_position();
+        self.advance(
+            current_time_position,
+            current_time_position + dt * self.get_speed(),
+        );
+    }
+
+    /// Moves playback position of the animation from `from` to `to` and emits signals in between,
+    /// blend spaces use it to drive playback of their inputs. The animation is not ticked at the
+    /// next [`AnimationContainer::update_animations`] call.
+    pub(in crate) fn drive(&mut self, from: f32, to: f32) {
+        self.driven = true;
+        self.advance(from, to);
+    }
+
+    fn advance(&mut self, current_time_position: f32, new_time_position: f32) {
+        self.set_time_position(current_time_position);
         self.update_pose();
 
-        let current_time_position = self.get_time_position();
-        let new_time_position = current_time_position + dt * self.get_speed();
+        self.pose.root_motion =
+            self.root_motion_between(current_time_position, new_time_position, self.looped);
 
-        for signal in self.signals.iter_mut() {
-            if self.speed >= 0.0
-                && (current_time_position < signal.time && new_time_position >= signal.time)
-                || self.speed < 0.0
-                    && (current_time_position > signal.time && new_time_position <= signal.time)
-            {
-                // TODO: Make this configurable.
-                if self.events.len() < 32 {
-                    self.events.push_back(AnimationEvent {
+        for segment in self.signal_segments(current_time_position, new_time_position) {
+            for signal in self.signals.iter() {
+                if signal.enabled && segment.contains(signal.time) {
+                    let event = AnimationEvent {
                         signal_id: signal.id,
-                    });
+                        name: signal.name.clone(),
+                        payload: signal.payload.clone(),
+                    };
+                    // TODO: Make this configurable.
+                    if self.events.len() < 32 {
+                        self.events.push_back(event.clone());
+                    }
+                    self.recent_events.push(event);
                 }
             }
         }
@@ -490,14 +1188,64 @@ impl Animation {
         self.set_time_position(new_time_position);
     }
 
+    /// Splits the playback between two time positions into segments within the playback range,
+    /// the playback could wrap around the range once if the animation is looped.
+    fn signal_segments(&self, from: f32, to: f32) -> Vec<PlaybackSegment> {
+        let range = self.playback_range();
+        let length = range.end - range.start;
+
+        // A signal that is exactly at the beginning of a segment is emitted only if the playback
+        // wrapped around, otherwise it was already emitted at previous update.
+        let segment = |begin, end, inclusive_begin| PlaybackSegment {
+            begin,
+            end,
+            inclusive_begin,
+        };
+        if to >= from {
+            if self.looped && length > 0.0 && to > range.end {
+                vec![
+                    segment(from, range.end, false),
+                    segment(range.start, to - length, true),
+                ]
+            } else {
+                vec![segment(from, to.min(range.end), false)]
+            }
+        } else if self.looped && length > 0.0 && to < range.start {
+            vec![
+                segment(from, range.start, false),
+                segment(range.end, to + length, true),
+            ]
+        } else {
+            vec![segment(from, to.max(range.start), false)]
+        }
+    }
+
     pub fn pop_event(&mut self) -> Option<AnimationEvent> {
         self.events.pop_front()
     }
 
+    /// Returns events that were produced during last update of the animation. Unlike
+    /// [`Self::pop_event`], it does not consume the events. The engine uses it to pass the events
+    /// to scripts, see [`crate::script::ScriptTrait::on_animation_event`] for more info.
+    pub fn recent_events(&self) -> &[AnimationEvent] {
+        &self.recent_events
+    }
+
     pub fn get_time_position(&self) -> f32 {
         self.time_position
     }
 
+    /// Returns time position normalized to `[0; 1]` range, it takes time slice into account.
+    pub fn get_normalized_time_position(&self) -> f32 {
+        let time_slice = self.playback_range();
+        let duration = time_slice.end - time_slice.start;
+        if duration > 0.0 {
+            (self.time_position - time_slice.start) / duration
+        } else {
+            0.0
+        }
+    }
+
     pub fn get_speed(&self) -> f32 {
         self.speed
     }
@@ -537,10 +1285,27 @@ impl Animation {
         self.resource.clone()
     }
 
+    /// Returns skeleton mapping that was used to retarget the animation from its resource (if any).
+    pub fn skeleton_mapping(&self) -> Option<SkeletonMappingResource> {
+        self.skeleton_mapping.clone()
+    }
+
     pub fn signals(&self) -> &[AnimationSignal] {
         &self.signals
     }
 
+    pub fn signals_mut(&mut self) -> &mut [AnimationSignal] {
+        &mut self.signals
+    }
+
+    pub fn remove_signal(&mut self, index: usize) -> AnimationSignal {
+        self.signals.remove(index)
+    }
+
+    pub fn insert_signal(&mut self, index: usize, signal: AnimationSignal) {
+        self.signals.insert(index, signal)
+    }
+
     pub fn retain_tracks<F>(&mut self, filter: F)
     where
         F: FnMut(&Track) -> bool,
@@ -617,6 +1382,11 @@ impl Animation {
             let new_resource = resource_manager.request_model(resource.state().path());
             *resource = new_resource;
         }
+        if let Some(skeleton_mapping) = self.skeleton_mapping.as_mut() {
+            let new_skeleton_mapping =
+                resource_manager.request_skeleton_mapping(skeleton_mapping.state().path());
+            *skeleton_mapping = new_skeleton_mapping;
+        }
     }
 
     pub(in crate) fn resolve(&mut self, graph: &Graph) {
@@ -624,6 +1394,12 @@ impl Animation {
         // do not store key frames in save file, but just keep reference to resource
         // from which key frames should be taken on load.
         if let Some(resource) = self.resource.clone() {
+            // Retargeted animations must be retargeted again using the same skeleton mapping.
+            let skeleton_mapping = self
+                .skeleton_mapping
+                .as_ref()
+                .and_then(|skeleton_mapping| skeleton_mapping.mapping());
+
             let resource = resource.state();
             match *resource {
                 ResourceState::Ok(ref data) => {
@@ -645,12 +1421,32 @@ impl Animation {
                             // you did animation retargeting from animation resource to your character
                             // instantiated model, which is essentially copies key frames to new
                             // animation targeted to character instance.
+                            let bone_mapping = skeleton_mapping
+                                .as_ref()
+                                .and_then(|mapping| mapping.by_target(track_node.name()));
+                            let source_name = bone_mapping
+                                .map_or(track_node.name(), |bone_mapping| {
+                                    bone_mapping.source.as_str()
+                                });
+
                             let mut found = false;
                             for ref_track in ref_animation.get_tracks().iter() {
-                                if track_node.name()
+                                if source_name
                                     == data.get_scene().graph[ref_track.get_node()].name()
                                 {
-                                    track.set_key_frames(ref_track.get_key_frames());
+                                    if let Some(bone_mapping) = bone_mapping {
+                                        let frames = retarget::retarget_key_frames(
+                                            ref_track.get_key_frames(),
+                                            &data.get_scene().graph,
+                                            ref_track.get_node(),
+                                            graph,
+                                            track.get_node(),
+                                            bone_mapping.translation,
+                                        );
+                                        track.set_key_frames(&frames);
+                                    } else {
+                                        track.set_key_frames(ref_track.get_key_frames());
+                                    }
                                     found = true;
                                     break;
                                 }
@@ -665,6 +1461,29 @@ impl Animation {
                                 );
                             }
                         }
+
+                        for track in self.blend_shape_tracks.iter_mut() {
+                            let track_node = &graph[track.node];
+
+                            if let Some(ref_track) =
+                                ref_animation.blend_shape_tracks.iter().find(|ref_track| {
+                                    ref_track.blend_shape == track.blend_shape
+                                        && data.get_scene().graph[ref_track.node].name()
+                                            == track_node.name()
+                                })
+                            {
+                                track.set_key_frames(&ref_track.frames);
+                            } else {
+                                Log::write(
+                                    MessageKind::Error,
+                                    format!(
+                                        "Failed to copy key frames of blend shape {} for node {}!",
+                                        track.blend_shape,
+                                        track_node.name()
+                                    ),
+                                );
+                            }
+                        }
                     }
                 }
                 ResourceState::LoadError {
@@ -694,11 +1513,192 @@ impl Animation {
                 }
             }
         }
+        Self::evaluate_values(
+            &self.blend_shape_tracks,
+            &self.curve_tracks,
+            self.time_position,
+            &mut self.pose,
+        );
+        self.strip_root_motion();
+    }
+
+    fn evaluate_values(
+        blend_shape_tracks: &[BlendShapeTrack],
+        curve_tracks: &[CurveTrack],
+        time: f32,
+        pose: &mut AnimationPose,
+    ) {
+        for track in blend_shape_tracks.iter() {
+            if track.is_enabled() {
+                if let Some(weight) = track.weight_at(time) {
+                    pose.set_blend_shape_weight(track.node, &track.blend_shape, weight);
+                }
+            }
+        }
+        for track in curve_tracks.iter() {
+            if track.is_enabled() {
+                if let Some(value) = track.value_at(time) {
+                    pose.set_value(track.node(), track.binding(), value);
+                }
+            }
+        }
+    }
+
+    pub fn set_root_motion_settings(&mut self, settings: Option<RootMotionSettings>) {
+        self.root_motion_settings = settings;
+    }
+
+    pub fn with_root_motion_settings(mut self, settings: RootMotionSettings) -> Self {
+        self.root_motion_settings = Some(settings);
+        self
+    }
+
+    pub fn root_motion_settings(&self) -> Option<&RootMotionSettings> {
+        self.root_motion_settings.as_ref()
+    }
+
+    pub fn root_motion_settings_mut(&mut self) -> Option<&mut RootMotionSettings> {
+        self.root_motion_settings.as_mut()
+    }
+
+    fn root_track(&self) -> Option<(&RootMotionSettings, &Track)> {
+        let settings = self.root_motion_settings.as_ref()?;
+        let track = self.track_of(settings.node)?;
+        if track.is_enabled() && !track.get_key_frames().is_empty() {
+            Some((settings, track))
+        } else {
+            None
+        }
+    }
+
+    /// Replaces extracted components of the root node transform in the pose with the ones at the
+    /// beginning of playback range, so the root node will stay in place.
+    fn strip_root_motion(&mut self) {
+        let reference = match self.root_track() {
+            Some((settings, track)) => track
+                .get_local_pose(self.playback_range().start)
+                .map(|reference| (*settings, reference)),
+            None => None,
+        };
+
+        if let Some((settings, reference)) = reference {
+            Self::strip_root_motion_from(&mut self.pose, &settings, &reference);
+        }
+    }
+
+    fn strip_root_motion_from(
+        pose: &mut AnimationPose,
+        settings: &RootMotionSettings,
+        reference: &LocalPose,
+    ) {
+        if let Some(local_pose) = pose.local_poses.get_mut(&settings.node) {
+            if !settings.ignore_x_movement {
+                local_pose.position.x = reference.position.x;
+            }
+            if !settings.ignore_y_movement {
+                local_pose.position.y = reference.position.y;
+            }
+            if !settings.ignore_z_movement {
+                local_pose.position.z = reference.position.z;
+            }
+            if !settings.ignore_rotations {
+                local_pose.rotation = reference.rotation;
+            }
+        }
+    }
+
+    /// Calculates movement of the root node between two time positions. `to` could be out of
+    /// playback range, in this case it will be wrapped around the range if `looped` is `true`
+    /// and clamped otherwise.
+    fn root_motion_between(&self, from: f32, to: f32, looped: bool) -> Option<RootMotion> {
+        let (settings, track) = self.root_track()?;
+        let range = self.playback_range();
+        let length = range.end - range.start;
+
+        let segments = if looped && length > 0.0 && to > range.end {
+            [(from, range.end), (range.start, to - length)]
+        } else if looped && length > 0.0 && to < range.start {
+            [(from, range.start), (range.end, to + length)]
+        } else {
+            let to = to.clamp(range.start, range.end);
+            [(from, to), (to, to)]
+        };
+
+        let reference = track.get_local_pose(range.start)?;
+
+        let mut motion = RootMotion::default();
+        for (begin, end) in segments {
+            let begin = track.get_local_pose(begin.clamp(range.start, range.end))?;
+            let end = track.get_local_pose(end.clamp(range.start, range.end))?;
+
+            let mut delta_position = end.position - begin.position;
+            if settings.ignore_x_movement {
+                delta_position.x = 0.0;
+            }
+            if settings.ignore_y_movement {
+                delta_position.y = 0.0;
+            }
+            if settings.ignore_z_movement {
+                delta_position.z = 0.0;
+            }
+
+            let segment = if settings.ignore_rotations {
+                RootMotion {
+                    delta_position,
+                    delta_rotation: UnitQuaternion::identity(),
+                }
+            } else {
+                // Express the deltas relative to the current orientation of the root excluding
+                // its orientation at the beginning of the animation.
+                let frame = reference.rotation * begin.rotation.inverse();
+                RootMotion {
+                    delta_position: frame * delta_position,
+                    delta_rotation: frame * end.rotation * reference.rotation.inverse(),
+                }
+            };
+
+            motion.append(&segment);
+        }
+
+        Some(motion)
+    }
+
+    /// Calculates root motion between two normalized (in `[0; 1]` range of the playback range)
+    /// time positions. `to` could be out of `[0; 1]` range, in this case the motion is wrapped
+    /// around the playback range. Returns `None` if the animation has no root motion.
+    pub fn root_motion_between_normalized(&self, from: f32, to: f32) -> Option<RootMotion> {
+        let range = self.playback_range();
+        let length = range.end - range.start;
+        self.root_motion_between(range.start + length * from, range.start + length * to, true)
     }
 
     pub fn get_pose(&self) -> &AnimationPose {
         &self.pose
     }
+
+    /// Samples the animation at given normalized (in `[0; 1]` range of the playback range) time
+    /// and writes the result into `pose`. Unlike normal playback, it does not modify time position
+    /// of the animation, so multiple animations could be sampled in sync with each other.
+    pub fn evaluate_pose_at_normalized(&self, normalized_time: f32, pose: &mut AnimationPose) {
+        let range = self.playback_range();
+        let time = range.start + (range.end - range.start) * normalized_time.clamp(0.0, 1.0);
+
+        pose.reset();
+        for track in self.tracks.iter() {
+            if track.is_enabled() {
+                if let Some(local_pose) = track.get_local_pose(time) {
+                    pose.add_local_pose(local_pose);
+                }
+            }
+        }
+        Self::evaluate_values(&self.blend_shape_tracks, &self.curve_tracks, time, pose);
+
+        if let Some((settings, track)) = self.root_track() {
+            if let Some(reference) = track.get_local_pose(range.start) {
+                Self::strip_root_motion_from(pose, settings, &reference);
+            }
+        }
+    }
 }
 
 impl Default for Animation {
@@ -714,7 +1714,13 @@ impl Default for Animation {
             pose: Default::default(),
             signals: Default::default(),
             events: Default::default(),
+            recent_events: Default::default(),
             time_slice: Default::default(),
+            root_motion_settings: None,
+            skeleton_mapping: None,
+            blend_shape_tracks: Default::default(),
+            curve_tracks: Default::default(),
+            driven: false,
         }
     }
 }
@@ -838,8 +1844,11 @@ impl AnimationContainer {
     }
 
     pub fn update_animations(&mut self, dt: f32) {
-        for animation in self.pool.iter_mut().filter(|anim| anim.enabled) {
-            animation.tick(dt);
+        for animation in self.pool.iter_mut() {
+            animation.recent_events.clear();
+            if animation.enabled && !std::mem::take(&mut animation.driven) {
+                animation.tick(dt);
+            }
         }
     }
 
@@ -886,3 +1895,215 @@ impl IndexMut<Handle<Animation>> for AnimationContainer {
         &mut self.pool[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            Animation, AnimationPose, AnimationSignal, BlendShapeKeyFrame, BlendShapeTrack,
+            KeyFrame, RootMotion, RootMotionSettings, SignalPayload, Track,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::node::Node,
+    };
+
+    fn make_animation() -> (Animation, Handle<Node>) {
+        let root = Handle::new(1, 1);
+
+        let mut track = Track::new();
+        track.set_node(root);
+        for (time, x) in [(0.0, 0.0), (2.0, 2.0)] {
+            track.add_key_frame(KeyFrame::new(
+                time,
+                Vector3::new(x, 1.0, 0.0),
+                Vector3::new(1.0, 1.0, 1.0),
+                UnitQuaternion::identity(),
+            ));
+        }
+
+        let mut animation = Animation::default().with_root_motion_settings(RootMotionSettings {
+            node: root,
+            ignore_y_movement: true,
+            ..Default::default()
+        });
+        animation.add_track(track);
+
+        (animation, root)
+    }
+
+    #[test]
+    fn test_root_motion_extraction() {
+        let (mut animation, root) = make_animation();
+
+        animation.set_time_position(1.0);
+        animation.tick(0.5);
+
+        let pose = animation.get_pose();
+        // Horizontal movement is stripped from the pose, vertical is not extracted.
+        assert_eq!(
+            pose.local_poses[&root].position,
+            Vector3::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            pose.root_motion().unwrap().delta_position,
+            Vector3::new(0.5, 0.0, 0.0)
+        );
+
+        // Motion must be continuous when the animation loops.
+        animation.set_time_position(1.75);
+        animation.tick(0.5);
+        let delta = animation.get_pose().root_motion().unwrap().delta_position;
+        assert!((delta - Vector3::new(0.5, 0.0, 0.0)).norm() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_root_motion_blending() {
+        let mut a = AnimationPose::default();
+        a.set_root_motion(Some(RootMotion {
+            delta_position: Vector3::new(1.0, 0.0, 0.0),
+            ..Default::default()
+        }));
+        let mut b = AnimationPose::default();
+        b.set_root_motion(Some(RootMotion {
+            delta_position: Vector3::new(0.0, 0.0, 1.0),
+            ..Default::default()
+        }));
+
+        let mut pose = AnimationPose::default();
+        pose.blend_with(&a, 0.5);
+        pose.blend_with(&b, 0.5);
+        assert_eq!(
+            pose.root_motion().unwrap().delta_position,
+            Vector3::new(0.5, 0.0, 0.5)
+        );
+
+        pose.reset();
+        assert!(pose.root_motion().is_none());
+
+        // Transition from `a` to `b`.
+        pose.blend_with(&a, 0.75);
+        pose.blend_with(&b, 0.25);
+        assert_eq!(
+            pose.root_motion().unwrap().delta_position,
+            Vector3::new(0.75, 0.0, 0.25)
+        );
+    }
+
+    #[test]
+    fn test_root_motion_blend_with() {
+        let mut motion = RootMotion {
+            delta_position: Vector3::new(2.0, 0.0, 0.0),
+            delta_rotation: UnitQuaternion::identity(),
+        };
+        let other = RootMotion {
+            delta_position: Vector3::new(0.0, 0.0, 4.0),
+            delta_rotation: UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                std::f32::consts::FRAC_PI_2,
+            ),
+        };
+
+        motion.blend_with(&other, 0.25);
+        assert!((motion.delta_position - Vector3::new(1.5, 0.0, 1.0)).norm() < 1.0e-5);
+        assert!((motion.delta_rotation.angle() - std::f32::consts::FRAC_PI_2 * 0.25).abs() < 0.05);
+
+        motion.blend_with(&other, 1.0);
+        assert!((motion.delta_position - other.delta_position).norm() < 1.0e-5);
+        assert!(motion.delta_rotation.angle_to(&other.delta_rotation) < 1.0e-3);
+    }
+
+    #[test]
+    fn test_blend_shape_track() {
+        let mesh = Handle::new(1, 1);
+
+        let mut track = BlendShapeTrack::new(mesh, "Smile");
+        track.add_key_frame(BlendShapeKeyFrame::new(2.0, 1.0));
+        track.add_key_frame(BlendShapeKeyFrame::new(0.0, 0.0));
+
+        let mut animation = Animation::default();
+        animation.add_blend_shape_track(track);
+        assert_eq!(animation.length(), 2.0);
+
+        let mut a = AnimationPose::default();
+        animation.evaluate_pose_at_normalized(0.5, &mut a);
+        assert_eq!(a.blend_shape_weight(mesh, "Smile"), Some(0.5));
+
+        let mut b = AnimationPose::default();
+        b.set_blend_shape_weight(mesh, "Smile", 1.0);
+
+        a.override_with(&b, 0.5, |_| true);
+        assert_eq!(a.blend_shape_weight(mesh, "Smile"), Some(0.75));
+        assert_eq!(a.blend_shape_weight(mesh, "Frown"), None);
+    }
+
+    #[test]
+    fn test_key_frame_reduction() {
+        let mut track = Track::new();
+        for (time, x) in [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 2.0), (4.0, 2.0)] {
+            track.add_key_frame(KeyFrame::new(
+                time,
+                Vector3::new(x, 0.0, 0.0),
+                Vector3::new(1.0, 1.0, 1.0),
+                UnitQuaternion::identity(),
+            ));
+        }
+
+        track.reduce_key_frames(0.001);
+
+        let times = track
+            .get_key_frames()
+            .iter()
+            .map(|k| k.time)
+            .collect::<Vec<_>>();
+        assert_eq!(times, vec![0.0, 2.0, 4.0]);
+        assert_eq!(
+            track.get_local_pose(1.5).unwrap().position,
+            Vector3::new(1.5, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_signals() {
+        let (mut animation, _) = make_animation();
+        animation.set_loop(true);
+        animation
+            .add_signal(AnimationSignal::new(0, 0.0).with_name("Start"))
+            .add_signal(
+                AnimationSignal::new(1, 1.8)
+                    .with_name("Footstep")
+                    .with_payload(SignalPayload::Number(1.0)),
+            );
+
+        let names = |animation: &Animation| {
+            animation
+                .recent_events()
+                .iter()
+                .map(|e| e.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Both signals are emitted when the playback wraps around.
+        animation.set_time_position(1.5);
+        animation.tick(1.0);
+        assert_eq!(names(&animation), vec!["Footstep", "Start"]);
+        assert_eq!(
+            animation.pop_event().unwrap().payload,
+            SignalPayload::Number(1.0)
+        );
+
+        // Signals are emitted once.
+        animation.recent_events.clear();
+        animation.tick(0.5);
+        assert!(names(&animation).is_empty());
+
+        // Signals outside of time slice are never emitted.
+        animation.set_time_slice(Some(0.0..1.0));
+        animation.set_time_position(0.5);
+        animation.recent_events.clear();
+        animation.tick(1.0);
+        assert_eq!(names(&animation), vec!["Start"]);
+    }
+}
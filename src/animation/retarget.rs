@@ -0,0 +1,351 @@
+//! Animation retargeting allows you to use animations authored for one skeleton on another
+//! skeleton, that may have different bone names, different bind poses (orientations of bones)
+//! and different proportions.
+//!
+//! Bones of source and target skeletons are matched using [`SkeletonMapping`], which is usually
+//! stored as a resource and shared across multiple animations. Rotations of the source bones are
+//! converted to the target bones using global orientations of the bones in bind poses, it means
+//! that both skeletons should be in the same pose (T-pose or A-pose) in their bind poses.
+//! Translations are handled according to [`TranslationRetargeting`] mode of each bone.
+//!
+//! Bind pose of a node is taken from the resource the node was instantiated from (if any), so it
+//! is safe to retarget animations to an instance that is already animated.
+//!
+//! Retargeting of a whole model resource could be done using
+//! [`Model::retarget_animations_with_mapping`](crate::resource::model::Model::retarget_animations_with_mapping),
+//! animations of animation blending state machines can be retargeted by specifying skeleton
+//! mapping in PlayAnimation nodes.
+
+use crate::{
+    animation::{curve_track::TrackBinding, Animation, KeyFrame},
+    asset::ResourceState,
+    core::{
+        algebra::{Matrix4, UnitQuaternion},
+        math::Matrix4Ext,
+        pool::Handle,
+    },
+    resource::skeleton_mapping::{SkeletonMapping, TranslationRetargeting},
+    scene::{graph::Graph, node::Node, transform::Transform},
+};
+
+/// Returns local transform of a node in its bind pose.
+fn bind_transform(graph: &Graph, node: Handle<Node>) -> Transform {
+    let node_ref = &graph[node];
+    if let Some(resource) = node_ref.resource() {
+        // The resource could be locked if it is the source of retargeting, in this case the
+        // current transform of the node is used.
+        if let Some(state) = resource.try_acquire_state() {
+            if let ResourceState::Ok(ref data) = *state {
+                if let Some(original) = data
+                    .get_scene()
+                    .graph
+                    .try_get(node_ref.original_handle_in_resource())
+                {
+                    return original.local_transform().clone();
+                }
+            }
+        }
+    }
+    node_ref.local_transform().clone()
+}
+
+/// Returns global transform of a node in its bind pose.
+fn global_bind_matrix(graph: &Graph, node: Handle<Node>) -> Matrix4<f32> {
+    let mut matrix = Matrix4::identity();
+    let mut current = node;
+    while let Some(current_ref) = graph.try_get(current) {
+        matrix = bind_transform(graph, current).matrix() * matrix;
+        current = current_ref.parent();
+    }
+    matrix
+}
+
+fn rotation_of(matrix: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_matrix(&matrix.basis())
+}
+
+struct BoneBindPose {
+    local: Transform,
+    global: Matrix4<f32>,
+    parent_global: Matrix4<f32>,
+}
+
+impl BoneBindPose {
+    fn new(graph: &Graph, node: Handle<Node>) -> Self {
+        Self {
+            local: bind_transform(graph, node),
+            global: global_bind_matrix(graph, node),
+            parent_global: global_bind_matrix(graph, graph[node].parent()),
+        }
+    }
+
+    // Length of the bind translation of the bone in world units.
+    fn height(&self) -> f32 {
+        self.parent_global
+            .transform_vector(&**self.local.position())
+            .norm()
+    }
+}
+
+/// Converts key frames of a source bone to a target bone. See module docs for more info.
+pub fn retarget_key_frames(
+    frames: &[KeyFrame],
+    source_graph: &Graph,
+    source_bone: Handle<Node>,
+    target_graph: &Graph,
+    target_bone: Handle<Node>,
+    translation: TranslationRetargeting,
+) -> Vec<KeyFrame> {
+    let source = BoneBindPose::new(source_graph, source_bone);
+    let target = BoneBindPose::new(target_graph, target_bone);
+
+    // Difference between global orientations of the bones (and their parents) in bind poses.
+    let offset = rotation_of(&source.global).inverse() * rotation_of(&target.global);
+    let parent_offset =
+        rotation_of(&source.parent_global).inverse() * rotation_of(&target.parent_global);
+
+    let left = target.local.pre_rotation().inverse()
+        * parent_offset.inverse()
+        * **source.local.pre_rotation();
+    let right = source.local.post_rotation().inverse() * offset * **target.local.post_rotation();
+
+    let source_height = source.height();
+    let height_ratio = if source_height > f32::EPSILON {
+        target.height() / source_height
+    } else {
+        1.0
+    };
+    let inv_target_parent_global = target
+        .parent_global
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity);
+
+    frames
+        .iter()
+        .map(|frame| {
+            let position = match translation {
+                TranslationRetargeting::Skeleton => **target.local.position(),
+                TranslationRetargeting::Animation => frame.position,
+                TranslationRetargeting::AnimationScaled => {
+                    let delta = source
+                        .parent_global
+                        .transform_vector(&(frame.position - **source.local.position()))
+                        .scale(height_ratio);
+                    **target.local.position() + inv_target_parent_global.transform_vector(&delta)
+                }
+            };
+
+            KeyFrame {
+                position,
+                scale: **target.local.scale(),
+                rotation: left * frame.rotation * right,
+                time: frame.time,
+            }
+        })
+        .collect()
+}
+
+/// Creates a copy of the source animation retargeted to a target skeleton, that starts from
+/// `target_root` node. Tracks of the bones that are not in the mapping or that cannot be found
+/// in the target hierarchy are removed. See module docs for more info.
+pub fn retarget_animation(
+    source: &Animation,
+    source_graph: &Graph,
+    target_graph: &Graph,
+    target_root: Handle<Node>,
+    mapping: &SkeletonMapping,
+) -> Animation {
+    let find_target = |source_node: Handle<Node>| {
+        source_graph
+            .try_get(source_node)
+            .and_then(|source_node| mapping.by_source(source_node.name()))
+            .map(|bone_mapping| {
+                (
+                    target_graph.find_by_name(target_root, &bone_mapping.target),
+                    bone_mapping.translation,
+                )
+            })
+            .filter(|(target_node, _)| target_node.is_some())
+    };
+
+    let mut animation = source.clone();
+
+    animation.retain_tracks(|track| find_target(track.get_node()).is_some());
+
+    for track in animation.get_tracks_mut() {
+        let source_node = track.get_node();
+        if let Some((target_node, translation)) = find_target(source_node) {
+            let frames = retarget_key_frames(
+                track.get_key_frames(),
+                source_graph,
+                source_node,
+                target_graph,
+                target_node,
+                translation,
+            );
+            track.set_key_frames(&frames);
+            track.set_node(target_node);
+        }
+    }
+
+    // Blend shapes do not depend on skeleton, so meshes are matched by their names.
+    for track in animation.blend_shape_tracks_mut() {
+        let target_node = source_graph
+            .try_get(track.node())
+            .map(|source_node| target_graph.find_by_name(target_root, source_node.name()))
+            .unwrap_or_default();
+        track.set_node(target_node);
+    }
+    animation.retain_blend_shape_tracks(|track| track.node().is_some());
+
+    // Transform curves can't be converted between skeletons, other curves are matched by names
+    // of nodes the same as blend shapes.
+    animation.retain_curve_tracks(|track| {
+        !matches!(
+            track.binding(),
+            TrackBinding::Position | TrackBinding::Rotation | TrackBinding::Scale
+        )
+    });
+    for track in animation.curve_tracks_mut() {
+        let target_node = source_graph
+            .try_get(track.node())
+            .map(|source_node| target_graph.find_by_name(target_root, source_node.name()))
+            .unwrap_or_default();
+        track.set_node(target_node);
+    }
+    animation.retain_curve_tracks(|track| track.node().is_some());
+
+    if let Some(settings) = animation.root_motion_settings_mut() {
+        settings.node = find_target(settings.node)
+            .map(|(target_node, _)| target_node)
+            .unwrap_or_default();
+    }
+
+    animation
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{retarget::retarget_animation, Animation, KeyFrame, Track},
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        resource::skeleton_mapping::{BoneMapping, SkeletonMapping, TranslationRetargeting},
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn add_bone(
+        graph: &mut Graph,
+        name: &str,
+        parent: Handle<Node>,
+        position: Vector3<f32>,
+        pre_rotation: UnitQuaternion<f32>,
+    ) -> Handle<Node> {
+        let bone = PivotBuilder::new(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_pre_rotation(pre_rotation)
+                    .build(),
+            ),
+        )
+        .build(graph);
+        graph.link_nodes(bone, parent);
+        bone
+    }
+
+    #[test]
+    fn test_retarget_animation() {
+        // Source skeleton has identity orientations, target skeleton is two times taller and its
+        // spine has different bind orientation.
+        let mut source_graph = Graph::new();
+        let source_root = source_graph.get_root();
+        let source_hips = add_bone(
+            &mut source_graph,
+            "Hips",
+            source_root,
+            Vector3::new(0.0, 1.0, 0.0),
+            UnitQuaternion::identity(),
+        );
+        let source_spine = add_bone(
+            &mut source_graph,
+            "Spine",
+            source_hips,
+            Vector3::new(0.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+        );
+
+        let mut target_graph = Graph::new();
+        let target_root = target_graph.get_root();
+        let target_hips = add_bone(
+            &mut target_graph,
+            "Pelvis",
+            target_root,
+            Vector3::new(0.0, 2.0, 0.0),
+            UnitQuaternion::identity(),
+        );
+        let spine_pre_rotation = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 1.0);
+        let target_spine = add_bone(
+            &mut target_graph,
+            "Spine01",
+            target_hips,
+            Vector3::new(0.0, 1.0, 0.0),
+            spine_pre_rotation,
+        );
+
+        let spine_rotation = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.5);
+
+        let mut animation = Animation::default();
+        for (node, position, rotation) in [
+            (
+                source_hips,
+                Vector3::new(1.0, 1.0, 0.0),
+                UnitQuaternion::identity(),
+            ),
+            (source_spine, Vector3::new(0.0, 0.5, 0.0), spine_rotation),
+        ] {
+            let mut track = Track::new();
+            track.set_node(node);
+            track.add_key_frame(KeyFrame::new(
+                0.0,
+                position,
+                Vector3::new(1.0, 1.0, 1.0),
+                rotation,
+            ));
+            animation.add_track(track);
+        }
+
+        let mapping = SkeletonMapping {
+            bones: vec![
+                BoneMapping::new("Hips", "Pelvis")
+                    .with_translation(TranslationRetargeting::AnimationScaled),
+                BoneMapping::new("Spine", "Spine01"),
+            ],
+        };
+
+        let retargeted = retarget_animation(
+            &animation,
+            &source_graph,
+            &target_graph,
+            target_root,
+            &mapping,
+        );
+
+        let hips = retargeted.track_of(target_hips).unwrap().get_key_frames()[0];
+        // Movement is scaled by the ratio of heights.
+        assert!((hips.position - Vector3::new(2.0, 2.0, 0.0)).norm() < 1.0e-5);
+
+        let spine = retargeted.track_of(target_spine).unwrap().get_key_frames()[0];
+        // Bone length of the target is preserved.
+        assert!((spine.position - Vector3::new(0.0, 1.0, 0.0)).norm() < 1.0e-5);
+        // Global rotation of the target bone must match the global rotation of the source bone
+        // multiplied by the bind orientation difference.
+        let global = spine_pre_rotation * spine.rotation;
+        assert!(global.angle_to(&(spine_rotation * spine_pre_rotation)) < 1.0e-4);
+    }
+}
@@ -22,12 +22,15 @@ use crate::{
     renderer::{framework::error::FrameworkError, Renderer},
     resource::{model::Model, texture::TextureKind},
     scene::{
-        graph::event::GraphEvent,
+        graph::{event::GraphEvent, Graph},
         node::{constructor::NodeConstructorContainer, Node, TypeUuidProvider},
         sound::SoundEngine,
         Scene, SceneContainer,
     },
-    script::{constructor::ScriptConstructorContainer, Script, ScriptContext, ScriptDeinitContext},
+    script::{
+        constructor::ScriptConstructorContainer, AnimationScriptEvent, Script, ScriptContext,
+        ScriptDeinitContext,
+    },
     utils::log::Log,
     window::{Window, WindowBuilder},
 };
@@ -250,6 +253,76 @@ fn process_node<T>(
     }
 }
 
+// Finds the closest node up in the hierarchy (including the given node) that has a script.
+fn find_script_receiver(graph: &Graph, mut handle: Handle<Node>) -> Option<Handle<Node>> {
+    while let Some(node) = graph.try_get(handle) {
+        if node.script.is_some() {
+            return Some(handle);
+        }
+        handle = node.parent();
+    }
+    None
+}
+
+// Passes events produced by animations and animation blending state machines during last update
+// of the scene to the scripts, see `ScriptTrait::on_animation_event` for more info.
+fn dispatch_animation_events(
+    scene: &mut Scene,
+    plugins: &mut [Box<dyn Plugin>],
+    resource_manager: &ResourceManager,
+    dt: f32,
+) {
+    let mut events = Vec::new();
+
+    for (handle, animation) in scene.animations.pair_iter() {
+        if animation.recent_events().is_empty() {
+            continue;
+        }
+        if let Some(receiver) = animation
+            .first_animated_node()
+            .and_then(|node| find_script_receiver(&scene.graph, node))
+        {
+            events.extend(animation.recent_events().iter().map(|event| {
+                (
+                    receiver,
+                    AnimationScriptEvent::Signal {
+                        animation: handle,
+                        event: event.clone(),
+                    },
+                )
+            }));
+        }
+    }
+
+    for (handle, machine) in scene.animation_machines.pair_iter() {
+        if machine.recent_events().is_empty() {
+            continue;
+        }
+        if let Some(receiver) = find_script_receiver(&scene.graph, machine.root) {
+            events.extend(machine.recent_events().iter().map(|event| {
+                (
+                    receiver,
+                    AnimationScriptEvent::Machine {
+                        machine: handle,
+                        event: event.clone(),
+                    },
+                )
+            }));
+        }
+    }
+
+    for (receiver, event) in events {
+        process_node(
+            scene,
+            dt,
+            receiver,
+            plugins,
+            resource_manager,
+            &mut |script, context| script.on_animation_event(&event, context),
+        );
+    }
+}
+
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
     plugins: &mut [Box<dyn Plugin>],
@@ -380,6 +453,12 @@ impl Engine {
             { unsafe { glow::Context::from_loader_function(|s| context.get_proc_address(s)) } };
 
         let sound_engine = SoundEngine::new();
+        for (backend, error) in sound_engine.lock().unwrap().device_errors() {
+            Log::warn(format!(
+                "Unable to open output device using {:?} backend. Reason: {}",
+                backend, error
+            ));
+        }
 
         let renderer = Renderer::new(
             glow_context,
@@ -602,6 +681,8 @@ impl Engine {
                 let (tx, rx) = mpsc::channel();
                 scene.graph.event_broadcaster.subscribe(tx);
 
+                dispatch_animation_events(scene, &mut self.plugins, &self.resource_manager, dt);
+
                 process_scripts(
                     scene,
                     &mut self.plugins,
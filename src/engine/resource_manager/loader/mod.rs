@@ -8,8 +8,11 @@ use std::{future::Future, pin::Pin};
 pub mod absm;
 pub mod curve;
 pub mod model;
+pub mod physics_material;
 pub mod shader;
+pub mod skeleton_mapping;
 pub mod sound;
+pub mod sound_cue;
 pub mod texture;
 
 /// Future type for resource loading. See 'ResourceLoader'.
@@ -0,0 +1,49 @@
+//! Physics material loader.
+
+use crate::{
+    engine::resource_manager::{
+        container::event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+    },
+    resource::physics_material::{
+        PhysicsMaterialImportOptions, PhysicsMaterialResource, PhysicsMaterialResourceState,
+    },
+    utils::log::Log,
+};
+
+/// Default implementation for physics material loading.
+pub struct PhysicsMaterialLoader;
+
+impl ResourceLoader<PhysicsMaterialResource, PhysicsMaterialImportOptions>
+    for PhysicsMaterialLoader
+{
+    fn load(
+        &self,
+        material: PhysicsMaterialResource,
+        _default_import_options: PhysicsMaterialImportOptions,
+        event_broadcaster: ResourceEventBroadcaster<PhysicsMaterialResource>,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = material.state().path().to_path_buf();
+
+            match PhysicsMaterialResourceState::from_file(&path).await {
+                Ok(material_state) => {
+                    Log::info(format!("Physics material {:?} is loaded!", path));
+
+                    material.state().commit_ok(material_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(material, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load physics material from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    material.state().commit_error(path, error);
+                }
+            }
+        })
+    }
+}
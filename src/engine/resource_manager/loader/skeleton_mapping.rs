@@ -0,0 +1,49 @@
+//! Skeleton mapping loader.
+
+use crate::{
+    engine::resource_manager::{
+        container::event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+    },
+    resource::skeleton_mapping::{
+        SkeletonMappingImportOptions, SkeletonMappingResource, SkeletonMappingResourceState,
+    },
+    utils::log::Log,
+};
+
+/// Default implementation for skeleton mapping loading.
+pub struct SkeletonMappingLoader;
+
+impl ResourceLoader<SkeletonMappingResource, SkeletonMappingImportOptions>
+    for SkeletonMappingLoader
+{
+    fn load(
+        &self,
+        mapping: SkeletonMappingResource,
+        _default_import_options: SkeletonMappingImportOptions,
+        event_broadcaster: ResourceEventBroadcaster<SkeletonMappingResource>,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = mapping.state().path().to_path_buf();
+
+            match SkeletonMappingResourceState::from_file(&path).await {
+                Ok(mapping_state) => {
+                    Log::info(format!("Skeleton mapping {:?} is loaded!", path));
+
+                    mapping.state().commit_ok(mapping_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(mapping, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load skeleton mapping from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    mapping.state().commit_error(path, error);
+                }
+            }
+        })
+    }
+}
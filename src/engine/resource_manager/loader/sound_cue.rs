@@ -0,0 +1,51 @@
+//! Sound cue loader.
+
+use crate::{
+    engine::resource_manager::{
+        container::event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        ResourceManager,
+    },
+    resource::sound_cue::{SoundCueImportOptions, SoundCueResource, SoundCueResourceState},
+    utils::log::Log,
+};
+
+/// Default implementation for sound cue loading.
+pub struct SoundCueLoader {
+    /// Resource manager to request sound buffers of cues.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader<SoundCueResource, SoundCueImportOptions> for SoundCueLoader {
+    fn load(
+        &self,
+        cue: SoundCueResource,
+        _default_import_options: SoundCueImportOptions,
+        event_broadcaster: ResourceEventBroadcaster<SoundCueResource>,
+        reload: bool,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+
+        Box::pin(async move {
+            let path = cue.state().path().to_path_buf();
+
+            match SoundCueResourceState::from_file(&path, resource_manager).await {
+                Ok(cue_state) => {
+                    Log::info(format!("Sound cue {:?} is loaded!", path));
+
+                    cue.state().commit_ok(cue_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(cue, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load sound cue from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    cue.state().commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -13,8 +13,11 @@ use crate::{
                 absm::AbsmLoader,
                 curve::CurveLoader,
                 model::ModelLoader,
+                physics_material::PhysicsMaterialLoader,
                 shader::ShaderLoader,
+                skeleton_mapping::SkeletonMappingLoader,
                 sound::{SoundBufferImportOptions, SoundBufferLoader},
+                sound_cue::SoundCueLoader,
                 texture::TextureLoader,
                 ResourceLoader,
             },
@@ -27,6 +30,9 @@ use crate::{
         absm::{AbsmImportOptions, AbsmResource},
         curve::{CurveImportOptions, CurveResource},
         model::{Model, ModelImportOptions},
+        physics_material::{PhysicsMaterialImportOptions, PhysicsMaterialResource},
+        skeleton_mapping::{SkeletonMappingImportOptions, SkeletonMappingResource},
+        sound_cue::{SoundCueImportOptions, SoundCueResource},
         texture::{Texture, TextureError, TextureImportOptions, TextureState},
     },
     utils::{log::Log, watcher::FileSystemWatcher},
@@ -59,6 +65,15 @@ pub struct ContainersStorage {
 
     /// Container for ABSM resources.
     pub absm: ResourceContainer<AbsmResource, AbsmImportOptions>,
+
+    /// Container for physics material resources.
+    pub physics_materials: ResourceContainer<PhysicsMaterialResource, PhysicsMaterialImportOptions>,
+
+    /// Container for skeleton mapping resources.
+    pub skeleton_mappings: ResourceContainer<SkeletonMappingResource, SkeletonMappingImportOptions>,
+
+    /// Container for sound cue resources.
+    pub sound_cues: ResourceContainer<SoundCueResource, SoundCueImportOptions>,
 }
 
 impl ContainersStorage {
@@ -110,6 +125,30 @@ impl ContainersStorage {
         self.absm.set_loader(loader);
     }
 
+    /// Sets a custom physics material loader.
+    pub fn set_physics_material_loader<L>(&mut self, loader: L)
+    where
+        L: 'static + ResourceLoader<PhysicsMaterialResource, PhysicsMaterialImportOptions>,
+    {
+        self.physics_materials.set_loader(loader);
+    }
+
+    /// Sets a custom skeleton mapping loader.
+    pub fn set_skeleton_mapping_loader<L>(&mut self, loader: L)
+    where
+        L: 'static + ResourceLoader<SkeletonMappingResource, SkeletonMappingImportOptions>,
+    {
+        self.skeleton_mappings.set_loader(loader);
+    }
+
+    /// Sets a custom sound cue loader.
+    pub fn set_sound_cue_loader<L>(&mut self, loader: L)
+    where
+        L: 'static + ResourceLoader<SoundCueResource, SoundCueImportOptions>,
+    {
+        self.sound_cues.set_loader(loader);
+    }
+
     /// Wait until all resources are loaded (or failed to load).
     pub fn wait_concurrent(&self) -> ResourceWaitContext {
         ResourceWaitContext {
@@ -119,6 +158,9 @@ impl ContainersStorage {
             shaders: self.shaders.resources(),
             textures: self.textures.resources(),
             sound_buffers: self.sound_buffers.resources(),
+            physics_materials: self.physics_materials.resources(),
+            skeleton_mappings: self.skeleton_mappings.resources(),
+            sound_cues: self.sound_cues.resources(),
         }
     }
 }
@@ -132,6 +174,9 @@ pub struct ResourceWaitContext {
     shaders: Vec<Shader>,
     textures: Vec<Texture>,
     sound_buffers: Vec<SoundBufferResource>,
+    physics_materials: Vec<PhysicsMaterialResource>,
+    skeleton_mappings: Vec<SkeletonMappingResource>,
+    sound_cues: Vec<SoundCueResource>,
 }
 
 impl ResourceWaitContext {
@@ -143,6 +188,9 @@ impl ResourceWaitContext {
         join_all(self.shaders).await;
         join_all(self.textures).await;
         join_all(self.sound_buffers).await;
+        join_all(self.physics_materials).await;
+        join_all(self.skeleton_mappings).await;
+        join_all(self.sound_cues).await;
     }
 }
 
@@ -199,7 +247,21 @@ impl ResourceManager {
             sound_buffers: ResourceContainer::new(task_pool.clone(), Box::new(SoundBufferLoader)),
             shaders: ResourceContainer::new(task_pool.clone(), Box::new(ShaderLoader)),
             curves: ResourceContainer::new(task_pool.clone(), Box::new(CurveLoader)),
-            absm: ResourceContainer::new(task_pool, Box::new(AbsmLoader)),
+            absm: ResourceContainer::new(task_pool.clone(), Box::new(AbsmLoader)),
+            physics_materials: ResourceContainer::new(
+                task_pool.clone(),
+                Box::new(PhysicsMaterialLoader),
+            ),
+            skeleton_mappings: ResourceContainer::new(
+                task_pool.clone(),
+                Box::new(SkeletonMappingLoader),
+            ),
+            sound_cues: ResourceContainer::new(
+                task_pool,
+                Box::new(SoundCueLoader {
+                    resource_manager: resource_manager.clone(),
+                }),
+            ),
         });
 
         resource_manager
@@ -340,6 +402,47 @@ impl ResourceManager {
         self.state().containers_mut().absm.request(path)
     }
 
+    /// Tries to load a new physics material resource from given path or get instance of existing,
+    /// if any. This method is asynchronous, it immediately returns a physics material which can be
+    /// shared across multiple places, the loading may fail, but it is internal state of the physics
+    /// material resource.
+    ///
+    /// # Async/.await
+    ///
+    /// Each physics material implements Future trait and can be used in async contexts.
+    pub fn request_physics_material<P: AsRef<Path>>(&self, path: P) -> PhysicsMaterialResource {
+        self.state()
+            .containers_mut()
+            .physics_materials
+            .request(path)
+    }
+
+    /// Tries to load a new skeleton mapping resource from given path or get instance of existing,
+    /// if any. This method is asynchronous, it immediately returns a skeleton mapping which can be
+    /// shared across multiple places, the loading may fail, but it is internal state of the
+    /// skeleton mapping resource.
+    ///
+    /// # Async/.await
+    ///
+    /// Each skeleton mapping implements Future trait and can be used in async contexts.
+    pub fn request_skeleton_mapping<P: AsRef<Path>>(&self, path: P) -> SkeletonMappingResource {
+        self.state()
+            .containers_mut()
+            .skeleton_mappings
+            .request(path)
+    }
+
+    /// Tries to load a new sound cue resource from given path or get instance of existing, if any.
+    /// This method is asynchronous, it immediately returns a sound cue which can be shared across
+    /// multiple places, the loading may fail, but it is internal state of the sound cue resource.
+    ///
+    /// # Async/.await
+    ///
+    /// Each sound cue implements Future trait and can be used in async contexts.
+    pub fn request_sound_cue<P: AsRef<Path>>(&self, path: P) -> SoundCueResource {
+        self.state().containers_mut().sound_cues.request(path)
+    }
+
     /// Reloads every loaded texture. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per texture.
     pub async fn reload_textures(&self) {
@@ -375,6 +478,35 @@ impl ResourceManager {
         join_all(resources).await;
     }
 
+    /// Reloads every loaded physics material resource. This method is asynchronous, internally it
+    /// uses thread pool to run reload on separate thread per resource.
+    pub async fn reload_physics_materials(&self) {
+        let resources = self
+            .state()
+            .containers_mut()
+            .physics_materials
+            .reload_resources();
+        join_all(resources).await;
+    }
+
+    /// Reloads every loaded skeleton mapping resource. This method is asynchronous, internally it
+    /// uses thread pool to run reload on separate thread per resource.
+    pub async fn reload_skeleton_mappings(&self) {
+        let resources = self
+            .state()
+            .containers_mut()
+            .skeleton_mappings
+            .reload_resources();
+        join_all(resources).await;
+    }
+
+    /// Reloads every loaded sound cue resource. This method is asynchronous, internally it uses
+    /// thread pool to run reload on separate thread per resource.
+    pub async fn reload_sound_cues(&self) {
+        let resources = self.state().containers_mut().sound_cues.reload_resources();
+        join_all(resources).await;
+    }
+
     /// Reloads every loaded sound buffer. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per sound buffer.
     pub async fn reload_sound_buffers(&self) {
@@ -397,6 +529,9 @@ impl ResourceManager {
             self.reload_shaders(),
             self.reload_curve_resources(),
             self.reload_absm_resources(),
+            self.reload_physics_materials(),
+            self.reload_skeleton_mappings(),
+            self.reload_sound_cues(),
         );
     }
 }
@@ -440,6 +575,9 @@ impl ResourceManagerState {
             + containers.shaders.count_pending_resources()
             + containers.curves.count_pending_resources()
             + containers.absm.count_pending_resources()
+            + containers.physics_materials.count_pending_resources()
+            + containers.skeleton_mappings.count_pending_resources()
+            + containers.sound_cues.count_pending_resources()
     }
 
     /// Returns total amount of loaded resources.
@@ -451,6 +589,9 @@ impl ResourceManagerState {
             + containers.shaders.count_loaded_resources()
             + containers.curves.count_loaded_resources()
             + containers.absm.count_loaded_resources()
+            + containers.physics_materials.count_loaded_resources()
+            + containers.skeleton_mappings.count_loaded_resources()
+            + containers.sound_cues.count_loaded_resources()
     }
 
     /// Returns total amount of registered resources.
@@ -462,6 +603,9 @@ impl ResourceManagerState {
             + containers.shaders.len()
             + containers.curves.len()
             + containers.absm.len()
+            + containers.physics_materials.len()
+            + containers.skeleton_mappings.len()
+            + containers.sound_cues.len()
     }
 
     /// Returns percentage of loading progress. This method is useful to show progress on
@@ -486,6 +630,9 @@ impl ResourceManagerState {
         containers.shaders.destroy_unused();
         containers.curves.destroy_unused();
         containers.absm.destroy_unused();
+        containers.physics_materials.destroy_unused();
+        containers.skeleton_mappings.destroy_unused();
+        containers.sound_cues.destroy_unused();
     }
 
     /// Update resource containers and do hot-reloading.
@@ -503,6 +650,9 @@ impl ResourceManagerState {
         containers.shaders.update(dt);
         containers.curves.update(dt);
         containers.absm.update(dt);
+        containers.physics_materials.update(dt);
+        containers.skeleton_mappings.update(dt);
+        containers.sound_cues.update(dt);
 
         if let Some(watcher) = self.watcher.as_ref() {
             if let Some(DebouncedEvent::Write(path)) = watcher.try_get_event() {
@@ -521,6 +671,9 @@ impl ResourceManagerState {
                     &mut containers.shaders as &mut dyn Container,
                     &mut containers.curves as &mut dyn Container,
                     &mut containers.absm as &mut dyn Container,
+                    &mut containers.physics_materials as &mut dyn Container,
+                    &mut containers.skeleton_mappings as &mut dyn Container,
+                    &mut containers.sound_cues as &mut dyn Container,
                 ] {
                     if container.try_reload_resource_from_path(&relative_path) {
                         break;
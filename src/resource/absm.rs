@@ -63,7 +63,7 @@ impl AbsmResource {
         let data = self.data_ref();
         let definition = &data.absm_definition;
 
-        AnimationsPack::load(&definition.collect_animation_paths(), resource_manager).await
+        definition.animations(resource_manager).await
     }
 
     /// Instantiates animation blending state machine to the specified scene for a given root node.
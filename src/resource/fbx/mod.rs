@@ -14,7 +14,9 @@ use crate::resource::model::{MaterialSearchOptions, ModelImportOptions};
 use crate::scene::mesh::Mesh;
 use crate::scene::pivot::PivotBuilder;
 use crate::{
-    animation::{Animation, AnimationContainer, KeyFrame, Track},
+    animation::{
+        Animation, AnimationContainer, BlendShapeKeyFrame, BlendShapeTrack, KeyFrame, Track,
+    },
     core::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3, Vector4},
         instant::Instant,
@@ -30,8 +32,8 @@ use crate::{
         document::FbxDocument,
         error::FbxError,
         scene::{
-            animation::FbxAnimationCurveNodeType, geometry::FbxGeometry, model::FbxModel,
-            FbxComponent, FbxMapping, FbxScene,
+            animation::FbxAnimationCurveNodeType, blend_shape::FbxBlendShapeChannel,
+            geometry::FbxGeometry, model::FbxModel, FbxComponent, FbxMapping, FbxScene,
         },
     },
     scene::{
@@ -39,7 +41,7 @@ use crate::{
         graph::Graph,
         mesh::{
             buffer::{VertexAttributeUsage, VertexWriteTrait},
-            surface::{Surface, SurfaceData, VertexWeightSet},
+            surface::{BlendShape, BlendShapeDelta, Surface, SurfaceData, VertexWeightSet},
             vertex::{AnimatedVertex, StaticVertex},
             MeshBuilder,
         },
@@ -243,6 +245,101 @@ impl FbxMeshBuilder {
 struct FbxSurfaceData {
     builder: FbxMeshBuilder,
     skin_data: Vec<VertexWeightSet>,
+    // Index of a control point of FBX geometry for each vertex, it is used to map blend shapes
+    // to vertices.
+    control_points: Vec<usize>,
+    blend_shapes: Vec<BlendShape>,
+}
+
+impl FbxSurfaceData {
+    fn build(self) -> Surface {
+        let mut data = self.builder.build();
+        data.blend_shapes = self.blend_shapes;
+        let mut surface = Surface::new(Arc::new(Mutex::new(data)));
+        surface.vertex_weights = self.skin_data;
+        surface
+    }
+}
+
+fn blend_shape_channels<'a>(
+    fbx_scene: &'a FbxScene,
+    model: &FbxModel,
+) -> Result<Vec<&'a FbxBlendShapeChannel>, FbxError> {
+    let mut channels = Vec::new();
+    for &geom_handle in model.geoms.iter() {
+        let geom = fbx_scene.get(geom_handle).as_geometry()?;
+        for &blend_shape_handle in geom.blend_shapes.iter() {
+            for &channel_handle in fbx_scene
+                .get(blend_shape_handle)
+                .as_blend_shape()?
+                .channels
+                .iter()
+            {
+                channels.push(fbx_scene.get(channel_handle).as_blend_shape_channel()?);
+            }
+        }
+    }
+    Ok(channels)
+}
+
+fn convert_blend_shapes(
+    fbx_scene: &FbxScene,
+    geom: &FbxGeometry,
+    geometric_transform: &Matrix4<f32>,
+    data_set: &mut [FbxSurfaceData],
+) -> Result<(), FbxError> {
+    for &blend_shape_handle in geom.blend_shapes.iter() {
+        for &channel_handle in fbx_scene
+            .get(blend_shape_handle)
+            .as_blend_shape()?
+            .channels
+            .iter()
+        {
+            let channel = fbx_scene.get(channel_handle).as_blend_shape_channel()?;
+
+            // In-between targets are not supported, only the full target (the last one) is used.
+            let shape = match channel.shapes.last() {
+                Some(&shape_handle) => fbx_scene.get(shape_handle).as_shape()?,
+                None => continue,
+            };
+
+            let mut offsets = FxHashMap::default();
+            for (i, &index) in shape.indices.iter().enumerate() {
+                let position = geometric_transform.transform_vector(&shape.vertices[i]);
+                let normal = shape
+                    .normals
+                    .get(i)
+                    .map(|normal| geometric_transform.transform_vector(normal))
+                    .unwrap_or_default();
+                offsets.insert(index as usize, (position, normal));
+            }
+
+            for data in data_set.iter_mut() {
+                let deltas = data
+                    .control_points
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(vertex, control_point)| {
+                        offsets
+                            .get(control_point)
+                            .map(|(position, normal)| BlendShapeDelta {
+                                vertex: vertex as u32,
+                                position: *position,
+                                normal: *normal,
+                            })
+                    })
+                    .collect::<Vec<_>>();
+
+                if !deltas.is_empty() {
+                    data.blend_shapes.push(BlendShape {
+                        name: channel.name.clone(),
+                        deltas,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 async fn create_surfaces(
@@ -259,14 +356,11 @@ async fn create_surfaces(
     if model.materials.is_empty() {
         assert_eq!(data_set.len(), 1);
         let data = data_set.into_iter().next().unwrap();
-        let mut surface = Surface::new(Arc::new(Mutex::new(data.builder.build())));
-        surface.vertex_weights = data.skin_data;
-        surfaces.push(surface);
+        surfaces.push(data.build());
     } else {
         assert_eq!(data_set.len(), model.materials.len());
         for (&material_handle, data) in model.materials.iter().zip(data_set.into_iter()) {
-            let mut surface = Surface::new(Arc::new(Mutex::new(data.builder.build())));
-            surface.vertex_weights = data.skin_data;
+            let surface = data.build();
             let material = fbx_scene.get(material_handle).as_material()?;
             if let Err(e) = surface.material().lock().set_property(
                 &ImmutableString::new("diffuseColor"),
@@ -418,6 +512,8 @@ async fn convert_mesh(
                     FbxMeshBuilder::Animated(RawMeshBuilder::new(1024, 1024))
                 },
                 skin_data: Default::default(),
+                control_points: Default::default(),
+                blend_shapes: Default::default(),
             };
             model.materials.len().max(1)
         ];
@@ -454,6 +550,7 @@ async fn convert_mesh(
                         if let Some(skin_data) = weights {
                             data.skin_data.push(skin_data);
                         }
+                        data.control_points.push(index);
                     }
                 }
             }
@@ -464,6 +561,8 @@ async fn convert_mesh(
             }
         }
 
+        convert_blend_shapes(fbx_scene, geom, &geometric_transform, &mut data_set)?;
+
         let mut surfaces = create_surfaces(
             fbx_scene,
             data_set,
@@ -485,9 +584,17 @@ async fn convert_mesh(
         }
     }
 
-    Ok(MeshBuilder::new(base)
+    let mut mesh = MeshBuilder::new(base)
         .with_surfaces(mesh_surfaces)
-        .build(graph))
+        .build_node();
+
+    if let Some(mesh) = mesh.cast_mut::<Mesh>() {
+        for channel in blend_shape_channels(fbx_scene, model)? {
+            mesh.set_blend_shape_weight(&channel.name, channel.deform_percent / 100.0);
+        }
+    }
+
+    Ok(graph.add_node(mesh))
 }
 
 fn convert_model_to_base(model: &FbxModel) -> BaseBuilder {
@@ -604,9 +711,38 @@ async fn convert_model(
             time = next_time;
         }
 
+        if model_import_options.animation_reduction_tolerance >= 0.0 {
+            track.reduce_key_frames(model_import_options.animation_reduction_tolerance);
+        }
+
         animations.get_mut(animation_handle).add_track(track);
     }
 
+    // Convert blend shape animations, FBX stores weights in percents.
+    for channel in blend_shape_channels(fbx_scene, model)? {
+        if channel.animation_curve_node.is_none() {
+            continue;
+        }
+
+        if let FbxComponent::AnimationCurveNode(curve_node) =
+            fbx_scene.get(channel.animation_curve_node)
+        {
+            if let Some(FbxComponent::AnimationCurve(curve)) = curve_node
+                .curves
+                .get("d|DeformPercent")
+                .map(|curve| fbx_scene.get(*curve))
+            {
+                let mut track = BlendShapeTrack::new(node_handle, &channel.name);
+                for key in curve.keys.iter() {
+                    track.add_key_frame(BlendShapeKeyFrame::new(key.time, key.value / 100.0));
+                }
+                animations
+                    .get_mut(animation_handle)
+                    .add_blend_shape_track(track);
+            }
+        }
+    }
+
     Ok(node_handle)
 }
 
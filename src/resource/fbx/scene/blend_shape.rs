@@ -0,0 +1,112 @@
+use crate::{
+    core::{algebra::Vector3, pool::Handle},
+    resource::fbx::{
+        document::{FbxNode, FbxNodeContainer},
+        error::FbxError,
+        scene::FbxComponent,
+    },
+};
+
+/// Geometry of a blend shape target, it contains offsets of control points of the base geometry.
+pub struct FbxShape {
+    pub indices: Vec<i32>,
+    pub vertices: Vec<Vector3<f32>>,
+    // Normals are optional.
+    pub normals: Vec<Vector3<f32>>,
+}
+
+fn read_vec3_array(
+    node_handle: Handle<FbxNode>,
+    nodes: &FbxNodeContainer,
+    name: &str,
+) -> Result<Vec<Vector3<f32>>, FbxError> {
+    let array_node_handle = nodes.find(node_handle, name)?;
+    let array_node = nodes.get_by_name(array_node_handle, "a")?;
+    let mut vectors = Vec::with_capacity(array_node.attrib_count() / 3);
+    for vector in array_node.attributes().chunks_exact(3) {
+        vectors.push(Vector3::new(
+            vector[0].as_f32()?,
+            vector[1].as_f32()?,
+            vector[2].as_f32()?,
+        ));
+    }
+    Ok(vectors)
+}
+
+impl FbxShape {
+    pub(in crate::resource::fbx) fn read(
+        shape_node_handle: Handle<FbxNode>,
+        nodes: &FbxNodeContainer,
+    ) -> Result<Self, FbxError> {
+        let indices_node_handle = nodes.find(shape_node_handle, "Indexes")?;
+        let indices_array_node = nodes.get_by_name(indices_node_handle, "a")?;
+        let mut indices = Vec::with_capacity(indices_array_node.attrib_count());
+        for index in indices_array_node.attributes() {
+            indices.push(index.as_i32()?);
+        }
+
+        let vertices = read_vec3_array(shape_node_handle, nodes, "Vertices")?;
+        if vertices.len() != indices.len() {
+            return Err(FbxError::IndexOutOfBounds);
+        }
+
+        let normals = if nodes.find(shape_node_handle, "Normals").is_ok() {
+            read_vec3_array(shape_node_handle, nodes, "Normals")?
+        } else {
+            Default::default()
+        };
+
+        Ok(Self {
+            indices,
+            vertices,
+            normals,
+        })
+    }
+}
+
+/// Blend shape deformer, it is attached to a geometry and holds a set of channels.
+#[derive(Default)]
+pub struct FbxBlendShape {
+    pub channels: Vec<Handle<FbxComponent>>,
+}
+
+/// Blend shape channel is a named blend shape with a weight (in percents), the weight can be
+/// animated.
+pub struct FbxBlendShapeChannel {
+    pub name: String,
+    pub deform_percent: f32,
+    /// Handles of shapes, usually there is only one shape, multiple shapes are used for
+    /// in-between targets.
+    pub shapes: Vec<Handle<FbxComponent>>,
+    /// Handle of animation curve node, that animates the weight of the channel.
+    pub animation_curve_node: Handle<FbxComponent>,
+}
+
+impl FbxBlendShapeChannel {
+    pub(in crate::resource::fbx) fn read(
+        channel_node_handle: Handle<FbxNode>,
+        nodes: &FbxNodeContainer,
+    ) -> Result<Self, FbxError> {
+        let channel_node = nodes.get(channel_node_handle);
+
+        let mut name = channel_node.get_attrib(1)?.as_string();
+        // Remove prefix
+        if name.starts_with("SubDeformer::") {
+            name = name.chars().skip(13).collect();
+        }
+
+        let deform_percent = match nodes.find(channel_node_handle, "DeformPercent") {
+            Ok(deform_percent_handle) => {
+                nodes.get(deform_percent_handle).get_attrib(0)?.as_f32()?
+            }
+            Err(_) => 0.0,
+        };
+
+        Ok(Self {
+            name,
+            deform_percent,
+            shapes: Default::default(),
+            animation_curve_node: Handle::NONE,
+        })
+    }
+}
@@ -6,4 +6,7 @@ pub mod absm;
 pub mod curve;
 pub mod fbx;
 pub mod model;
+pub mod physics_material;
+pub mod skeleton_mapping;
+pub mod sound_cue;
 pub mod texture;
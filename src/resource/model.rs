@@ -18,7 +18,7 @@
 //! Currently only FBX (common format in game industry for storing complex 3d models)
 //! and RGS (native Fyroxed format) formats are supported.
 use crate::{
-    animation::{Animation, AnimationContainer},
+    animation::{retarget, Animation, AnimationContainer},
     asset::{define_new_resource, Resource, ResourceData},
     core::{
         inspect::{Inspect, PropertyInfo},
@@ -29,7 +29,10 @@ use crate::{
         resource_manager::{options::ImportOptions, ResourceManager},
         SerializationContext,
     },
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        skeleton_mapping::SkeletonMappingResource,
+    },
     scene::{
         graph::{map::NodeHandleMap, Graph},
         node::Node,
@@ -177,6 +180,37 @@ impl Model {
                 anim_copy.get_tracks_mut()[i].set_node(instance_node);
             }
 
+            for track in anim_copy.blend_shape_tracks_mut() {
+                let instance_node = data
+                    .scene
+                    .graph
+                    .try_get(track.node())
+                    .map(|ref_node| graph.find_by_name(root, ref_node.name()))
+                    .unwrap_or_default();
+                track.set_node(instance_node);
+            }
+            anim_copy.retain_blend_shape_tracks(|track| track.node().is_some());
+
+            for track in anim_copy.curve_tracks_mut() {
+                let instance_node = data
+                    .scene
+                    .graph
+                    .try_get(track.node())
+                    .map(|ref_node| graph.find_by_name(root, ref_node.name()))
+                    .unwrap_or_default();
+                track.set_node(instance_node);
+            }
+            anim_copy.retain_curve_tracks(|track| track.node().is_some());
+
+            if let Some(settings) = anim_copy.root_motion_settings_mut() {
+                settings.node = data
+                    .scene
+                    .graph
+                    .try_get(settings.node)
+                    .map(|ref_node| graph.find_by_name(root, ref_node.name()))
+                    .unwrap_or_default();
+            }
+
             animation_handles.push(animations.add(anim_copy));
         }
 
@@ -211,6 +245,65 @@ impl Model {
     ) -> Vec<Handle<Animation>> {
         self.retarget_animations_internal(root, &mut dest_scene.graph, &mut dest_scene.animations)
     }
+
+    pub(crate) fn retarget_animations_with_mapping_internal(
+        &self,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        animations: &mut AnimationContainer,
+        skeleton_mapping: &SkeletonMappingResource,
+    ) -> Vec<Handle<Animation>> {
+        let mapping = match skeleton_mapping.mapping() {
+            Some(mapping) => mapping,
+            None => {
+                Log::err(format!(
+                    "Unable to retarget animations from {:?}, skeleton mapping {:?} is not loaded!",
+                    self.state().path(),
+                    skeleton_mapping.state().path()
+                ));
+                return Vec::new();
+            }
+        };
+
+        let data = self.data_ref();
+        let mut animation_handles = Vec::new();
+
+        for ref_anim in data.scene.animations.iter() {
+            let mut anim_copy =
+                retarget::retarget_animation(ref_anim, &data.scene.graph, graph, root, &mapping);
+
+            // Keep references to the resources, so the animation can be retargeted again when
+            // loading a save file.
+            anim_copy.resource = Some(self.clone());
+            anim_copy.skeleton_mapping = Some(skeleton_mapping.clone());
+
+            animation_handles.push(animations.add(anim_copy));
+        }
+
+        animation_handles
+    }
+
+    /// Does the same as [`Self::retarget_animations`], but allows to retarget animations to a
+    /// skeleton with different bone names, bind pose and proportions using given skeleton
+    /// mapping. See [`crate::animation::retarget`] module docs for more info.
+    ///
+    /// # Notes
+    ///
+    /// Skeleton mapping must be fully loaded before calling this method, otherwise no animations
+    /// will be retargeted.
+    pub fn retarget_animations_with_mapping(
+        &self,
+        root: Handle<Node>,
+        dest_scene: &mut Scene,
+        skeleton_mapping: &SkeletonMappingResource,
+    ) -> Vec<Handle<Animation>> {
+        self.retarget_animations_with_mapping_internal(
+            root,
+            &mut dest_scene.graph,
+            &mut dest_scene.animations,
+            skeleton_mapping,
+        )
+    }
 }
 
 impl ResourceData for ModelData {
@@ -320,11 +413,30 @@ impl MaterialSearchOptions {
 /// ```
 ///
 /// Check documentation of the field of the structure for more info about each parameter.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Inspect)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Inspect)]
 pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+    /// Maximum error (in units for position and scale, in radians for rotation) of key frames
+    /// that could be removed from animation tracks, because they can be restored by interpolation
+    /// of neighbour key frames. Zero value removes only redundant key frames, negative value
+    /// disables the reduction. See [`crate::animation::Track::reduce_key_frames`] for more info.
+    #[serde(default = "default_animation_reduction_tolerance")]
+    pub animation_reduction_tolerance: f32,
+}
+
+fn default_animation_reduction_tolerance() -> f32 {
+    0.001
+}
+
+impl Default for ModelImportOptions {
+    fn default() -> Self {
+        Self {
+            material_search_options: Default::default(),
+            animation_reduction_tolerance: default_animation_reduction_tolerance(),
+        }
+    }
 }
 
 impl ImportOptions for ModelImportOptions {}
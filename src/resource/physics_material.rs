@@ -0,0 +1,176 @@
+//! Physics material resource holds a [`PhysicsMaterial`] - a set of surface properties (friction,
+//! restitution, combine rules, a surface type and sound transmission) that can be shared across multiple colliders and
+//! terrain layers, so the properties of a surface can be changed in one place.
+
+use crate::{
+    asset::{define_new_resource, Resource, ResourceData, ResourceState},
+    core::{
+        inspect::{Inspect, PropertyInfo},
+        io::FileLoadError,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::options::ImportOptions,
+    scene::graph::physics::CoefficientCombineRule,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+/// A set of surface properties used by the physics engine and by game logic.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Inspect)]
+pub struct PhysicsMaterial {
+    /// Friction coefficient of the surface.
+    #[inspect(min_value = 0.0, step = 0.05)]
+    pub friction: f32,
+
+    /// Defines how friction coefficients of two colliders are combined.
+    pub friction_combine_rule: CoefficientCombineRule,
+
+    /// Restitution coefficient of the surface.
+    #[inspect(min_value = 0.0, step = 0.05)]
+    pub restitution: f32,
+
+    /// Defines how restitution coefficients of two colliders are combined.
+    pub restitution_combine_rule: CoefficientCombineRule,
+
+    /// User-defined surface type identifier. The engine does not use it, it can be used to pick
+    /// footstep sounds, impact effects, etc.
+    pub surface_type: u32,
+
+    /// Defines how much sound passes through the surface, where 0.0 - the surface fully blocks
+    /// sound and 1.0 - the surface is acoustically transparent. It is used by sound occlusion.
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    #[visit(optional)] // Backward compatibility
+    pub sound_transmission: f32,
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self {
+            friction: 0.0,
+            friction_combine_rule: Default::default(),
+            restitution: 0.0,
+            restitution_combine_rule: Default::default(),
+            surface_type: 0,
+            sound_transmission: 0.0,
+        }
+    }
+}
+
+impl PhysicsMaterial {
+    /// Saves the material to the specified file.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("PhysicsMaterial", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+}
+
+/// An error that may occur during physics material resource loading.
+#[derive(Debug, thiserror::Error)]
+pub enum PhysicsMaterialResourceError {
+    /// An i/o error has occurred.
+    #[error("A file load error has occurred {0:?}")]
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    #[error("An error that may occur due to version incompatibilities. {0:?}")]
+    Visit(VisitError),
+}
+
+impl From<FileLoadError> for PhysicsMaterialResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for PhysicsMaterialResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`PhysicsMaterialResource`]
+#[derive(Debug, Visit, Default)]
+pub struct PhysicsMaterialResourceState {
+    pub(in crate) path: PathBuf,
+    /// Actual physics material.
+    pub material: PhysicsMaterial,
+}
+
+impl ResourceData for PhysicsMaterialResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+}
+
+impl PhysicsMaterialResourceState {
+    /// Load a physics material resource from the specific file path.
+    pub async fn from_file(path: &Path) -> Result<Self, PhysicsMaterialResourceError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut material = PhysicsMaterial::default();
+        material.visit("PhysicsMaterial", &mut visitor)?;
+        Ok(Self {
+            material,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+define_new_resource!(
+    /// See module docs.
+    PhysicsMaterialResource<PhysicsMaterialResourceState, PhysicsMaterialResourceError>
+);
+
+impl PhysicsMaterialResource {
+    /// Returns a copy of the material if the resource is loaded, `None` - otherwise.
+    pub fn material(&self) -> Option<PhysicsMaterial> {
+        if let ResourceState::Ok(ref state) = *self.state() {
+            Some(state.material)
+        } else {
+            None
+        }
+    }
+}
+
+/// Import options for physics material resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct PhysicsMaterialImportOptions {}
+
+impl ImportOptions for PhysicsMaterialImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::prelude::*, resource::physics_material::PhysicsMaterial,
+        scene::graph::physics::CoefficientCombineRule,
+    };
+
+    #[test]
+    fn test_physics_material_visit() {
+        let mut material = PhysicsMaterial {
+            friction: 0.7,
+            friction_combine_rule: CoefficientCombineRule::Max,
+            restitution: 0.2,
+            restitution_combine_rule: CoefficientCombineRule::Min,
+            surface_type: 3,
+            sound_transmission: 0.25,
+        };
+
+        let mut visitor = Visitor::new();
+        material.visit("PhysicsMaterial", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = PhysicsMaterial::default();
+        loaded.visit("PhysicsMaterial", &mut visitor).unwrap();
+
+        assert_eq!(material, loaded);
+    }
+}
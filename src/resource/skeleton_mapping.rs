@@ -0,0 +1,210 @@
+//! Skeleton mapping resource holds a [`SkeletonMapping`] - a set of pairs of bone names that
+//! defines which bone of a source skeleton corresponds to which bone of a target skeleton. It is
+//! used for animation retargeting between skeletons with different bone names and proportions,
+//! see [`crate::animation::retarget`] module docs for more info.
+
+use crate::{
+    asset::{define_new_resource, Resource, ResourceData, ResourceState},
+    core::{
+        inspect::{Inspect, PropertyInfo},
+        io::FileLoadError,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::options::ImportOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines where translation of a target bone is taken from.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Visit, Inspect, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum TranslationRetargeting {
+    /// Translation is taken from the bind pose of the target skeleton, so lengths of the bones of
+    /// the target skeleton are preserved. Suitable for most of the bones.
+    Skeleton,
+
+    /// Translation is taken from the animation as is.
+    Animation,
+
+    /// Animated translation is applied relative to the bind pose of the target bone and scaled by
+    /// the ratio of heights of the target and source bones. Suitable for root bones (hips, pelvis)
+    /// of characters with different heights.
+    AnimationScaled,
+}
+
+impl Default for TranslationRetargeting {
+    fn default() -> Self {
+        Self::Skeleton
+    }
+}
+
+/// A pair of bones of source and target skeletons.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Inspect)]
+pub struct BoneMapping {
+    /// Name of a bone of the source skeleton.
+    pub source: String,
+    /// Name of a bone of the target skeleton.
+    pub target: String,
+    /// Defines where translation of the target bone is taken from.
+    pub translation: TranslationRetargeting,
+}
+
+impl BoneMapping {
+    /// Creates new mapping entry that takes translation from the target skeleton.
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(source: S, target: T) -> Self {
+        Self {
+            source: source.as_ref().to_owned(),
+            target: target.as_ref().to_owned(),
+            translation: Default::default(),
+        }
+    }
+
+    /// Sets the way translation of the bone is retargeted.
+    pub fn with_translation(mut self, translation: TranslationRetargeting) -> Self {
+        self.translation = translation;
+        self
+    }
+}
+
+/// A set of bone pairs, see module docs for more info.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Inspect)]
+pub struct SkeletonMapping {
+    /// Bones of the source skeleton that are not in the mapping will be ignored during
+    /// retargeting.
+    pub bones: Vec<BoneMapping>,
+}
+
+impl SkeletonMapping {
+    /// Returns mapping entry for given name of a bone of the source skeleton.
+    pub fn by_source(&self, source: &str) -> Option<&BoneMapping> {
+        self.bones.iter().find(|bone| bone.source == source)
+    }
+
+    /// Returns mapping entry for given name of a bone of the target skeleton.
+    pub fn by_target(&self, target: &str) -> Option<&BoneMapping> {
+        self.bones.iter().find(|bone| bone.target == target)
+    }
+
+    /// Saves the mapping to the specified file.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("SkeletonMapping", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+}
+
+/// An error that may occur during skeleton mapping resource loading.
+#[derive(Debug, thiserror::Error)]
+pub enum SkeletonMappingResourceError {
+    /// An i/o error has occurred.
+    #[error("A file load error has occurred {0:?}")]
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    #[error("An error that may occur due to version incompatibilities. {0:?}")]
+    Visit(VisitError),
+}
+
+impl From<FileLoadError> for SkeletonMappingResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SkeletonMappingResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`SkeletonMappingResource`]
+#[derive(Debug, Visit, Default)]
+pub struct SkeletonMappingResourceState {
+    pub(crate) path: PathBuf,
+    /// Actual skeleton mapping.
+    pub mapping: SkeletonMapping,
+}
+
+impl ResourceData for SkeletonMappingResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+}
+
+impl SkeletonMappingResourceState {
+    /// Load a skeleton mapping resource from the specific file path.
+    pub async fn from_file(path: &Path) -> Result<Self, SkeletonMappingResourceError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut mapping = SkeletonMapping::default();
+        mapping.visit("SkeletonMapping", &mut visitor)?;
+        Ok(Self {
+            mapping,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+define_new_resource!(
+    /// See module docs.
+    SkeletonMappingResource<SkeletonMappingResourceState, SkeletonMappingResourceError>
+);
+
+impl SkeletonMappingResource {
+    /// Returns a copy of the mapping if the resource is loaded, `None` - otherwise.
+    pub fn mapping(&self) -> Option<SkeletonMapping> {
+        if let ResourceState::Ok(ref state) = *self.state() {
+            Some(state.mapping.clone())
+        } else {
+            None
+        }
+    }
+}
+
+/// Import options for skeleton mapping resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct SkeletonMappingImportOptions {}
+
+impl ImportOptions for SkeletonMappingImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::prelude::*,
+        resource::skeleton_mapping::{BoneMapping, SkeletonMapping, TranslationRetargeting},
+    };
+
+    #[test]
+    fn test_skeleton_mapping_visit() {
+        let mut mapping = SkeletonMapping {
+            bones: vec![
+                BoneMapping::new("mixamorig:Hips", "Pelvis")
+                    .with_translation(TranslationRetargeting::AnimationScaled),
+                BoneMapping::new("mixamorig:Spine", "Spine01"),
+            ],
+        };
+
+        let mut visitor = Visitor::new();
+        mapping.visit("SkeletonMapping", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = SkeletonMapping::default();
+        loaded.visit("SkeletonMapping", &mut visitor).unwrap();
+
+        assert_eq!(mapping, loaded);
+        assert_eq!(
+            loaded.by_target("Spine01").unwrap().source,
+            "mixamorig:Spine"
+        );
+        assert!(loaded.by_source("Pelvis").is_none());
+    }
+}
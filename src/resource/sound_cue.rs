@@ -0,0 +1,606 @@
+//! Sound cue resource holds a [`SoundCue`] - a randomized container of sounds. Each time a cue is
+//! played, it picks one of its variations (respecting weights and avoiding recent repeats) and
+//! randomizes gain and pitch of a new sound. It is useful for sounds that are played often, such
+//! as footsteps, impacts, gunshots, etc. - variation makes them much less annoying.
+//!
+//! Cue also limits the amount of its sounds that are playing at the same time, when the limit is
+//! reached it either refuses to play a new sound or "steals" a voice from one of the playing
+//! sounds (see [`VoiceStealing`]).
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox::{
+//!     core::{algebra::Vector3, pool::Handle},
+//!     resource::sound_cue::SoundCueResource,
+//!     scene::{graph::Graph, node::Node},
+//! };
+//!
+//! fn play_footstep(cue: &SoundCueResource, graph: &mut Graph, position: Vector3<f32>) {
+//!     let _sound: Handle<Node> = cue.play(graph, position);
+//! }
+//! ```
+
+use crate::{
+    asset::{define_new_resource, Resource, ResourceData, ResourceState},
+    core::{
+        algebra::Vector3,
+        inspect::{Inspect, PropertyInfo},
+        io::FileLoadError,
+        numeric_range::RangeExt,
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::{options::ImportOptions, ResourceManager},
+    rand::{self, Rng},
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{Sound, SoundBufferResource, SoundBuilder, Status},
+        transform::TransformBuilder,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// A single sound of a cue.
+#[derive(Clone, Debug, PartialEq, Visit, Inspect)]
+pub struct SoundCueVariation {
+    /// Sound buffer of the variation.
+    pub buffer: Option<SoundBufferResource>,
+
+    /// Relative probability of the variation to be picked. Variations with zero weight are never
+    /// picked.
+    #[inspect(min_value = 0.0, step = 0.1)]
+    pub weight: f32,
+}
+
+impl Default for SoundCueVariation {
+    fn default() -> Self {
+        Self {
+            buffer: None,
+            weight: 1.0,
+        }
+    }
+}
+
+impl SoundCueVariation {
+    /// Creates new variation with given buffer and weight.
+    pub fn new(buffer: SoundBufferResource, weight: f32) -> Self {
+        Self {
+            buffer: Some(buffer),
+            weight,
+        }
+    }
+}
+
+/// Defines what happens when a cue is played while maximum amount of its instances is already
+/// playing.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Visit, Inspect, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum VoiceStealing {
+    /// New sound won't be played.
+    None,
+
+    /// The oldest playing sound is stopped and replaced with new one.
+    Oldest,
+
+    /// The sound that is farthest from the listener is stopped and replaced with new one.
+    Farthest,
+}
+
+impl Default for VoiceStealing {
+    fn default() -> Self {
+        Self::Oldest
+    }
+}
+
+/// A randomized container of sounds, see module docs for more info.
+#[derive(Clone, Debug, PartialEq, Visit, Inspect)]
+pub struct SoundCue {
+    /// A set of sounds from which one is picked each time the cue is played.
+    pub variations: Vec<SoundCueVariation>,
+
+    /// Range of gain of a new sound.
+    pub gain: Range<f32>,
+
+    /// Range of pitch of a new sound.
+    pub pitch: Range<f32>,
+
+    /// Amount of recently played variations that won't be picked again. It is automatically
+    /// reduced if there are not enough variations.
+    pub no_repeat: u32,
+
+    /// Maximum amount of sounds of the cue that can play at the same time in a scene. Zero means
+    /// that the amount is unlimited.
+    pub max_instances: u32,
+
+    /// Defines what happens when maximum amount of sounds is reached.
+    pub voice_stealing: VoiceStealing,
+
+    /// Radius of new sounds. See [`Sound::set_radius`] for more info.
+    #[inspect(min_value = 0.0, step = 0.05)]
+    pub radius: f32,
+
+    /// Max distance of new sounds. See [`Sound::set_max_distance`] for more info.
+    #[inspect(min_value = 0.0, step = 0.05)]
+    pub max_distance: f32,
+
+    /// Rolloff factor of new sounds. See [`Sound::set_rolloff_factor`] for more info.
+    #[inspect(min_value = 0.0, step = 0.05)]
+    pub rolloff_factor: f32,
+
+    /// Spatial blend factor of new sounds. See [`Sound::set_spatial_blend`] for more info.
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub spatial_blend: f32,
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        Self {
+            variations: Default::default(),
+            gain: 1.0..1.0,
+            pitch: 1.0..1.0,
+            no_repeat: 1,
+            max_instances: 0,
+            voice_stealing: Default::default(),
+            radius: 10.0,
+            max_distance: f32::MAX,
+            rolloff_factor: 1.0,
+            spatial_blend: 1.0,
+        }
+    }
+}
+
+fn random_value(range: &Range<f32>) -> f32 {
+    // Empty ranges are not allowed by random number generator.
+    if range.start == range.end {
+        range.start
+    } else {
+        range.random()
+    }
+}
+
+impl SoundCue {
+    /// Saves the cue to the specified file.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("SoundCue", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Picks index of a variation, using weights of variations and avoiding variations that are
+    /// stored in the history. The history is updated with picked variation. Returns `None` if
+    /// there is nothing to pick.
+    pub fn pick_variation<R: Rng + ?Sized>(
+        &self,
+        history: &mut Vec<usize>,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let candidates = self
+            .variations
+            .iter()
+            .enumerate()
+            .filter(|(_, variation)| variation.buffer.is_some() && variation.weight > 0.0)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // At least one variation must be available.
+        let no_repeat = (self.no_repeat as usize).min(candidates.len() - 1);
+        let recent = &history[history.len().saturating_sub(no_repeat)..];
+        let candidates = candidates
+            .into_iter()
+            .filter(|index| !recent.contains(index))
+            .collect::<Vec<_>>();
+
+        let total_weight = candidates
+            .iter()
+            .map(|&index| self.variations[index].weight)
+            .sum::<f32>();
+        let mut value = rng.gen_range(0.0..total_weight);
+        let mut picked = *candidates.last().unwrap();
+        for &index in candidates.iter() {
+            let weight = self.variations[index].weight;
+            if value < weight {
+                picked = index;
+                break;
+            }
+            value -= weight;
+        }
+
+        history.push(picked);
+        let excess = history.len().saturating_sub(self.no_repeat as usize);
+        history.drain(..excess);
+
+        Some(picked)
+    }
+
+    /// Creates a sound builder for given variation with randomized gain and pitch. The sound
+    /// will start playing immediately and will be removed when it ends.
+    pub fn make_sound_builder(&self, variation: usize, base_builder: BaseBuilder) -> SoundBuilder {
+        SoundBuilder::new(base_builder)
+            .with_buffer(
+                self.variations
+                    .get(variation)
+                    .and_then(|variation| variation.buffer.clone()),
+            )
+            .with_gain(random_value(&self.gain))
+            .with_pitch(random_value(&self.pitch) as f64)
+            .with_radius(self.radius)
+            .with_max_distance(self.max_distance)
+            .with_rolloff_factor(self.rolloff_factor)
+            .with_spatial_blend_factor(self.spatial_blend)
+            .with_play_once(true)
+            .with_status(Status::Playing)
+    }
+}
+
+/// An error that may occur during sound cue resource loading.
+#[derive(Debug, thiserror::Error)]
+pub enum SoundCueResourceError {
+    /// An i/o error has occurred.
+    #[error("A file load error has occurred {0:?}")]
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    #[error("An error that may occur due to version incompatibilities. {0:?}")]
+    Visit(VisitError),
+}
+
+impl From<FileLoadError> for SoundCueResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SoundCueResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`SoundCueResource`]
+#[derive(Debug, Visit, Default)]
+pub struct SoundCueResourceState {
+    pub(in crate) path: PathBuf,
+    /// Actual sound cue.
+    pub cue: SoundCue,
+    // Recently picked variations.
+    #[visit(skip)]
+    history: Vec<usize>,
+}
+
+impl ResourceData for SoundCueResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+}
+
+impl SoundCueResourceState {
+    /// Load a sound cue resource from the specific file path. Sound buffers of the cue are
+    /// requested from given resource manager.
+    pub async fn from_file(
+        path: &Path,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, SoundCueResourceError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut cue = SoundCue::default();
+        cue.visit("SoundCue", &mut visitor)?;
+
+        // Only paths of sound buffers are stored, so request actual buffers.
+        for variation in cue.variations.iter_mut() {
+            if let Some(buffer) = variation.buffer.as_mut() {
+                let buffer_path = buffer.state().path().to_path_buf();
+                *buffer = resource_manager.request_sound_buffer(buffer_path);
+            }
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            cue,
+            history: Default::default(),
+        })
+    }
+}
+
+define_new_resource!(
+    /// See module docs.
+    SoundCueResource<SoundCueResourceState, SoundCueResourceError>
+);
+
+impl SoundCueResource {
+    /// Returns a copy of the cue if the resource is loaded, `None` - otherwise.
+    pub fn cue(&self) -> Option<SoundCue> {
+        if let ResourceState::Ok(ref state) = *self.state() {
+            Some(state.cue.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Plays the cue at given position in the graph. Returns a handle of new [`Sound`] node or
+    /// [`Handle::NONE`] if the cue is not loaded, has no variations or maximum amount of its
+    /// sounds is reached and voice stealing is disabled.
+    pub fn play(&self, graph: &mut Graph, position: Vector3<f32>) -> Handle<Node> {
+        let (builder, max_instances, voice_stealing) = {
+            let mut state = self.state();
+            if let ResourceState::Ok(ref mut state) = *state {
+                let variation = match state
+                    .cue
+                    .pick_variation(&mut state.history, &mut rand::thread_rng())
+                {
+                    Some(variation) => variation,
+                    None => return Handle::NONE,
+                };
+                let builder = state.cue.make_sound_builder(
+                    variation,
+                    BaseBuilder::new().with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+                );
+                (
+                    builder,
+                    state.cue.max_instances as usize,
+                    state.cue.voice_stealing,
+                )
+            } else {
+                return Handle::NONE;
+            }
+        };
+
+        // Sounds of the cue are tracked per scene, stopped sounds are removed by the graph.
+        let key = self.key();
+        let mut instances = graph
+            .sound_context
+            .cue_instances
+            .remove(&key)
+            .unwrap_or_default();
+        instances.retain(|&instance| {
+            graph
+                .try_get(instance)
+                .and_then(|node| node.cast::<Sound>())
+                .map_or(false, |sound| sound.status() != Status::Stopped)
+        });
+
+        if max_instances > 0 && instances.len() >= max_instances {
+            let victim = match voice_stealing {
+                VoiceStealing::None => None,
+                VoiceStealing::Oldest => Some(0),
+                VoiceStealing::Farthest => {
+                    let listener = graph.sound_context.listener_position();
+                    instances
+                        .iter()
+                        .enumerate()
+                        .map(|(index, &instance)| {
+                            let distance =
+                                graph[instance].global_position().metric_distance(&listener);
+                            (index, distance)
+                        })
+                        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                        .map(|(index, _)| index)
+                }
+            };
+
+            match victim {
+                Some(victim) => {
+                    let victim = instances.remove(victim);
+                    graph.remove_node(victim);
+                }
+                None => {
+                    graph.sound_context.cue_instances.insert(key, instances);
+                    return Handle::NONE;
+                }
+            }
+        }
+
+        let sound = builder.build(graph);
+        instances.push(sound);
+        graph.sound_context.cue_instances.insert(key, instances);
+        sound
+    }
+}
+
+/// Import options for sound cue resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct SoundCueImportOptions {}
+
+impl ImportOptions for SoundCueImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{Resource, ResourceState},
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+            visitor::prelude::*,
+        },
+        rand::{
+            rngs::{mock::StepRng, StdRng},
+            SeedableRng,
+        },
+        resource::sound_cue::{
+            SoundCue, SoundCueResource, SoundCueResourceState, SoundCueVariation, VoiceStealing,
+        },
+        scene::{
+            graph::Graph,
+            node::Node,
+            sound::{DataSource, SoundBufferResource},
+        },
+    };
+    use std::path::PathBuf;
+
+    fn make_buffer(name: &str) -> SoundBufferResource {
+        Resource::new(ResourceState::new_pending(PathBuf::from(name))).into()
+    }
+
+    fn make_cue(count: usize) -> SoundCue {
+        SoundCue {
+            variations: (0..count)
+                .map(|i| SoundCueVariation::new(make_buffer(&format!("{}.wav", i)), 1.0))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sound_cue_no_repeat() {
+        let mut cue = make_cue(3);
+        cue.no_repeat = 2;
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut history = Vec::new();
+        let mut prev = Vec::new();
+        for _ in 0..100 {
+            let variation = cue.pick_variation(&mut history, &mut rng).unwrap();
+            assert!(!prev.contains(&variation));
+            prev.push(variation);
+            if prev.len() > 2 {
+                prev.remove(0);
+            }
+        }
+
+        // There is only one variation, so it must be repeated.
+        let cue = make_cue(1);
+        let mut history = Vec::new();
+        assert_eq!(cue.pick_variation(&mut history, &mut rng), Some(0));
+        assert_eq!(cue.pick_variation(&mut history, &mut rng), Some(0));
+    }
+
+    #[test]
+    fn test_sound_cue_weights() {
+        let mut cue = make_cue(3);
+        cue.no_repeat = 0;
+        cue.variations[1].weight = 0.0;
+        cue.variations[2].buffer = None;
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut history = Vec::new();
+        for _ in 0..100 {
+            assert_eq!(cue.pick_variation(&mut history, &mut rng), Some(0));
+        }
+        assert!(history.is_empty());
+
+        cue.variations[0].weight = 0.0;
+        assert_eq!(cue.pick_variation(&mut history, &mut rng), None);
+    }
+
+    #[test]
+    fn test_sound_cue_weighted_choice() {
+        let mut cue = make_cue(3);
+        cue.no_repeat = 0;
+        cue.variations[1].weight = 2.0;
+
+        // Random value is in [0; 4) range, variations occupy [0; 1), [1; 3) and [3; 4) ranges.
+        let mut history = Vec::new();
+        for (random, expected) in [(0, 0), (0x4000_0000, 1), (0xBFFF_FFFF, 1), (0xFFFF_FFFF, 2)] {
+            let mut rng = StepRng::new(random, 0);
+            assert_eq!(cue.pick_variation(&mut history, &mut rng), Some(expected));
+        }
+
+        // Recently played variation is excluded, so the same value picks next variation.
+        cue.no_repeat = 1;
+        let mut history = vec![1];
+        let mut rng = StepRng::new(0x8000_0000, 0);
+        assert_eq!(cue.pick_variation(&mut history, &mut rng), Some(2));
+        assert_eq!(history, vec![2]);
+    }
+
+    fn make_cue_resource(voice_stealing: VoiceStealing) -> SoundCueResource {
+        // Sounds need loaded buffers to be played.
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.0; 44100],
+        })
+        .unwrap();
+        let cue = SoundCue {
+            variations: vec![SoundCueVariation::new(buffer, 1.0)],
+            max_instances: 2,
+            voice_stealing,
+            ..Default::default()
+        };
+        SoundCueResource(Resource::new(ResourceState::Ok(SoundCueResourceState {
+            path: Default::default(),
+            cue,
+            history: Default::default(),
+        })))
+    }
+
+    fn play(cue: &SoundCueResource, graph: &mut Graph, x: f32) -> Handle<Node> {
+        let sound = cue.play(graph, Vector3::new(x, 0.0, 0.0));
+        graph.update(Vector2::new(100.0, 100.0), 0.0);
+        sound
+    }
+
+    #[test]
+    fn test_sound_cue_voice_stealing() {
+        // Oldest sound is replaced.
+        let mut graph = Graph::new();
+        let cue = make_cue_resource(VoiceStealing::Oldest);
+        let first = play(&cue, &mut graph, 1.0);
+        let second = play(&cue, &mut graph, 5.0);
+        let third = play(&cue, &mut graph, 2.0);
+        assert!(third.is_some());
+        assert!(graph.try_get(first).is_none());
+        assert!(graph.try_get(second).is_some());
+
+        // Farthest from the listener sound is replaced.
+        let mut graph = Graph::new();
+        let cue = make_cue_resource(VoiceStealing::Farthest);
+        let first = play(&cue, &mut graph, 1.0);
+        let second = play(&cue, &mut graph, 5.0);
+        let third = play(&cue, &mut graph, 2.0);
+        assert!(third.is_some());
+        assert!(graph.try_get(first).is_some());
+        assert!(graph.try_get(second).is_none());
+
+        // Nothing is replaced, new sound is not played.
+        let mut graph = Graph::new();
+        let cue = make_cue_resource(VoiceStealing::None);
+        let first = play(&cue, &mut graph, 1.0);
+        let second = play(&cue, &mut graph, 5.0);
+        assert!(play(&cue, &mut graph, 2.0).is_none());
+        assert!(graph.try_get(first).is_some());
+        assert!(graph.try_get(second).is_some());
+    }
+
+    #[test]
+    fn test_sound_cue_visit() {
+        let mut cue = SoundCue {
+            gain: 0.5..0.8,
+            pitch: 0.9..1.1,
+            no_repeat: 2,
+            max_instances: 4,
+            voice_stealing: VoiceStealing::Farthest,
+            ..Default::default()
+        };
+
+        let mut visitor = Visitor::new();
+        cue.visit("SoundCue", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = SoundCue::default();
+        loaded.visit("SoundCue", &mut visitor).unwrap();
+
+        assert_eq!(cue, loaded);
+    }
+}
@@ -5,7 +5,9 @@ use crate::scene::graph::map::NodeHandleMap;
 use crate::{
     core::variable::{InheritError, TemplateVariable},
     core::{
-        algebra::Vector3,
+        algebra::{
+            Matrix3, Matrix4, Point3, Rotation3, SymmetricEigen, UnitQuaternion, Vector2, Vector3,
+        },
         inspect::{Inspect, PropertyInfo},
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -14,12 +16,17 @@ use crate::{
     },
     engine::resource_manager::ResourceManager,
     impl_directly_inheritable_entity_trait,
+    resource::physics_material::PhysicsMaterialResource,
     scene::{
         base::{Base, BaseBuilder},
         graph::{
-            physics::{CoefficientCombineRule, ContactPair, PhysicsWorld},
+            physics::{CoefficientCombineRule, ContactPair, PhysicsWorld, TriggerEvent},
             Graph,
         },
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
         node::{Node, NodeTrait, SyncContext, TypeUuidProvider},
         DirectlyInheritableEntity,
     },
@@ -28,6 +35,7 @@ use crate::{
 use rapier3d::geometry::{self, ColliderHandle};
 use std::{
     cell::Cell,
+    cmp::Ordering,
     ops::{Deref, DerefMut},
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
@@ -193,6 +201,37 @@ pub struct ConvexPolyhedronShape {
     pub geometry_source: GeometrySource,
 }
 
+/// Approximate convex decomposition of a mesh. The mesh is split into a set of convex parts using
+/// V-HACD algorithm, the parts are then combined into a single compound shape. This is the
+/// preferred way of using concave meshes on dynamic rigid bodies.
+#[derive(Clone, Debug, Visit, Inspect, PartialEq)]
+pub struct ConvexDecompositionShape {
+    /// A handle to a mesh node.
+    pub geometry_source: GeometrySource,
+    /// Maximum concavity of each convex part. Smaller values produce more precise decomposition
+    /// with larger amount of parts.
+    #[inspect(min_value = 0.0, step = 0.001)]
+    pub concavity: f32,
+    /// Maximum amount of convex parts the decomposition may produce.
+    #[inspect(min_value = 1.0)]
+    pub max_convex_hulls: u32,
+    /// Resolution of the voxel grid used for decomposition. Larger values produce more precise
+    /// decomposition, but significantly increase computation time.
+    #[inspect(min_value = 2.0)]
+    pub resolution: u32,
+}
+
+impl Default for ConvexDecompositionShape {
+    fn default() -> Self {
+        Self {
+            geometry_source: Default::default(),
+            concavity: 0.01,
+            max_convex_hulls: 1024,
+            resolution: 64,
+        }
+    }
+}
+
 /// Pairwise filtering using bit masks.
 ///
 /// This filtering method is based on two 32-bit values:
@@ -224,6 +263,12 @@ impl InteractionGroups {
             filter,
         }
     }
+
+    /// Checks whether interaction is allowed between two interaction groups. See [`InteractionGroups`]
+    /// docs for exact rules.
+    pub fn test(self, rhs: Self) -> bool {
+        (self.memberships & rhs.filter) != 0 && (rhs.memberships & self.filter) != 0
+    }
 }
 
 impl Default for InteractionGroups {
@@ -257,6 +302,7 @@ impl Inspect for ColliderShape {
             ColliderShape::Trimesh(v) => v.properties(),
             ColliderShape::Heightfield(v) => v.properties(),
             ColliderShape::Polyhedron(v) => v.properties(),
+            ColliderShape::ConvexDecomposition(v) => v.properties(),
         }
     }
 }
@@ -284,6 +330,8 @@ pub enum ColliderShape {
     Heightfield(HeightfieldShape),
     /// See [`ConvexPolyhedronShape`] docs.
     Polyhedron(ConvexPolyhedronShape),
+    /// See [`ConvexDecompositionShape`] docs.
+    ConvexDecomposition(ConvexDecompositionShape),
 }
 
 impl Default for ColliderShape {
@@ -368,6 +416,211 @@ impl ColliderShape {
     pub fn heightfield(geometry_source: GeometrySource) -> Self {
         Self::Heightfield(HeightfieldShape { geometry_source })
     }
+
+    /// Initializes a convex decomposition shape defined by a handle to mesh node, decomposition
+    /// parameters are set to defaults.
+    pub fn convex_decomposition(geometry_source: GeometrySource) -> Self {
+        Self::ConvexDecomposition(ConvexDecompositionShape {
+            geometry_source,
+            ..Default::default()
+        })
+    }
+}
+
+/// A kind of a primitive shape that can be fitted around a set of points, see [`FittedPrimitive`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PrimitiveFitKind {
+    /// Oriented box.
+    Cuboid,
+    /// Sphere.
+    Ball,
+    /// Capsule aligned with the principal axis of the points.
+    Capsule,
+}
+
+/// A simple shape fitted around a set of points (usually vertices of a mesh). Fitted primitives
+/// are much cheaper to simulate than triangle meshes or convex decompositions, and they are good
+/// enough for most of the dynamic objects.
+///
+/// # Notes
+///
+/// The shape is defined in its own space, `position` and `rotation` defines where the shape should
+/// be placed in the space of the points, so usually they should be used as local position and
+/// rotation of the collider node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FittedPrimitive {
+    /// Fitted shape.
+    pub shape: ColliderShape,
+    /// Center of the shape in the space of the points.
+    pub position: Vector3<f32>,
+    /// Orientation of the shape in the space of the points.
+    pub rotation: UnitQuaternion<f32>,
+}
+
+impl FittedPrimitive {
+    /// Fits a primitive of the given kind around the set of points. Boxes and capsules are
+    /// oriented along principal axes of the points, spheres are computed using Ritter's algorithm.
+    /// Returns `None` if the set of points is empty.
+    pub fn from_points(kind: PrimitiveFitKind, points: &[Vector3<f32>]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        Some(match kind {
+            PrimitiveFitKind::Ball => {
+                let (position, radius) = bounding_sphere(points);
+                Self {
+                    shape: ColliderShape::ball(radius),
+                    position,
+                    rotation: UnitQuaternion::identity(),
+                }
+            }
+            PrimitiveFitKind::Cuboid => {
+                let basis = principal_axes(points);
+                let (min, max) = local_bounds(&basis, points);
+                let half_extents = (max - min).scale(0.5);
+                Self {
+                    shape: ColliderShape::cuboid(half_extents.x, half_extents.y, half_extents.z),
+                    position: basis * (min + max).scale(0.5),
+                    rotation: UnitQuaternion::from_rotation_matrix(
+                        &Rotation3::from_matrix_unchecked(basis),
+                    ),
+                }
+            }
+            PrimitiveFitKind::Capsule => {
+                // First principal axis has the largest spread of the points, use it as capsule axis.
+                let basis = principal_axes(points);
+                let (min, max) = local_bounds(&basis, points);
+                let center = (min + max).scale(0.5);
+
+                let radius = points
+                    .iter()
+                    .map(|p| {
+                        let local = basis.tr_mul(p) - center;
+                        Vector2::new(local.y, local.z).norm()
+                    })
+                    .fold(0.0f32, f32::max);
+
+                // Find minimal half height of the axis segment, so every point will be inside
+                // either cylindrical part or one of the hemispheres.
+                let half_height = points
+                    .iter()
+                    .map(|p| {
+                        let local = basis.tr_mul(p) - center;
+                        let distance_sqr = local.y * local.y + local.z * local.z;
+                        local.x.abs() - (radius * radius - distance_sqr).max(0.0).sqrt()
+                    })
+                    .fold(0.0f32, f32::max);
+
+                let axis = basis.column(0).into_owned().scale(half_height);
+                Self {
+                    shape: ColliderShape::capsule(-axis, axis, radius),
+                    position: basis * center,
+                    rotation: UnitQuaternion::identity(),
+                }
+            }
+        })
+    }
+
+    /// Fits a primitive of the given kind around vertices of the mesh. Vertices are transformed
+    /// using the given transform first, it allows to fit the primitive in the space of some other
+    /// node (usually a rigid body the collider will be attached to).
+    pub fn from_mesh(
+        kind: PrimitiveFitKind,
+        mesh: &Mesh,
+        transform: &Matrix4<f32>,
+    ) -> Option<Self> {
+        let mut points = Vec::new();
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.lock();
+            for vertex in data.vertex_buffer.iter() {
+                if let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) {
+                    points.push(transform.transform_point(&Point3::from(position)).coords);
+                }
+            }
+        }
+        Self::from_points(kind, &points)
+    }
+}
+
+// Returns orthonormal right-handed basis formed by principal axes of the points, columns of the
+// matrix are sorted by the spread of the points along them in descending order.
+fn principal_axes(points: &[Vector3<f32>]) -> Matrix3<f32> {
+    let count = points.len() as f32;
+    let mean = points
+        .iter()
+        .fold(Vector3::default(), |acc, p| acc + p)
+        .scale(1.0 / count);
+
+    let mut covariance = Matrix3::zeros();
+    for p in points {
+        let d = p - mean;
+        covariance += d * d.transpose();
+    }
+    covariance /= count;
+
+    let eigen = SymmetricEigen::new(covariance);
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| {
+        eigen.eigenvalues[*b]
+            .partial_cmp(&eigen.eigenvalues[*a])
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let x = eigen.eigenvectors.column(order[0]).into_owned();
+    let y = eigen.eigenvectors.column(order[1]).into_owned();
+    // Build third axis explicitly to guarantee right-handedness.
+    let z = x.cross(&y);
+
+    Matrix3::from_columns(&[x, y, z])
+}
+
+// Calculates bounds of the points in the space of the given orthonormal basis.
+fn local_bounds(basis: &Matrix3<f32>, points: &[Vector3<f32>]) -> (Vector3<f32>, Vector3<f32>) {
+    let mut min = Vector3::repeat(f32::MAX);
+    let mut max = Vector3::repeat(-f32::MAX);
+    for p in points {
+        let local = basis.tr_mul(p);
+        min = min.inf(&local);
+        max = max.sup(&local);
+    }
+    (min, max)
+}
+
+// Ritter's bounding sphere. It is not the minimal one, but it is usually within 5-20% of the
+// optimal one and it is very fast to compute.
+fn bounding_sphere(points: &[Vector3<f32>]) -> (Vector3<f32>, f32) {
+    let farthest_from = |origin: &Vector3<f32>| {
+        points
+            .iter()
+            .max_by(|a, b| {
+                (*a - origin)
+                    .norm_squared()
+                    .partial_cmp(&(*b - origin).norm_squared())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let a = farthest_from(&points[0]);
+    let b = farthest_from(&a);
+
+    let mut center = (a + b).scale(0.5);
+    let mut radius = (b - a).norm() * 0.5;
+
+    for p in points {
+        let distance = (p - center).norm();
+        if distance > radius {
+            let new_radius = (radius + distance) * 0.5;
+            center += (p - center).scale((new_radius - radius) / distance);
+            radius = new_radius;
+        }
+    }
+
+    (center, radius)
 }
 
 /// Collider is a geometric entity that can be attached to a rigid body to allow participate it
@@ -403,6 +656,10 @@ pub struct Collider {
     #[inspect(getter = "Deref::deref")]
     pub(crate) restitution_combine_rule: TemplateVariable<CoefficientCombineRule>,
 
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) material: TemplateVariable<Option<PhysicsMaterialResource>>,
+
     #[visit(skip)]
     #[inspect(skip)]
     pub(in crate) native: Cell<ColliderHandle>,
@@ -417,7 +674,8 @@ impl_directly_inheritable_entity_trait!(Collider;
     collision_groups,
     solver_groups,
     friction_combine_rule,
-    restitution_combine_rule
+    restitution_combine_rule,
+    material
 );
 
 impl Default for Collider {
@@ -433,6 +691,7 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -465,6 +724,7 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            material: self.material.clone(),
             // Do not copy.
             native: Cell::new(ColliderHandle::invalid()),
         }
@@ -650,6 +910,35 @@ impl Collider {
         *self.restitution_combine_rule
     }
 
+    /// Sets the new physics material of the collider. When the material is set and loaded, its
+    /// friction, restitution and combine rules are used instead of the values of the collider.
+    pub fn set_material(&mut self, material: Option<PhysicsMaterialResource>) {
+        self.material.set(material);
+    }
+
+    /// Returns current physics material of the collider.
+    pub fn material(&self) -> Option<PhysicsMaterialResource> {
+        (*self.material).clone()
+    }
+
+    /// Returns surface type of the physics material of the collider, `None` if there is no
+    /// material or it is not loaded yet.
+    pub fn surface_type(&self) -> Option<u32> {
+        self.material
+            .as_ref()
+            .and_then(|m| m.material())
+            .map(|m| m.surface_type)
+    }
+
+    /// Returns sound transmission of the physics material of the collider, `None` if there is no
+    /// material or it is not loaded yet.
+    pub fn sound_transmission(&self) -> Option<f32> {
+        self.material
+            .as_ref()
+            .and_then(|m| m.material())
+            .map(|m| m.sound_transmission)
+    }
+
     /// Returns an iterator that yields contact information for the collider.
     pub fn contacts<'a>(
         &self,
@@ -658,6 +947,16 @@ impl Collider {
         physics.contacts_with(self.native.get())
     }
 
+    /// Returns an iterator that yields trigger events produced by the collider during the last
+    /// simulation step. The collider must be a sensor to produce the events, see
+    /// [`TriggerEvent`] docs for more info.
+    pub fn trigger_events<'a>(
+        &self,
+        physics: &'a PhysicsWorld,
+    ) -> impl Iterator<Item = &'a TriggerEvent> + 'a {
+        physics.trigger_events_of(self.native.get())
+    }
+
     pub(crate) fn needs_sync_model(&self) -> bool {
         self.shape.need_sync()
             || self.friction.need_sync()
@@ -668,6 +967,7 @@ impl Collider {
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
+            || self.material.need_sync()
     }
 }
 
@@ -697,7 +997,11 @@ impl NodeTrait for Collider {
     }
 
     fn restore_resources(&mut self, resource_manager: ResourceManager) {
-        self.base.restore_resources(resource_manager);
+        self.base.restore_resources(resource_manager.clone());
+
+        let mut state = resource_manager.state();
+        let physics_materials = &mut state.containers_mut().physics_materials;
+        physics_materials.try_restore_template_resource(&mut self.material);
     }
 
     fn remap_handles(&mut self, old_new_mapping: &NodeHandleMap) {
@@ -724,6 +1028,15 @@ impl NodeTrait for Collider {
                     ))
                 }
             }
+            ColliderShape::ConvexDecomposition(ref mut decomposition) => {
+                if !old_new_mapping.try_map(&mut decomposition.geometry_source.0) {
+                    Log::warn(format!(
+                        "Unable to remap geometry source of a Convex Decomposition collider {} shape. Handle is {}!",
+                        *self.base.name,
+                        decomposition.geometry_source.0
+                    ))
+                }
+            }
             _ => (),
         }
     }
@@ -760,6 +1073,7 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    material: Option<PhysicsMaterialResource>,
 }
 
 impl ColliderBuilder {
@@ -776,6 +1090,7 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: None,
         }
     }
 
@@ -833,6 +1148,12 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired physics material.
+    pub fn with_material(mut self, material: Option<PhysicsMaterialResource>) -> Self {
+        self.material = material;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -846,6 +1167,7 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            material: self.material.into(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -863,11 +1185,17 @@ impl ColliderBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::scene::{
-        base::{test::check_inheritable_properties_equality, BaseBuilder},
-        collider::{Collider, ColliderBuilder, ColliderShape, InteractionGroups},
-        graph::physics::CoefficientCombineRule,
-        node::NodeTrait,
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            collider::{
+                Collider, ColliderBuilder, ColliderShape, FittedPrimitive, InteractionGroups,
+                PrimitiveFitKind,
+            },
+            graph::physics::CoefficientCombineRule,
+            node::NodeTrait,
+        },
     };
 
     #[test]
@@ -893,4 +1221,95 @@ mod test {
         check_inheritable_properties_equality(&child.base, &parent.base);
         check_inheritable_properties_equality(&child, parent);
     }
+
+    #[test]
+    fn test_interaction_groups_test() {
+        let a = InteractionGroups::new(0b01, 0b10);
+        let b = InteractionGroups::new(0b10, 0b01);
+        let c = InteractionGroups::new(0b10, 0b10);
+
+        assert!(a.test(b));
+        assert!(b.test(a));
+        assert!(!a.test(c));
+        assert!(InteractionGroups::default().test(c));
+    }
+
+    fn box_corners(half_extents: Vector3<f32>, offset: Vector3<f32>) -> Vec<Vector3<f32>> {
+        let mut points = Vec::new();
+        for x in [-1.0, 1.0] {
+            for y in [-1.0, 1.0] {
+                for z in [-1.0, 1.0] {
+                    points.push(offset + half_extents.component_mul(&Vector3::new(x, y, z)));
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_fit_cuboid() {
+        let offset = Vector3::new(1.0, 2.0, 3.0);
+        let points = box_corners(Vector3::new(2.0, 1.0, 0.5), offset);
+
+        let fitted = FittedPrimitive::from_points(PrimitiveFitKind::Cuboid, &points).unwrap();
+
+        assert!((fitted.position - offset).norm() < 0.001);
+        if let ColliderShape::Cuboid(cuboid) = fitted.shape {
+            let mut extents = [
+                cuboid.half_extents.x,
+                cuboid.half_extents.y,
+                cuboid.half_extents.z,
+            ];
+            extents.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            assert!((extents[0] - 2.0).abs() < 0.001);
+            assert!((extents[1] - 1.0).abs() < 0.001);
+            assert!((extents[2] - 0.5).abs() < 0.001);
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_fit_ball() {
+        let points = box_corners(Vector3::new(1.0, 1.0, 1.0), Vector3::default());
+
+        let fitted = FittedPrimitive::from_points(PrimitiveFitKind::Ball, &points).unwrap();
+
+        if let ColliderShape::Ball(ball) = fitted.shape {
+            for p in points {
+                assert!((p - fitted.position).norm() <= ball.radius + 0.001);
+            }
+            assert!((ball.radius - 3.0f32.sqrt()).abs() < 0.001);
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_fit_capsule() {
+        // Capsule aligned with Y axis with half height 1.0 and radius 0.5.
+        let mut points = vec![Vector3::new(0.0, 1.5, 0.0), Vector3::new(0.0, -1.5, 0.0)];
+        for i in 0..16 {
+            let angle = i as f32 / 16.0 * std::f32::consts::TAU;
+            for y in [-1.0, 0.0, 1.0] {
+                points.push(Vector3::new(angle.cos() * 0.5, y, angle.sin() * 0.5));
+            }
+        }
+
+        let fitted = FittedPrimitive::from_points(PrimitiveFitKind::Capsule, &points).unwrap();
+
+        assert!(fitted.position.norm() < 0.001);
+        if let ColliderShape::Capsule(capsule) = fitted.shape {
+            assert!((capsule.radius - 0.5).abs() < 0.001);
+            assert!(((capsule.end - capsule.begin).norm() - 2.0).abs() < 0.001);
+            assert!((capsule.end - capsule.begin).normalize().y.abs() > 0.999);
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_fit_empty() {
+        assert!(FittedPrimitive::from_points(PrimitiveFitKind::Cuboid, &[]).is_none());
+    }
 }
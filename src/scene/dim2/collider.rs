@@ -14,11 +14,15 @@ use crate::{
     },
     engine::resource_manager::ResourceManager,
     impl_directly_inheritable_entity_trait,
+    resource::physics_material::PhysicsMaterialResource,
     scene::{
         base::{Base, BaseBuilder},
         collider::InteractionGroups,
         dim2::physics::{ContactPair, PhysicsWorld},
-        graph::{physics::CoefficientCombineRule, Graph},
+        graph::{
+            physics::{CoefficientCombineRule, TriggerEvent},
+            Graph,
+        },
         node::{Node, NodeTrait, SyncContext, TypeUuidProvider},
         DirectlyInheritableEntity,
     },
@@ -271,6 +275,10 @@ pub struct Collider {
     #[inspect(getter = "Deref::deref")]
     pub(crate) restitution_combine_rule: TemplateVariable<CoefficientCombineRule>,
 
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) material: TemplateVariable<Option<PhysicsMaterialResource>>,
+
     #[visit(skip)]
     #[inspect(skip)]
     pub(in crate) native: Cell<ColliderHandle>,
@@ -285,7 +293,8 @@ impl_directly_inheritable_entity_trait!(Collider;
     collision_groups,
     solver_groups,
     friction_combine_rule,
-    restitution_combine_rule
+    restitution_combine_rule,
+    material
 );
 
 impl Default for Collider {
@@ -301,6 +310,7 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -333,6 +343,7 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            material: self.material.clone(),
             // Do not copy.
             native: Cell::new(ColliderHandle::invalid()),
         }
@@ -518,6 +529,26 @@ impl Collider {
         *self.restitution_combine_rule
     }
 
+    /// Sets the new physics material of the collider. When the material is set and loaded, its
+    /// friction, restitution and combine rules are used instead of the values of the collider.
+    pub fn set_material(&mut self, material: Option<PhysicsMaterialResource>) {
+        self.material.set(material);
+    }
+
+    /// Returns current physics material of the collider.
+    pub fn material(&self) -> Option<PhysicsMaterialResource> {
+        (*self.material).clone()
+    }
+
+    /// Returns surface type of the physics material of the collider, `None` if there is no
+    /// material or it is not loaded yet.
+    pub fn surface_type(&self) -> Option<u32> {
+        self.material
+            .as_ref()
+            .and_then(|m| m.material())
+            .map(|m| m.surface_type)
+    }
+
     /// Returns an iterator that yields contact information for the collider.
     pub fn contacts<'a>(
         &self,
@@ -526,6 +557,16 @@ impl Collider {
         physics.contacts_with(self.native.get())
     }
 
+    /// Returns an iterator that yields trigger events produced by the collider during the last
+    /// simulation step. The collider must be a sensor to produce the events, see
+    /// [`TriggerEvent`] docs for more info.
+    pub fn trigger_events<'a>(
+        &self,
+        physics: &'a PhysicsWorld,
+    ) -> impl Iterator<Item = &'a TriggerEvent> + 'a {
+        physics.trigger_events_of(self.native.get())
+    }
+
     pub(crate) fn needs_sync_model(&self) -> bool {
         self.shape.need_sync()
             || self.friction.need_sync()
@@ -536,6 +577,7 @@ impl Collider {
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
+            || self.material.need_sync()
     }
 }
 
@@ -565,7 +607,11 @@ impl NodeTrait for Collider {
     }
 
     fn restore_resources(&mut self, resource_manager: ResourceManager) {
-        self.base.restore_resources(resource_manager);
+        self.base.restore_resources(resource_manager.clone());
+
+        let mut state = resource_manager.state();
+        let physics_materials = &mut state.containers_mut().physics_materials;
+        physics_materials.try_restore_template_resource(&mut self.material);
     }
 
     fn remap_handles(&mut self, old_new_mapping: &NodeHandleMap) {
@@ -628,6 +674,7 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    material: Option<PhysicsMaterialResource>,
 }
 
 impl ColliderBuilder {
@@ -644,6 +691,7 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: None,
         }
     }
 
@@ -701,6 +749,12 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired physics material.
+    pub fn with_material(mut self, material: Option<PhysicsMaterialResource>) -> Self {
+        self.material = material;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -714,6 +768,7 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            material: self.material.into(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -7,7 +7,6 @@ use crate::{
         algebra::{Isometry2, Matrix4, Point2, Translation2, UnitComplex, UnitQuaternion, Vector3},
         algebra::{Isometry3, Point3, Rotation3, Translation3},
         arrayvec::ArrayVec,
-        color::Color,
         inspect::{Inspect, PropertyInfo},
         instant,
         math::Matrix4Ext,
@@ -15,13 +14,17 @@ use crate::{
         visitor::prelude::*,
         BiDirHashMap,
     },
+    resource::physics_material::PhysicsMaterial,
     scene::{
         self,
         collider::{self},
         debug::{Line, SceneDrawingContext},
         dim2::{self, collider::ColliderShape, rigidbody::ApplyAction},
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                draw_cross, impl_trigger_events, FeatureId, IntegrationParameters,
+                PhysicsDebugFlags, PhysicsPerformanceStatistics, TriggerEventQueue,
+            },
             NodePool,
         },
         node::Node,
@@ -40,7 +43,7 @@ use rapier2d::{
         InteractionGroups, NarrowPhase, Ray, SharedShape, TriMesh,
     },
     math::UnitVector,
-    pipeline::{EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{PhysicsPipeline, QueryPipeline},
 };
 use std::{
     cell::RefCell,
@@ -190,6 +193,8 @@ pub struct ContactPair {
     pub has_any_active_contact: bool,
 }
 
+impl_trigger_events!(items, rapier2d);
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -264,6 +269,10 @@ fn collider_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape
     }
 }
 
+fn to_vec3(v: Vector2<f32>) -> Vector3<f32> {
+    Vector3::new(v.x, v.y, 0.0)
+}
+
 fn isometry2_to_mat4(isometry: &Isometry2<f32>) -> Matrix4<f32> {
     Isometry3 {
         rotation: UnitQuaternion::from_euler_angles(0.0, 0.0, isometry.rotation.angle()),
@@ -274,6 +283,62 @@ fn isometry2_to_mat4(isometry: &Isometry2<f32>) -> Matrix4<f32> {
     .to_homogeneous()
 }
 
+fn draw_joint(
+    context: &mut SceneDrawingContext,
+    body1: &RigidBody,
+    body2: &RigidBody,
+    data: &GenericJoint,
+) {
+    let frame1 = isometry2_to_mat4(&(body1.position() * data.local_frame1));
+    let frame2 = isometry2_to_mat4(&(body2.position() * data.local_frame2));
+
+    let scale = Matrix4::new_scaling(PhysicsDebugFlags::ANCHOR_SIZE);
+    context.draw_transform(frame1 * scale);
+    context.draw_transform(frame2 * scale);
+    context.add_line(Line {
+        begin: frame1.position(),
+        end: frame2.position(),
+        color: PhysicsDebugFlags::JOINT_COLOR,
+    });
+
+    let limits = &data.limits;
+    let limit_axes = data.limit_axes;
+
+    // Linear limits are drawn as segments along respective axes of the first frame.
+    for (i, (mask, axis)) in [
+        (JointAxesMask::X, Vector3::x()),
+        (JointAxesMask::Y, Vector3::y()),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if limit_axes.contains(mask) {
+            context.add_line(Line {
+                begin: frame1
+                    .transform_point(&Point3::from(axis * limits[i].min))
+                    .coords,
+                end: frame1
+                    .transform_point(&Point3::from(axis * limits[i].max))
+                    .coords,
+                color: PhysicsDebugFlags::LIMITS_COLOR,
+            });
+        }
+    }
+
+    // There is only one rotational axis in 2D, its limits are drawn as an arc.
+    if limit_axes.contains(JointAxesMask::ANG_X) {
+        context.draw_circle_segment(
+            Vector3::default(),
+            PhysicsDebugFlags::ANCHOR_SIZE,
+            24,
+            limits[2].min,
+            limits[2].max,
+            frame1,
+            PhysicsDebugFlags::LIMITS_COLOR,
+        );
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -333,10 +398,23 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[inspect(skip)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
     #[visit(skip)]
     #[inspect(skip)]
     query: RefCell<QueryPipeline>,
+    // A queue of trigger events, it also keeps track of intersecting trigger-collider pairs.
+    #[visit(optional)] // Backward compatibility
+    #[inspect(skip)]
+    trigger_events: TriggerEventQueue,
+    // Sleeping thresholds that were applied to native rigid bodies last time.
+    #[visit(skip)]
+    #[inspect(skip)]
+    applied_sleep_thresholds: (f32, f32),
+    // The highest amount of additional solver iterations requested by awake dynamic bodies,
+    // collected during the sync of rigid body nodes and reset after each simulation step.
+    #[visit(skip)]
+    #[inspect(skip)]
+    additional_solver_iterations: u32,
 }
 
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
@@ -376,16 +454,26 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
+            trigger_events: Default::default(),
+            applied_sleep_thresholds: (
+                RigidBodyActivation::default_linear_threshold(),
+                RigidBodyActivation::default_angular_threshold(),
+            ),
+            additional_solver_iterations: 0,
         }
     }
 
     pub(crate) fn update(&mut self) {
         let time = instant::Instant::now();
 
+        self.trigger_events.clear_events();
+
         if self.enabled {
+            self.apply_sleep_thresholds();
+
             let integration_parameters = rapier2d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt,
                 min_ccd_dt: self.integration_parameters.min_ccd_dt,
@@ -396,7 +484,8 @@ impl PhysicsWorld {
                 allowed_linear_error: self.integration_parameters.allowed_linear_error,
                 max_penetration_correction: self.integration_parameters.max_penetration_correction,
                 prediction_distance: self.integration_parameters.prediction_distance,
-                max_velocity_iterations: self.integration_parameters.max_velocity_iterations
+                max_velocity_iterations: (self.integration_parameters.max_velocity_iterations
+                    + self.additional_solver_iterations)
                     as usize,
                 max_velocity_friction_iterations: self
                     .integration_parameters
@@ -425,13 +514,42 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            self.handle_collision_events();
         }
 
+        self.additional_solver_iterations = 0;
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    impl_trigger_events!(methods, rapier2d);
+
+    fn sleep_thresholds(&self) -> (f32, f32) {
+        (
+            self.integration_parameters.linear_sleep_threshold,
+            self.integration_parameters.angular_sleep_threshold,
+        )
+    }
+
+    // Propagates changed world-level sleeping thresholds to every native rigid body that can sleep.
+    fn apply_sleep_thresholds(&mut self) {
+        let (linear_threshold, angular_threshold) = self.sleep_thresholds();
+        if self.applied_sleep_thresholds != (linear_threshold, angular_threshold) {
+            for (_, body) in self.bodies.set.iter_mut() {
+                let activation = body.activation_mut();
+                // Negative thresholds mark bodies that cannot sleep.
+                if activation.linear_threshold >= 0.0 {
+                    activation.linear_threshold = linear_threshold;
+                    activation.angular_threshold = angular_threshold;
+                }
+            }
+            self.applied_sleep_thresholds = (linear_threshold, angular_threshold);
+        }
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -439,6 +557,15 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        if let Some(body) = self.bodies.set.get(handle) {
+            // Attached colliders will be removed too.
+            for collider in body.colliders() {
+                if let Some(&collider_node) = self.colliders.map.value_of(collider) {
+                    self.trigger_events.remove_collider(collider_node);
+                }
+            }
+        }
+
         assert!(self.bodies.map.remove_by_key(&handle).is_some());
         self.bodies.set.remove(
             handle,
@@ -465,6 +592,10 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
+        if let Some(&collider_node) = self.colliders.map.value_of(&handle) {
+            self.trigger_events.remove_collider(collider_node);
+        }
+
         if self
             .colliders
             .set
@@ -496,75 +627,201 @@ impl PhysicsWorld {
     }
 
     /// Draws physics world. Very useful for debugging, it allows you to see where are
-    /// rigid bodies, which colliders they have and so on.
+    /// rigid bodies, which colliders they have and so on. It draws colliders only, use
+    /// [`Self::draw_with_flags`] to draw other parts of the world.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        for (_, body) in self.bodies.set.iter() {
-            context.draw_transform(isometry2_to_mat4(body.position()));
-        }
+        self.draw_with_flags(context, &PhysicsDebugFlags::default())
+    }
 
-        for (_, collider) in self.colliders.set.iter() {
-            let body = self.bodies.set.get(collider.parent().unwrap()).unwrap();
-            let collider_local_transform =
-                isometry2_to_mat4(collider.position_wrt_parent().unwrap());
-            let transform = isometry2_to_mat4(body.position()) * collider_local_transform;
-            if let Some(trimesh) = collider.shape().as_trimesh() {
-                let trimesh: &TriMesh = trimesh;
-                for triangle in trimesh.triangles() {
-                    let a = transform
-                        .transform_point(&Point3::from(triangle.a.to_homogeneous()))
-                        .coords;
-                    let b = transform
-                        .transform_point(&Point3::from(triangle.b.to_homogeneous()))
-                        .coords;
-                    let c = transform
-                        .transform_point(&Point3::from(triangle.c.to_homogeneous()))
-                        .coords;
-                    context.draw_triangle(a, b, c, Color::opaque(200, 200, 200));
+    /// Draws parts of physics world, defined by the given set of flags. See [`PhysicsDebugFlags`]
+    /// docs for more info.
+    pub fn draw_with_flags(&self, context: &mut SceneDrawingContext, flags: &PhysicsDebugFlags) {
+        if flags.colliders {
+            for (_, body) in self.bodies.set.iter() {
+                context.draw_transform(isometry2_to_mat4(body.position()));
+            }
+
+            for (_, collider) in self.colliders.set.iter() {
+                let body = self.bodies.set.get(collider.parent().unwrap()).unwrap();
+                let collider_local_transform =
+                    isometry2_to_mat4(collider.position_wrt_parent().unwrap());
+                let transform = isometry2_to_mat4(body.position()) * collider_local_transform;
+                let color =
+                    flags.collider_color(body.is_fixed(), body.is_kinematic(), body.is_sleeping());
+                if let Some(trimesh) = collider.shape().as_trimesh() {
+                    let trimesh: &TriMesh = trimesh;
+                    for triangle in trimesh.triangles() {
+                        let a = transform
+                            .transform_point(&Point3::from(triangle.a.to_homogeneous()))
+                            .coords;
+                        let b = transform
+                            .transform_point(&Point3::from(triangle.b.to_homogeneous()))
+                            .coords;
+                        let c = transform
+                            .transform_point(&Point3::from(triangle.c.to_homogeneous()))
+                            .coords;
+                        context.draw_triangle(a, b, c, color);
+                    }
+                } else if let Some(cuboid) = collider.shape().as_cuboid() {
+                    context.draw_rectangle(
+                        cuboid.half_extents.x,
+                        cuboid.half_extents.y,
+                        transform,
+                        color,
+                    );
+                } else if let Some(ball) = collider.shape().as_ball() {
+                    context.draw_circle(
+                        body.position().translation.vector.to_homogeneous(),
+                        ball.radius,
+                        10,
+                        transform,
+                        color,
+                    );
+                } else if let Some(triangle) = collider.shape().as_triangle() {
+                    context.draw_triangle(
+                        triangle.a.to_homogeneous(),
+                        triangle.b.to_homogeneous(),
+                        triangle.c.to_homogeneous(),
+                        color,
+                    );
+                } else if let Some(capsule) = collider.shape().as_capsule() {
+                    context.draw_segment_flat_capsule(
+                        capsule.segment.a.coords,
+                        capsule.segment.b.coords,
+                        capsule.radius,
+                        10,
+                        transform,
+                        color,
+                    );
+                } else if let Some(heightfield) = collider.shape().as_heightfield() {
+                    for segment in heightfield.segments() {
+                        let a = transform
+                            .transform_point(&Point3::from(segment.a.to_homogeneous()))
+                            .coords;
+                        let b = transform
+                            .transform_point(&Point3::from(segment.b.to_homogeneous()))
+                            .coords;
+                        context.add_line(Line {
+                            begin: a,
+                            end: b,
+                            color,
+                        });
+                    }
                 }
-            } else if let Some(cuboid) = collider.shape().as_cuboid() {
+            }
+        }
+
+        if flags.bounding_boxes {
+            for (_, collider) in self.colliders.set.iter() {
+                let aabb = collider.compute_aabb();
                 context.draw_rectangle(
-                    cuboid.half_extents.x,
-                    cuboid.half_extents.y,
-                    transform,
-                    Color::opaque(200, 200, 200),
-                );
-            } else if let Some(ball) = collider.shape().as_ball() {
-                context.draw_circle(
-                    body.position().translation.vector.to_homogeneous(),
-                    ball.radius,
-                    10,
-                    transform,
-                    Color::opaque(200, 200, 200),
+                    (aabb.maxs.x - aabb.mins.x) * 0.5,
+                    (aabb.maxs.y - aabb.mins.y) * 0.5,
+                    Matrix4::new_translation(&to_vec3(aabb.center().coords)),
+                    PhysicsDebugFlags::AABB_COLOR,
                 );
-            } else if let Some(triangle) = collider.shape().as_triangle() {
-                context.draw_triangle(
-                    triangle.a.to_homogeneous(),
-                    triangle.b.to_homogeneous(),
-                    triangle.c.to_homogeneous(),
-                    Color::opaque(200, 200, 200),
-                );
-            } else if let Some(capsule) = collider.shape().as_capsule() {
-                context.draw_segment_flat_capsule(
-                    capsule.segment.a.coords,
-                    capsule.segment.b.coords,
-                    capsule.radius,
-                    10,
-                    transform,
-                    Color::opaque(200, 200, 200),
-                );
-            } else if let Some(heightfield) = collider.shape().as_heightfield() {
-                for segment in heightfield.segments() {
-                    let a = transform
-                        .transform_point(&Point3::from(segment.a.to_homogeneous()))
-                        .coords;
-                    let b = transform
-                        .transform_point(&Point3::from(segment.b.to_homogeneous()))
-                        .coords;
+            }
+        }
+
+        if flags.contacts {
+            self.draw_contacts(context);
+        }
+
+        if flags.joints {
+            self.draw_joints(context);
+        }
+
+        if flags.center_of_mass || flags.velocities {
+            for (_, body) in self.bodies.set.iter() {
+                let center = to_vec3((body.position() * body.mass_properties().local_com).coords);
+
+                if flags.center_of_mass {
+                    draw_cross(
+                        context,
+                        center,
+                        0.1,
+                        PhysicsDebugFlags::CENTER_OF_MASS_COLOR,
+                    );
+                }
+
+                if flags.velocities {
                     context.add_line(Line {
-                        begin: a,
-                        end: b,
-                        color: Color::opaque(200, 200, 200),
+                        begin: center,
+                        end: center + to_vec3(*body.linvel()),
+                        color: PhysicsDebugFlags::LIN_VEL_COLOR,
                     });
+                    // Angular velocity is a scalar in 2D, so it is drawn as an arc, which length
+                    // is proportional to the velocity.
+                    let angvel = body.angvel();
+                    context.draw_circle_segment(
+                        center,
+                        0.25,
+                        24,
+                        angvel.min(0.0),
+                        angvel.max(0.0),
+                        Matrix4::identity(),
+                        PhysicsDebugFlags::ANG_VEL_COLOR,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_contacts(&self, context: &mut SceneDrawingContext) {
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            if let Some(collider1) = self.colliders.set.get(pair.collider1) {
+                for manifold in pair.manifolds.iter() {
+                    let normal = to_vec3(manifold.data.normal);
+
+                    for point in manifold.points.iter() {
+                        let position =
+                            to_vec3(collider1.position().transform_point(&point.local_p1).coords);
+
+                        draw_cross(context, position, 0.05, PhysicsDebugFlags::CONTACT_COLOR);
+                        context.add_line(Line {
+                            begin: position,
+                            end: position + normal.scale(PhysicsDebugFlags::NORMAL_LENGTH),
+                            color: PhysicsDebugFlags::NORMAL_COLOR,
+                        });
+                        context.add_line(Line {
+                            begin: position,
+                            end: position
+                                + normal.scale(
+                                    point
+                                        .data
+                                        .impulse
+                                        .min(PhysicsDebugFlags::MAX_IMPULSE_LENGTH),
+                                ),
+                            color: PhysicsDebugFlags::IMPULSE_COLOR,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_joints(&self, context: &mut SceneDrawingContext) {
+        for (_, joint) in self.joints.set.iter() {
+            if let (Some(body1), Some(body2)) = (
+                self.bodies.set.get(joint.body1),
+                self.bodies.set.get(joint.body2),
+            ) {
+                draw_joint(context, body1, body2, &joint.data);
+            }
+        }
+
+        // Multibody joints connect a link with its parent link.
+        for (_, multibody, link) in self.multibody_joints.set.iter() {
+            if let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) {
+                if let (Some(body1), Some(body2)) = (
+                    self.bodies.set.get(parent.rigid_body_handle()),
+                    self.bodies.set.get(link.rigid_body_handle()),
+                ) {
+                    draw_joint(context, body1, body2, &link.joint.data);
                 }
             }
         }
@@ -683,6 +940,15 @@ impl PhysicsWorld {
         // 1) `get_mut` is **very** expensive because it forces physics engine to recalculate contacts
         //    and a lot of other stuff, this is why we need `anything_changed` flag.
         if rigid_body_node.native.get() != RigidBodyHandle::invalid() {
+            if rigid_body_node.body_type() == crate::scene::rigidbody::RigidBodyType::Dynamic
+                && !rigid_body_node.is_sleeping()
+            {
+                self.additional_solver_iterations = self
+                    .additional_solver_iterations
+                    .max(rigid_body_node.additional_solver_iterations());
+            }
+
+            let (linear_sleep_threshold, angular_sleep_threshold) = self.sleep_thresholds();
             let mut actions = rigid_body_node.actions.lock();
             if rigid_body_node.need_sync_model() || !actions.is_empty() {
                 if let Some(native) = self.bodies.set.get_mut(rigid_body_node.native.get()) {
@@ -714,10 +980,8 @@ impl PhysicsWorld {
                     rigid_body_node.can_sleep.try_sync_model(|v| {
                         let mut activation = native.activation_mut();
                         if v {
-                            activation.linear_threshold =
-                                RigidBodyActivation::default_linear_threshold();
-                            activation.angular_threshold =
-                                RigidBodyActivation::default_angular_threshold();
+                            activation.linear_threshold = linear_sleep_threshold;
+                            activation.angular_threshold = angular_sleep_threshold;
                         } else {
                             activation.sleeping = false;
                             activation.linear_threshold = -1.0;
@@ -785,6 +1049,13 @@ impl PhysicsWorld {
                 false,
             );
 
+            if rigid_body_node.is_can_sleep() {
+                let (linear_threshold, angular_threshold) = self.sleep_thresholds();
+                let activation = body.activation_mut();
+                activation.linear_threshold = linear_threshold;
+                activation.angular_threshold = angular_threshold;
+            }
+
             rigid_body_node.native.set(self.add_body(handle, body));
 
             Log::writeln(
@@ -842,17 +1113,35 @@ impl PhysicsWorld {
                     collider_node
                         .friction
                         .try_sync_model(|v| native.set_friction(v));
-                    collider_node
-                        .is_sensor
-                        .try_sync_model(|v| native.set_sensor(v));
+                    collider_node.is_sensor.try_sync_model(|v| {
+                        native.set_sensor(v);
+                        native.set_active_events(active_events(v));
+                        native.set_active_collision_types(active_collision_types(v));
+                    });
                     collider_node
                         .friction_combine_rule
                         .try_sync_model(|v| native.set_friction_combine_rule(v.into()));
                     collider_node
                         .restitution_combine_rule
                         .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
+                    collider_node.material.try_sync_model(|_| {
+                        // Restore own properties of the collider, they will be overwritten by
+                        // the material below if it is set.
+                        native.set_friction(collider_node.friction());
+                        native.set_restitution(collider_node.restitution());
+                        native.set_friction_combine_rule(
+                            collider_node.friction_combine_rule().into(),
+                        );
+                        native.set_restitution_combine_rule(
+                            collider_node.restitution_combine_rule().into(),
+                        );
+                    });
                 }
             }
+
+            if let Some(material) = collider_node.material.as_ref().and_then(|m| m.material()) {
+                self.apply_physics_material(collider_node.native.get(), &material);
+            }
         } else if let Some(parent_body) = nodes
             .try_borrow(collider_node.parent())
             .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
@@ -881,7 +1170,9 @@ impl PhysicsWorld {
                             collider_node.solver_groups().memberships,
                             collider_node.solver_groups().filter,
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(active_events(collider_node.is_sensor()))
+                        .active_collision_types(active_collision_types(collider_node.is_sensor()));
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -892,6 +1183,12 @@ impl PhysicsWorld {
 
                     collider_node.native.set(native_handle);
 
+                    if let Some(material) =
+                        collider_node.material.as_ref().and_then(|m| m.material())
+                    {
+                        self.apply_physics_material(native_handle, &material);
+                    }
+
                     Log::writeln(
                         MessageKind::Information,
                         format!(
@@ -904,6 +1201,31 @@ impl PhysicsWorld {
         }
     }
 
+    // Overrides surface properties of a native collider with the properties of a physics material.
+    fn apply_physics_material(&mut self, handle: ColliderHandle, material: &PhysicsMaterial) {
+        let friction_combine_rule: rapier2d::dynamics::CoefficientCombineRule =
+            material.friction_combine_rule.into();
+        let restitution_combine_rule: rapier2d::dynamics::CoefficientCombineRule =
+            material.restitution_combine_rule.into();
+
+        // `get_mut` is very expensive, so check if anything has changed first.
+        let needs_update = self.colliders.set.get(handle).map_or(false, |native| {
+            native.friction() != material.friction
+                || native.restitution() != material.restitution
+                || native.friction_combine_rule() != friction_combine_rule
+                || native.restitution_combine_rule() != restitution_combine_rule
+        });
+
+        if needs_update {
+            if let Some(native) = self.colliders.set.get_mut(handle) {
+                native.set_friction(material.friction);
+                native.set_restitution(material.restitution);
+                native.set_friction_combine_rule(friction_combine_rule);
+                native.set_restitution_combine_rule(restitution_combine_rule);
+            }
+        }
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
@@ -103,6 +103,10 @@ pub struct RigidBody {
     #[inspect(getter = "Deref::deref")]
     pub(crate) gravity_scale: TemplateVariable<f32>,
 
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) additional_solver_iterations: TemplateVariable<u32>,
+
     #[visit(skip)]
     #[inspect(skip)]
     pub(crate) sleeping: bool,
@@ -128,7 +132,8 @@ impl_directly_inheritable_entity_trait!(RigidBody;
     ccd_enabled,
     can_sleep,
     dominance,
-    gravity_scale
+    gravity_scale,
+    additional_solver_iterations
 );
 
 impl Debug for RigidBody {
@@ -154,6 +159,7 @@ impl Default for RigidBody {
             can_sleep: TemplateVariable::new(true),
             dominance: Default::default(),
             gravity_scale: TemplateVariable::new(1.0),
+            additional_solver_iterations: Default::default(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
         }
@@ -191,6 +197,7 @@ impl Clone for RigidBody {
             can_sleep: self.can_sleep.clone(),
             dominance: self.dominance.clone(),
             gravity_scale: self.gravity_scale.clone(),
+            additional_solver_iterations: self.additional_solver_iterations.clone(),
             // Do not copy.
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
@@ -330,6 +337,19 @@ impl RigidBody {
         *self.dominance
     }
 
+    /// Sets the amount of additional velocity solver iterations the rigid body needs. Physics
+    /// world uses the highest requested value among all awake dynamic bodies on top of its own
+    /// [`IntegrationParameters::max_velocity_iterations`](crate::scene::graph::physics::IntegrationParameters::max_velocity_iterations).
+    /// This is useful for fast or heavy bodies that need more precise contact resolution.
+    pub fn set_additional_solver_iterations(&mut self, iterations: u32) {
+        self.additional_solver_iterations.set(iterations);
+    }
+
+    /// Returns the amount of additional velocity solver iterations the rigid body needs.
+    pub fn additional_solver_iterations(&self) -> u32 {
+        *self.additional_solver_iterations
+    }
+
     /// Applies a force at the center-of-mass of this rigid-body. The force will be applied in the
     /// next simulation step. This does nothing on non-dynamic bodies.
     pub fn apply_force(&mut self, force: Vector2<f32>) {
@@ -492,6 +512,7 @@ pub struct RigidBodyBuilder {
     can_sleep: bool,
     dominance: i8,
     gravity_scale: f32,
+    additional_solver_iterations: u32,
 }
 
 impl RigidBodyBuilder {
@@ -512,6 +533,7 @@ impl RigidBodyBuilder {
             can_sleep: true,
             dominance: 0,
             gravity_scale: 1.0,
+            additional_solver_iterations: 0,
         }
     }
 
@@ -581,6 +603,12 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets desired amount of additional velocity solver iterations.
+    pub fn with_additional_solver_iterations(mut self, iterations: u32) -> Self {
+        self.additional_solver_iterations = iterations;
+        self
+    }
+
     /// Sets initial state of the body (sleeping or not).
     pub fn with_sleeping(mut self, sleeping: bool) -> Self {
         self.sleeping = sleeping;
@@ -610,6 +638,7 @@ impl RigidBodyBuilder {
             can_sleep: self.can_sleep.into(),
             dominance: self.dominance.into(),
             gravity_scale: self.gravity_scale.into(),
+            additional_solver_iterations: self.additional_solver_iterations.into(),
             native: Cell::new(RigidBodyHandle::invalid()),
             actions: Default::default(),
         }
@@ -652,6 +681,7 @@ mod test {
             .with_lin_damping(0.1)
             .with_ang_damping(0.1)
             .with_dominance(123)
+            .with_additional_solver_iterations(4)
             .with_translation_locked(true)
             .build_node();
 
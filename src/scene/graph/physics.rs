@@ -16,6 +16,7 @@ use crate::{
         visitor::prelude::*,
         BiDirHashMap,
     },
+    resource::physics_material::PhysicsMaterial,
     scene::{
         self,
         collider::{self, ColliderShape, GeometrySource},
@@ -47,12 +48,15 @@ use rapier3d::{
         InteractionGroups, NarrowPhase, Ray, Shape, SharedShape, TriMesh,
     },
     math::UnitVector,
-    pipeline::{EventHandler, PhysicsPipeline, QueryPipeline},
+    parry::transformation::vhacd::VHACDParameters,
+    pipeline::{PhysicsPipeline, QueryPipeline},
     prelude::JointAxis,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cell::{Cell, RefCell},
     cmp::Ordering,
+    collections::VecDeque,
     fmt::{Debug, Formatter},
     hash::Hash,
     sync::Arc,
@@ -183,6 +187,82 @@ impl PhysicsPerformanceStatistics {
     }
 }
 
+/// A set of flags that defines which parts of a physics world will be drawn by debug drawing
+/// methods, such as [`PhysicsWorld::draw_with_flags`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Inspect)]
+pub struct PhysicsDebugFlags {
+    /// Draw wireframes of colliders and basis vectors of rigid bodies.
+    pub colliders: bool,
+    /// Color collider wireframes by the state of their rigid bodies: green - active dynamic
+    /// body, blue - sleeping body, yellow - kinematic body, gray - fixed body.
+    pub body_states: bool,
+    /// Draw contact points, contact normals and normal impulses applied at contact points.
+    pub contacts: bool,
+    /// Draw joint anchors (as basis vectors), a line between anchors and joint limits. Both impulse
+    /// and multibody joints are drawn.
+    pub joints: bool,
+    /// Draw centers of mass of rigid bodies.
+    pub center_of_mass: bool,
+    /// Draw linear (cyan) and angular (magenta) velocities of rigid bodies.
+    pub velocities: bool,
+    /// Draw axis-aligned bounding boxes of colliders that are used by the broad phase.
+    pub bounding_boxes: bool,
+}
+
+impl Default for PhysicsDebugFlags {
+    fn default() -> Self {
+        Self {
+            colliders: true,
+            body_states: false,
+            contacts: false,
+            joints: false,
+            center_of_mass: false,
+            velocities: false,
+            bounding_boxes: false,
+        }
+    }
+}
+
+impl PhysicsDebugFlags {
+    pub(crate) const COLLIDER_COLOR: Color = Color::opaque(200, 200, 200);
+    pub(crate) const CONTACT_COLOR: Color = Color::opaque(255, 0, 0);
+    pub(crate) const NORMAL_COLOR: Color = Color::opaque(255, 255, 0);
+    pub(crate) const IMPULSE_COLOR: Color = Color::opaque(255, 127, 0);
+    pub(crate) const JOINT_COLOR: Color = Color::opaque(255, 255, 255);
+    pub(crate) const LIMITS_COLOR: Color = Color::opaque(255, 0, 255);
+    pub(crate) const CENTER_OF_MASS_COLOR: Color = Color::opaque(255, 255, 255);
+    pub(crate) const LIN_VEL_COLOR: Color = Color::opaque(0, 255, 255);
+    pub(crate) const ANG_VEL_COLOR: Color = Color::opaque(255, 0, 255);
+    pub(crate) const AABB_COLOR: Color = Color::opaque(0, 127, 255);
+
+    // Length of contact normals.
+    pub(crate) const NORMAL_LENGTH: f32 = 0.25;
+    // Normal impulses could be arbitrary large, so they're clamped to keep the picture readable.
+    pub(crate) const MAX_IMPULSE_LENGTH: f32 = 2.0;
+    // Size of a single joint anchor axis.
+    pub(crate) const ANCHOR_SIZE: f32 = 0.25;
+
+    /// Returns a color for a collider wireframe, respecting `body_states` flag.
+    pub(crate) fn collider_color(
+        &self,
+        is_fixed: bool,
+        is_kinematic: bool,
+        sleeping: bool,
+    ) -> Color {
+        if !self.body_states {
+            Self::COLLIDER_COLOR
+        } else if is_fixed {
+            Color::opaque(127, 127, 127)
+        } else if is_kinematic {
+            Color::opaque(255, 255, 0)
+        } else if sleeping {
+            Color::opaque(0, 0, 255)
+        } else {
+            Color::opaque(0, 255, 0)
+        }
+    }
+}
+
 /// A ray intersection result.
 #[derive(Debug, Clone)]
 pub struct Intersection {
@@ -323,6 +403,323 @@ pub struct ContactPair {
     pub has_any_active_contact: bool,
 }
 
+/// Information about a collider that entered or left a trigger volume. Trigger volume is any
+/// collider that is marked as sensor (see [`crate::scene::collider::Collider::set_is_sensor`]).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Visit)]
+pub struct TriggerContact {
+    /// A handle of the sensor collider that acts as a trigger volume.
+    pub trigger: Handle<Node>,
+    /// A handle of the rigid body to which the trigger collider is attached.
+    pub trigger_body: Handle<Node>,
+    /// A handle of the collider that entered or left the trigger volume.
+    pub collider: Handle<Node>,
+    /// A handle of the rigid body to which the collider is attached.
+    pub body: Handle<Node>,
+    /// Collision groups of the collider that entered or left the trigger volume. It could be used
+    /// to filter out unwanted events, see [`TriggerEvent::test`].
+    pub collision_groups: collider::InteractionGroups,
+}
+
+impl TriggerContact {
+    fn is_same_pair(&self, trigger: Handle<Node>, collider: Handle<Node>) -> bool {
+        self.trigger == trigger && self.collider == collider
+    }
+}
+
+/// An event that is produced by a physics world when a collider enters or leaves a trigger volume.
+///
+/// # Notes
+///
+/// Trigger events live for one simulation step only, they're available right after the physics
+/// world was updated until its next update. This means that plugins and scripts will receive every
+/// event from their `update` methods. `Exited` events that were produced by removal of a trigger or
+/// a collider are delivered together with the events of the next simulation step.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::scene::graph::physics::{PhysicsWorld, TriggerEvent};
+/// fn print_trigger_events(physics: &PhysicsWorld) {
+///     for event in physics.trigger_events() {
+///         match event {
+///             TriggerEvent::Entered(contact) => {
+///                 println!("{} entered {}", contact.collider, contact.trigger)
+///             }
+///             TriggerEvent::Exited(contact) => {
+///                 println!("{} left {}", contact.collider, contact.trigger)
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerEvent {
+    /// A collider has started intersecting with a trigger volume.
+    Entered(TriggerContact),
+    /// A collider has stopped intersecting with a trigger volume. It is also produced when either
+    /// trigger or collider was removed.
+    Exited(TriggerContact),
+}
+
+impl TriggerEvent {
+    /// Returns a reference to contact information of the event.
+    pub fn contact(&self) -> &TriggerContact {
+        match self {
+            TriggerEvent::Entered(contact) | TriggerEvent::Exited(contact) => contact,
+        }
+    }
+
+    /// Returns `true` if collision groups of the collider that produced the event are allowed to
+    /// interact with the given groups. See [`collider::InteractionGroups`] docs for more info.
+    pub fn test(&self, groups: collider::InteractionGroups) -> bool {
+        self.contact().collision_groups.test(groups)
+    }
+}
+
+/// Trigger event queue keeps track of intersecting trigger-collider pairs and generates events
+/// when the set of pairs changes.
+#[derive(Default, Debug)]
+pub(crate) struct TriggerEventQueue {
+    events: VecDeque<TriggerEvent>,
+    // Events that were produced by removal of colliders after the last simulation step. Colliders
+    // are usually removed by scripts and plugins, which run after the step, so such events are
+    // delayed until the next step to be seen by every consumer.
+    removed: Vec<TriggerEvent>,
+    // Pairs that are currently intersecting.
+    active: Vec<TriggerContact>,
+    // Pairs that were loaded from a save file. They must be confirmed by a first simulation step
+    // after loading, otherwise every loaded pair will produce `Entered` event once again.
+    restored: Vec<TriggerContact>,
+}
+
+impl Visit for TriggerEventQueue {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        if region.is_reading() {
+            self.restored.visit("Active", &mut region)?;
+        } else {
+            self.active.visit("Active", &mut region)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TriggerEventQueue {
+    /// Removes events of the previous simulation step, must be called before the next step.
+    pub(crate) fn clear_events(&mut self) {
+        self.events.clear();
+        self.events.extend(self.removed.drain(..));
+    }
+
+    pub(crate) fn enter(&mut self, contact: TriggerContact) {
+        if self
+            .active
+            .iter()
+            .any(|c| c.is_same_pair(contact.trigger, contact.collider))
+        {
+            return;
+        }
+
+        if let Some(index) = self
+            .restored
+            .iter()
+            .position(|c| c.is_same_pair(contact.trigger, contact.collider))
+        {
+            // The pair was intersecting before the world was saved, do not produce the event.
+            self.restored.swap_remove(index);
+        } else {
+            self.events.push_back(TriggerEvent::Entered(contact));
+        }
+
+        self.active.push(contact);
+    }
+
+    pub(crate) fn exit(&mut self, trigger: Handle<Node>, collider: Handle<Node>) {
+        if let Some(index) = self
+            .active
+            .iter()
+            .position(|c| c.is_same_pair(trigger, collider))
+        {
+            let contact = self.active.swap_remove(index);
+            self.events.push_back(TriggerEvent::Exited(contact));
+        }
+    }
+
+    /// Produces `Exited` event for every pair that includes the given collider.
+    pub(crate) fn remove_collider(&mut self, collider: Handle<Node>) {
+        let mut i = 0;
+        while i < self.active.len() {
+            let contact = self.active[i];
+            if contact.trigger == collider || contact.collider == collider {
+                self.active.swap_remove(i);
+                self.removed.push(TriggerEvent::Exited(contact));
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Every loaded pair that wasn't confirmed by the simulation step is not intersecting anymore.
+    pub(crate) fn flush_restored(&mut self) {
+        for contact in self.restored.drain(..) {
+            self.events.push_back(TriggerEvent::Exited(contact));
+        }
+    }
+
+    pub(crate) fn events(&self) -> impl Iterator<Item = &TriggerEvent> {
+        self.events.iter()
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<TriggerEvent> {
+        self.events.pop_front()
+    }
+}
+
+// Trigger events are produced the same way by both 2D and 3D physics worlds, but the worlds are
+// using different native physics engines, so the shared code is generated by this macro. The
+// `items` arm must be used in a module of a physics world and the `methods` arm - in its `impl`
+// block.
+macro_rules! impl_trigger_events {
+    (items, $rapier:ident) => {
+        // Collects collision events produced by native physics engine during simulation step.
+        #[derive(Default)]
+        struct CollisionEventCollector {
+            events: $crate::core::parking_lot::Mutex<Vec<$rapier::geometry::CollisionEvent>>,
+        }
+
+        impl $rapier::pipeline::EventHandler for CollisionEventCollector {
+            fn handle_collision_event(
+                &self,
+                _bodies: &$rapier::dynamics::RigidBodySet,
+                _colliders: &$rapier::geometry::ColliderSet,
+                event: $rapier::geometry::CollisionEvent,
+                _contact_pair: Option<&$rapier::geometry::ContactPair>,
+            ) {
+                self.events.lock().push(event);
+            }
+        }
+
+        // Only sensors produce collision events, they're used to generate trigger events.
+        fn active_events(is_sensor: bool) -> $rapier::pipeline::ActiveEvents {
+            if is_sensor {
+                $rapier::pipeline::ActiveEvents::COLLISION_EVENTS
+            } else {
+                $rapier::pipeline::ActiveEvents::empty()
+            }
+        }
+
+        // Trigger volumes are often attached to fixed bodies, but by default collision detection
+        // is disabled for pairs of non-dynamic bodies, so sensors must enable every collision type.
+        fn active_collision_types(is_sensor: bool) -> $rapier::geometry::ActiveCollisionTypes {
+            if is_sensor {
+                $rapier::geometry::ActiveCollisionTypes::all()
+            } else {
+                $rapier::geometry::ActiveCollisionTypes::default()
+            }
+        }
+    };
+    (methods, $rapier:ident) => {
+        fn handle_collision_events(&mut self) {
+            let events = std::mem::take(&mut *self.event_handler.events.lock());
+
+            for event in events {
+                // Events of removed colliders are handled in `remove_collider`.
+                if let (Some(native1), Some(native2)) = (
+                    self.colliders.set.get(event.collider1()),
+                    self.colliders.set.get(event.collider2()),
+                ) {
+                    for (trigger_handle, trigger, collider_handle, collider) in [
+                        (event.collider1(), native1, event.collider2(), native2),
+                        (event.collider2(), native2, event.collider1(), native1),
+                    ] {
+                        if !trigger.is_sensor() {
+                            continue;
+                        }
+
+                        if let (Some(&trigger_node), Some(&collider_node)) = (
+                            self.colliders.map.value_of(&trigger_handle),
+                            self.colliders.map.value_of(&collider_handle),
+                        ) {
+                            if event.started() {
+                                let body_node_of = |c: &$rapier::geometry::Collider| {
+                                    c.parent()
+                                        .and_then(|h| self.bodies.map.value_of(&h).cloned())
+                                        .unwrap_or_default()
+                                };
+
+                                let groups = collider.collision_groups();
+
+                                self.trigger_events.enter(
+                                    $crate::scene::graph::physics::TriggerContact {
+                                        trigger: trigger_node,
+                                        trigger_body: body_node_of(trigger),
+                                        collider: collider_node,
+                                        body: body_node_of(collider),
+                                        collision_groups:
+                                            $crate::scene::collider::InteractionGroups::new(
+                                                groups.memberships,
+                                                groups.filter,
+                                            ),
+                                    },
+                                );
+                            } else {
+                                self.trigger_events.exit(trigger_node, collider_node);
+                            }
+                        }
+                    }
+                }
+            }
+
+            self.trigger_events.flush_restored();
+        }
+
+        /// Returns an iterator over trigger events produced by the last simulation step. See
+        /// [`crate::scene::graph::physics::TriggerEvent`] docs for more info.
+        pub fn trigger_events(
+            &self,
+        ) -> impl Iterator<Item = &$crate::scene::graph::physics::TriggerEvent> {
+            self.trigger_events.events()
+        }
+
+        /// Returns an iterator over trigger events produced by the last simulation step, whose
+        /// colliders are allowed to interact with the given interaction groups.
+        pub fn filtered_trigger_events(
+            &self,
+            groups: $crate::scene::collider::InteractionGroups,
+        ) -> impl Iterator<Item = &$crate::scene::graph::physics::TriggerEvent> {
+            self.trigger_events.events().filter(move |e| e.test(groups))
+        }
+
+        /// Extracts first trigger event from the queue. Keep in mind that the event will be
+        /// unavailable for any other consumer (plugin or script), use [`Self::trigger_events`] if
+        /// you need to share events.
+        pub fn pop_trigger_event(&mut self) -> Option<$crate::scene::graph::physics::TriggerEvent> {
+            self.trigger_events.pop()
+        }
+
+        pub(crate) fn trigger_events_of(
+            &self,
+            trigger: $rapier::geometry::ColliderHandle,
+        ) -> impl Iterator<Item = &$crate::scene::graph::physics::TriggerEvent> {
+            let trigger = self
+                .colliders
+                .map
+                .value_of(&trigger)
+                .cloned()
+                .unwrap_or_default();
+            self.trigger_events
+                .events()
+                .filter(move |e| trigger.is_some() && e.contact().trigger == trigger)
+        }
+    };
+}
+
+pub(crate) use impl_trigger_events;
+
+impl_trigger_events!(items, rapier3d);
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -476,9 +873,12 @@ fn make_trimesh(
     }
 }
 
-/// Creates new convex polyhedron collider shape from given mesh node. It also bakes scale into
-/// vertices of trimesh because rapier does not support collider scaling yet.
-fn make_polyhedron_shape(owner_inv_transform: Matrix4<f32>, mesh: &Mesh) -> SharedShape {
+/// Collects vertices and triangles of given mesh node. It also bakes scale into vertices because
+/// rapier does not support collider scaling yet.
+fn make_mesh_geometry(
+    owner_inv_transform: Matrix4<f32>,
+    mesh: &Mesh,
+) -> (Vec<Point3<f32>>, Vec<[u32; 3]>) {
     let mut mesh_builder = RawMeshBuilder::new(0, 0);
 
     // Create inverse transform that will discard rotation and translation, but leave scaling and
@@ -550,9 +950,48 @@ fn make_polyhedron_shape(owner_inv_transform: Matrix4<f32>, mesh: &Mesh) -> Shar
         .map(|t| [t.0[0], t.0[1], t.0[2]])
         .collect::<Vec<_>>();
 
+    (vertices, indices)
+}
+
+/// Creates new convex polyhedron collider shape from given mesh node.
+fn make_polyhedron_shape(owner_inv_transform: Matrix4<f32>, mesh: &Mesh) -> SharedShape {
+    let (vertices, indices) = make_mesh_geometry(owner_inv_transform, mesh);
+
     SharedShape::convex_decomposition(&vertices, &indices)
 }
 
+/// Creates new compound collider shape by approximate convex decomposition of given mesh node.
+fn make_convex_decomposition_shape(
+    owner_inv_transform: Matrix4<f32>,
+    mesh: &Mesh,
+    decomposition: &collider::ConvexDecompositionShape,
+) -> Option<SharedShape> {
+    let (vertices, indices) = make_mesh_geometry(owner_inv_transform, mesh);
+
+    if indices.is_empty() {
+        Log::writeln(
+            MessageKind::Warning,
+            format!(
+                "Failed to create convex decomposition collider for {}, it has no vertices!",
+                mesh.name()
+            ),
+        );
+
+        return None;
+    }
+
+    let params = VHACDParameters {
+        concavity: decomposition.concavity,
+        max_convex_hulls: decomposition.max_convex_hulls.max(1),
+        resolution: decomposition.resolution.max(2),
+        ..Default::default()
+    };
+
+    Some(SharedShape::convex_decomposition_with_params(
+        &vertices, &indices, &params,
+    ))
+}
+
 /// Creates height field shape from given terrain.
 fn make_heightfield(terrain: &Terrain) -> SharedShape {
     assert!(!terrain.chunks_ref().is_empty());
@@ -649,6 +1088,12 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        ColliderShape::ConvexDecomposition(decomposition) => pool
+            .try_borrow(decomposition.geometry_source.0)
+            .and_then(|n| n.cast::<Mesh>())
+            .and_then(|mesh| {
+                make_convex_decomposition_shape(owner_inv_global_transform, mesh, decomposition)
+            }),
     }
 }
 
@@ -788,6 +1233,24 @@ pub struct IntegrationParameters {
         description = "Maximum number of substeps performed by the  solver (default: `1`)."
     )]
     pub max_ccd_substeps: u32,
+
+    /// Linear velocity below which a rigid body is allowed to fall asleep (default: `0.4`). Applies
+    /// to every rigid body that can sleep.
+    #[inspect(
+        min_value = 0.0,
+        description = "Linear velocity below which a rigid body is allowed to fall asleep (default: `0.4`)."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub linear_sleep_threshold: f32,
+
+    /// Angular velocity below which a rigid body is allowed to fall asleep (default: `0.5`). Applies
+    /// to every rigid body that can sleep.
+    #[inspect(
+        min_value = 0.0,
+        description = "Angular velocity below which a rigid body is allowed to fall asleep (default: `0.5`)."
+    )]
+    #[visit(optional)] // Backward compatibility
+    pub angular_sleep_threshold: f32,
 }
 
 impl Default for IntegrationParameters {
@@ -808,6 +1271,8 @@ impl Default for IntegrationParameters {
             interleave_restitution_and_friction_resolution: true,
             min_island_size: 128,
             max_ccd_substeps: 1,
+            linear_sleep_threshold: RigidBodyActivation::default_linear_threshold(),
+            angular_sleep_threshold: RigidBodyActivation::default_angular_threshold(),
         }
     }
 }
@@ -871,20 +1336,38 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[inspect(skip)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector,
     #[visit(skip)]
     #[inspect(skip)]
     query: RefCell<QueryPipeline>,
+    // A queue of trigger events, it also keeps track of intersecting trigger-collider pairs.
+    #[visit(optional)] // Backward compatibility
+    #[inspect(skip)]
+    trigger_events: TriggerEventQueue,
+    // Sleeping thresholds that were applied to native rigid bodies last time.
+    #[visit(skip)]
+    #[inspect(skip)]
+    applied_sleep_thresholds: (f32, f32),
+    // The highest amount of additional solver iterations requested by awake dynamic bodies,
+    // collected during the sync of rigid body nodes and reset after each simulation step.
+    #[visit(skip)]
+    #[inspect(skip)]
+    additional_solver_iterations: u32,
 }
 
-fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDrawingContext) {
+fn draw_shape(
+    shape: &dyn Shape,
+    transform: Matrix4<f32>,
+    context: &mut SceneDrawingContext,
+    color: Color,
+) {
     if let Some(trimesh) = shape.as_trimesh() {
         let trimesh: &TriMesh = trimesh;
         for triangle in trimesh.triangles() {
             let a = transform.transform_point(&triangle.a);
             let b = transform.transform_point(&triangle.b);
             let c = transform.transform_point(&triangle.c);
-            context.draw_triangle(a.coords, b.coords, c.coords, Color::opaque(200, 200, 200));
+            context.draw_triangle(a.coords, b.coords, c.coords, color);
         }
     } else if let Some(cuboid) = shape.as_cuboid() {
         let min = -cuboid.half_extents;
@@ -892,7 +1375,7 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
         context.draw_oob(
             &AxisAlignedBoundingBox::from_min_max(min, max),
             transform,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(ball) = shape.as_ball() {
         context.draw_sphere(
@@ -900,16 +1383,10 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
             10,
             10,
             ball.radius,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(cone) = shape.as_cone() {
-        context.draw_cone(
-            10,
-            cone.radius,
-            cone.half_height * 2.0,
-            transform,
-            Color::opaque(200, 200, 200),
-        );
+        context.draw_cone(10, cone.radius, cone.half_height * 2.0, transform, color);
     } else if let Some(cylinder) = shape.as_cylinder() {
         context.draw_cylinder(
             10,
@@ -917,7 +1394,7 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
             cylinder.half_height * 2.0,
             true,
             transform,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(round_cylinder) = shape.as_round_cylinder() {
         context.draw_cylinder(
@@ -926,14 +1403,14 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
             round_cylinder.inner_shape.half_height * 2.0,
             false,
             transform,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(triangle) = shape.as_triangle() {
         context.draw_triangle(
             triangle.a.coords,
             triangle.b.coords,
             triangle.c.coords,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(capsule) = shape.as_capsule() {
         context.draw_segment_capsule(
@@ -943,14 +1420,14 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
             10,
             10,
             transform,
-            Color::opaque(200, 200, 200),
+            color,
         );
     } else if let Some(heightfield) = shape.as_heightfield() {
         for triangle in heightfield.triangles() {
             let a = transform.transform_point(&triangle.a);
             let b = transform.transform_point(&triangle.b);
             let c = transform.transform_point(&triangle.c);
-            context.draw_triangle(a.coords, b.coords, c.coords, Color::opaque(200, 200, 200));
+            context.draw_triangle(a.coords, b.coords, c.coords, color);
         }
     } else if let Some(polyhedron) = shape.as_convex_polyhedron() {
         for edge in polyhedron.edges() {
@@ -963,13 +1440,103 @@ fn draw_shape(shape: &dyn Shape, transform: Matrix4<f32>, context: &mut SceneDra
             context.add_line(Line {
                 begin: pa,
                 end: pb,
-                color: Color::opaque(200, 200, 200),
+                color,
             })
         }
     } else if let Some(compound) = shape.as_compound() {
         for (isometry, shape) in compound.shapes() {
             let child_transform = isometry.to_homogeneous() * transform;
-            draw_shape(&**shape, child_transform, context);
+            draw_shape(&**shape, child_transform, context, color);
+        }
+    }
+}
+
+pub(crate) fn draw_cross(
+    context: &mut SceneDrawingContext,
+    position: Vector3<f32>,
+    size: f32,
+    color: Color,
+) {
+    for axis in [Vector3::x(), Vector3::y(), Vector3::z()] {
+        context.add_line(Line {
+            begin: position - axis.scale(size),
+            end: position + axis.scale(size),
+            color,
+        });
+    }
+}
+
+fn draw_joint(
+    context: &mut SceneDrawingContext,
+    body1: &RigidBody,
+    body2: &RigidBody,
+    data: &GenericJoint,
+) {
+    let frame1 = (body1.position() * data.local_frame1).to_homogeneous();
+    let frame2 = (body2.position() * data.local_frame2).to_homogeneous();
+
+    let scale = Matrix4::new_scaling(PhysicsDebugFlags::ANCHOR_SIZE);
+    context.draw_transform(frame1 * scale);
+    context.draw_transform(frame2 * scale);
+    context.add_line(Line {
+        begin: frame1.position(),
+        end: frame2.position(),
+        color: PhysicsDebugFlags::JOINT_COLOR,
+    });
+
+    let limits = &data.limits;
+    let limit_axes = data.limit_axes;
+
+    // Linear limits are drawn as segments along respective axes of the first frame.
+    for (i, (mask, axis)) in [
+        (JointAxesMask::X, Vector3::x()),
+        (JointAxesMask::Y, Vector3::y()),
+        (JointAxesMask::Z, Vector3::z()),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if limit_axes.contains(mask) {
+            context.add_line(Line {
+                begin: frame1
+                    .transform_point(&Point3::from(axis * limits[i].min))
+                    .coords,
+                end: frame1
+                    .transform_point(&Point3::from(axis * limits[i].max))
+                    .coords,
+                color: PhysicsDebugFlags::LIMITS_COLOR,
+            });
+        }
+    }
+
+    // Angular limits are drawn as arcs in a plane that is orthogonal to a respective
+    // axis of the first frame. Circle segments are drawn in XY plane, so the frame must
+    // be rotated to make Z axis of the segment to match the rotation axis.
+    for (i, (mask, plane_rotation)) in [
+        (
+            JointAxesMask::ANG_X,
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::FRAC_PI_2),
+        ),
+        (
+            JointAxesMask::ANG_Y,
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -std::f32::consts::FRAC_PI_2),
+        ),
+        (JointAxesMask::ANG_Z, UnitQuaternion::identity()),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if limit_axes.contains(mask) {
+            let limits = &limits[i + 3];
+            context.draw_circle_segment(
+                Vector3::default(),
+                PhysicsDebugFlags::ANCHOR_SIZE,
+                24,
+                limits.min,
+                limits.max,
+                frame1 * plane_rotation.to_homogeneous(),
+                PhysicsDebugFlags::LIMITS_COLOR,
+            );
         }
     }
 }
@@ -1009,16 +1576,26 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
+            trigger_events: Default::default(),
+            applied_sleep_thresholds: (
+                RigidBodyActivation::default_linear_threshold(),
+                RigidBodyActivation::default_angular_threshold(),
+            ),
+            additional_solver_iterations: 0,
         }
     }
 
     pub(super) fn update(&mut self) {
         let time = instant::Instant::now();
 
+        self.trigger_events.clear_events();
+
         if self.enabled {
+            self.apply_sleep_thresholds();
+
             let integration_parameters = rapier3d::dynamics::IntegrationParameters {
                 dt: self.integration_parameters.dt,
                 min_ccd_dt: self.integration_parameters.min_ccd_dt,
@@ -1029,7 +1606,8 @@ impl PhysicsWorld {
                 allowed_linear_error: self.integration_parameters.allowed_linear_error,
                 max_penetration_correction: self.integration_parameters.max_penetration_correction,
                 prediction_distance: self.integration_parameters.prediction_distance,
-                max_velocity_iterations: self.integration_parameters.max_velocity_iterations
+                max_velocity_iterations: (self.integration_parameters.max_velocity_iterations
+                    + self.additional_solver_iterations)
                     as usize,
                 max_velocity_friction_iterations: self
                     .integration_parameters
@@ -1058,13 +1636,42 @@ impl PhysicsWorld {
                 &mut self.multibody_joints.set,
                 &mut self.ccd_solver,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            self.handle_collision_events();
         }
 
+        self.additional_solver_iterations = 0;
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    impl_trigger_events!(methods, rapier3d);
+
+    fn sleep_thresholds(&self) -> (f32, f32) {
+        (
+            self.integration_parameters.linear_sleep_threshold,
+            self.integration_parameters.angular_sleep_threshold,
+        )
+    }
+
+    // Propagates changed world-level sleeping thresholds to every native rigid body that can sleep.
+    fn apply_sleep_thresholds(&mut self) {
+        let (linear_threshold, angular_threshold) = self.sleep_thresholds();
+        if self.applied_sleep_thresholds != (linear_threshold, angular_threshold) {
+            for (_, body) in self.bodies.set.iter_mut() {
+                let activation = body.activation_mut();
+                // Negative thresholds mark bodies that cannot sleep.
+                if activation.linear_threshold >= 0.0 {
+                    activation.linear_threshold = linear_threshold;
+                    activation.angular_threshold = angular_threshold;
+                }
+            }
+            self.applied_sleep_thresholds = (linear_threshold, angular_threshold);
+        }
+    }
+
     pub(super) fn add_body(&mut self, owner: Handle<Node>, body: RigidBody) -> RigidBodyHandle {
         let handle = self.bodies.set.insert(body);
         self.bodies.map.insert(handle, owner);
@@ -1072,6 +1679,15 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        if let Some(body) = self.bodies.set.get(handle) {
+            // Attached colliders will be removed too.
+            for collider in body.colliders() {
+                if let Some(&collider_node) = self.colliders.map.value_of(collider) {
+                    self.trigger_events.remove_collider(collider_node);
+                }
+            }
+        }
+
         assert!(self.bodies.map.remove_by_key(&handle).is_some());
         self.bodies.set.remove(
             handle,
@@ -1098,6 +1714,10 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
+        if let Some(&collider_node) = self.colliders.map.value_of(&handle) {
+            self.trigger_events.remove_collider(collider_node);
+        }
+
         if self
             .colliders
             .set
@@ -1129,17 +1749,134 @@ impl PhysicsWorld {
     }
 
     /// Draws physics world. Very useful for debugging, it allows you to see where are
-    /// rigid bodies, which colliders they have and so on.
+    /// rigid bodies, which colliders they have and so on. It draws colliders only, use
+    /// [`Self::draw_with_flags`] to draw other parts of the world.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        for (_, body) in self.bodies.set.iter() {
-            context.draw_transform(body.position().to_homogeneous());
+        self.draw_with_flags(context, &PhysicsDebugFlags::default())
+    }
+
+    /// Draws parts of physics world, defined by the given set of flags. See [`PhysicsDebugFlags`]
+    /// docs for more info.
+    pub fn draw_with_flags(&self, context: &mut SceneDrawingContext, flags: &PhysicsDebugFlags) {
+        if flags.colliders {
+            for (_, body) in self.bodies.set.iter() {
+                context.draw_transform(body.position().to_homogeneous());
+            }
+
+            for (_, collider) in self.colliders.set.iter() {
+                let body = self.bodies.set.get(collider.parent().unwrap()).unwrap();
+                let collider_local_transform =
+                    collider.position_wrt_parent().unwrap().to_homogeneous();
+                let transform = body.position().to_homogeneous() * collider_local_transform;
+                let color =
+                    flags.collider_color(body.is_fixed(), body.is_kinematic(), body.is_sleeping());
+                draw_shape(collider.shape(), transform, context, color);
+            }
+        }
+
+        if flags.bounding_boxes {
+            for (_, collider) in self.colliders.set.iter() {
+                let aabb = collider.compute_aabb();
+                context.draw_aabb(
+                    &AxisAlignedBoundingBox::from_min_max(aabb.mins.coords, aabb.maxs.coords),
+                    PhysicsDebugFlags::AABB_COLOR,
+                );
+            }
+        }
+
+        if flags.contacts {
+            self.draw_contacts(context);
+        }
+
+        if flags.joints {
+            self.draw_joints(context);
+        }
+
+        if flags.center_of_mass || flags.velocities {
+            for (_, body) in self.bodies.set.iter() {
+                let center = (body.position() * body.mass_properties().local_com).coords;
+
+                if flags.center_of_mass {
+                    draw_cross(
+                        context,
+                        center,
+                        0.1,
+                        PhysicsDebugFlags::CENTER_OF_MASS_COLOR,
+                    );
+                }
+
+                if flags.velocities {
+                    context.add_line(Line {
+                        begin: center,
+                        end: center + body.linvel(),
+                        color: PhysicsDebugFlags::LIN_VEL_COLOR,
+                    });
+                    context.add_line(Line {
+                        begin: center,
+                        end: center + body.angvel(),
+                        color: PhysicsDebugFlags::ANG_VEL_COLOR,
+                    });
+                }
+            }
+        }
+    }
+
+    fn draw_contacts(&self, context: &mut SceneDrawingContext) {
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            if let Some(collider1) = self.colliders.set.get(pair.collider1) {
+                for manifold in pair.manifolds.iter() {
+                    let normal = manifold.data.normal;
+
+                    for point in manifold.points.iter() {
+                        let position = collider1.position().transform_point(&point.local_p1).coords;
+
+                        draw_cross(context, position, 0.05, PhysicsDebugFlags::CONTACT_COLOR);
+                        context.add_line(Line {
+                            begin: position,
+                            end: position + normal.scale(PhysicsDebugFlags::NORMAL_LENGTH),
+                            color: PhysicsDebugFlags::NORMAL_COLOR,
+                        });
+                        context.add_line(Line {
+                            begin: position,
+                            end: position
+                                + normal.scale(
+                                    point
+                                        .data
+                                        .impulse
+                                        .min(PhysicsDebugFlags::MAX_IMPULSE_LENGTH),
+                                ),
+                            color: PhysicsDebugFlags::IMPULSE_COLOR,
+                        });
+                    }
+                }
+            }
         }
+    }
 
-        for (_, collider) in self.colliders.set.iter() {
-            let body = self.bodies.set.get(collider.parent().unwrap()).unwrap();
-            let collider_local_transform = collider.position_wrt_parent().unwrap().to_homogeneous();
-            let transform = body.position().to_homogeneous() * collider_local_transform;
-            draw_shape(collider.shape(), transform, context);
+    fn draw_joints(&self, context: &mut SceneDrawingContext) {
+        for (_, joint) in self.joints.set.iter() {
+            if let (Some(body1), Some(body2)) = (
+                self.bodies.set.get(joint.body1),
+                self.bodies.set.get(joint.body2),
+            ) {
+                draw_joint(context, body1, body2, &joint.data);
+            }
+        }
+
+        // Multibody joints connect a link with its parent link.
+        for (_, multibody, link) in self.multibody_joints.set.iter() {
+            if let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) {
+                if let (Some(body1), Some(body2)) = (
+                    self.bodies.set.get(parent.rigid_body_handle()),
+                    self.bodies.set.get(link.rigid_body_handle()),
+                ) {
+                    draw_joint(context, body1, body2, &link.joint.data);
+                }
+            }
         }
     }
 
@@ -1258,6 +1995,15 @@ impl PhysicsWorld {
         // 1) `get_mut` is **very** expensive because it forces physics engine to recalculate contacts
         //    and a lot of other stuff, this is why we need `anything_changed` flag.
         if rigid_body_node.native.get() != RigidBodyHandle::invalid() {
+            if rigid_body_node.body_type() == scene::rigidbody::RigidBodyType::Dynamic
+                && !rigid_body_node.is_sleeping()
+            {
+                self.additional_solver_iterations = self
+                    .additional_solver_iterations
+                    .max(rigid_body_node.additional_solver_iterations());
+            }
+
+            let (linear_sleep_threshold, angular_sleep_threshold) = self.sleep_thresholds();
             let mut actions = rigid_body_node.actions.lock();
             if rigid_body_node.need_sync_model() || !actions.is_empty() {
                 if let Some(native) = self.bodies.set.get_mut(rigid_body_node.native.get()) {
@@ -1289,10 +2035,8 @@ impl PhysicsWorld {
                     rigid_body_node.can_sleep.try_sync_model(|v| {
                         let mut activation = native.activation_mut();
                         if v {
-                            activation.linear_threshold =
-                                RigidBodyActivation::default_linear_threshold();
-                            activation.angular_threshold =
-                                RigidBodyActivation::default_angular_threshold();
+                            activation.linear_threshold = linear_sleep_threshold;
+                            activation.angular_threshold = angular_sleep_threshold;
                         } else {
                             activation.sleeping = false;
                             activation.linear_threshold = -1.0;
@@ -1377,9 +2121,15 @@ impl PhysicsWorld {
                 builder = builder.lock_translations();
             }
 
-            rigid_body_node
-                .native
-                .set(self.add_body(handle, builder.build()));
+            let mut body = builder.build();
+            if rigid_body_node.is_can_sleep() {
+                let (linear_threshold, angular_threshold) = self.sleep_thresholds();
+                let activation = body.activation_mut();
+                activation.linear_threshold = linear_threshold;
+                activation.angular_threshold = angular_threshold;
+            }
+
+            rigid_body_node.native.set(self.add_body(handle, body));
 
             Log::writeln(
                 MessageKind::Information,
@@ -1442,17 +2192,35 @@ impl PhysicsWorld {
                     collider_node
                         .friction
                         .try_sync_model(|v| native.set_friction(v));
-                    collider_node
-                        .is_sensor
-                        .try_sync_model(|v| native.set_sensor(v));
+                    collider_node.is_sensor.try_sync_model(|v| {
+                        native.set_sensor(v);
+                        native.set_active_events(active_events(v));
+                        native.set_active_collision_types(active_collision_types(v));
+                    });
                     collider_node
                         .friction_combine_rule
                         .try_sync_model(|v| native.set_friction_combine_rule(v.into()));
                     collider_node
                         .restitution_combine_rule
                         .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
+                    collider_node.material.try_sync_model(|_| {
+                        // Restore own properties of the collider, they will be overwritten by
+                        // the material below if it is set.
+                        native.set_friction(collider_node.friction());
+                        native.set_restitution(collider_node.restitution());
+                        native.set_friction_combine_rule(
+                            collider_node.friction_combine_rule().into(),
+                        );
+                        native.set_restitution_combine_rule(
+                            collider_node.restitution_combine_rule().into(),
+                        );
+                    });
                 }
             }
+
+            if let Some(material) = collider_node.material.as_ref().and_then(|m| m.material()) {
+                self.apply_physics_material(collider_node.native.get(), &material);
+            }
         } else if let Some(parent_body) = nodes
             .try_borrow(collider_node.parent())
             .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
@@ -1487,7 +2255,9 @@ impl PhysicsWorld {
                             collider_node.solver_groups().memberships,
                             collider_node.solver_groups().filter,
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(active_events(collider_node.is_sensor()))
+                        .active_collision_types(active_collision_types(collider_node.is_sensor()));
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1498,6 +2268,12 @@ impl PhysicsWorld {
 
                     collider_node.native.set(native_handle);
 
+                    if let Some(material) =
+                        collider_node.material.as_ref().and_then(|m| m.material())
+                    {
+                        self.apply_physics_material(native_handle, &material);
+                    }
+
                     Log::writeln(
                         MessageKind::Information,
                         format!(
@@ -1510,6 +2286,31 @@ impl PhysicsWorld {
         }
     }
 
+    // Overrides surface properties of a native collider with the properties of a physics material.
+    fn apply_physics_material(&mut self, handle: ColliderHandle, material: &PhysicsMaterial) {
+        let friction_combine_rule: rapier3d::dynamics::CoefficientCombineRule =
+            material.friction_combine_rule.into();
+        let restitution_combine_rule: rapier3d::dynamics::CoefficientCombineRule =
+            material.restitution_combine_rule.into();
+
+        // `get_mut` is very expensive, so check if anything has changed first.
+        let needs_update = self.colliders.set.get(handle).map_or(false, |native| {
+            native.friction() != material.friction
+                || native.restitution() != material.restitution
+                || native.friction_combine_rule() != friction_combine_rule
+                || native.restitution_combine_rule() != restitution_combine_rule
+        });
+
+        if needs_update {
+            if let Some(native) = self.colliders.set.get_mut(handle) {
+                native.set_friction(material.friction);
+                native.set_restitution(material.restitution);
+                native.set_friction_combine_rule(friction_combine_rule);
+                native.set_restitution_combine_rule(restitution_combine_rule);
+            }
+        }
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
@@ -1635,3 +2436,120 @@ impl Debug for PhysicsWorld {
         write!(f, "PhysicsWorld")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::pool::Handle,
+        scene::graph::physics::{
+            active_collision_types, active_events, PhysicsWorld, TriggerContact, TriggerEvent,
+            TriggerEventQueue,
+        },
+    };
+    use rapier3d::{
+        dynamics::{RigidBodyBuilder, RigidBodyType},
+        geometry::ColliderBuilder,
+    };
+
+    fn contact(trigger: u32, collider: u32) -> TriggerContact {
+        TriggerContact {
+            trigger: Handle::new(trigger, 1),
+            collider: Handle::new(collider, 1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_trigger_event_queue() {
+        let mut queue = TriggerEventQueue::default();
+
+        queue.enter(contact(1, 2));
+        // Repeated enter must be ignored.
+        queue.enter(contact(1, 2));
+        queue.exit(contact(1, 2).trigger, contact(1, 2).collider);
+        // Exit without enter must be ignored.
+        queue.exit(contact(1, 3).trigger, contact(1, 3).collider);
+
+        assert_eq!(queue.pop(), Some(TriggerEvent::Entered(contact(1, 2))));
+        assert_eq!(queue.pop(), Some(TriggerEvent::Exited(contact(1, 2))));
+        assert_eq!(queue.pop(), None);
+
+        queue.enter(contact(1, 2));
+        queue.enter(contact(1, 3));
+        queue.clear_events();
+        queue.remove_collider(contact(1, 3).collider);
+        // Events of removed colliders are delivered with the events of the next step.
+        assert_eq!(queue.pop(), None);
+
+        queue.clear_events();
+        assert_eq!(
+            queue.events().collect::<Vec<_>>(),
+            vec![&TriggerEvent::Exited(contact(1, 3))]
+        );
+    }
+
+    #[test]
+    fn test_trigger_event_queue_restore() {
+        let mut queue = TriggerEventQueue {
+            restored: vec![contact(1, 2), contact(1, 3)],
+            ..Default::default()
+        };
+
+        // Pair (1, 2) is still intersecting after load, (1, 3) is not.
+        queue.enter(contact(1, 2));
+        queue.flush_restored();
+
+        assert_eq!(
+            queue.events().collect::<Vec<_>>(),
+            vec![&TriggerEvent::Exited(contact(1, 3))]
+        );
+        assert_eq!(queue.active, vec![contact(1, 2)]);
+    }
+
+    #[test]
+    fn test_trigger_exit_on_collider_removal() {
+        let mut world = PhysicsWorld::new();
+
+        let trigger_body = Handle::new(1, 1);
+        let trigger = Handle::new(2, 1);
+        let body = Handle::new(3, 1);
+        let collider = Handle::new(4, 1);
+
+        let native_trigger_body = world.add_body(
+            trigger_body,
+            RigidBodyBuilder::new(RigidBodyType::Fixed).build(),
+        );
+        world.add_collider(
+            trigger,
+            native_trigger_body,
+            ColliderBuilder::ball(1.0)
+                .sensor(true)
+                .active_events(active_events(true))
+                .active_collision_types(active_collision_types(true))
+                .build(),
+        );
+        let native_body = world.add_body(body, RigidBodyBuilder::new(RigidBodyType::Fixed).build());
+        let native_collider =
+            world.add_collider(collider, native_body, ColliderBuilder::ball(1.0).build());
+
+        let contact = |e: &TriggerEvent| (e.contact().trigger, e.contact().collider);
+
+        world.update();
+        let events = world.trigger_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], TriggerEvent::Entered(_)));
+        assert_eq!(contact(events[0]), (trigger, collider));
+
+        // Scripts and plugins remove nodes between updates of the world.
+        assert!(world.remove_collider(native_collider));
+
+        world.update();
+        let events = world.trigger_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], TriggerEvent::Exited(_)));
+        assert_eq!(contact(events[0]), (trigger, collider));
+
+        world.update();
+        assert_eq!(world.trigger_events().count(), 0);
+    }
+}
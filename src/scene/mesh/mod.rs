@@ -86,6 +86,15 @@ impl RenderPath {
     }
 }
 
+/// Weight of blend shapes with the same name in the surfaces of a mesh.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct BlendShapeWeight {
+    /// Name of blend shapes.
+    pub name: String,
+    /// Weight of blend shapes, usually in `[0; 1]` range.
+    pub weight: f32,
+}
+
 /// See module docs.
 #[derive(Debug, Inspect, Clone, Visit)]
 pub struct Mesh {
@@ -112,6 +121,14 @@ pub struct Mesh {
     #[inspect(skip)]
     #[visit(skip)]
     world_bounding_box: Cell<AxisAlignedBoundingBox>,
+
+    #[inspect(skip)]
+    #[visit(optional)] // Backward compatibility
+    blend_shape_weights: Vec<BlendShapeWeight>,
+
+    #[inspect(skip)]
+    #[visit(skip)]
+    blend_shapes_dirty: bool,
 }
 
 impl_directly_inheritable_entity_trait!(Mesh;
@@ -133,6 +150,8 @@ impl Default for Mesh {
             local_bounding_box_dirty: Cell::new(true),
             render_path: TemplateVariable::new(RenderPath::Deferred),
             decal_layer_index: TemplateVariable::new(0),
+            blend_shape_weights: Default::default(),
+            blend_shapes_dirty: false,
         }
     }
 }
@@ -184,6 +203,82 @@ impl Mesh {
         self.local_bounding_box_dirty.set(true);
     }
 
+    /// Sets weight of blend shapes with given name in all surfaces of the mesh. Blend shapes are
+    /// applied on CPU on next update of the mesh.
+    pub fn set_blend_shape_weight(&mut self, name: &str, weight: f32) {
+        if let Some(existing) = self
+            .blend_shape_weights
+            .iter_mut()
+            .find(|existing| existing.name == name)
+        {
+            if existing.weight == weight {
+                return;
+            }
+            existing.weight = weight;
+        } else {
+            self.blend_shape_weights.push(BlendShapeWeight {
+                name: name.to_owned(),
+                weight,
+            });
+        }
+        self.blend_shapes_dirty = true;
+    }
+
+    /// Returns weight of blend shapes with given name, blend shapes without explicitly set
+    /// weight have zero weight.
+    pub fn blend_shape_weight(&self, name: &str) -> f32 {
+        self.blend_shape_weights
+            .iter()
+            .find(|existing| existing.name == name)
+            .map_or(0.0, |existing| existing.weight)
+    }
+
+    /// Returns all explicitly set weights of blend shapes.
+    pub fn blend_shape_weights(&self) -> &[BlendShapeWeight] {
+        &self.blend_shape_weights
+    }
+
+    /// Returns names of all blend shapes of every surface of the mesh.
+    pub fn blend_shape_names(&self) -> Vec<String> {
+        let mut names = Vec::<String>::new();
+        for surface in self.surfaces.iter() {
+            let data = surface.original_data();
+            for blend_shape in data.lock().blend_shapes.iter() {
+                if !names.contains(&blend_shape.name) {
+                    names.push(blend_shape.name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    fn update_blend_shapes(&mut self) {
+        if self.blend_shape_weights.is_empty() {
+            return;
+        }
+
+        let weights = &self.blend_shape_weights;
+        let weight_of = |name: &str| {
+            weights
+                .iter()
+                .find(|existing| existing.name == name)
+                .map_or(0.0, |existing| existing.weight)
+        };
+
+        let mut changed = false;
+        for surface in self.surfaces.get_mut_silent() {
+            // Surfaces that are not deformed yet could be just instantiated or cloned.
+            if self.blend_shapes_dirty || !surface.is_deformed() {
+                changed |= surface.apply_blend_shapes(weight_of);
+            }
+        }
+
+        if changed {
+            self.local_bounding_box_dirty.set(true);
+        }
+        self.blend_shapes_dirty = false;
+    }
+
     /// Sets new render path for the mesh.
     pub fn set_render_path(&mut self, render_path: RenderPath) {
         self.render_path.set(render_path);
@@ -316,6 +411,8 @@ impl NodeTrait for Mesh {
     }
 
     fn update(&mut self, context: &mut UpdateContext) -> bool {
+        self.update_blend_shapes();
+
         if self.local_bounding_box_dirty.get() {
             let mut bounding_box = AxisAlignedBoundingBox::default();
             for surface in self.surfaces.iter() {
@@ -402,6 +499,8 @@ impl MeshBuilder {
             render_path: self.render_path.into(),
             decal_layer_index: self.decal_layer_index.into(),
             world_bounding_box: Default::default(),
+            blend_shape_weights: Default::default(),
+            blend_shapes_dirty: false,
         })
     }
 
@@ -29,9 +29,33 @@ use crate::{
     },
     utils::raw_mesh::{RawMesh, RawMeshBuilder},
 };
-use fxhash::FxHasher;
+use fxhash::{FxHashSet, FxHasher};
 use std::{hash::Hasher, sync::Arc};
 
+/// Offset of a single vertex of a blend shape.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Visit)]
+pub struct BlendShapeDelta {
+    /// Index of affected vertex in the vertex buffer of a surface.
+    pub vertex: u32,
+    /// Offset of the position of the vertex when the blend shape has full weight.
+    pub position: Vector3<f32>,
+    /// Offset of the normal of the vertex when the blend shape has full weight.
+    pub normal: Vector3<f32>,
+}
+
+/// Blend shape (morph target) is a named set of vertex offsets, that is mixed with original
+/// geometry of a surface using a weight in `[0; 1]` range. Blend shapes are used for facial
+/// animation, corrective shapes and so on. Weights of blend shapes are set per mesh, see
+/// [`crate::scene::mesh::Mesh::set_blend_shape_weight`].
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct BlendShape {
+    /// Name of the blend shape, blend shapes with the same name in different surfaces of a mesh
+    /// share the same weight.
+    pub name: String,
+    /// Offsets of affected vertices, other vertices are not affected by the blend shape.
+    pub deltas: Vec<BlendShapeDelta>,
+}
+
 /// Data source of a surface. Each surface can share same data source, this is used
 /// in instancing technique to render multiple instances of same model at different
 /// places.
@@ -41,6 +65,8 @@ pub struct SurfaceData {
     pub vertex_buffer: VertexBuffer,
     /// Current geometry buffer.
     pub geometry_buffer: TriangleBuffer,
+    /// Blend shapes of the surface.
+    pub blend_shapes: Vec<BlendShape>,
     // If true - indicates that surface was generated and does not have reference
     // resource. Procedural data will be serialized.
     is_procedural: bool,
@@ -57,11 +83,69 @@ impl SurfaceData {
         Self {
             vertex_buffer,
             geometry_buffer: triangles,
+            blend_shapes: Default::default(),
             is_procedural,
             cache_entry: AtomicIndex::unassigned(),
         }
     }
 
+    /// Copies vertices of the `source` into this data and applies blend shapes of the `source`
+    /// on top of them. Weight of each blend shape is provided by `weight_of` callback by the name
+    /// of the blend shape. Vertex layout and vertex count of the source must match with the layout
+    /// and count of this data.
+    pub fn apply_blend_shapes<F>(
+        &mut self,
+        source: &SurfaceData,
+        mut weight_of: F,
+    ) -> Result<(), VertexFetchError>
+    where
+        F: FnMut(&str) -> f32,
+    {
+        self.vertex_buffer = source.vertex_buffer.clone();
+
+        let mut vertex_buffer = self.vertex_buffer.modify();
+        let has_normals = vertex_buffer.has_attribute(VertexAttributeUsage::Normal);
+        let mut affected_normals = FxHashSet::default();
+
+        for blend_shape in source.blend_shapes.iter() {
+            let weight = weight_of(&blend_shape.name);
+            if weight == 0.0 {
+                continue;
+            }
+
+            for delta in blend_shape.deltas.iter() {
+                if let Some(mut view) = vertex_buffer.get_mut(delta.vertex as usize) {
+                    let position = view.read_3_f32(VertexAttributeUsage::Position)?;
+                    view.write_3_f32(
+                        VertexAttributeUsage::Position,
+                        position + delta.position.scale(weight),
+                    )?;
+
+                    if has_normals {
+                        let normal = view.read_3_f32(VertexAttributeUsage::Normal)?;
+                        view.write_3_f32(
+                            VertexAttributeUsage::Normal,
+                            normal + delta.normal.scale(weight),
+                        )?;
+                        affected_normals.insert(delta.vertex);
+                    }
+                }
+            }
+        }
+
+        for vertex in affected_normals {
+            if let Some(mut view) = vertex_buffer.get_mut(vertex as usize) {
+                let normal = view.read_3_f32(VertexAttributeUsage::Normal)?;
+                view.write_3_f32(
+                    VertexAttributeUsage::Normal,
+                    normal.try_normalize(f32::EPSILON).unwrap_or(normal),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Applies given transform for every spatial part of the data (vertex position, normal, tangent).
     pub fn transform_geometry(&mut self, transform: &Matrix4<f32>) -> Result<(), VertexFetchError> {
         // Discard scale by inverse and transpose given transform (M^-1)^T
@@ -103,6 +187,7 @@ impl SurfaceData {
             geometry_buffer: TriangleBuffer::new(raw.triangles),
             is_procedural,
             cache_entry: AtomicIndex::unassigned(),
+            blend_shapes: Default::default(),
         }
     }
 
@@ -853,7 +938,9 @@ impl Visit for SurfaceData {
 
         if self.is_procedural {
             self.vertex_buffer.visit("VertexBuffer", &mut region)?;
-            self.geometry_buffer.visit("GeometryBuffer", &mut region)?
+            self.geometry_buffer.visit("GeometryBuffer", &mut region)?;
+            // Backward compatibility.
+            let _ = self.blend_shapes.visit("BlendShapes", &mut region);
         }
 
         Ok(())
@@ -939,7 +1026,7 @@ impl VertexWeightSet {
 }
 
 /// See module docs.
-#[derive(Debug, Clone, Inspect, Visit)]
+#[derive(Debug, Inspect, Visit)]
 pub struct Surface {
     // Wrapped into option to be able to implement Default for serialization.
     // In normal conditions it must never be None!
@@ -956,6 +1043,28 @@ pub struct Surface {
     pub vertex_weights: Vec<VertexWeightSet>,
     /// Array of handle to scene nodes which are used as bones.
     pub bones: Vec<Handle<Node>>,
+    // Original data of the surface, if the surface is deformed by blend shapes. In this case
+    // `data` holds a unique copy of the original data with blend shapes applied.
+    #[inspect(skip)]
+    #[visit(optional)] // Backward compatibility
+    blend_shape_source: Option<Arc<Mutex<SurfaceData>>>,
+}
+
+impl Clone for Surface {
+    fn clone(&self) -> Self {
+        Self {
+            // Deformed data is unique per surface, so the copy starts from the original data and
+            // will be deformed again by its mesh.
+            data: self
+                .blend_shape_source
+                .clone()
+                .or_else(|| self.data.clone()),
+            material: self.material.clone(),
+            vertex_weights: self.vertex_weights.clone(),
+            bones: self.bones.clone(),
+            blend_shape_source: None,
+        }
+    }
 }
 
 impl PartialEq for Surface {
@@ -982,6 +1091,7 @@ impl Default for Surface {
             material: Arc::new(Mutex::new(Material::standard())),
             vertex_weights: Default::default(),
             bones: Default::default(),
+            blend_shape_source: None,
         }
     }
 }
@@ -1015,6 +1125,46 @@ impl Surface {
         self.data.as_ref().unwrap().clone()
     }
 
+    /// Returns original data of the surface, that is not affected by blend shapes.
+    pub fn original_data(&self) -> Arc<Mutex<SurfaceData>> {
+        self.blend_shape_source
+            .clone()
+            .unwrap_or_else(|| self.data())
+    }
+
+    /// Returns true if the surface uses its own copy of the data, deformed by blend shapes.
+    pub fn is_deformed(&self) -> bool {
+        self.blend_shape_source.is_some()
+    }
+
+    /// Applies blend shapes of the original data using given weights. On first call the surface
+    /// gets its own copy of the data, so other surfaces that share the same data will not be
+    /// affected. Returns `true` if the geometry of the surface was changed.
+    pub fn apply_blend_shapes<F>(&mut self, weight_of: F) -> bool
+    where
+        F: FnMut(&str) -> f32,
+    {
+        let source = self.original_data();
+        let source = source.lock();
+
+        if source.blend_shapes.is_empty() {
+            return false;
+        }
+
+        if self.blend_shape_source.is_none() {
+            let copy = SurfaceData::new(
+                source.vertex_buffer.clone(),
+                source.geometry_buffer.clone(),
+                source.is_procedural,
+            );
+            self.blend_shape_source = self.data.replace(Arc::new(Mutex::new(copy)));
+        }
+
+        let result = self.data().lock().apply_blend_shapes(&source, weight_of);
+
+        result.is_ok()
+    }
+
     /// Returns current material of the surface.
     pub fn material(&self) -> &Arc<Mutex<Material>> {
         &self.material
@@ -1070,6 +1220,63 @@ impl SurfaceBuilder {
                 .unwrap_or_else(|| Arc::new(Mutex::new(Material::standard()))),
             vertex_weights: Default::default(),
             bones: self.bones,
+            blend_shape_source: None,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, parking_lot::Mutex},
+        scene::mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            surface::{BlendShape, BlendShapeDelta, Surface, SurfaceData},
+        },
+    };
+    use std::sync::Arc;
+
+    fn position(surface: &Surface, vertex: usize) -> Vector3<f32> {
+        surface
+            .data()
+            .lock()
+            .vertex_buffer
+            .get(vertex)
+            .unwrap()
+            .read_3_f32(VertexAttributeUsage::Position)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_apply_blend_shapes() {
+        let mut data = SurfaceData::make_unit_xy_quad();
+        data.blend_shapes.push(BlendShape {
+            name: "Smile".to_owned(),
+            deltas: vec![BlendShapeDelta {
+                vertex: 0,
+                position: Vector3::new(0.0, 0.0, 1.0),
+                normal: Default::default(),
+            }],
+        });
+        let data = Arc::new(Mutex::new(data));
+
+        let mut surface = Surface::new(data.clone());
+        let original = position(&surface, 0);
+
+        assert!(surface.apply_blend_shapes(|name| if name == "Smile" { 0.5 } else { 0.0 }));
+        assert!(surface.is_deformed());
+        assert_eq!(
+            position(&surface, 0),
+            original + Vector3::new(0.0, 0.0, 0.5)
+        );
+
+        // Shared data must stay intact.
+        assert!(Arc::ptr_eq(&surface.original_data(), &data));
+        assert_eq!(position(&Surface::new(data), 0), original);
+
+        // A copy starts from the original data.
+        let copy = surface.clone();
+        assert!(!copy.is_deformed());
+        assert_eq!(position(&copy, 0), original);
+    }
+}
@@ -422,13 +422,18 @@ impl SceneLoader {
         join_all(skybox_textures).await;
 
         let mut animation_resources = Vec::new();
+        let mut skeleton_mappings = Vec::new();
         for animation in scene.animations.iter_mut() {
             animation.restore_resources(resource_manager.clone());
             if let Some(resource) = animation.resource.as_ref() {
                 animation_resources.push(resource.clone());
             }
+            if let Some(skeleton_mapping) = animation.skeleton_mapping.as_ref() {
+                skeleton_mappings.push(skeleton_mapping.clone());
+            }
         }
         join_all(animation_resources).await;
+        join_all(skeleton_mappings).await;
 
         let mut animation_machines = Vec::new();
         for machine in scene.animation_machines.iter_mut() {
@@ -644,9 +649,9 @@ impl Scene {
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
 
         for machine in self.animation_machines.iter_mut() {
-            machine
-                .evaluate_pose(&self.animations, dt)
-                .apply(&mut self.graph);
+            machine.evaluate_pose(&self.animations, dt);
+            machine.sync_animations(&mut self.animations);
+            machine.solve_ik(&self.graph).apply(&mut self.graph);
         }
     }
 
@@ -665,6 +670,22 @@ impl Scene {
             for track in animation.get_tracks_mut() {
                 track.set_node(old_new_map.map[&track.get_node()]);
             }
+            animation
+                .retain_blend_shape_tracks(|track| old_new_map.map.contains_key(&track.node()));
+            for track in animation.blend_shape_tracks_mut() {
+                track.set_node(old_new_map.map[&track.node()]);
+            }
+            animation.retain_curve_tracks(|track| old_new_map.map.contains_key(&track.node()));
+            for track in animation.curve_tracks_mut() {
+                track.set_node(old_new_map.map[&track.node()]);
+            }
+            if let Some(settings) = animation.root_motion_settings_mut() {
+                settings.node = old_new_map
+                    .map
+                    .get(&settings.node)
+                    .cloned()
+                    .unwrap_or_default();
+            }
         }
 
         let mut animation_machines = self.animation_machines.clone();
@@ -674,6 +695,14 @@ impl Scene {
                 .get(&machine.root)
                 .cloned()
                 .unwrap_or_default();
+            for layer in machine.layers.iter_mut() {
+                layer
+                    .mask
+                    .remap(|bone| old_new_map.map.get(&bone).cloned().unwrap_or_default());
+            }
+            for solver in machine.ik_solvers.iter_mut() {
+                solver.remap(|node| old_new_map.map.get(&node).cloned().unwrap_or_default());
+            }
         }
 
         (
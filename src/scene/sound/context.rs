@@ -2,20 +2,35 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         inspect::{Inspect, PropertyInfo},
         pool::{Handle, Pool, Ticket},
         visitor::prelude::*,
     },
+    fxhash::FxHashMap,
     resource::model::Model,
     scene::{
         graph::{map::NodeHandleMap, NodePool},
-        sound::{self, effect::Effect, Sound},
+        node::Node,
+        sound::{self, effect::Effect, occlusion::OcclusionSettings, Sound},
     },
     utils::log::{Log, MessageKind},
 };
 use fyrox_sound::{
-    context::DistanceModel,
-    effects::{reverb::Reverb, BaseEffect, EffectInput, InputFilter},
+    bus::{AudioBus, AudioBusGraph, Ducking},
+    context::{
+        DistanceModel, VoiceStatistics, DEFAULT_MAX_ACTIVE_VOICES, DEFAULT_SPEED_OF_SOUND,
+        DEFAULT_VIRTUALIZATION_THRESHOLD,
+    },
+    effects::{
+        chorus::Chorus,
+        compressor::{Compressor, Limiter},
+        delay::Delay,
+        distortion::Distortion,
+        equalizer::Equalizer,
+        reverb::Reverb,
+        BaseEffect, Effect as NativeEffect, EffectInput, InputFilter,
+    },
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
@@ -28,14 +43,38 @@ pub struct SoundContext {
     renderer: Renderer,
     distance_model: DistanceModel,
     paused: bool,
+    #[visit(optional)] // Backward compatibility
+    #[inspect(min_value = 0.0, step = 0.05)]
+    doppler_factor: f32,
+    #[visit(optional)] // Backward compatibility
+    #[inspect(min_value = 0.0, step = 1.0)]
+    speed_of_sound: f32,
+    #[visit(optional)] // Backward compatibility
+    occlusion: OcclusionSettings,
+    #[visit(optional)] // Backward compatibility
+    max_active_voices: u32,
+    #[visit(optional)] // Backward compatibility
+    #[inspect(min_value = 0.0, step = 0.001)]
+    virtualization_threshold: f32,
     #[inspect(skip)]
     pub(crate) effects: Pool<Effect>,
     #[inspect(read_only)]
     // A model resource from which this context was instantiated from.
     pub(crate) resource: Option<Model>,
+    #[visit(optional)] // Backward compatibility
+    #[inspect(skip)]
+    bus_graph: AudioBusGraph,
+    // Whether the native context has received the bus graph or not.
+    #[visit(skip)]
+    #[inspect(skip)]
+    bus_graph_synced: bool,
     #[visit(skip)]
     #[inspect(skip)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+    // Sounds that were spawned by sound cues, they're grouped by keys of cue resources.
+    #[visit(skip)]
+    #[inspect(skip)]
+    pub(crate) cue_instances: FxHashMap<usize, Vec<Handle<Node>>>,
 }
 
 impl Default for SoundContext {
@@ -45,9 +84,17 @@ impl Default for SoundContext {
             renderer: Default::default(),
             distance_model: Default::default(),
             paused: false,
+            doppler_factor: 1.0,
+            speed_of_sound: DEFAULT_SPEED_OF_SOUND,
+            occlusion: Default::default(),
+            max_active_voices: DEFAULT_MAX_ACTIVE_VOICES,
+            virtualization_threshold: DEFAULT_VIRTUALIZATION_THRESHOLD,
             effects: Default::default(),
             resource: None,
+            bus_graph: Default::default(),
+            bus_graph_synced: false,
             native: fyrox_sound::context::SoundContext::new(),
+            cue_instances: Default::default(),
         }
     }
 }
@@ -134,6 +181,72 @@ impl SoundContext {
         self.distance_model
     }
 
+    /// Sets new doppler factor. It exaggerates (values larger than 1.0) or attenuates (values less
+    /// than 1.0) pitch shift caused by the Doppler effect. Zero disables the effect completely.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.doppler_factor = doppler_factor.max(0.0);
+    }
+
+    /// Returns current doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.doppler_factor
+    }
+
+    /// Sets speed of sound in units per second. Default value is 343.3, which is suitable if one
+    /// unit of your game world is one meter.
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.speed_of_sound = speed_of_sound.max(f32::EPSILON);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.speed_of_sound
+    }
+
+    /// Sets new settings of sound occlusion. See [`sound::occlusion`] module docs for more info.
+    pub fn set_occlusion_settings(&mut self, settings: OcclusionSettings) {
+        self.occlusion = settings;
+    }
+
+    /// Returns current settings of sound occlusion.
+    pub fn occlusion_settings(&self) -> &OcclusionSettings {
+        &self.occlusion
+    }
+
+    /// Returns a copy of current settings of sound occlusion.
+    pub fn occlusion_settings_owned(&self) -> OcclusionSettings {
+        self.occlusion.clone()
+    }
+
+    /// Sets maximum amount of sounds that can be mixed at the same time. When there are more
+    /// playing sounds, the least important ones (see [`Sound::set_priority`]) become virtual:
+    /// they're not mixed, but their playback time is still advancing.
+    pub fn set_max_active_voices(&mut self, max_active_voices: u32) {
+        self.max_active_voices = max_active_voices;
+    }
+
+    /// Returns maximum amount of sounds that can be mixed at the same time.
+    pub fn max_active_voices(&self) -> u32 {
+        self.max_active_voices
+    }
+
+    /// Sets audibility below which sounds become virtual. Audibility is an estimation of loudness
+    /// of a sound which takes into account its gain, distance attenuation and occlusion. Zero
+    /// threshold disables virtualization of inaudible sounds.
+    pub fn set_virtualization_threshold(&mut self, threshold: f32) {
+        self.virtualization_threshold = threshold.max(0.0);
+    }
+
+    /// Returns audibility below which sounds become virtual.
+    pub fn virtualization_threshold(&self) -> f32 {
+        self.virtualization_threshold
+    }
+
+    /// Returns position of the listener in world space.
+    pub fn listener_position(&self) -> Vector3<f32> {
+        self.native.state().listener().position()
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -145,6 +258,12 @@ impl SoundContext {
         self.native.state().full_render_duration()
     }
 
+    /// Returns statistics of voice management (amount of active and virtual sounds and mixing
+    /// time) for the last rendered frame.
+    pub fn voice_statistics(&self) -> VoiceStatistics {
+        self.native.state().voice_statistics()
+    }
+
     /// Returns current renderer.
     pub fn renderer(&self) -> Renderer {
         self.renderer.clone()
@@ -167,9 +286,98 @@ impl SoundContext {
         self.master_gain
     }
 
+    /// Returns shared reference to the graph of mixer buses.
+    pub fn bus_graph(&self) -> &AudioBusGraph {
+        &self.bus_graph
+    }
+
+    /// Sets new graph of mixer buses and returns the old one.
+    pub fn set_bus_graph(&mut self, bus_graph: AudioBusGraph) -> AudioBusGraph {
+        let old = std::mem::replace(&mut self.bus_graph, bus_graph);
+        self.bus_graph_synced = false;
+        old
+    }
+
+    /// Adds new mixer bus and returns its handle.
+    pub fn add_bus(&mut self, bus: AudioBus) -> Handle<AudioBus> {
+        self.bus_graph_synced = false;
+        self.bus_graph.add_bus(bus)
+    }
+
+    /// Removes a mixer bus, the master bus cannot be removed.
+    pub fn remove_bus(&mut self, bus: Handle<AudioBus>) -> Option<AudioBus> {
+        self.bus_graph_synced = false;
+        self.bus_graph.remove_bus(bus)
+    }
+
+    /// Borrows a mixer bus.
+    pub fn try_get_bus(&self, bus: Handle<AudioBus>) -> Option<&AudioBus> {
+        self.bus_graph.try_get(bus)
+    }
+
+    // Buses are modified in place in the native context, so their runtime state (effect tails,
+    // ducking envelope, etc.) is preserved.
+    fn modify_bus<F: Fn(&mut AudioBus)>(&mut self, bus: Handle<AudioBus>, func: F) {
+        if let Some(bus_ref) = self.bus_graph.try_get_mut(bus) {
+            func(bus_ref);
+        }
+        if let Some(bus_ref) = self.native.state().bus_graph_mut().try_get_mut(bus) {
+            func(bus_ref);
+        }
+    }
+
+    /// Sets new name of a mixer bus.
+    pub fn set_bus_name(&mut self, bus: Handle<AudioBus>, name: String) {
+        self.modify_bus(bus, |b| b.set_name(&name));
+    }
+
+    /// Sets new parent of a mixer bus.
+    pub fn set_bus_parent(&mut self, bus: Handle<AudioBus>, parent: Handle<AudioBus>) {
+        self.modify_bus(bus, |b| b.set_parent(parent));
+    }
+
+    /// Sets new gain of a mixer bus.
+    pub fn set_bus_gain(&mut self, bus: Handle<AudioBus>, gain: f32) {
+        self.modify_bus(bus, |b| b.set_gain(gain));
+    }
+
+    /// Mutes or unmutes a mixer bus.
+    pub fn set_bus_muted(&mut self, bus: Handle<AudioBus>, muted: bool) {
+        self.modify_bus(bus, |b| b.set_muted(muted));
+    }
+
+    /// Enables or disables solo mode of a mixer bus.
+    pub fn set_bus_solo(&mut self, bus: Handle<AudioBus>, solo: bool) {
+        self.modify_bus(bus, |b| b.set_solo(solo));
+    }
+
+    /// Sets ducking settings of a mixer bus.
+    pub fn set_bus_ducking(&mut self, bus: Handle<AudioBus>, ducking: Option<Ducking>) {
+        self.modify_bus(bus, |b| b.set_ducking(ducking.clone()));
+    }
+
+    /// Sets effects chain of a mixer bus.
+    pub fn set_bus_effects(
+        &mut self,
+        bus: Handle<AudioBus>,
+        effects: Vec<fyrox_sound::effects::Effect>,
+    ) {
+        self.modify_bus(bus, |b| *b.effects_mut() = effects.clone());
+    }
+
     pub(crate) fn update(&mut self, nodes: &NodePool) {
         let mut state = self.native.state();
 
+        if !self.bus_graph_synced {
+            state.set_bus_graph(self.bus_graph.clone());
+            self.bus_graph_synced = true;
+        }
+
+        state.set_doppler_factor(self.doppler_factor);
+        state.set_speed_of_sound(self.speed_of_sound);
+        state.set_max_active_voices(self.max_active_voices);
+        state.set_virtualization_threshold(self.virtualization_threshold);
+
         fn sync_effect_inputs(
             native_effect: &mut fyrox_sound::effects::BaseEffect,
             inputs: &[sound::effect::EffectInput],
@@ -195,41 +403,176 @@ impl SoundContext {
             }
         }
 
+        fn sync_base_effect(
+            native_effect: &mut fyrox_sound::effects::BaseEffect,
+            effect: &sound::effect::BaseEffect,
+            nodes: &NodePool,
+        ) {
+            effect.gain.try_sync_model(|v| native_effect.set_gain(v));
+            effect.inputs.try_sync_model(|v| {
+                native_effect.clear_inputs();
+                sync_effect_inputs(native_effect, &v, nodes)
+            });
+        }
+
+        fn make_native_effect(effect: &Effect, nodes: &NodePool) -> NativeEffect {
+            let mut native_effect = match effect {
+                Effect::Reverb(reverb) => {
+                    let mut native_reverb = Reverb::new(BaseEffect::default());
+                    native_reverb.set_fc(reverb.fc());
+                    native_reverb.set_decay_time(Duration::from_secs_f32(reverb.decay_time()));
+                    native_reverb.set_dry(reverb.dry());
+                    native_reverb.set_wet(reverb.wet());
+                    NativeEffect::Reverb(native_reverb)
+                }
+                Effect::Equalizer(equalizer) => NativeEffect::Equalizer(
+                    Equalizer::new(BaseEffect::default()).with_bands(equalizer.bands_owned()),
+                ),
+                Effect::Compressor(compressor) => {
+                    let mut native_compressor = Compressor::new(BaseEffect::default());
+                    native_compressor.set_threshold(compressor.threshold());
+                    native_compressor.set_ratio(compressor.ratio());
+                    native_compressor.set_attack(compressor.attack());
+                    native_compressor.set_release(compressor.release());
+                    native_compressor.set_makeup_gain(compressor.makeup_gain());
+                    native_compressor.set_lookahead(compressor.lookahead());
+                    NativeEffect::Compressor(native_compressor)
+                }
+                Effect::Limiter(limiter) => {
+                    let mut native_limiter = Limiter::new(BaseEffect::default());
+                    native_limiter.set_threshold(limiter.threshold());
+                    native_limiter.set_release(limiter.release());
+                    native_limiter.set_lookahead(limiter.lookahead());
+                    NativeEffect::Limiter(native_limiter)
+                }
+                Effect::Delay(delay) => {
+                    let mut native_delay = Delay::new(BaseEffect::default());
+                    native_delay.set_time(delay.time());
+                    native_delay.set_feedback(delay.feedback());
+                    native_delay.set_dry(delay.dry());
+                    native_delay.set_wet(delay.wet());
+                    native_delay.set_tempo_sync(delay.tempo_sync());
+                    NativeEffect::Delay(native_delay)
+                }
+                Effect::Chorus(chorus) => {
+                    let mut native_chorus = Chorus::new(BaseEffect::default());
+                    native_chorus.set_rate(chorus.rate());
+                    native_chorus.set_depth(chorus.depth());
+                    native_chorus.set_delay(chorus.delay());
+                    native_chorus.set_feedback(chorus.feedback());
+                    native_chorus.set_dry(chorus.dry());
+                    native_chorus.set_wet(chorus.wet());
+                    NativeEffect::Chorus(native_chorus)
+                }
+                Effect::Distortion(distortion) => {
+                    let mut native_distortion = Distortion::new(BaseEffect::default());
+                    native_distortion.set_shape(distortion.shape());
+                    native_distortion.set_drive(distortion.drive());
+                    native_distortion.set_dry(distortion.dry());
+                    native_distortion.set_wet(distortion.wet());
+                    NativeEffect::Distortion(native_distortion)
+                }
+            };
+            native_effect.set_gain(effect.gain());
+            sync_effect_inputs(&mut native_effect, effect.inputs(), nodes);
+            native_effect
+        }
+
         for effect in self.effects.iter() {
             if effect.native.get().is_some() {
                 let native_effect = state.effect_mut(effect.native.get());
-                if let (
-                    fyrox_sound::effects::Effect::Reverb(native_reverb),
-                    Effect::Reverb(reverb),
-                ) = (native_effect, effect)
-                {
-                    reverb.decay_time.try_sync_model(|v| {
-                        native_reverb.set_decay_time(Duration::from_secs_f32(v))
-                    });
-                    reverb.gain.try_sync_model(|v| native_reverb.set_gain(v));
-                    reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
-                    reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
-                    reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
-                    reverb.inputs.try_sync_model(|v| {
-                        native_reverb.clear_inputs();
-                        sync_effect_inputs(native_reverb, &v, nodes)
-                    });
-                }
-            } else {
-                match effect {
-                    Effect::Reverb(reverb) => {
-                        let mut native_reverb = Reverb::new(BaseEffect::default());
-                        native_reverb.set_gain(reverb.gain());
-                        native_reverb.set_fc(reverb.fc());
-                        native_reverb.set_decay_time(Duration::from_secs_f32(reverb.decay_time()));
-                        native_reverb.set_dry(reverb.dry());
-                        native_reverb.set_wet(reverb.wet());
-                        sync_effect_inputs(&mut *native_reverb, &*reverb.inputs, nodes);
-                        let native =
-                            state.add_effect(fyrox_sound::effects::Effect::Reverb(native_reverb));
-                        reverb.native.set(native);
+                sync_base_effect(native_effect, effect, nodes);
+                match (native_effect, effect) {
+                    (NativeEffect::Reverb(native_reverb), Effect::Reverb(reverb)) => {
+                        reverb.decay_time.try_sync_model(|v| {
+                            native_reverb.set_decay_time(Duration::from_secs_f32(v))
+                        });
+                        reverb.wet.try_sync_model(|v| native_reverb.set_wet(v));
+                        reverb.dry.try_sync_model(|v| native_reverb.set_dry(v));
+                        reverb.fc.try_sync_model(|v| native_reverb.set_fc(v));
+                    }
+                    (NativeEffect::Equalizer(native_equalizer), Effect::Equalizer(equalizer)) => {
+                        equalizer
+                            .bands
+                            .try_sync_model(|v| native_equalizer.set_bands(v));
+                    }
+                    (
+                        NativeEffect::Compressor(native_compressor),
+                        Effect::Compressor(compressor),
+                    ) => {
+                        compressor
+                            .threshold
+                            .try_sync_model(|v| native_compressor.set_threshold(v));
+                        compressor
+                            .ratio
+                            .try_sync_model(|v| native_compressor.set_ratio(v));
+                        compressor
+                            .attack
+                            .try_sync_model(|v| native_compressor.set_attack(v));
+                        compressor
+                            .release
+                            .try_sync_model(|v| native_compressor.set_release(v));
+                        compressor
+                            .makeup_gain
+                            .try_sync_model(|v| native_compressor.set_makeup_gain(v));
+                        compressor
+                            .lookahead
+                            .try_sync_model(|v| native_compressor.set_lookahead(v));
+                    }
+                    (NativeEffect::Limiter(native_limiter), Effect::Limiter(limiter)) => {
+                        limiter
+                            .threshold
+                            .try_sync_model(|v| native_limiter.set_threshold(v));
+                        limiter
+                            .release
+                            .try_sync_model(|v| native_limiter.set_release(v));
+                        limiter
+                            .lookahead
+                            .try_sync_model(|v| native_limiter.set_lookahead(v));
+                    }
+                    (NativeEffect::Delay(native_delay), Effect::Delay(delay)) => {
+                        delay.time.try_sync_model(|v| native_delay.set_time(v));
+                        delay
+                            .feedback
+                            .try_sync_model(|v| native_delay.set_feedback(v));
+                        delay.dry.try_sync_model(|v| native_delay.set_dry(v));
+                        delay.wet.try_sync_model(|v| native_delay.set_wet(v));
+                        delay
+                            .tempo_sync
+                            .try_sync_model(|v| native_delay.set_tempo_sync(v));
+                    }
+                    (NativeEffect::Chorus(native_chorus), Effect::Chorus(chorus)) => {
+                        chorus.rate.try_sync_model(|v| native_chorus.set_rate(v));
+                        chorus.depth.try_sync_model(|v| native_chorus.set_depth(v));
+                        chorus.delay.try_sync_model(|v| native_chorus.set_delay(v));
+                        chorus
+                            .feedback
+                            .try_sync_model(|v| native_chorus.set_feedback(v));
+                        chorus.dry.try_sync_model(|v| native_chorus.set_dry(v));
+                        chorus.wet.try_sync_model(|v| native_chorus.set_wet(v));
                     }
+                    (
+                        NativeEffect::Distortion(native_distortion),
+                        Effect::Distortion(distortion),
+                    ) => {
+                        distortion
+                            .shape
+                            .try_sync_model(|v| native_distortion.set_shape(v));
+                        distortion
+                            .drive
+                            .try_sync_model(|v| native_distortion.set_drive(v));
+                        distortion
+                            .dry
+                            .try_sync_model(|v| native_distortion.set_dry(v));
+                        distortion
+                            .wet
+                            .try_sync_model(|v| native_distortion.set_wet(v));
+                    }
+                    _ => (),
                 }
+            } else {
+                let native = state.add_effect(make_native_effect(effect, nodes));
+                effect.native.set(native);
             }
         }
     }
@@ -244,6 +587,18 @@ impl SoundContext {
         }
     }
 
+    pub(crate) fn set_sound_velocity(&mut self, sound: &Sound, velocity: Vector3<f32>) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_velocity(velocity);
+        }
+    }
+
+    pub(crate) fn set_sound_occlusion(&mut self, sound: &Sound, gain: f32, cutoff_frequency: f32) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_occlusion(gain, cutoff_frequency);
+        }
+    }
+
     pub(crate) fn sync_with_sound(&self, sound: &mut Sound) {
         if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
             // Sync back.
@@ -284,6 +639,12 @@ impl SoundContext {
             sound
                 .spatial_blend
                 .try_sync_model(|v| source.set_spatial_blend(v));
+            sound.bus.try_sync_model(|v| {
+                source.set_bus(v);
+            });
+            sound.priority.try_sync_model(|v| {
+                source.set_priority(v);
+            });
             sound.buffer.try_sync_model(|v| {
                 Log::verify(source.set_buffer(v));
             });
@@ -311,6 +672,8 @@ impl SoundContext {
                 .with_radius(sound.radius())
                 .with_max_distance(sound.max_distance())
                 .with_rolloff_factor(sound.rolloff_factor())
+                .with_bus(sound.bus())
+                .with_priority(sound.priority())
                 .build()
             {
                 Ok(source) => {
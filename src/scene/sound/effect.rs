@@ -12,6 +12,11 @@ use crate::{
 };
 use fyrox_core::define_is_as;
 use fyrox_sound::dsp::filters::Biquad;
+pub use fyrox_sound::effects::{
+    delay::TempoSync,
+    distortion::DistortionShape,
+    equalizer::{EqualizerBand, EqualizerBandKind},
+};
 use std::{
     cell::Cell,
     ops::{Deref, DerefMut},
@@ -95,12 +100,30 @@ impl Default for BaseEffect {
 pub enum Effect {
     /// See [`ReverbEffect`] docs.
     Reverb(ReverbEffect),
+    /// See [`EqualizerEffect`] docs.
+    Equalizer(EqualizerEffect),
+    /// See [`CompressorEffect`] docs.
+    Compressor(CompressorEffect),
+    /// See [`LimiterEffect`] docs.
+    Limiter(LimiterEffect),
+    /// See [`DelayEffect`] docs.
+    Delay(DelayEffect),
+    /// See [`ChorusEffect`] docs.
+    Chorus(ChorusEffect),
+    /// See [`DistortionEffect`] docs.
+    Distortion(DistortionEffect),
 }
 
 impl Inspect for Effect {
     fn properties(&self) -> Vec<PropertyInfo<'_>> {
         match self {
             Effect::Reverb(v) => v.properties(),
+            Effect::Equalizer(v) => v.properties(),
+            Effect::Compressor(v) => v.properties(),
+            Effect::Limiter(v) => v.properties(),
+            Effect::Delay(v) => v.properties(),
+            Effect::Chorus(v) => v.properties(),
+            Effect::Distortion(v) => v.properties(),
         }
     }
 }
@@ -111,6 +134,12 @@ impl Deref for Effect {
     fn deref(&self) -> &Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Equalizer(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Limiter(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Distortion(v) => v,
         }
     }
 }
@@ -119,6 +148,12 @@ impl DerefMut for Effect {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
             Effect::Reverb(v) => v,
+            Effect::Equalizer(v) => v,
+            Effect::Compressor(v) => v,
+            Effect::Limiter(v) => v,
+            Effect::Delay(v) => v,
+            Effect::Chorus(v) => v,
+            Effect::Distortion(v) => v,
         }
     }
 }
@@ -131,6 +166,12 @@ impl Default for Effect {
 
 impl Effect {
     define_is_as!(Effect : Reverb -> ref ReverbEffect => fn is_reverb, fn as_reverb, fn as_reverb_mut);
+    define_is_as!(Effect : Equalizer -> ref EqualizerEffect => fn is_equalizer, fn as_equalizer, fn as_equalizer_mut);
+    define_is_as!(Effect : Compressor -> ref CompressorEffect => fn is_compressor, fn as_compressor, fn as_compressor_mut);
+    define_is_as!(Effect : Limiter -> ref LimiterEffect => fn is_limiter, fn as_limiter, fn as_limiter_mut);
+    define_is_as!(Effect : Delay -> ref DelayEffect => fn is_delay, fn as_delay, fn as_delay_mut);
+    define_is_as!(Effect : Chorus -> ref ChorusEffect => fn is_chorus, fn as_chorus, fn as_chorus_mut);
+    define_is_as!(Effect : Distortion -> ref DistortionEffect => fn is_distortion, fn as_distortion, fn as_distortion_mut);
 }
 
 /// Base effect builder allows you to build an effect.
@@ -337,3 +378,914 @@ impl ReverbEffectBuilder {
         context.add_effect(self.build_effect())
     }
 }
+
+/// Parametric equalizer, it is a chain of filters (bands), each band boosts or cuts some range
+/// of frequencies.
+#[derive(Visit, Inspect, Debug, Default)]
+pub struct EqualizerEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(getter = "Deref::deref")]
+    pub(crate) bands: TemplateVariable<Vec<EqualizerBand>>,
+}
+
+impl Deref for EqualizerEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for EqualizerEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl EqualizerEffect {
+    /// Sets new bands of the equalizer.
+    pub fn set_bands(&mut self, bands: Vec<EqualizerBand>) {
+        self.bands.set(bands);
+    }
+
+    /// Returns current bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns a copy of current bands of the equalizer.
+    pub fn bands_owned(&self) -> Vec<EqualizerBand> {
+        self.bands.get().clone()
+    }
+}
+
+/// Allows you to create a new equalizer effect.
+pub struct EqualizerEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    bands: Vec<EqualizerBand>,
+}
+
+impl EqualizerEffectBuilder {
+    /// Creates new equalizer effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            bands: Default::default(),
+        }
+    }
+
+    define_with!(
+        /// Sets desired bands of the equalizer.
+        fn with_bands(bands: Vec<EqualizerBand>)
+    );
+
+    /// Creates new equalizer effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Equalizer(EqualizerEffect {
+            base: self.base_builder.build(),
+            bands: self.bands.into(),
+        })
+    }
+
+    /// Creates new equalizer effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Compressor reduces dynamic range of a signal - it makes loud parts quieter.
+#[derive(Visit, Inspect, Debug)]
+pub struct CompressorEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(min_value = -96.0, max_value = 0.0, step = 0.5, getter = "Deref::deref")]
+    pub(crate) threshold: TemplateVariable<f32>,
+    #[inspect(min_value = 1.0, step = 0.1, getter = "Deref::deref")]
+    pub(crate) ratio: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, step = 0.001, getter = "Deref::deref")]
+    pub(crate) attack: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, step = 0.01, getter = "Deref::deref")]
+    pub(crate) release: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, step = 0.5, getter = "Deref::deref")]
+    pub(crate) makeup_gain: TemplateVariable<f32>,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 0.1,
+        step = 0.001,
+        getter = "Deref::deref"
+    )]
+    pub(crate) lookahead: TemplateVariable<f32>,
+}
+
+impl Default for CompressorEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            threshold: TemplateVariable::new(-12.0),
+            ratio: TemplateVariable::new(4.0),
+            attack: TemplateVariable::new(0.01),
+            release: TemplateVariable::new(0.1),
+            makeup_gain: TemplateVariable::new(0.0),
+            lookahead: TemplateVariable::new(0.005),
+        }
+    }
+}
+
+impl Deref for CompressorEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CompressorEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl CompressorEffect {
+    /// Sets level (in decibels) above which compression starts.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold.set(threshold.min(0.0));
+    }
+
+    /// Returns level (in decibels) above which compression starts.
+    pub fn threshold(&self) -> f32 {
+        *self.threshold
+    }
+
+    /// Sets compression ratio, for example 4.0 means that 4 dB above threshold will become 1 dB.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio.set(ratio.max(1.0));
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        *self.ratio
+    }
+
+    /// Sets time (in seconds) that is needed to apply gain reduction.
+    pub fn set_attack(&mut self, attack: f32) {
+        self.attack.set(attack.max(0.0));
+    }
+
+    /// Returns attack time (in seconds).
+    pub fn attack(&self) -> f32 {
+        *self.attack
+    }
+
+    /// Sets time (in seconds) that is needed to remove gain reduction.
+    pub fn set_release(&mut self, release: f32) {
+        self.release.set(release.max(0.0));
+    }
+
+    /// Returns release time (in seconds).
+    pub fn release(&self) -> f32 {
+        *self.release
+    }
+
+    /// Sets gain (in decibels) that is applied after compression to restore loudness.
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) {
+        self.makeup_gain.set(makeup_gain);
+    }
+
+    /// Returns makeup gain (in decibels).
+    pub fn makeup_gain(&self) -> f32 {
+        *self.makeup_gain
+    }
+
+    /// Sets lookahead time (in seconds).
+    pub fn set_lookahead(&mut self, lookahead: f32) {
+        self.lookahead.set(lookahead.max(0.0));
+    }
+
+    /// Returns lookahead time (in seconds).
+    pub fn lookahead(&self) -> f32 {
+        *self.lookahead
+    }
+}
+
+/// Allows you to create a new compressor effect.
+pub struct CompressorEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+    lookahead: f32,
+}
+
+impl CompressorEffectBuilder {
+    /// Creates new compressor effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            threshold: -12.0,
+            ratio: 4.0,
+            attack: 0.01,
+            release: 0.1,
+            makeup_gain: 0.0,
+            lookahead: 0.005,
+        }
+    }
+
+    define_with!(
+        /// Sets desired threshold (in decibels).
+        fn with_threshold(threshold: f32)
+    );
+
+    define_with!(
+        /// Sets desired compression ratio.
+        fn with_ratio(ratio: f32)
+    );
+
+    define_with!(
+        /// Sets desired attack time (in seconds).
+        fn with_attack(attack: f32)
+    );
+
+    define_with!(
+        /// Sets desired release time (in seconds).
+        fn with_release(release: f32)
+    );
+
+    define_with!(
+        /// Sets desired makeup gain (in decibels).
+        fn with_makeup_gain(makeup_gain: f32)
+    );
+
+    define_with!(
+        /// Sets desired lookahead time (in seconds).
+        fn with_lookahead(lookahead: f32)
+    );
+
+    /// Creates new compressor effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Compressor(CompressorEffect {
+            base: self.base_builder.build(),
+            threshold: self.threshold.into(),
+            ratio: self.ratio.into(),
+            attack: self.attack.into(),
+            release: self.release.into(),
+            makeup_gain: self.makeup_gain.into(),
+            lookahead: self.lookahead.into(),
+        })
+    }
+
+    /// Creates new compressor effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Brickwall limiter guarantees that output signal never exceeds given threshold.
+#[derive(Visit, Inspect, Debug)]
+pub struct LimiterEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(min_value = -96.0, max_value = 0.0, step = 0.1, getter = "Deref::deref")]
+    pub(crate) threshold: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, step = 0.01, getter = "Deref::deref")]
+    pub(crate) release: TemplateVariable<f32>,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 0.1,
+        step = 0.001,
+        getter = "Deref::deref"
+    )]
+    pub(crate) lookahead: TemplateVariable<f32>,
+}
+
+impl Default for LimiterEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            threshold: TemplateVariable::new(-0.1),
+            release: TemplateVariable::new(0.05),
+            lookahead: TemplateVariable::new(0.005),
+        }
+    }
+}
+
+impl Deref for LimiterEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for LimiterEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl LimiterEffect {
+    /// Sets maximum level (in decibels) of output signal.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold.set(threshold.min(0.0));
+    }
+
+    /// Returns maximum level (in decibels) of output signal.
+    pub fn threshold(&self) -> f32 {
+        *self.threshold
+    }
+
+    /// Sets time (in seconds) that is needed to remove gain reduction.
+    pub fn set_release(&mut self, release: f32) {
+        self.release.set(release.max(0.0));
+    }
+
+    /// Returns release time (in seconds).
+    pub fn release(&self) -> f32 {
+        *self.release
+    }
+
+    /// Sets lookahead time (in seconds).
+    pub fn set_lookahead(&mut self, lookahead: f32) {
+        self.lookahead.set(lookahead.max(0.0));
+    }
+
+    /// Returns lookahead time (in seconds).
+    pub fn lookahead(&self) -> f32 {
+        *self.lookahead
+    }
+}
+
+/// Allows you to create a new limiter effect.
+pub struct LimiterEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    threshold: f32,
+    release: f32,
+    lookahead: f32,
+}
+
+impl LimiterEffectBuilder {
+    /// Creates new limiter effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            threshold: -0.1,
+            release: 0.05,
+            lookahead: 0.005,
+        }
+    }
+
+    define_with!(
+        /// Sets desired threshold (in decibels).
+        fn with_threshold(threshold: f32)
+    );
+
+    define_with!(
+        /// Sets desired release time (in seconds).
+        fn with_release(release: f32)
+    );
+
+    define_with!(
+        /// Sets desired lookahead time (in seconds).
+        fn with_lookahead(lookahead: f32)
+    );
+
+    /// Creates new limiter effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Limiter(LimiterEffect {
+            base: self.base_builder.build(),
+            threshold: self.threshold.into(),
+            release: self.release.into(),
+            lookahead: self.lookahead.into(),
+        })
+    }
+
+    /// Creates new limiter effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Delay (echo) repeats input signal after some time.
+#[derive(Visit, Inspect, Debug)]
+pub struct DelayEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 10.0,
+        step = 0.01,
+        getter = "Deref::deref"
+    )]
+    pub(crate) time: TemplateVariable<f32>,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 0.99,
+        step = 0.01,
+        getter = "Deref::deref"
+    )]
+    pub(crate) feedback: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) dry: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) wet: TemplateVariable<f32>,
+    #[inspect(getter = "Deref::deref")]
+    pub(crate) tempo_sync: TemplateVariable<Option<TempoSync>>,
+}
+
+impl Default for DelayEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            time: TemplateVariable::new(0.3),
+            feedback: TemplateVariable::new(0.3),
+            dry: TemplateVariable::new(1.0),
+            wet: TemplateVariable::new(0.5),
+            tempo_sync: Default::default(),
+        }
+    }
+}
+
+impl Deref for DelayEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for DelayEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl DelayEffect {
+    /// Sets delay time in seconds. It is ignored if tempo sync is used.
+    pub fn set_time(&mut self, time: f32) {
+        self.time.set(time.max(0.0));
+    }
+
+    /// Returns delay time in seconds.
+    pub fn time(&self) -> f32 {
+        *self.time
+    }
+
+    /// Sets amount of delayed signal that is fed back into the delay line.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback.set(feedback.clamp(0.0, 0.99));
+    }
+
+    /// Returns feedback.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry.set(dry.clamp(0.0, 1.0));
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of delayed signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet.set(wet.clamp(0.0, 1.0));
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Sets tempo sync, `None` means that delay time in seconds will be used.
+    pub fn set_tempo_sync(&mut self, tempo_sync: Option<TempoSync>) {
+        self.tempo_sync.set(tempo_sync);
+    }
+
+    /// Returns current tempo sync.
+    pub fn tempo_sync(&self) -> Option<TempoSync> {
+        self.tempo_sync.get().clone()
+    }
+}
+
+/// Allows you to create a new delay effect.
+pub struct DelayEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    time: f32,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+    tempo_sync: Option<TempoSync>,
+}
+
+impl DelayEffectBuilder {
+    /// Creates new delay effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            time: 0.3,
+            feedback: 0.3,
+            dry: 1.0,
+            wet: 0.5,
+            tempo_sync: None,
+        }
+    }
+
+    define_with!(
+        /// Sets desired delay time (in seconds).
+        fn with_time(time: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback.
+        fn with_feedback(feedback: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    define_with!(
+        /// Sets desired tempo sync.
+        fn with_tempo_sync(tempo_sync: Option<TempoSync>)
+    );
+
+    /// Creates new delay effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Delay(DelayEffect {
+            base: self.base_builder.build(),
+            time: self.time.into(),
+            feedback: self.feedback.into(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+            tempo_sync: self.tempo_sync.into(),
+        })
+    }
+
+    /// Creates new delay effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Chorus mixes input signal with its modulated delayed copy. With short delay and strong
+/// feedback it becomes a flanger.
+#[derive(Visit, Inspect, Debug)]
+pub struct ChorusEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 20.0,
+        step = 0.05,
+        getter = "Deref::deref"
+    )]
+    pub(crate) rate: TemplateVariable<f32>,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 0.05,
+        step = 0.0005,
+        getter = "Deref::deref"
+    )]
+    pub(crate) depth: TemplateVariable<f32>,
+    #[inspect(
+        min_value = 0.0,
+        max_value = 0.05,
+        step = 0.0005,
+        getter = "Deref::deref"
+    )]
+    pub(crate) delay: TemplateVariable<f32>,
+    #[inspect(min_value = -0.99, max_value = 0.99, step = 0.01, getter = "Deref::deref")]
+    pub(crate) feedback: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) dry: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) wet: TemplateVariable<f32>,
+}
+
+impl Default for ChorusEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            rate: TemplateVariable::new(0.8),
+            depth: TemplateVariable::new(0.003),
+            delay: TemplateVariable::new(0.02),
+            feedback: TemplateVariable::new(0.0),
+            dry: TemplateVariable::new(1.0),
+            wet: TemplateVariable::new(0.5),
+        }
+    }
+}
+
+impl Deref for ChorusEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ChorusEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl ChorusEffect {
+    /// Sets frequency (in hertz) of the modulation.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate.set(rate.max(0.0));
+    }
+
+    /// Returns frequency (in hertz) of the modulation.
+    pub fn rate(&self) -> f32 {
+        *self.rate
+    }
+
+    /// Sets modulation depth (in seconds).
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth.set(depth.max(0.0));
+    }
+
+    /// Returns modulation depth (in seconds).
+    pub fn depth(&self) -> f32 {
+        *self.depth
+    }
+
+    /// Sets base delay time (in seconds).
+    pub fn set_delay(&mut self, delay: f32) {
+        self.delay.set(delay.max(0.0));
+    }
+
+    /// Returns base delay time (in seconds).
+    pub fn delay(&self) -> f32 {
+        *self.delay
+    }
+
+    /// Sets amount of delayed signal that is fed back.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback.set(feedback.clamp(-0.99, 0.99));
+    }
+
+    /// Returns feedback.
+    pub fn feedback(&self) -> f32 {
+        *self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry.set(dry.clamp(0.0, 1.0));
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of modulated signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet.set(wet.clamp(0.0, 1.0));
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+}
+
+/// Allows you to create a new chorus effect.
+pub struct ChorusEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    rate: f32,
+    depth: f32,
+    delay: f32,
+    feedback: f32,
+    dry: f32,
+    wet: f32,
+}
+
+impl ChorusEffectBuilder {
+    /// Creates new chorus effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            rate: 0.8,
+            depth: 0.003,
+            delay: 0.02,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.5,
+        }
+    }
+
+    /// Creates new builder with flanger preset.
+    pub fn flanger(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            rate: 0.25,
+            depth: 0.0015,
+            delay: 0.002,
+            feedback: 0.7,
+            wet: 0.7,
+            ..Self::new(base_builder)
+        }
+    }
+
+    define_with!(
+        /// Sets desired modulation rate (in hertz).
+        fn with_rate(rate: f32)
+    );
+
+    define_with!(
+        /// Sets desired modulation depth (in seconds).
+        fn with_depth(depth: f32)
+    );
+
+    define_with!(
+        /// Sets desired base delay (in seconds).
+        fn with_delay(delay: f32)
+    );
+
+    define_with!(
+        /// Sets desired feedback.
+        fn with_feedback(feedback: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    /// Creates new chorus effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Chorus(ChorusEffect {
+            base: self.base_builder.build(),
+            rate: self.rate.into(),
+            depth: self.depth.into(),
+            delay: self.delay.into(),
+            feedback: self.feedback.into(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+        })
+    }
+
+    /// Creates new chorus effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
+
+/// Waveshaper distortion adds harmonics to a signal.
+#[derive(Visit, Inspect, Debug)]
+pub struct DistortionEffect {
+    pub(crate) base: BaseEffect,
+    #[inspect(getter = "Deref::deref")]
+    pub(crate) shape: TemplateVariable<DistortionShape>,
+    #[inspect(
+        min_value = 1.0,
+        max_value = 100.0,
+        step = 0.1,
+        getter = "Deref::deref"
+    )]
+    pub(crate) drive: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) dry: TemplateVariable<f32>,
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05, getter = "Deref::deref")]
+    pub(crate) wet: TemplateVariable<f32>,
+}
+
+impl Default for DistortionEffect {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            shape: Default::default(),
+            drive: TemplateVariable::new(4.0),
+            dry: TemplateVariable::new(0.0),
+            wet: TemplateVariable::new(1.0),
+        }
+    }
+}
+
+impl Deref for DistortionEffect {
+    type Target = BaseEffect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for DistortionEffect {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl DistortionEffect {
+    /// Sets transfer function of the distortion.
+    pub fn set_shape(&mut self, shape: DistortionShape) {
+        self.shape.set(shape);
+    }
+
+    /// Returns transfer function of the distortion.
+    pub fn shape(&self) -> DistortionShape {
+        *self.shape
+    }
+
+    /// Sets amplification of the signal before shaping.
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive.set(drive.max(1.0));
+    }
+
+    /// Returns drive.
+    pub fn drive(&self) -> f32 {
+        *self.drive
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry.set(dry.clamp(0.0, 1.0));
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets how much of distorted signal should be passed to output.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet.set(wet.clamp(0.0, 1.0));
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+}
+
+/// Allows you to create a new distortion effect.
+pub struct DistortionEffectBuilder {
+    base_builder: BaseEffectBuilder,
+    shape: DistortionShape,
+    drive: f32,
+    dry: f32,
+    wet: f32,
+}
+
+impl DistortionEffectBuilder {
+    /// Creates new distortion effect builder.
+    pub fn new(base_builder: BaseEffectBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: DistortionShape::SoftClip,
+            drive: 4.0,
+            dry: 0.0,
+            wet: 1.0,
+        }
+    }
+
+    define_with!(
+        /// Sets desired shape of the distortion.
+        fn with_shape(shape: DistortionShape)
+    );
+
+    define_with!(
+        /// Sets desired drive.
+        fn with_drive(drive: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry coefficient.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet coefficient.
+        fn with_wet(wet: f32)
+    );
+
+    /// Creates new distortion effect.
+    pub fn build_effect(self) -> Effect {
+        Effect::Distortion(DistortionEffect {
+            base: self.base_builder.build(),
+            shape: self.shape.into(),
+            drive: self.drive.into(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+        })
+    }
+
+    /// Creates new distortion effect and adds it to the context.
+    pub fn build(self, context: &mut SoundContext) -> Handle<Effect> {
+        context.add_effect(self.build_effect())
+    }
+}
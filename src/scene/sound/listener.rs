@@ -6,6 +6,7 @@
 use crate::{
     core::variable::InheritError,
     core::{
+        algebra::Vector3,
         inspect::{Inspect, PropertyInfo},
         math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
         pool::Handle,
@@ -16,7 +17,7 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::{map::NodeHandleMap, Graph},
-        node::{Node, NodeTrait, SyncContext, TypeUuidProvider},
+        node::{Node, NodeTrait, SyncContext, TypeUuidProvider, UpdateContext},
     },
 };
 use std::ops::{Deref, DerefMut};
@@ -38,6 +39,13 @@ use std::ops::{Deref, DerefMut};
 #[derive(Visit, Inspect, Default, Clone, Debug)]
 pub struct Listener {
     base: Base,
+    // Velocity is derived from movement of the node, it is used to calculate Doppler effect.
+    #[inspect(skip)]
+    #[visit(skip)]
+    velocity: Vector3<f32>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
 }
 
 impl Deref for Listener {
@@ -54,6 +62,14 @@ impl DerefMut for Listener {
     }
 }
 
+impl Listener {
+    /// Returns velocity of the listener in world space. It is calculated automatically from
+    /// movement of the node and used to apply Doppler effect.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+}
+
 impl TypeUuidProvider for Listener {
     fn type_uuid() -> Uuid {
         uuid!("2c7dabc1-5666-4256-b020-01532701e4c6")
@@ -102,6 +118,19 @@ impl NodeTrait for Listener {
         let native = state.listener_mut();
         native.set_position(self.global_position());
         native.set_basis(self.global_transform().basis());
+        native.set_velocity(self.velocity);
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) -> bool {
+        let position = self.global_position();
+        if let Some(prev_position) = self.prev_position {
+            if context.dt > 0.0 {
+                self.velocity = (position - prev_position).scale(1.0 / context.dt);
+            }
+        }
+        self.prev_position = Some(position);
+
+        self.base.update_lifetime(context.dt)
     }
 }
 
@@ -120,6 +149,8 @@ impl ListenerBuilder {
     pub fn build_listener(self) -> Listener {
         Listener {
             base: self.base_builder.build_base(),
+            velocity: Default::default(),
+            prev_position: None,
         }
     }
 
@@ -3,7 +3,7 @@
 use crate::{
     core::variable::{InheritError, TemplateVariable},
     core::{
-        algebra::Matrix4,
+        algebra::{Matrix4, Vector3},
         inspect::{Inspect, PropertyInfo},
         math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
         pool::Handle,
@@ -25,7 +25,12 @@ use crate::{
 // Re-export some the fyrox_sound entities.
 pub use fyrox_sound::{
     buffer::{DataSource, SoundBufferResource, SoundBufferResourceLoadError, SoundBufferState},
-    context::{DistanceModel, SAMPLE_RATE},
+    bus::{AudioBus, AudioBusGraph, Ducking},
+    context::{
+        DistanceModel, VoiceStatistics, DEFAULT_MAX_ACTIVE_VOICES, DEFAULT_SPEED_OF_SOUND,
+        DEFAULT_VIRTUALIZATION_THRESHOLD, SAMPLE_RATE,
+    },
+    device::{enumerate_devices, Backend, DeviceConfig, DeviceInfo},
     dsp::{filters::*, DelayLine},
     engine::SoundEngine,
     error::SoundError,
@@ -34,7 +39,7 @@ pub use fyrox_sound::{
     source::Status,
 };
 
-use crate::scene::graph::map::NodeHandleMap;
+use crate::scene::{graph::map::NodeHandleMap, sound::occlusion::OcclusionState};
 use fyrox_sound::source::SoundSource;
 use std::{
     cell::Cell,
@@ -45,6 +50,7 @@ use std::{
 pub mod context;
 pub mod effect;
 pub mod listener;
+pub mod occlusion;
 
 /// Sound source.
 #[derive(Visit, Inspect, Debug)]
@@ -74,9 +80,28 @@ pub struct Sound {
     playback_time: TemplateVariable<Duration>,
     #[inspect(getter = "Deref::deref")]
     spatial_blend: TemplateVariable<f32>,
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) bus: TemplateVariable<Handle<AudioBus>>,
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    occlusion_enabled: TemplateVariable<bool>,
+    #[inspect(getter = "Deref::deref")]
+    #[visit(optional)] // Backward compatibility
+    priority: TemplateVariable<u32>,
     #[inspect(skip)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
+    // Velocity is derived from movement of the node, it is used to calculate Doppler effect.
+    #[inspect(skip)]
+    #[visit(skip)]
+    velocity: Vector3<f32>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
+    #[inspect(skip)]
+    #[visit(skip)]
+    occlusion_state: OcclusionState,
 }
 
 impl_directly_inheritable_entity_trait!(Sound;
@@ -90,7 +115,10 @@ impl_directly_inheritable_entity_trait!(Sound;
     radius,
     max_distance,
     rolloff_factor,
-    playback_time
+    playback_time,
+    bus,
+    occlusion_enabled,
+    priority
 );
 
 impl Deref for Sound {
@@ -123,7 +151,13 @@ impl Default for Sound {
             rolloff_factor: TemplateVariable::new(1.0),
             playback_time: Default::default(),
             spatial_blend: TemplateVariable::new(1.0),
+            bus: Default::default(),
+            occlusion_enabled: TemplateVariable::new(false),
+            priority: TemplateVariable::new(0),
             native: Default::default(),
+            velocity: Default::default(),
+            prev_position: None,
+            occlusion_state: Default::default(),
         }
     }
 }
@@ -144,8 +178,14 @@ impl Clone for Sound {
             rolloff_factor: self.rolloff_factor.clone(),
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
+            bus: self.bus.clone(),
+            occlusion_enabled: self.occlusion_enabled.clone(),
+            priority: self.priority.clone(),
             // Do not copy.
             native: Default::default(),
+            velocity: Default::default(),
+            prev_position: None,
+            occlusion_state: Default::default(),
         }
     }
 }
@@ -317,6 +357,52 @@ impl Sound {
     pub fn max_distance(&self) -> f32 {
         *self.max_distance
     }
+
+    /// Sets a mixer bus to which the sound will be routed. Invalid handle means that the sound is
+    /// routed to the master bus, see [`context::SoundContext::add_bus`].
+    pub fn set_bus(&mut self, bus: Handle<AudioBus>) {
+        self.bus.set(bus);
+    }
+
+    /// Returns a mixer bus to which the sound is routed.
+    pub fn bus(&self) -> Handle<AudioBus> {
+        *self.bus
+    }
+
+    /// Enables or disables geometry-based occlusion of the sound. See [`occlusion`] module docs
+    /// for more info.
+    pub fn set_occlusion_enabled(&mut self, enabled: bool) {
+        self.occlusion_enabled.set(enabled);
+    }
+
+    /// Returns true if geometry-based occlusion of the sound is enabled.
+    pub fn is_occlusion_enabled(&self) -> bool {
+        *self.occlusion_enabled
+    }
+
+    /// Sets priority of the sound. When there are more playing sounds than the context can mix
+    /// (see [`context::SoundContext::set_max_active_voices`]), sounds with higher priority are
+    /// mixed first, the rest become virtual. Default priority is 0.
+    pub fn set_priority(&mut self, priority: u32) {
+        self.priority.set(priority);
+    }
+
+    /// Returns priority of the sound.
+    pub fn priority(&self) -> u32 {
+        *self.priority
+    }
+
+    /// Returns current occlusion factor of the sound in `[0; 1]` range, where 0.0 means that
+    /// there are no obstacles between the sound and the listener.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion_state.occlusion
+    }
+
+    /// Returns velocity of the sound in world space. It is calculated automatically from movement
+    /// of the node and used to apply Doppler effect.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
 }
 
 impl NodeTrait for Sound {
@@ -384,6 +470,40 @@ impl NodeTrait for Sound {
     fn update(&mut self, context: &mut UpdateContext) -> bool {
         context.sound_context.sync_with_sound(self);
 
+        let position = self.global_position();
+        if let Some(prev_position) = self.prev_position {
+            if context.dt > 0.0 {
+                self.velocity = (position - prev_position).scale(1.0 / context.dt);
+                context
+                    .sound_context
+                    .set_sound_velocity(self, self.velocity);
+            }
+        }
+        self.prev_position = Some(position);
+
+        if *self.occlusion_enabled {
+            let settings = context.sound_context.occlusion_settings().clone();
+            let listener = context.sound_context.listener_position();
+            let physics = &*context.physics;
+            let nodes = context.nodes;
+            if self.occlusion_state.update(&settings, context.dt, || {
+                settings.calculate_transmission(physics, nodes, position, listener)
+            }) {
+                let (gain, cutoff_frequency) =
+                    settings.occlusion_params(self.occlusion_state.occlusion);
+                context
+                    .sound_context
+                    .set_sound_occlusion(self, gain, cutoff_frequency);
+            }
+        } else if self.occlusion_state.occlusion != 0.0 {
+            self.occlusion_state = Default::default();
+            context.sound_context.set_sound_occlusion(
+                self,
+                1.0,
+                SoundSource::MAX_OCCLUSION_CUTOFF_FREQUENCY,
+            );
+        }
+
         self.base.update_lifetime(context.dt)
             && !(self.is_play_once() && self.status() == Status::Stopped)
     }
@@ -404,6 +524,9 @@ pub struct SoundBuilder {
     rolloff_factor: f32,
     playback_time: Duration,
     spatial_blend: f32,
+    bus: Handle<AudioBus>,
+    occlusion_enabled: bool,
+    priority: u32,
 }
 
 impl SoundBuilder {
@@ -423,6 +546,9 @@ impl SoundBuilder {
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
             playback_time: Default::default(),
+            bus: Default::default(),
+            occlusion_enabled: false,
+            priority: 0,
         }
     }
 
@@ -486,6 +612,21 @@ impl SoundBuilder {
         fn with_playback_time(playback_time: Duration)
     );
 
+    define_with!(
+        /// Sets desired mixer bus. See [`Sound::set_bus`] for more info.
+        fn with_bus(bus: Handle<AudioBus>)
+    );
+
+    define_with!(
+        /// Enables or disables occlusion. See [`Sound::set_occlusion_enabled`] for more info.
+        fn with_occlusion_enabled(occlusion_enabled: bool)
+    );
+
+    define_with!(
+        /// Sets desired priority. See [`Sound::set_priority`] for more info.
+        fn with_priority(priority: u32)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -503,7 +644,13 @@ impl SoundBuilder {
             rolloff_factor: self.rolloff_factor.into(),
             playback_time: self.playback_time.into(),
             spatial_blend: self.spatial_blend.into(),
+            bus: self.bus.into(),
+            occlusion_enabled: self.occlusion_enabled.into(),
+            priority: self.priority.into(),
             native: Default::default(),
+            velocity: Default::default(),
+            prev_position: None,
+            occlusion_state: Default::default(),
         }
     }
 
@@ -521,10 +668,15 @@ impl SoundBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::scene::{
-        base::{test::check_inheritable_properties_equality, BaseBuilder},
-        node::NodeTrait,
-        sound::{Sound, SoundBuilder},
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            graph::Graph,
+            node::NodeTrait,
+            sound::{Sound, SoundBuilder},
+            transform::TransformBuilder,
+        },
     };
     use fyrox_sound::source::Status;
     use std::time::Duration;
@@ -551,4 +703,32 @@ mod test {
         check_inheritable_properties_equality(&child.base, &parent.base);
         check_inheritable_properties_equality(&child, parent);
     }
+
+    #[test]
+    fn test_sound_velocity() {
+        let mut graph = Graph::new();
+        let sound = SoundBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        graph.update(Vector2::new(100.0, 100.0), 0.5);
+        assert_eq!(
+            graph[sound].cast::<Sound>().unwrap().velocity(),
+            Vector3::default()
+        );
+
+        graph[sound]
+            .local_transform_mut()
+            .set_position(Vector3::new(3.0, 0.0, 0.0));
+        graph.update(Vector2::new(100.0, 100.0), 0.5);
+        assert_eq!(
+            graph[sound].cast::<Sound>().unwrap().velocity(),
+            Vector3::new(4.0, 0.0, 0.0)
+        );
+    }
 }
@@ -0,0 +1,194 @@
+//! Geometry-based sound occlusion.
+//!
+//! # Overview
+//!
+//! Occlusion makes sounds quieter and muffled when there are obstacles between a sound and the
+//! listener. Every sound with enabled occlusion (see [`super::Sound::set_occlusion_enabled`])
+//! periodically casts a ray from the listener to itself using 3D physics. Each collider hit by the
+//! ray lets only a portion of sound through, this portion is defined by sound transmission of
+//! collider's physics material (see [`crate::resource::physics_material::PhysicsMaterial`]).
+//! Colliders without material use [`OcclusionSettings::default_transmission`], sensors are
+//! ignored.
+//!
+//! Final occlusion factor is used to reduce gain of the sound and to lower cut-off frequency of
+//! its low-pass filter. Occlusion factor changes smoothly over time, so a sound won't "jump" when
+//! an obstacle appears or disappears.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        inspect::{Inspect, PropertyInfo},
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::{Collider, InteractionGroups},
+        graph::{
+            physics::{PhysicsWorld, RayCastOptions},
+            NodePool,
+        },
+    },
+};
+
+/// Global settings of sound occlusion, they're stored in sound context of a scene. See module
+/// docs for more info.
+#[derive(Debug, Clone, PartialEq, Visit, Inspect)]
+pub struct OcclusionSettings {
+    /// Time (in seconds) between two consecutive ray casts of a sound. Lower values give faster
+    /// response to changes in geometry at the cost of performance.
+    #[inspect(min_value = 0.0, step = 0.01)]
+    pub update_interval: f32,
+
+    /// Time (in seconds) that is needed for occlusion factor to reach ~63% of its new value.
+    #[inspect(min_value = 0.0, step = 0.01)]
+    pub smoothing_time: f32,
+
+    /// Gain of a fully occluded sound.
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub occluded_gain: f32,
+
+    /// Cut-off frequency (in hertz) of low-pass filter of a fully occluded sound.
+    #[inspect(min_value = 20.0, max_value = 20000.0, step = 10.0)]
+    pub occluded_cutoff_frequency: f32,
+
+    /// Sound transmission of colliders without physics material.
+    #[inspect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub default_transmission: f32,
+
+    /// Collision groups of rays. Use it to exclude some colliders from occlusion.
+    pub groups: InteractionGroups,
+}
+
+impl Default for OcclusionSettings {
+    fn default() -> Self {
+        Self {
+            update_interval: 0.1,
+            smoothing_time: 0.1,
+            occluded_gain: 0.3,
+            occluded_cutoff_frequency: 800.0,
+            default_transmission: 0.3,
+            groups: Default::default(),
+        }
+    }
+}
+
+impl OcclusionSettings {
+    /// Calculates how much sound passes from `source` to `listener` by casting a ray between
+    /// them. Returns a value in `[0; 1]` range, where 0.0 means that sound is fully blocked.
+    pub fn calculate_transmission(
+        &self,
+        physics: &PhysicsWorld,
+        nodes: &NodePool,
+        source: Vector3<f32>,
+        listener: Vector3<f32>,
+    ) -> f32 {
+        let dir = source - listener;
+        let distance = dir.norm();
+        if distance <= f32::EPSILON {
+            return 1.0;
+        }
+
+        let mut intersections = Vec::new();
+        physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(listener),
+                ray_direction: dir,
+                max_len: distance,
+                groups: self.groups,
+                sort_results: false,
+            },
+            &mut intersections,
+        );
+
+        let mut transmission = 1.0;
+        for intersection in intersections {
+            if let Some(collider) = nodes
+                .try_borrow(intersection.collider)
+                .and_then(|n| n.cast::<Collider>())
+            {
+                if !collider.is_sensor() {
+                    transmission *= collider
+                        .sound_transmission()
+                        .unwrap_or(self.default_transmission)
+                        .clamp(0.0, 1.0);
+                }
+            }
+        }
+        transmission
+    }
+
+    /// Returns gain and cut-off frequency of low-pass filter for given occlusion factor.
+    pub fn occlusion_params(&self, occlusion: f32) -> (f32, f32) {
+        let max = fyrox_sound::source::SoundSource::MAX_OCCLUSION_CUTOFF_FREQUENCY;
+        let min = self.occluded_cutoff_frequency.max(1.0).min(max);
+        let gain = 1.0 + (self.occluded_gain - 1.0) * occlusion;
+        // Interpolate frequency in logarithmic scale, because perception of pitch is logarithmic.
+        let cutoff_frequency = max * (min / max).powf(occlusion);
+        (gain, cutoff_frequency)
+    }
+}
+
+/// Runtime occlusion state of a sound.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OcclusionState {
+    // Time left until next ray cast.
+    timer: f32,
+    target: f32,
+    pub(crate) occlusion: f32,
+}
+
+impl OcclusionState {
+    /// Updates occlusion factor and returns true if it was changed.
+    pub(crate) fn update<F: FnOnce() -> f32>(
+        &mut self,
+        settings: &OcclusionSettings,
+        dt: f32,
+        calculate_transmission: F,
+    ) -> bool {
+        self.timer -= dt;
+        if self.timer <= 0.0 {
+            self.timer = settings.update_interval;
+            self.target = 1.0 - calculate_transmission();
+        }
+
+        let prev = self.occlusion;
+        if settings.smoothing_time > 0.0 {
+            let k = 1.0 - (-dt / settings.smoothing_time).exp();
+            self.occlusion += (self.target - self.occlusion) * k;
+        } else {
+            self.occlusion = self.target;
+        }
+        prev != self.occlusion
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::sound::occlusion::{OcclusionSettings, OcclusionState};
+
+    #[test]
+    fn test_occlusion_params() {
+        let settings = OcclusionSettings::default();
+
+        let (gain, cutoff) = settings.occlusion_params(0.0);
+        assert_eq!(gain, 1.0);
+        assert!((cutoff - 20000.0).abs() < 0.01);
+
+        let (gain, cutoff) = settings.occlusion_params(1.0);
+        assert!((gain - settings.occluded_gain).abs() < f32::EPSILON);
+        assert!((cutoff - settings.occluded_cutoff_frequency).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_occlusion_smoothing() {
+        let settings = OcclusionSettings::default();
+        let mut state = OcclusionState::default();
+
+        assert!(state.update(&settings, 0.05, || 0.0));
+        assert!(state.occlusion > 0.0 && state.occlusion < 1.0);
+
+        for _ in 0..100 {
+            state.update(&settings, 0.05, || 0.0);
+        }
+        assert!((state.occlusion - 1.0).abs() < 0.001);
+    }
+}
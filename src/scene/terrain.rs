@@ -2,6 +2,7 @@
 
 use crate::scene::graph::map::NodeHandleMap;
 use crate::{
+    asset::ResourceState,
     core::variable::{InheritError, TemplateVariable},
     core::{
         algebra::{Matrix4, Point3, Vector2, Vector3},
@@ -18,7 +19,10 @@ use crate::{
     engine::resource_manager::ResourceManager,
     impl_directly_inheritable_entity_trait,
     material::Material,
-    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureWrapMode},
+    resource::{
+        physics_material::PhysicsMaterialResource,
+        texture::{Texture, TextureKind, TexturePixelKind, TextureWrapMode},
+    },
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
@@ -56,6 +60,11 @@ pub struct Layer {
 
     #[inspect(skip)]
     pub(in crate) chunk_masks: Vec<Texture>,
+
+    /// Physics material of the layer. It could be used to fetch surface properties at a point on
+    /// the terrain, see [`Terrain::physics_material_at`].
+    #[visit(optional)] // Backward compatibility
+    pub physics_material: Option<PhysicsMaterialResource>,
 }
 
 impl PartialEq for Layer {
@@ -63,6 +72,7 @@ impl PartialEq for Layer {
         self.mask_property_name == other.mask_property_name
             && self.chunk_masks == other.chunk_masks
             && Arc::ptr_eq(&self.material, &other.material)
+            && self.physics_material == other.physics_material
     }
 }
 
@@ -603,8 +613,41 @@ impl Terrain {
                     )
                 })
                 .collect(),
+            physics_material: None,
         }
     }
+
+    /// Returns physics material of the top-most layer that has a physics material and covers
+    /// (mask value is at least 50%) given point. The point is given in world coordinates, it will
+    /// be projected on the terrain.
+    pub fn physics_material_at(&self, p: Vector3<f32>) -> Option<PhysicsMaterialResource> {
+        let position = self.project(p)?;
+
+        let (chunk_index, chunk) = self.chunks.iter().enumerate().find(|(_, chunk)| {
+            let local = position - chunk.local_position();
+            local.x >= 0.0 && local.x <= chunk.width && local.y >= 0.0 && local.y <= chunk.length
+        })?;
+
+        let local = position - chunk.local_position();
+
+        self.layers.iter().rev().find_map(|layer| {
+            let physics_material = layer.physics_material.as_ref()?;
+            let mask = layer.chunk_masks.get(chunk_index)?;
+            let state = mask.state();
+            if let ResourceState::Ok(ref data) = *state {
+                if let TextureKind::Rectangle { width, height } = data.kind() {
+                    let x = ((local.x / chunk.width) * (width - 1) as f32).round() as usize;
+                    let z = ((local.y / chunk.length) * (height - 1) as f32).round() as usize;
+                    if let Some(&value) = data.data().get(z * width as usize + x) {
+                        if value >= 128 {
+                            return Some(physics_material.clone());
+                        }
+                    }
+                }
+            }
+            None
+        })
+    }
 }
 
 impl NodeTrait for Terrain {
@@ -662,6 +705,12 @@ impl NodeTrait for Terrain {
         for layer in self.layers() {
             layer.material.lock().resolve(resource_manager.clone());
         }
+
+        let mut state = resource_manager.state();
+        let physics_materials = &mut state.containers_mut().physics_materials;
+        for layer in self.layers.get_mut_silent().iter_mut() {
+            physics_materials.try_restore_optional_resource(&mut layer.physics_material);
+        }
     }
 
     fn remap_handles(&mut self, old_new_mapping: &NodeHandleMap) {
@@ -923,6 +972,7 @@ impl TerrainBuilder {
                                 )
                             })
                             .collect(),
+                        physics_material: None,
                     }
                 })
                 .collect::<Vec<_>>()
@@ -4,6 +4,10 @@
 
 use crate::scene::graph::map::NodeHandleMap;
 use crate::{
+    animation::{
+        machine::{self, Machine},
+        Animation, AnimationEvent,
+    },
     core::{
         inspect::{Inspect, PropertyInfo},
         pool::Handle,
@@ -101,6 +105,27 @@ pub struct ScriptDeinitContext<'a, 'b> {
     pub node_handle: Handle<Node>,
 }
 
+/// An event of an animation or an animation blending state machine, see
+/// [`ScriptTrait::on_animation_event`] for more info.
+#[derive(Clone, Debug)]
+pub enum AnimationScriptEvent {
+    /// Playback of an animation has passed one of its signals.
+    Signal {
+        /// Handle of the animation that produced the event.
+        animation: Handle<Animation>,
+        /// The event itself, it contains name and payload of the signal.
+        event: AnimationEvent,
+    },
+
+    /// An animation blending state machine has changed its state or transition.
+    Machine {
+        /// Handle of the machine that produced the event.
+        machine: Handle<Machine>,
+        /// The event itself.
+        event: machine::Event,
+    },
+}
+
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
 /// custom behaviour to game entities.
 pub trait ScriptTrait: BaseScript + ComponentProvider {
@@ -204,6 +229,23 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// Does not work in editor mode, works only in play mode.
     fn on_update(&mut self, #[allow(unused_variables)] context: ScriptContext) {}
 
+    /// Called when an animation passes one of its signals or when an animation blending state
+    /// machine changes its state or transition. An event is passed to the script of the closest
+    /// node up in the hierarchy (including the node itself) that has a script, the search starts
+    /// from the first node animated by an animation or from the root node of a machine. The method
+    /// is called after the scene was updated, but before [`Self::on_update`]. It could be used,
+    /// for example, to play footstep sounds or spawn effects.
+    ///
+    /// # Editor-specific information
+    ///
+    /// Does not work in editor mode, works only in play mode.
+    fn on_animation_event(
+        &mut self,
+        #[allow(unused_variables)] event: &AnimationScriptEvent,
+        #[allow(unused_variables)] context: ScriptContext,
+    ) {
+    }
+
     /// Called right after the parent node was copied, giving you the ability to remap handles to
     /// nodes stored inside of your script.
     ///